@@ -0,0 +1,435 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate rand;
+
+use self::rand::Rng;
+use battleship::board::{on_grid, Grid, Orientation, Ship, ShotResult, FLEET, GRID_SIZE};
+use connect4::core::GridPosition;
+
+///Constant number of random layouts the AI compares when placing its fleet, keeping the one with the fewest touching ships
+const PLACEMENT_CANDIDATES: usize = 50;
+
+///Constant extra weight given to a possible ship spot for each unresolved hit it covers, so found ships are finished off
+const HIT_WEIGHT: i32 = 20;
+
+///
+/// Enum for how a Battleship AI picks its shots, from weakest to strongest
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Strategy {
+    /// Fires at any cell not fired at yet
+    Random,
+    /// Fires on a checkerboard until it hits, then at the cells next to its hits until the ship is sunk
+    HuntTarget,
+    /// Counts every way the ships still afloat could fit around the shots so far and fires at the likeliest cell
+    ProbabilityDensity,
+}
+
+impl Strategy {
+    ///Returns the strategy for an AI difficulty: 1 is Random, 2 is HuntTarget and 3 or more is ProbabilityDensity
+    pub fn from_difficulty(difficulty: i32) -> Strategy {
+        match difficulty {
+            d if d <= 1 => Strategy::Random,
+            2 => Strategy::HuntTarget,
+            _ => Strategy::ProbabilityDensity,
+        }
+    }
+}
+
+///Returns the cells of a grid that haven't been fired at yet
+fn open_cells(target: &Grid) -> Vec<GridPosition> {
+    (0..GRID_SIZE)
+        .flat_map(|y| (0..GRID_SIZE).map(move |x| GridPosition::new(x, y)))
+        .filter(|pos| target.shot_at(*pos).is_none())
+        .collect()
+}
+
+///Returns the cells covered by sunk ships, which are revealed to the player who sank them
+fn sunk_cells(target: &Grid) -> Vec<GridPosition> {
+    target
+        .shots
+        .iter()
+        .filter_map(|(_, result)| match result {
+            ShotResult::Sunk(index) => Some(target.ships[*index].cells()),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+///Returns the cells that were hit but aren't part of a sunk ship, so a ship afloat must cover them
+fn unresolved_hits(target: &Grid) -> Vec<GridPosition> {
+    let sunk = sunk_cells(target);
+    target
+        .shots
+        .iter()
+        .filter(|(pos, result)| *result != ShotResult::Miss && !sunk.contains(pos))
+        .map(|(pos, _)| *pos)
+        .collect()
+}
+
+///Returns the lengths of the ships that haven't been sunk yet
+fn remaining_lengths(target: &Grid) -> Vec<i32> {
+    let sunk: Vec<&str> = target
+        .shots
+        .iter()
+        .filter_map(|(_, result)| match result {
+            ShotResult::Sunk(index) => Some(target.ships[*index].name),
+            _ => None,
+        })
+        .collect();
+    FLEET
+        .iter()
+        .filter(|(name, _)| !sunk.contains(name))
+        .map(|(_, length)| *length)
+        .collect()
+}
+
+///Returns the number of pairs of side-by-side cells that belong to two different ships
+fn contacts(grid: &Grid) -> i32 {
+    let mut count = 0;
+    for (i, ship) in grid.ships.iter().enumerate() {
+        for cell in ship.cells() {
+            for next in &[
+                GridPosition::new(cell.x + 1, cell.y),
+                GridPosition::new(cell.x, cell.y + 1),
+            ] {
+                if grid.ship_at(*next).is_some_and(|j| j != i) {
+                    count += 1;
+                }
+            }
+        }
+    }
+    count
+}
+
+///
+/// Returns the cells a hunt/target AI would fire at. Once two hits line up, the open cells at either end of the line
+/// are picked; otherwise the open cells next to any unresolved hit. With no hits to follow up, the open cells of a
+/// checkerboard are picked, since every ship covers at least two cells
+///
+/// # Arguments
+/// * target = Grid being fired at
+/// * open   = Cells of the grid that haven't been fired at yet
+///
+fn hunt_target_cells(target: &Grid, open: &[GridPosition]) -> Vec<GridPosition> {
+    let hits = unresolved_hits(target);
+    let directions = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+    let mut line_ends = Vec::new();
+    let mut neighbors = Vec::new();
+    for hit in &hits {
+        for (dx, dy) in directions.iter() {
+            let next = GridPosition::new(hit.x + dx, hit.y + dy);
+            if open.contains(&next) {
+                neighbors.push(next);
+            }
+            //Walk along the line of hits running through this one and the hit behind it
+            if hits.contains(&GridPosition::new(hit.x - dx, hit.y - dy)) {
+                let mut end = next;
+                while hits.contains(&end) {
+                    end = GridPosition::new(end.x + dx, end.y + dy);
+                }
+                if open.contains(&end) {
+                    line_ends.push(end);
+                }
+            }
+        }
+    }
+    if !line_ends.is_empty() {
+        return line_ends;
+    }
+    if !neighbors.is_empty() {
+        return neighbors;
+    }
+    let checkerboard: Vec<GridPosition> = open
+        .iter()
+        .filter(|pos| (pos.x + pos.y) % 2 == 0)
+        .cloned()
+        .collect();
+    if checkerboard.is_empty() {
+        open.to_vec()
+    } else {
+        checkerboard
+    }
+}
+
+///
+/// Returns the open cells covered by the most possible spots for the ships still afloat. A spot can't cover a miss or
+/// a sunk ship, and while there are unresolved hits only spots covering one of them are counted, weighted by how many
+/// they cover
+///
+/// # Arguments
+/// * target = Grid being fired at
+/// * open   = Cells of the grid that haven't been fired at yet
+///
+fn densest_cells(target: &Grid, open: &[GridPosition]) -> Vec<GridPosition> {
+    let hits = unresolved_hits(target);
+    let sunk = sunk_cells(target);
+    let blocked = |pos: &GridPosition| {
+        !on_grid(*pos) || target.shot_at(*pos) == Some(ShotResult::Miss) || sunk.contains(pos)
+    };
+    let mut scores = vec![0; (GRID_SIZE * GRID_SIZE) as usize];
+    for length in remaining_lengths(target) {
+        for y in 0..GRID_SIZE {
+            for x in 0..GRID_SIZE {
+                for orientation in &[Orientation::Horizontal, Orientation::Vertical] {
+                    let cells =
+                        Ship::new("", length, GridPosition::new(x, y), *orientation).cells();
+                    if cells.iter().any(&blocked) {
+                        continue;
+                    }
+                    let covered = cells.iter().filter(|pos| hits.contains(pos)).count() as i32;
+                    if !hits.is_empty() && covered == 0 {
+                        continue;
+                    }
+                    for pos in cells.iter().filter(|pos| open.contains(pos)) {
+                        scores[(pos.y * GRID_SIZE + pos.x) as usize] += 1 + HIT_WEIGHT * covered;
+                    }
+                }
+            }
+        }
+    }
+    let score = |pos: &GridPosition| scores[(pos.y * GRID_SIZE + pos.x) as usize];
+    let best = open.iter().map(score).max().unwrap_or(0);
+    if best == 0 {
+        return open.to_vec();
+    }
+    open.iter()
+        .filter(|pos| score(pos) == best)
+        .cloned()
+        .collect()
+}
+
+///
+/// A struct representing an AI or bot player for Battleship which has methods to place its fleet and pick shots
+///
+/// # Fields
+/// * team            = Integer value (1-2) representing the team the AI plays for
+/// * strategy        = Strategy enum for how the AI picks its shots, set from its difficulty
+/// * last_move_frame = Integer used to track when the AI last aimed or fired. Set to -1 until it aims each turn
+///
+pub struct AI {
+    pub team: i32,
+    strategy: Strategy,
+    pub last_move_frame: i32,
+}
+
+impl AI {
+    ///
+    /// Method to initialize and return an AI object
+    ///
+    /// # Arguments
+    /// * team       = Integer value of the team the AI plays for
+    /// * difficulty = Integer value that determines how "smart" the AI is; see Strategy::from_difficulty
+    ///
+    pub fn new(team: i32, difficulty: i32) -> Self {
+        AI {
+            team,
+            strategy: Strategy::from_difficulty(difficulty),
+            last_move_frame: -1,
+        }
+    }
+
+    ///
+    /// Method to return a grid with the AI's whole fleet placed. A Random AI places its ships anywhere; stronger AIs
+    /// compare several random layouts and keep the one with the fewest ships touching, since a hunt/target opponent
+    /// finds ships next to one it has sunk for free
+    ///
+    /// # Arguments
+    /// * rng = Random number generator used to place the ships
+    ///
+    pub fn place_fleet<R: Rng>(&self, rng: &mut R) -> Grid {
+        let mut grid = Grid::new();
+        grid.place_randomly(rng);
+        if self.strategy == Strategy::Random {
+            return grid;
+        }
+        let mut best = contacts(&grid);
+        for _ in 1..PLACEMENT_CANDIDATES {
+            if best == 0 {
+                break;
+            }
+            let mut candidate = Grid::new();
+            candidate.place_randomly(rng);
+            let count = contacts(&candidate);
+            if count < best {
+                grid = candidate;
+                best = count;
+            }
+        }
+        grid
+    }
+
+    ///
+    /// Method to pick the cell to fire at next. Only what the AI's player could see is used: the results of its shots
+    /// and the ships it has sunk. The grid must have a cell left that hasn't been fired at
+    ///
+    /// # Arguments
+    /// * target = Grid of the other team, being fired at
+    /// * rng    = Random number generator used to pick between equally good cells
+    ///
+    pub fn pick_shot<R: Rng>(&self, target: &Grid, rng: &mut R) -> GridPosition {
+        let open = open_cells(target);
+        let choices = match self.strategy {
+            Strategy::Random => open,
+            Strategy::HuntTarget => hunt_target_cells(target, &open),
+            Strategy::ProbabilityDensity => densest_cells(target, &open),
+        };
+        choices[rng.gen_range(0, choices.len())]
+    }
+}
+
+#[cfg(test)]
+mod ai_tests {
+    use super::rand::{SeedableRng, StdRng};
+    use super::*;
+
+    //Method to create a grid with only a ship of the given length placed horizontally at a cell
+    fn create_test_grid(name: &'static str, length: i32, x: i32, y: i32) -> Grid {
+        let mut grid = Grid::new();
+        assert!(grid.place(Ship::new(
+            name,
+            length,
+            GridPosition::new(x, y),
+            Orientation::Horizontal,
+        )));
+        grid
+    }
+
+    //Method to create a seeded random number generator so picks are repeatable
+    fn create_test_rng() -> StdRng {
+        SeedableRng::from_seed(&[7][..])
+    }
+
+    mod strategy {
+        use super::*;
+
+        mod from_difficulty {
+            use super::*;
+
+            #[test]
+            fn should_get_stronger_with_difficulty() {
+                assert_eq!(Strategy::from_difficulty(1), Strategy::Random);
+                assert_eq!(Strategy::from_difficulty(2), Strategy::HuntTarget);
+                assert_eq!(Strategy::from_difficulty(3), Strategy::ProbabilityDensity);
+                assert_eq!(Strategy::from_difficulty(6), Strategy::ProbabilityDensity);
+            }
+        }
+    }
+
+    mod ai {
+        use super::*;
+
+        mod place_fleet {
+            use super::*;
+
+            #[test]
+            fn should_place_fleet_without_touching_ships() {
+                let mut rng = create_test_rng();
+                let grid = AI::new(2, 3).place_fleet(&mut rng);
+                assert!(grid.is_fleet_placed());
+                assert_eq!(contacts(&grid), 0);
+                assert!(AI::new(2, 1).place_fleet(&mut rng).is_fleet_placed());
+            }
+        }
+
+        mod pick_shot {
+            use super::*;
+
+            #[test]
+            fn should_fire_at_last_open_cell() {
+                let mut rng = create_test_rng();
+                let mut grid = create_test_grid("Carrier", 5, 0, 0);
+                for y in 0..GRID_SIZE {
+                    for x in 0..GRID_SIZE {
+                        if (x, y) != (9, 9) {
+                            grid.fire(GridPosition::new(x, y));
+                        }
+                    }
+                }
+                for difficulty in 1..4 {
+                    let shot = AI::new(1, difficulty).pick_shot(&grid, &mut rng);
+                    assert_eq!(shot, GridPosition::new(9, 9));
+                }
+            }
+
+            #[test]
+            fn should_hunt_on_checkerboard() {
+                let mut rng = create_test_rng();
+                let grid = create_test_grid("Destroyer", 2, 0, 0);
+                for _ in 0..20 {
+                    let shot = AI::new(1, 2).pick_shot(&grid, &mut rng);
+                    assert_eq!((shot.x + shot.y) % 2, 0);
+                }
+            }
+
+            #[test]
+            fn should_target_cells_next_to_hit() {
+                let mut rng = create_test_rng();
+                let mut grid = create_test_grid("Cruiser", 3, 4, 5);
+                grid.fire(GridPosition::new(5, 5));
+                let neighbors = [
+                    GridPosition::new(4, 5),
+                    GridPosition::new(6, 5),
+                    GridPosition::new(5, 4),
+                    GridPosition::new(5, 6),
+                ];
+                for difficulty in 2..4 {
+                    let shot = AI::new(1, difficulty).pick_shot(&grid, &mut rng);
+                    assert!(neighbors.contains(&shot));
+                }
+            }
+
+            #[test]
+            fn should_extend_line_of_hits() {
+                let mut rng = create_test_rng();
+                let mut grid = create_test_grid("Carrier", 5, 3, 5);
+                grid.fire(GridPosition::new(5, 5));
+                grid.fire(GridPosition::new(6, 5));
+                for _ in 0..10 {
+                    let shot = AI::new(1, 2).pick_shot(&grid, &mut rng);
+                    assert!(shot == GridPosition::new(4, 5) || shot == GridPosition::new(7, 5));
+                }
+            }
+
+            #[test]
+            fn should_leave_sunk_ships_alone() {
+                let mut grid = create_test_grid("Destroyer", 2, 0, 0);
+                grid.fire(GridPosition::new(0, 0));
+                assert_eq!(unresolved_hits(&grid), vec![GridPosition::new(0, 0)]);
+                grid.fire(GridPosition::new(1, 0));
+                assert!(unresolved_hits(&grid).is_empty());
+                assert_eq!(remaining_lengths(&grid), vec![5, 4, 3, 3]);
+            }
+
+            #[test]
+            fn should_only_fire_where_ships_fit() {
+                let mut rng = create_test_rng();
+                let mut grid = create_test_grid("Destroyer", 2, 0, 9);
+                //Leave only a single open cell at (5, 5) and the destroyer's two cells open
+                for y in 0..GRID_SIZE {
+                    for x in 0..GRID_SIZE {
+                        if (x, y) != (5, 5) && y != 9 || x > 1 && y == 9 {
+                            grid.fire(GridPosition::new(x, y));
+                        }
+                    }
+                }
+                for _ in 0..10 {
+                    let shot = AI::new(1, 3).pick_shot(&grid, &mut rng);
+                    assert_eq!(shot.y, 9);
+                }
+            }
+
+            #[test]
+            fn should_prefer_center_with_no_shots() {
+                let mut rng = create_test_rng();
+                let grid = Grid::new();
+                let shot = AI::new(1, 3).pick_shot(&grid, &mut rng);
+                assert!(shot.x > 0 && shot.x < GRID_SIZE - 1);
+                assert!(shot.y > 0 && shot.y < GRID_SIZE - 1);
+            }
+        }
+    }
+}
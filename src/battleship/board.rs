@@ -0,0 +1,438 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate rand;
+
+use self::rand::Rng;
+use connect4::core::GridPosition;
+
+///Constant number of rows and columns in each player's grid
+pub const GRID_SIZE: i32 = 10;
+
+///Constant list of the ships in each fleet, as (name, length), in the order they are placed
+pub const FLEET: [(&str, i32); 5] = [
+    ("Carrier", 5),
+    ("Battleship", 4),
+    ("Cruiser", 3),
+    ("Submarine", 3),
+    ("Destroyer", 2),
+];
+
+///
+/// Enum for the direction a ship extends from its origin cell
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Orientation {
+    /// The ship covers cells to the right of its origin
+    Horizontal,
+    /// The ship covers cells below its origin
+    Vertical,
+}
+
+impl Orientation {
+    ///Returns the other orientation, used to rotate a ship being placed
+    pub fn rotated(self) -> Orientation {
+        match self {
+            Orientation::Horizontal => Orientation::Vertical,
+            Orientation::Vertical => Orientation::Horizontal,
+        }
+    }
+}
+
+///
+/// A struct representing a ship in a player's fleet
+///
+/// # Fields
+/// * name        = Name of the ship, shown when it is sunk
+/// * length      = Number of cells the ship covers
+/// * origin      = GridPosition of the top-left cell the ship covers (x is the column, y is the row)
+/// * orientation = Orientation enum for which way the ship extends from its origin
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Ship {
+    pub name: &'static str,
+    pub length: i32,
+    pub origin: GridPosition,
+    pub orientation: Orientation,
+}
+
+impl Ship {
+    /// Constructor for Ship.
+    pub fn new(
+        name: &'static str,
+        length: i32,
+        origin: GridPosition,
+        orientation: Orientation,
+    ) -> Self {
+        Ship {
+            name,
+            length,
+            origin,
+            orientation,
+        }
+    }
+
+    ///Returns the ships of a full fleet, each at the top-left corner and horizontal, in the order they are placed
+    pub fn fleet() -> Vec<Ship> {
+        FLEET
+            .iter()
+            .map(|(name, length)| {
+                Ship::new(
+                    name,
+                    *length,
+                    GridPosition::new(0, 0),
+                    Orientation::Horizontal,
+                )
+            })
+            .collect()
+    }
+
+    ///Returns every cell the ship covers, starting from its origin
+    pub fn cells(&self) -> Vec<GridPosition> {
+        (0..self.length)
+            .map(|i| match self.orientation {
+                Orientation::Horizontal => GridPosition::new(self.origin.x + i, self.origin.y),
+                Orientation::Vertical => GridPosition::new(self.origin.x, self.origin.y + i),
+            })
+            .collect()
+    }
+
+    ///Returns true if the ship covers the given cell
+    pub fn covers(&self, pos: GridPosition) -> bool {
+        self.cells().contains(&pos)
+    }
+}
+
+///
+/// Enum for the result of a shot at a cell of a player's grid
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShotResult {
+    /// The shot hit open water
+    Miss,
+    /// The shot hit a ship that still has cells left afloat
+    Hit,
+    /// The shot hit the last cell left afloat of the ship with the given index in the fleet
+    Sunk(usize),
+}
+
+///
+/// A struct for one player's grid: the ships they placed and the shots the other player fired at it
+///
+/// # Fields
+/// * ships = Vector of Ships placed so far, in the order they were placed
+/// * shots = Vector of (GridPosition, ShotResult) pairs for every shot fired at the grid, in the order they were fired
+///
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Grid {
+    pub ships: Vec<Ship>,
+    pub shots: Vec<(GridPosition, ShotResult)>,
+}
+
+///Returns true if the cell is inside a grid
+pub fn on_grid(pos: GridPosition) -> bool {
+    pos.x >= 0 && pos.x < GRID_SIZE && pos.y >= 0 && pos.y < GRID_SIZE
+}
+
+impl Grid {
+    ///Constructor for an empty grid with no ships placed and no shots fired
+    pub fn new() -> Self {
+        Grid::default()
+    }
+
+    ///Returns true if the ship fits inside the grid without overlapping a ship already placed
+    pub fn can_place(&self, ship: &Ship) -> bool {
+        ship.cells()
+            .iter()
+            .all(|pos| on_grid(*pos) && self.ship_at(*pos).is_none())
+    }
+
+    ///Method to add a ship to the grid. Returns false, leaving the grid as it was, if the ship can't be placed there
+    pub fn place(&mut self, ship: Ship) -> bool {
+        if !self.can_place(&ship) {
+            return false;
+        }
+        self.ships.push(ship);
+        true
+    }
+
+    ///Method to take a placed ship back off the grid, so it can be moved. Returns the ship covering the cell, if any
+    pub fn remove_ship_at(&mut self, pos: GridPosition) -> Option<Ship> {
+        let index = self.ship_at(pos)?;
+        Some(self.ships.remove(index))
+    }
+
+    ///
+    /// Method to place every ship of the fleet that isn't on the grid yet at a random free spot
+    ///
+    /// # Arguments
+    /// * rng = Random number generator used to pick each ship's spot and orientation
+    ///
+    pub fn place_randomly<R: Rng>(&mut self, rng: &mut R) {
+        for ship in self.unplaced_ships() {
+            loop {
+                let orientation = if rng.gen() {
+                    Orientation::Horizontal
+                } else {
+                    Orientation::Vertical
+                };
+                let origin =
+                    GridPosition::new(rng.gen_range(0, GRID_SIZE), rng.gen_range(0, GRID_SIZE));
+                if self.place(Ship::new(ship.name, ship.length, origin, orientation)) {
+                    break;
+                }
+            }
+        }
+    }
+
+    ///Returns the ships of the fleet that haven't been placed on the grid yet, in the order they are placed
+    pub fn unplaced_ships(&self) -> Vec<Ship> {
+        Ship::fleet()
+            .into_iter()
+            .filter(|ship| !self.ships.iter().any(|placed| placed.name == ship.name))
+            .collect()
+    }
+
+    ///Returns true if every ship of the fleet has been placed
+    pub fn is_fleet_placed(&self) -> bool {
+        self.unplaced_ships().is_empty()
+    }
+
+    ///Returns the index of the ship covering the cell, or None if the cell is open water
+    pub fn ship_at(&self, pos: GridPosition) -> Option<usize> {
+        self.ships.iter().position(|ship| ship.covers(pos))
+    }
+
+    ///Returns the result of the shot fired at the cell, or None if the cell hasn't been fired at
+    pub fn shot_at(&self, pos: GridPosition) -> Option<ShotResult> {
+        self.shots
+            .iter()
+            .find(|(shot, _)| *shot == pos)
+            .map(|(_, result)| *result)
+    }
+
+    ///
+    /// Method to fire a shot at a cell. Returns the ShotResult, or None if the cell is off the grid or was already
+    /// fired at
+    ///
+    /// # Arguments
+    /// * pos = GridPosition of the cell fired at
+    ///
+    pub fn fire(&mut self, pos: GridPosition) -> Option<ShotResult> {
+        if !on_grid(pos) || self.shot_at(pos).is_some() {
+            return None;
+        }
+        let result = match self.ship_at(pos) {
+            None => ShotResult::Miss,
+            Some(index) => {
+                let hits = self.ships[index]
+                    .cells()
+                    .iter()
+                    .filter(|cell| **cell == pos || self.shot_at(**cell).is_some())
+                    .count() as i32;
+                if hits == self.ships[index].length {
+                    ShotResult::Sunk(index)
+                } else {
+                    ShotResult::Hit
+                }
+            }
+        };
+        self.shots.push((pos, result));
+        Some(result)
+    }
+
+    ///Returns true if every cell of the ship with the given index has been hit
+    pub fn is_sunk(&self, index: usize) -> bool {
+        self.ships[index]
+            .cells()
+            .iter()
+            .all(|cell| self.shot_at(*cell).is_some())
+    }
+
+    ///Returns true if every ship on the grid has been sunk
+    pub fn all_sunk(&self) -> bool {
+        !self.ships.is_empty() && (0..self.ships.len()).all(|i| self.is_sunk(i))
+    }
+
+    ///Returns the number of shots fired at the grid that hit a ship
+    pub fn hit_count(&self) -> usize {
+        self.shots
+            .iter()
+            .filter(|(_, result)| *result != ShotResult::Miss)
+            .count()
+    }
+
+    ///Returns the ships on the grid that haven't been sunk yet
+    pub fn ships_afloat(&self) -> Vec<Ship> {
+        (0..self.ships.len())
+            .filter(|i| !self.is_sunk(*i))
+            .map(|i| self.ships[i])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod board_tests {
+    use super::rand::{SeedableRng, StdRng};
+    use super::*;
+
+    //Method to create a grid with the full fleet placed in rows 0 to 4, each ship starting at the left edge
+    fn create_test_grid() -> Grid {
+        let mut grid = Grid::new();
+        for (i, ship) in Ship::fleet().into_iter().enumerate() {
+            assert!(grid.place(Ship::new(
+                ship.name,
+                ship.length,
+                GridPosition::new(0, i as i32),
+                Orientation::Horizontal,
+            )));
+        }
+        grid
+    }
+
+    mod ship {
+        use super::*;
+
+        mod cells {
+            use super::*;
+
+            #[test]
+            fn should_extend_from_origin() {
+                let ship = Ship::new(
+                    "Destroyer",
+                    2,
+                    GridPosition::new(3, 4),
+                    Orientation::Vertical,
+                );
+                assert_eq!(
+                    ship.cells(),
+                    vec![GridPosition::new(3, 4), GridPosition::new(3, 5)]
+                );
+                let ship = Ship::new(
+                    "Cruiser",
+                    3,
+                    GridPosition::new(7, 0),
+                    Orientation::Horizontal,
+                );
+                assert!(ship.covers(GridPosition::new(9, 0)));
+                assert!(!ship.covers(GridPosition::new(6, 0)));
+            }
+        }
+    }
+
+    mod grid {
+        use super::*;
+
+        mod place {
+            use super::*;
+
+            #[test]
+            fn should_reject_ships_off_grid_or_overlapping() {
+                let mut grid = Grid::new();
+                let carrier = Ship::new(
+                    "Carrier",
+                    5,
+                    GridPosition::new(6, 0),
+                    Orientation::Horizontal,
+                );
+                assert!(!grid.place(carrier));
+                let carrier = Ship::new(
+                    "Carrier",
+                    5,
+                    GridPosition::new(5, 0),
+                    Orientation::Horizontal,
+                );
+                assert!(grid.place(carrier));
+                let crossing =
+                    Ship::new("Cruiser", 3, GridPosition::new(7, 0), Orientation::Vertical);
+                assert!(!grid.place(crossing));
+                //Ships may touch
+                let touching =
+                    Ship::new("Cruiser", 3, GridPosition::new(7, 1), Orientation::Vertical);
+                assert!(grid.place(touching));
+                assert_eq!(grid.ships.len(), 2);
+            }
+
+            #[test]
+            fn should_pick_up_placed_ship() {
+                let mut grid = create_test_grid();
+                assert!(grid.is_fleet_placed());
+                let ship = grid.remove_ship_at(GridPosition::new(2, 1));
+                assert_eq!(ship.map(|s| s.name), Some("Battleship"));
+                assert_eq!(grid.unplaced_ships().len(), 1);
+                assert_eq!(grid.remove_ship_at(GridPosition::new(9, 9)), None);
+            }
+        }
+
+        mod place_randomly {
+            use super::*;
+
+            #[test]
+            fn should_place_remaining_fleet() {
+                let mut rng: StdRng = SeedableRng::from_seed(&[7][..]);
+                let mut grid = Grid::new();
+                let destroyer = Ship::new(
+                    "Destroyer",
+                    2,
+                    GridPosition::new(0, 0),
+                    Orientation::Vertical,
+                );
+                grid.place(destroyer);
+                grid.place_randomly(&mut rng);
+                assert!(grid.is_fleet_placed());
+                assert_eq!(grid.ships.len(), FLEET.len());
+                assert_eq!(grid.ships[0], destroyer);
+                let cells: usize = grid.ships.iter().map(|s| s.length as usize).sum();
+                let covered = (0..GRID_SIZE)
+                    .flat_map(|x| (0..GRID_SIZE).map(move |y| GridPosition::new(x, y)))
+                    .filter(|pos| grid.ship_at(*pos).is_some())
+                    .count();
+                assert_eq!(covered, cells);
+            }
+        }
+
+        mod fire {
+            use super::*;
+
+            #[test]
+            fn should_report_miss_hit_and_sunk() {
+                let mut grid = create_test_grid();
+                assert_eq!(grid.fire(GridPosition::new(9, 9)), Some(ShotResult::Miss));
+                assert_eq!(grid.fire(GridPosition::new(0, 4)), Some(ShotResult::Hit));
+                assert_eq!(
+                    grid.fire(GridPosition::new(1, 4)),
+                    Some(ShotResult::Sunk(4))
+                );
+                assert!(grid.is_sunk(4));
+                assert_eq!(grid.hit_count(), 2);
+                assert_eq!(grid.ships_afloat().len(), FLEET.len() - 1);
+            }
+
+            #[test]
+            fn should_not_fire_twice_or_off_grid() {
+                let mut grid = create_test_grid();
+                assert!(grid.fire(GridPosition::new(3, 3)).is_some());
+                assert_eq!(grid.fire(GridPosition::new(3, 3)), None);
+                assert_eq!(grid.fire(GridPosition::new(10, 0)), None);
+                assert_eq!(grid.shots.len(), 1);
+            }
+        }
+
+        mod all_sunk {
+            use super::*;
+
+            #[test]
+            fn should_need_every_ship_cell_hit() {
+                let mut grid = create_test_grid();
+                let cells: Vec<GridPosition> = grid.ships.iter().flat_map(|s| s.cells()).collect();
+                for pos in cells {
+                    assert!(!grid.all_sunk());
+                    assert_ne!(grid.fire(pos), Some(ShotResult::Miss));
+                }
+                assert!(grid.all_sunk());
+                assert!(!Grid::new().all_sunk());
+            }
+        }
+    }
+}
@@ -0,0 +1,917 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+extern crate rand;
+
+use battleship::ai::AI;
+use battleship::board::{on_grid, Grid, Orientation, Ship, ShotResult, GRID_SIZE};
+use connect4::button::{create_button, draw_text, mouse_location, Button};
+use connect4::core::{GameAction, GridPosition, MyColor, PlayerInfo};
+use ggez::event::KeyCode;
+use ggez::input::mouse::MouseButton;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+use history::MatchRecord;
+use screen::GameScreen;
+use settings::Settings;
+use sound::SoundEffect;
+
+///Constant dimensions for the Battleship window, fitting both grids side by side
+pub const BATTLESHIP_SCREEN_SIZE: (f32, f32) = (960.0, 640.0);
+
+///Constant pixel size of each grid cell
+const CELL_SIZE: f32 = 40.0;
+
+///Constant top-left corners of the two grids: the left one shows the player's own fleet, the right one the enemy's waters
+const GRID_POSITIONS: [(f32, f32); 2] = [(50.0, 110.0), (510.0, 110.0)];
+
+///Constant font size for the status line, grid labels and messages
+const BATTLESHIP_FONT_SIZE: f32 = 24.0;
+
+///Constant y location of the first message line under the grids
+const MESSAGE_POS_Y: f32 = 520.0;
+
+///Constant y location of the row of buttons along the bottom of the screen
+const BUTTON_POS_Y: f32 = 585.0;
+
+///Constant radius of the marker drawn for a shot that hit open water
+const MISS_RADIUS: f32 = 6.0;
+
+///Constant radius of the marker drawn for a shot that hit a ship
+const HIT_RADIUS: f32 = 12.0;
+
+///
+/// Enum for the part of a Battleship game being played
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BattlePhase {
+    /// Each player in turn places their fleet on their own grid
+    Placing,
+    /// Players take turns firing one shot at the other player's grid
+    Firing,
+    /// One player's fleet has been sunk
+    GameOver,
+}
+
+///Returns the grid cell under a point for the grid with the given index in GRID_POSITIONS, or None if it is off the grid
+fn cell_at(grid_index: usize, point: Point2<f32>) -> Option<GridPosition> {
+    let origin = GRID_POSITIONS[grid_index];
+    let pos = GridPosition::new(
+        ((point.x - origin.0) / CELL_SIZE).floor() as i32,
+        ((point.y - origin.1) / CELL_SIZE).floor() as i32,
+    );
+    if on_grid(pos) {
+        Some(pos)
+    } else {
+        None
+    }
+}
+
+///Returns the rectangle covered by a cell of the grid with the given index in GRID_POSITIONS
+fn cell_rect(grid_index: usize, pos: GridPosition) -> graphics::Rect {
+    let origin = GRID_POSITIONS[grid_index];
+    graphics::Rect::new(
+        origin.0 + pos.x as f32 * CELL_SIZE,
+        origin.1 + pos.y as f32 * CELL_SIZE,
+        CELL_SIZE,
+        CELL_SIZE,
+    )
+}
+
+///
+/// A struct that contains the state of a game of Battleship. When two people share the screen, a hand-off screen
+/// hides the grids whenever the turn passes to the other player. Against the AI, the grids are always shown from the
+/// human player's side
+///
+/// # Fields
+/// * grids             = Vector of Grids, where grids[i] holds team i + 1's fleet and the shots fired at it
+/// * team_names        = Vector of player names, where team_names[i] is the name for team i + 1
+/// * team_colors       = Vector of MyColor objects, where team_colors[i] is the color team i + 1's ships are drawn in
+/// * team              = Integer value (1 or 2) of the team placing ships or firing
+/// * ai_players        = Vector of AI objects, one for each team played by the AI
+/// * ai_difficulty     = Difficulty given to the AI players, recorded in the match history
+/// * ai_move_delay     = Number of frames an AI shows the cell it aims at before firing, and its shot before ending its turn
+/// * aim               = Option of the cell the AI is about to fire at, or None
+/// * frames            = Integer counter for the number of times the update method is called; times AI shots
+/// * phase             = BattlePhase enum for the part of the game being played
+/// * hand_off          = Boolean indicating if the hand-off screen is hiding the grids until the next player is ready
+/// * held_ship         = Option of the Ship being placed, drawn under the mouse, or None if there is none to place
+/// * grab_offset       = Index of the cell of the held ship that is under the mouse
+/// * hover             = Option of the cell under the mouse in the grid that can be clicked, or None
+/// * shot_fired        = Boolean indicating if the team has fired their shot this turn
+/// * message           = Text shown under the grids, e.g. the result of the last shot
+/// * main_menu_button  = Button drawn to return to the main menu
+/// * random_button     = Button drawn while placing to put the ships not placed yet at random spots
+/// * clear_button      = Button drawn while placing to take every ship back off the grid
+/// * done_button       = Button drawn once the whole fleet is placed to end the team's placement
+/// * end_turn_button   = Button drawn once the team has fired to pass the turn
+/// * ready_button      = Button drawn on the hand-off screen to show the next player's grids
+/// * play_again_button = Button drawn once the game is over to start a new game with the same players
+/// * background_color  = Color drawn behind the grids
+/// * sound_queue       = Vector of SoundEffect enums triggered since the main menu last played them
+/// * moves             = Vector of shots fired so far, in order, each stored as row * GRID_SIZE + column
+/// * elapsed           = Seconds the current game has been played for
+/// * finished_match    = Option of a MatchRecord for a game that just ended, taken by the main menu to be saved
+///
+pub struct BattleshipState {
+    grids: Vec<Grid>,
+    team_names: Vec<String>,
+    team_colors: Vec<MyColor>,
+    team: i32,
+    ai_players: Vec<AI>,
+    ai_difficulty: i32,
+    ai_move_delay: i32,
+    aim: Option<GridPosition>,
+    frames: usize,
+    phase: BattlePhase,
+    hand_off: bool,
+    held_ship: Option<Ship>,
+    grab_offset: i32,
+    hover: Option<GridPosition>,
+    shot_fired: bool,
+    message: String,
+    main_menu_button: Button,
+    random_button: Button,
+    clear_button: Button,
+    done_button: Button,
+    end_turn_button: Button,
+    ready_button: Button,
+    play_again_button: Button,
+    background_color: graphics::Color,
+    pub sound_queue: Vec<SoundEffect>,
+    moves: Vec<i32>,
+    elapsed: f32,
+    pub finished_match: Option<MatchRecord>,
+}
+
+impl BattleshipState {
+    ///Constructor - player_info holds the name, color and whether the AI plays for team 1 and team 2, in that order,
+    ///and settings holds the AI and theme options. Teams without player info get the defaults
+    pub fn new(
+        ctx: &mut Context,
+        player_info: Vec<PlayerInfo>,
+        settings: &Settings,
+    ) -> BattleshipState {
+        let mut team_names = Vec::new();
+        let mut team_colors = Vec::new();
+        let mut ai_players = Vec::new();
+        let given = player_info.len();
+        for (i, info) in player_info
+            .into_iter()
+            .chain(PlayerInfo::defaults().into_iter().skip(given))
+            .take(2)
+            .enumerate()
+        {
+            if info.is_ai {
+                ai_players.push(AI::new(i as i32 + 1, settings.ai_difficulty));
+            }
+            team_names.push(info.name);
+            team_colors.push(info.color);
+        }
+        let left = GRID_POSITIONS[0].0;
+        let right = GRID_POSITIONS[1].0;
+        let mut state = BattleshipState {
+            grids: Vec::new(),
+            team_names,
+            team_colors,
+            team: 1,
+            ai_players,
+            ai_difficulty: settings.ai_difficulty,
+            ai_move_delay: settings.ai_move_delay,
+            aim: None,
+            frames: 0,
+            phase: BattlePhase::Placing,
+            hand_off: false,
+            held_ship: None,
+            grab_offset: 0,
+            hover: None,
+            shot_fired: false,
+            message: String::new(),
+            main_menu_button: create_button(
+                ctx,
+                "Main Menu",
+                (left, BUTTON_POS_Y),
+                BATTLESHIP_FONT_SIZE,
+                (MyColor::Brown, MyColor::Green),
+            ),
+            random_button: create_button(
+                ctx,
+                "Random",
+                (right, BUTTON_POS_Y),
+                BATTLESHIP_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            clear_button: create_button(
+                ctx,
+                "Clear",
+                (right + 130.0, BUTTON_POS_Y),
+                BATTLESHIP_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            done_button: create_button(
+                ctx,
+                "Done",
+                (right + 230.0, BUTTON_POS_Y),
+                BATTLESHIP_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            end_turn_button: create_button(
+                ctx,
+                "End Turn",
+                (right, BUTTON_POS_Y),
+                BATTLESHIP_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            ready_button: create_button(
+                ctx,
+                "Ready",
+                (right, BUTTON_POS_Y),
+                BATTLESHIP_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            play_again_button: create_button(
+                ctx,
+                "Play Again",
+                (right, BUTTON_POS_Y),
+                BATTLESHIP_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            background_color: settings.theme.game_background(),
+            sound_queue: Vec::new(),
+            moves: Vec::new(),
+            elapsed: 0.0,
+            finished_match: None,
+        };
+        state.reset_game();
+        state
+    }
+
+    ///Method to clear both grids and start again, with the AI placing its fleets and the first human player placing next
+    fn reset_game(&mut self) {
+        self.grids = vec![Grid::new(), Grid::new()];
+        for ai in &mut self.ai_players {
+            self.grids[ai.team as usize - 1] = ai.place_fleet(&mut rand::thread_rng());
+            ai.last_move_frame = -1;
+        }
+        self.phase = BattlePhase::Placing;
+        self.hand_off = false;
+        self.shot_fired = false;
+        self.aim = None;
+        self.message.clear();
+        self.moves.clear();
+        self.elapsed = 0.0;
+        self.next_placement();
+        self.update_buttons();
+    }
+
+    ///Returns true if the AI plays for the team
+    fn is_ai(&self, team: i32) -> bool {
+        self.ai_players.iter().any(|ai| ai.team == team)
+    }
+
+    ///Returns the team whose side the grids are shown from: the team to play if human, else the human they play against
+    fn viewing_team(&self) -> i32 {
+        if !self.is_ai(self.team) || self.is_ai(self.other_team()) {
+            self.team
+        } else {
+            self.other_team()
+        }
+    }
+
+    ///Method to move on to the first team that hasn't placed their fleet, or to firing with team 1 once both have
+    fn next_placement(&mut self) {
+        match (1..3).find(|team| !self.grids[*team as usize - 1].is_fleet_placed()) {
+            Some(team) => {
+                self.team = team;
+                self.hold_next_ship();
+            }
+            None => {
+                self.team = 1;
+                self.phase = BattlePhase::Firing;
+                self.held_ship = None;
+            }
+        }
+    }
+
+    ///Returns the grid of the team placing ships or firing
+    fn own_grid(&self) -> &Grid {
+        &self.grids[self.team as usize - 1]
+    }
+
+    ///Returns the team that isn't placing ships or firing
+    fn other_team(&self) -> i32 {
+        self.team % 2 + 1
+    }
+
+    ///Method to pick up the first ship of the team's fleet that hasn't been placed yet, if any
+    fn hold_next_ship(&mut self) {
+        self.held_ship = self.own_grid().unplaced_ships().into_iter().next();
+        self.grab_offset = 0;
+    }
+
+    ///Returns the held ship moved so the grabbed cell is under the hovered cell, or None if either is missing
+    fn held_ship_at_hover(&self) -> Option<Ship> {
+        let (mut ship, hover) = (self.held_ship?, self.hover?);
+        ship.origin = match ship.orientation {
+            Orientation::Horizontal => GridPosition::new(hover.x - self.grab_offset, hover.y),
+            Orientation::Vertical => GridPosition::new(hover.x, hover.y - self.grab_offset),
+        };
+        Some(ship)
+    }
+
+    ///Method to show only the buttons that can be used in the current part of the game
+    fn update_buttons(&mut self) {
+        let placing = self.phase == BattlePhase::Placing && !self.hand_off;
+        self.ready_button.active = self.hand_off;
+        self.random_button.active = placing;
+        self.clear_button.active = placing;
+        self.done_button.active =
+            placing && self.held_ship.is_none() && self.own_grid().is_fleet_placed();
+        self.end_turn_button.active = self.phase == BattlePhase::Firing
+            && !self.hand_off
+            && self.shot_fired
+            && !self.is_ai(self.team);
+        self.play_again_button.active = self.phase == BattlePhase::GameOver;
+    }
+
+    ///Method to rotate the held ship, keeping the grabbed cell under the mouse
+    fn rotate_held_ship(&mut self) {
+        if let Some(ref mut ship) = self.held_ship {
+            ship.orientation = ship.orientation.rotated();
+        }
+    }
+
+    ///Method to place the held ship at the hovered cell, then pick up the next ship. Returns false if it doesn't fit
+    fn place_held_ship(&mut self) -> bool {
+        let ship = match self.held_ship_at_hover() {
+            Some(ship) => ship,
+            None => return false,
+        };
+        if !self.grids[self.team as usize - 1].place(ship) {
+            self.sound_queue.push(SoundEffect::InvalidMove);
+            return false;
+        }
+        self.sound_queue.push(SoundEffect::Drop);
+        self.hold_next_ship();
+        true
+    }
+
+    ///Method to end the team's placement: the other team places next if they haven't yet, then the game moves on to
+    ///firing with team 1. The hand-off screen is only needed when two people share the screen
+    fn finish_placement(&mut self) {
+        println!(
+            "{} placed their fleet",
+            self.team_names[self.team as usize - 1]
+        );
+        self.next_placement();
+        self.hand_off = self.ai_players.is_empty();
+        self.message.clear();
+    }
+
+    ///
+    /// Method to fire the team's shot for the turn at a cell of the other team's grid. Returns false if the team
+    /// already fired this turn or the cell was fired at before
+    ///
+    /// # Arguments
+    /// * pos = GridPosition of the cell fired at
+    ///
+    fn fire(&mut self, pos: GridPosition) -> bool {
+        if self.shot_fired {
+            return false;
+        }
+        let other = self.other_team() as usize - 1;
+        let result = match self.grids[other].fire(pos) {
+            Some(result) => result,
+            None => {
+                self.sound_queue.push(SoundEffect::InvalidMove);
+                return false;
+            }
+        };
+        self.moves.push(pos.y * GRID_SIZE + pos.x);
+        self.shot_fired = true;
+        self.sound_queue.push(SoundEffect::Drop);
+        let name = self.team_names[self.team as usize - 1].clone();
+        //Shots are described from the side of the player watching
+        self.message = match result {
+            ShotResult::Miss if self.viewing_team() == self.team => String::from("Miss"),
+            ShotResult::Hit if self.viewing_team() == self.team => String::from("Hit!"),
+            ShotResult::Sunk(index) if self.viewing_team() == self.team => {
+                format!("You sank their {}!", self.grids[other].ships[index].name)
+            }
+            ShotResult::Miss => format!("{} missed", name),
+            ShotResult::Hit => format!("{} hit your ship!", name),
+            ShotResult::Sunk(index) => {
+                format!(
+                    "{} sank your {}!",
+                    name, self.grids[other].ships[index].name
+                )
+            }
+        };
+        println!("{} fires at ({}, {}): {:?}", name, pos.x, pos.y, result);
+        if self.grids[other].all_sunk() {
+            println!("{} wins; Game ends", name);
+            self.phase = BattlePhase::GameOver;
+            self.message = format!("{} sank the whole fleet!", name);
+            self.sound_queue.push(SoundEffect::Win);
+            self.finish_match(self.team);
+        }
+        true
+    }
+
+    ///Method to pass the turn to the other team, showing the hand-off screen until they are ready if both are human
+    fn end_turn(&mut self) {
+        self.team = self.other_team();
+        self.shot_fired = false;
+        self.aim = None;
+        self.hand_off = self.ai_players.is_empty();
+        //Against the AI, the last shot's result stays up until the next shot
+        if self.hand_off {
+            self.message.clear();
+        }
+    }
+
+    ///
+    /// Method to create the MatchRecord for a game that just ended, so it can be added to the match history
+    ///
+    /// # Arguments
+    /// * winner = Team number of the winner
+    ///
+    fn finish_match(&mut self, winner: i32) {
+        let ai_teams: Vec<i32> = self.ai_players.iter().map(|ai| ai.team).collect();
+        self.finished_match = Some(MatchRecord::for_teams(
+            "Battleship",
+            &self.team_names,
+            &ai_teams,
+            self.ai_difficulty,
+            self.moves.clone(),
+            winner,
+            self.elapsed,
+        ));
+    }
+
+    ///Method to draw the screen shown between turns, which hides both grids until the next player clicks Ready
+    fn draw_hand_off(&self, ctx: &mut Context) -> GameResult {
+        let name = &self.team_names[self.team as usize - 1];
+        let other = &self.team_names[self.other_team() as usize - 1];
+        draw_text(
+            ctx,
+            &format!("Pass to {}", name),
+            (GRID_POSITIONS[0].0, 200.0),
+            48.0,
+            self.team_colors[self.team as usize - 1],
+        )?;
+        draw_text(
+            ctx,
+            &format!(
+                "{}, look away! {}, click Ready when no one else can see",
+                other, name
+            ),
+            (GRID_POSITIONS[0].0, 280.0),
+            BATTLESHIP_FONT_SIZE,
+            MyColor::White,
+        )
+    }
+
+    ///Method to draw the status line, both grids with their labels and the message lines under them
+    fn draw_grids(&self, ctx: &mut Context) -> GameResult {
+        let name = &self.team_names[self.team as usize - 1];
+        let viewer = self.viewing_team();
+        let (status, labels) = match self.phase {
+            BattlePhase::Placing => (
+                format!("{}: place your fleet", name),
+                [String::from("Your Fleet"), String::from("Ships")],
+            ),
+            BattlePhase::Firing if self.is_ai(self.team) => (
+                format!("{} is firing...", name),
+                [String::from("Your Fleet"), String::from("Enemy Waters")],
+            ),
+            BattlePhase::Firing => (
+                format!("{}: fire at the enemy", name),
+                [String::from("Your Fleet"), String::from("Enemy Waters")],
+            ),
+            BattlePhase::GameOver => (
+                format!("{} wins!", name),
+                [
+                    format!("{}'s Fleet", self.team_names[0]),
+                    format!("{}'s Fleet", self.team_names[1]),
+                ],
+            ),
+        };
+        draw_text(
+            ctx,
+            &status,
+            (GRID_POSITIONS[0].0, 20.0),
+            40.0,
+            self.team_colors[self.team as usize - 1],
+        )?;
+        for (i, label) in labels.iter().enumerate() {
+            draw_text(
+                ctx,
+                label,
+                (GRID_POSITIONS[i].0, GRID_POSITIONS[i].1 - 35.0),
+                BATTLESHIP_FONT_SIZE,
+                MyColor::White,
+            )?;
+        }
+
+        let mut mb = graphics::MeshBuilder::new();
+        match self.phase {
+            BattlePhase::Placing => {
+                self.build_grid(&mut mb, 0, self.team as usize - 1, true)?;
+                if let Some(ship) = self.held_ship_at_hover() {
+                    let color = if self.own_grid().can_place(&ship) {
+                        graphics::Color::from_rgba(0, 200, 0, 160)
+                    } else {
+                        graphics::Color::from_rgba(200, 0, 0, 160)
+                    };
+                    for pos in ship.cells().into_iter().filter(|pos| on_grid(*pos)) {
+                        mb.rectangle(graphics::DrawMode::fill(), cell_rect(0, pos), color);
+                    }
+                }
+            }
+            BattlePhase::Firing => {
+                self.build_grid(&mut mb, 0, viewer as usize - 1, true)?;
+                self.build_grid(&mut mb, 1, viewer as usize % 2, false)?;
+                //Outline the cell the player is pointing at, or the AI is aiming at on whichever grid it fires at
+                let (grid_index, target) = if self.is_ai(self.team) {
+                    (if viewer == self.team { 1 } else { 0 }, self.aim)
+                } else {
+                    (1, self.hover)
+                };
+                if let Some(pos) = target.filter(|_| !self.shot_fired) {
+                    mb.rectangle(
+                        graphics::DrawMode::stroke(3.0),
+                        cell_rect(grid_index, pos),
+                        MyColor::Yellow.get_draw_color(),
+                    );
+                }
+            }
+            BattlePhase::GameOver => {
+                self.build_grid(&mut mb, 0, 0, true)?;
+                self.build_grid(&mut mb, 1, 1, true)?;
+            }
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))?;
+
+        if self.phase == BattlePhase::Placing {
+            self.draw_ship_list(ctx)?;
+        }
+        let mut lines = Vec::new();
+        if self.phase == BattlePhase::GameOver {
+            lines.push(self.message.clone());
+            for i in 0..2 {
+                lines.push(self.summary(i));
+            }
+        } else if !self.message.is_empty() {
+            lines.push(self.message.clone());
+        }
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(
+                ctx,
+                line,
+                (GRID_POSITIONS[0].0, MESSAGE_POS_Y + 22.0 * i as f32),
+                if i == 0 { BATTLESHIP_FONT_SIZE } else { 20.0 },
+                MyColor::White,
+            )?;
+        }
+        Ok(())
+    }
+
+    ///Returns the game-over summary line for team i + 1: their shots, hits, accuracy and ships left afloat
+    fn summary(&self, i: usize) -> String {
+        //Team i + 1's shots were fired at the other team's grid
+        let target = &self.grids[1 - i];
+        let shots = target.shots.len();
+        let hits = target.hit_count();
+        let accuracy = if shots == 0 {
+            0.0
+        } else {
+            100.0 * hits as f32 / shots as f32
+        };
+        format!(
+            "{}: {} shots, {} hits ({:.0}% accuracy), {} of {} ships left",
+            self.team_names[i],
+            shots,
+            hits,
+            accuracy,
+            self.grids[i].ships_afloat().len(),
+            self.grids[i].ships.len()
+        )
+    }
+
+    ///Method to draw the list of ships to place right of the grid, with the held ship highlighted, and how to place them
+    fn draw_ship_list(&self, ctx: &mut Context) -> GameResult {
+        let unplaced = self.own_grid().unplaced_ships();
+        for (i, ship) in Ship::fleet().iter().enumerate() {
+            let color = if self.held_ship.map(|s| s.name) == Some(ship.name) {
+                MyColor::Yellow
+            } else if unplaced.iter().any(|s| s.name == ship.name) {
+                MyColor::White
+            } else {
+                MyColor::Green
+            };
+            draw_text(
+                ctx,
+                &format!("{} ({})", ship.name, ship.length),
+                (GRID_POSITIONS[1].0, GRID_POSITIONS[1].1 + 35.0 * i as f32),
+                BATTLESHIP_FONT_SIZE,
+                color,
+            )?;
+        }
+        let help = [
+            "Click your grid to place the yellow ship",
+            "Right-click or press R to rotate it",
+            "Drag a placed ship to move it",
+        ];
+        for (i, line) in help.iter().enumerate() {
+            draw_text(
+                ctx,
+                line,
+                (
+                    GRID_POSITIONS[1].0,
+                    GRID_POSITIONS[1].1 + 230.0 + 28.0 * i as f32,
+                ),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Method to add a grid, with its ships and shot markers, to a MeshBuilder
+    ///
+    /// # Arguments
+    /// * mb         = MeshBuilder the grid is added to
+    /// * grid_index = Index in GRID_POSITIONS of where the grid is drawn
+    /// * team_index = Index of the grid drawn in grids (team number - 1)
+    /// * show_ships = Boolean indicating if ships still afloat are drawn; sunk ships are always shown
+    ///
+    fn build_grid(
+        &self,
+        mb: &mut graphics::MeshBuilder,
+        grid_index: usize,
+        team_index: usize,
+        show_ships: bool,
+    ) -> GameResult {
+        let grid = &self.grids[team_index];
+        let origin = GRID_POSITIONS[grid_index];
+        let size = CELL_SIZE * GRID_SIZE as f32;
+        mb.rectangle(
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(origin.0, origin.1, size, size),
+            graphics::Color::from_rgba(20, 60, 120, 255),
+        );
+        for (i, ship) in grid.ships.iter().enumerate() {
+            let sunk = grid.is_sunk(i);
+            if !show_ships && !sunk {
+                continue;
+            }
+            let color = if sunk {
+                graphics::Color::from_rgba(90, 90, 90, 255)
+            } else {
+                self.team_colors[team_index].get_draw_color()
+            };
+            let cells = ship.cells();
+            let first = cell_rect(grid_index, cells[0]);
+            let last = cell_rect(grid_index, cells[cells.len() - 1]);
+            mb.rectangle(
+                graphics::DrawMode::fill(),
+                graphics::Rect::new(
+                    first.x + 4.0,
+                    first.y + 4.0,
+                    last.right() - first.x - 8.0,
+                    last.bottom() - first.y - 8.0,
+                ),
+                color,
+            );
+        }
+        let line_color = graphics::Color::from_rgba(120, 160, 220, 255);
+        for i in 0..=GRID_SIZE {
+            let offset = i as f32 * CELL_SIZE;
+            mb.line(
+                &[
+                    Point2 {
+                        x: origin.0 + offset,
+                        y: origin.1,
+                    },
+                    Point2 {
+                        x: origin.0 + offset,
+                        y: origin.1 + size,
+                    },
+                ],
+                1.0,
+                line_color,
+            )?;
+            mb.line(
+                &[
+                    Point2 {
+                        x: origin.0,
+                        y: origin.1 + offset,
+                    },
+                    Point2 {
+                        x: origin.0 + size,
+                        y: origin.1 + offset,
+                    },
+                ],
+                1.0,
+                line_color,
+            )?;
+        }
+        for (pos, result) in &grid.shots {
+            let rect = cell_rect(grid_index, *pos);
+            let center = Point2 {
+                x: rect.x + CELL_SIZE / 2.0,
+                y: rect.y + CELL_SIZE / 2.0,
+            };
+            if *result == ShotResult::Miss {
+                mb.circle(
+                    graphics::DrawMode::fill(),
+                    center,
+                    MISS_RADIUS,
+                    1.0,
+                    graphics::WHITE,
+                );
+            } else {
+                mb.circle(
+                    graphics::DrawMode::fill(),
+                    center,
+                    HIT_RADIUS,
+                    1.0,
+                    MyColor::Red.get_draw_color(),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl GameScreen for BattleshipState {
+    fn screen_size(&self) -> (f32, f32) {
+        BATTLESHIP_SCREEN_SIZE
+    }
+
+    /// Update method - counts the time the game has been played for and plays the AI's turns
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        self.frames += 1; //Timing mechanism for AI shots
+        if self.phase != BattlePhase::GameOver && !self.hand_off {
+            self.elapsed += ggez::timer::delta(_ctx).as_secs_f32();
+        }
+        if self.phase != BattlePhase::Firing {
+            return Ok(());
+        }
+        let team = self.team;
+        if let Some(i) = self.ai_players.iter().position(|ai| ai.team == team) {
+            let target = self.other_team() as usize - 1;
+            //Pick a cell and aim at it, fire once enough frames have passed, then end the turn after as many again
+            if self.ai_players[i].last_move_frame < 0 {
+                self.aim = Some(
+                    self.ai_players[i].pick_shot(&self.grids[target], &mut rand::thread_rng()),
+                );
+                self.ai_players[i].last_move_frame = self.frames as i32;
+            } else if self.frames
+                > (self.ai_players[i].last_move_frame + self.ai_move_delay) as usize
+            {
+                if self.shot_fired {
+                    self.ai_players[i].last_move_frame = -1;
+                    self.end_turn();
+                } else if let Some(pos) = self.aim {
+                    self.fire(pos);
+                    self.ai_players[i].last_move_frame = self.frames as i32;
+                }
+            }
+            self.update_buttons();
+        }
+        Ok(())
+    }
+
+    ///Draw method to render the grids, messages and buttons, or the hand-off screen between turns
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::clear(ctx, self.background_color);
+        if self.hand_off {
+            self.draw_hand_off(ctx)?;
+        } else {
+            self.draw_grids(ctx)?;
+        }
+        self.main_menu_button.draw(ctx)?;
+        self.random_button.draw(ctx)?;
+        self.clear_button.draw(ctx)?;
+        self.done_button.draw(ctx)?;
+        self.end_turn_button.draw(ctx)?;
+        self.ready_button.draw(ctx)?;
+        self.play_again_button.draw(ctx)?;
+        graphics::present(ctx)?;
+        ggez::timer::yield_now();
+        Ok(())
+    }
+
+    ///Method to update the hovered cell and the highlighted buttons when the mouse moves
+    fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        let point = mouse_location(ctx);
+        self.hover = match self.phase {
+            _ if self.hand_off => None,
+            BattlePhase::Placing => cell_at(0, point),
+            BattlePhase::Firing => cell_at(1, point),
+            BattlePhase::GameOver => None,
+        };
+        self.main_menu_button.check_button_under_mouse(ctx);
+        self.random_button.check_button_under_mouse(ctx);
+        self.clear_button.check_button_under_mouse(ctx);
+        self.done_button.check_button_under_mouse(ctx);
+        self.end_turn_button.check_button_under_mouse(ctx);
+        self.ready_button.check_button_under_mouse(ctx);
+        self.play_again_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method to pick up a placed ship under the mouse so it can be dragged, or rotate the held ship on a right-click
+    fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton) {
+        self.mouse_motion_event(ctx);
+        if self.phase != BattlePhase::Placing || self.hand_off {
+            return;
+        }
+        if button == MouseButton::Right {
+            self.rotate_held_ship();
+            return;
+        }
+        if let Some(hover) = self.hover {
+            let team_index = self.team as usize - 1;
+            if let Some(ship) = self.grids[team_index].remove_ship_at(hover) {
+                self.grab_offset = ship
+                    .cells()
+                    .iter()
+                    .position(|pos| *pos == hover)
+                    .unwrap_or(0) as i32;
+                self.held_ship = Some(ship);
+                self.update_buttons();
+            }
+        }
+    }
+
+    ///Method to handle a click on the Battleship screen, returning what the main menu should do next
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton) -> GameAction {
+        self.mouse_motion_event(ctx);
+        if self.main_menu_button.check_button_under_mouse(ctx) {
+            println!("Main Menu Button pressed; Main Menu should pop up");
+            self.sound_queue.push(SoundEffect::Click);
+            return GameAction::MainMenu;
+        }
+        if self.ready_button.check_button_under_mouse(ctx) {
+            self.sound_queue.push(SoundEffect::Click);
+            self.hand_off = false;
+        } else if self.random_button.check_button_under_mouse(ctx) {
+            self.sound_queue.push(SoundEffect::Click);
+            let team_index = self.team as usize - 1;
+            self.grids[team_index].place_randomly(&mut rand::thread_rng());
+            self.held_ship = None;
+        } else if self.clear_button.check_button_under_mouse(ctx) {
+            self.sound_queue.push(SoundEffect::Click);
+            self.grids[self.team as usize - 1] = Grid::new();
+            self.hold_next_ship();
+        } else if self.done_button.check_button_under_mouse(ctx) {
+            self.sound_queue.push(SoundEffect::Click);
+            self.finish_placement();
+        } else if self.end_turn_button.check_button_under_mouse(ctx) {
+            self.sound_queue.push(SoundEffect::Click);
+            self.end_turn();
+        } else if self.play_again_button.check_button_under_mouse(ctx) {
+            println!("Play Again button pressed; Grids reset");
+            self.sound_queue.push(SoundEffect::Click);
+            self.reset_game();
+        } else if button == MouseButton::Left && !self.hand_off {
+            if let Some(hover) = self.hover {
+                match self.phase {
+                    BattlePhase::Placing => {
+                        self.place_held_ship();
+                    }
+                    BattlePhase::Firing if !self.is_ai(self.team) => {
+                        self.fire(hover);
+                    }
+                    BattlePhase::Firing => (),
+                    BattlePhase::GameOver => (),
+                }
+            }
+        }
+        self.update_buttons();
+        GameAction::None
+    }
+
+    ///Method active whenever a key is pressed. The R key rotates the ship being placed
+    fn key_down_event(&mut self, keycode: KeyCode) {
+        if keycode == KeyCode::R && self.phase == BattlePhase::Placing && !self.hand_off {
+            self.rotate_held_ship();
+        }
+    }
+
+    fn drain_sounds(&mut self) -> Vec<SoundEffect> {
+        self.sound_queue.drain(..).collect()
+    }
+
+    fn take_finished_match(&mut self) -> Option<MatchRecord> {
+        self.finished_match.take()
+    }
+}
+
+//NOTE: Tests not included since testing requires usage of mouse and other UI features
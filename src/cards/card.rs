@@ -0,0 +1,177 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use std::fmt;
+
+///
+/// Enum for the suit of a playing card, in the order a sorted hand shows them so the colors alternate
+///
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Spades,
+    Hearts,
+}
+
+impl Suit {
+    ///Returns every suit, in sorting order
+    pub fn all() -> [Suit; 4] {
+        [Suit::Clubs, Suit::Diamonds, Suit::Spades, Suit::Hearts]
+    }
+
+    ///Returns the symbol shown on cards of the suit
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Suit::Clubs => "\u{2663}",
+            Suit::Diamonds => "\u{2666}",
+            Suit::Spades => "\u{2660}",
+            Suit::Hearts => "\u{2665}",
+        }
+    }
+
+    ///Returns true for the red suits (diamonds and hearts)
+    pub fn is_red(self) -> bool {
+        self == Suit::Diamonds || self == Suit::Hearts
+    }
+}
+
+///
+/// Enum for the rank of a playing card. Aces rank above kings, and each rank's value is its number on the card
+/// (11 to 14 for the jack, queen, king and ace)
+///
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Rank {
+    Two = 2,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Rank {
+    ///Returns every rank, from lowest to highest
+    pub fn all() -> [Rank; 13] {
+        [
+            Rank::Two,
+            Rank::Three,
+            Rank::Four,
+            Rank::Five,
+            Rank::Six,
+            Rank::Seven,
+            Rank::Eight,
+            Rank::Nine,
+            Rank::Ten,
+            Rank::Jack,
+            Rank::Queen,
+            Rank::King,
+            Rank::Ace,
+        ]
+    }
+
+    ///Returns the value of the rank, from 2 for a two to 14 for an ace
+    pub fn value(self) -> i32 {
+        self as i32
+    }
+
+    ///Returns the label shown on cards of the rank, e.g. "10" or "Q"
+    pub fn label(self) -> String {
+        match self {
+            Rank::Jack => String::from("J"),
+            Rank::Queen => String::from("Q"),
+            Rank::King => String::from("K"),
+            Rank::Ace => String::from("A"),
+            rank => rank.value().to_string(),
+        }
+    }
+}
+
+///
+/// A struct representing a playing card. Cards sort by suit, then by rank
+///
+/// # Fields
+/// * suit = Suit enum of the card
+/// * rank = Rank enum of the card
+///
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Card {
+    pub suit: Suit,
+    pub rank: Rank,
+}
+
+impl Card {
+    /// Constructor for Card.
+    pub fn new(suit: Suit, rank: Rank) -> Self {
+        Card { suit, rank }
+    }
+
+    ///Returns a number from 0 to 51 for the card, used to record it in the match history
+    pub fn index(self) -> i32 {
+        self.suit as i32 * 13 + self.rank.value() - Rank::Two.value()
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.rank.label(), self.suit.symbol())
+    }
+}
+
+#[cfg(test)]
+mod card_tests {
+    use super::*;
+
+    mod rank {
+        use super::*;
+
+        mod value {
+            use super::*;
+
+            #[test]
+            fn should_count_up_from_two_to_ace() {
+                let values: Vec<i32> = Rank::all().iter().map(|rank| rank.value()).collect();
+                assert_eq!(values, (2..15).collect::<Vec<i32>>());
+                assert_eq!(Rank::Ten.label(), "10");
+                assert_eq!(Rank::Ace.label(), "A");
+            }
+        }
+    }
+
+    mod card {
+        use super::*;
+
+        mod index {
+            use super::*;
+
+            #[test]
+            fn should_number_cards_by_suit_then_rank() {
+                assert_eq!(Card::new(Suit::Diamonds, Rank::Two).index(), 13);
+                assert_eq!(Card::new(Suit::Clubs, Rank::Two).index(), 0);
+                assert_eq!(Card::new(Suit::Hearts, Rank::Ace).index(), 51);
+            }
+        }
+
+        mod fmt {
+            use super::*;
+
+            #[test]
+            fn should_show_rank_and_suit() {
+                assert_eq!(
+                    Card::new(Suit::Spades, Rank::Queen).to_string(),
+                    "Q\u{2660}"
+                );
+                assert_eq!(Card::new(Suit::Hearts, Rank::Ten).to_string(), "10\u{2665}");
+                assert!(Card::new(Suit::Diamonds, Rank::Two).suit.is_red());
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate rand;
+
+use self::rand::Rng;
+use cards::card::{Card, Rank, Suit};
+
+///
+/// A struct representing a deck of playing cards. Cards are drawn and dealt from the top, which is the end of the deck
+///
+/// # Fields
+/// * cards = Vector of the cards left in the deck, with the top card last
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Deck {
+    cards: Vec<Card>,
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::new()
+    }
+}
+
+impl Deck {
+    ///Constructor - returns a full 52 card deck, sorted by suit and rank
+    pub fn new() -> Self {
+        let mut cards = Vec::with_capacity(52);
+        for suit in Suit::all().iter() {
+            for rank in Rank::all().iter() {
+                cards.push(Card::new(*suit, *rank));
+            }
+        }
+        Deck { cards }
+    }
+
+    ///Returns a full 52 card deck in a random order. Shuffling with a seeded StdRng always gives the same order
+    pub fn shuffled<R: Rng>(rng: &mut R) -> Self {
+        let mut deck = Deck::new();
+        deck.shuffle(rng);
+        deck
+    }
+
+    ///Method to shuffle the cards left in the deck
+    pub fn shuffle<R: Rng>(&mut self, rng: &mut R) {
+        rng.shuffle(&mut self.cards);
+    }
+
+    ///Method to take the top card of the deck, returning None if the deck is empty
+    pub fn draw(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    ///
+    /// Method to deal cards one at a time around the table, the way they are dealt by hand. Returns a hand for each
+    /// player, which are shorter than count if the deck runs out
+    ///
+    /// # Arguments
+    /// * players = Number of hands to deal
+    /// * count   = Number of cards dealt to each hand
+    ///
+    pub fn deal(&mut self, players: usize, count: usize) -> Vec<Vec<Card>> {
+        let mut hands = vec![Vec::with_capacity(count); players];
+        for _ in 0..count {
+            for hand in &mut hands {
+                if let Some(card) = self.draw() {
+                    hand.push(card);
+                }
+            }
+        }
+        hands
+    }
+}
+
+#[cfg(test)]
+mod deck_tests {
+    use super::rand::{SeedableRng, StdRng};
+    use super::*;
+
+    mod deck {
+        use super::*;
+
+        mod new {
+            use super::*;
+
+            #[test]
+            fn should_hold_every_card_once() {
+                let deck = Deck::new();
+                assert_eq!(deck.cards.len(), 52);
+                let indices: Vec<i32> = deck.cards.iter().map(|c| c.index()).collect();
+                assert_eq!(indices, (0..52).collect::<Vec<i32>>());
+            }
+        }
+
+        mod shuffled {
+            use super::*;
+
+            //Method to shuffle a deck with a random number generator seeded with the given number
+            fn seeded_deck(seed: usize) -> Deck {
+                let mut rng: StdRng = SeedableRng::from_seed(&[seed][..]);
+                Deck::shuffled(&mut rng)
+            }
+
+            #[test]
+            fn should_shuffle_same_cards_in_repeatable_order() {
+                let deck = seeded_deck(7);
+                assert_eq!(deck, seeded_deck(7));
+                assert_ne!(deck, Deck::new());
+                assert_ne!(deck, seeded_deck(8));
+                let mut cards = deck.cards;
+                cards.sort();
+                assert_eq!(cards, Deck::new().cards);
+            }
+        }
+
+        mod deal {
+            use super::*;
+
+            #[test]
+            fn should_deal_round_the_table_from_the_top() {
+                let mut deck = Deck::new();
+                let hands = deck.deal(4, 13);
+                assert_eq!(deck.draw(), None);
+                assert!(hands.iter().all(|hand| hand.len() == 13));
+                assert_eq!(hands[0][0], Card::new(Suit::Hearts, Rank::Ace));
+                assert_eq!(hands[1][0], Card::new(Suit::Hearts, Rank::King));
+                assert_eq!(hands[0][1], Card::new(Suit::Hearts, Rank::Ten));
+            }
+
+            #[test]
+            fn should_stop_when_deck_runs_out() {
+                let mut deck = Deck::new();
+                let hands = deck.deal(3, 20);
+                assert_eq!(deck.draw(), None);
+                assert_eq!(
+                    hands.iter().map(|hand| hand.len()).collect::<Vec<usize>>(),
+                    vec![18, 17, 17]
+                );
+            }
+        }
+    }
+}
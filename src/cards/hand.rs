@@ -0,0 +1,53 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use cards::card::{Card, Suit};
+
+///Method to sort a hand by suit, in the order of Suit::all, then from lowest to highest rank
+pub fn sort_hand(hand: &mut [Card]) {
+    hand.sort();
+}
+
+///Returns the cards of a suit held in a hand, in the order they are held
+pub fn cards_of_suit(hand: &[Card], suit: Suit) -> Vec<Card> {
+    hand.iter()
+        .filter(|card| card.suit == suit)
+        .cloned()
+        .collect()
+}
+
+///Returns the number of cards of a suit held in a hand
+pub fn count_suit(hand: &[Card], suit: Suit) -> usize {
+    hand.iter().filter(|card| card.suit == suit).count()
+}
+
+#[cfg(test)]
+mod hand_tests {
+    use super::*;
+    use cards::card::Rank;
+
+    mod count_suit {
+        use super::*;
+
+        #[test]
+        fn should_count_cards_of_suit() {
+            let mut hand = vec![
+                Card::new(Suit::Hearts, Rank::Jack),
+                Card::new(Suit::Clubs, Rank::Ace),
+                Card::new(Suit::Hearts, Rank::Two),
+            ];
+            sort_hand(&mut hand);
+            assert_eq!(hand[0], Card::new(Suit::Clubs, Rank::Ace));
+            assert_eq!(count_suit(&hand, Suit::Hearts), 2);
+            assert_eq!(
+                cards_of_suit(&hand, Suit::Hearts),
+                vec![
+                    Card::new(Suit::Hearts, Rank::Two),
+                    Card::new(Suit::Hearts, Rank::Jack),
+                ]
+            );
+            assert_eq!(cards_of_suit(&hand, Suit::Diamonds), Vec::new());
+        }
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use cards::card::{Card, Suit};
+use cards::hand::cards_of_suit;
+
+///Returns the suit led to a trick, or None if no card has been played to it yet
+pub fn lead_suit(trick: &[(usize, Card)]) -> Option<Suit> {
+    trick.first().map(|(_, card)| card.suit)
+}
+
+///
+/// Returns the cards of a hand that may be played to a trick when players must follow suit: the cards of the suit
+/// led if any are held, otherwise the whole hand
+///
+/// # Arguments
+/// * hand = Cards held by the player
+/// * lead = Option of the suit led, or None if the player leads the trick
+///
+pub fn follow_suit(hand: &[Card], lead: Option<Suit>) -> Vec<Card> {
+    let follow = lead.map_or(Vec::new(), |suit| cards_of_suit(hand, suit));
+    if follow.is_empty() {
+        hand.to_vec()
+    } else {
+        follow
+    }
+}
+
+///
+/// Returns the seat that won a trick: whoever played the highest trump, or the highest card of the suit led if no
+/// trump was played. Returns 0 for an empty trick
+///
+/// # Arguments
+/// * trick = Seats and the cards they played, in the order they were played
+/// * trump = Option of the trump suit, or None for games without trumps
+///
+pub fn trick_winner(trick: &[(usize, Card)], trump: Option<Suit>) -> usize {
+    let lead = match lead_suit(trick) {
+        Some(suit) => suit,
+        None => return 0,
+    };
+    let winning_suit = match trump {
+        Some(trump) if trick.iter().any(|(_, card)| card.suit == trump) => trump,
+        _ => lead,
+    };
+    trick
+        .iter()
+        .filter(|(_, card)| card.suit == winning_suit)
+        .max_by_key(|(_, card)| card.rank)
+        .map_or(trick[0].0, |(seat, _)| *seat)
+}
+
+#[cfg(test)]
+mod trick_tests {
+    use super::*;
+    use cards::card::Rank;
+
+    mod follow_suit {
+        use super::*;
+
+        #[test]
+        fn should_only_allow_suit_led_when_held() {
+            let hand = vec![
+                Card::new(Suit::Clubs, Rank::Four),
+                Card::new(Suit::Hearts, Rank::Nine),
+            ];
+            assert_eq!(
+                follow_suit(&hand, Some(Suit::Hearts)),
+                vec![Card::new(Suit::Hearts, Rank::Nine)]
+            );
+            assert_eq!(follow_suit(&hand, Some(Suit::Spades)), hand);
+            assert_eq!(follow_suit(&hand, None), hand);
+        }
+    }
+
+    mod trick_winner {
+        use super::*;
+
+        #[test]
+        fn should_only_count_suit_led_without_trumps() {
+            let trick = vec![
+                (2, Card::new(Suit::Diamonds, Rank::Five)),
+                (3, Card::new(Suit::Spades, Rank::Ace)),
+                (0, Card::new(Suit::Diamonds, Rank::Jack)),
+                (1, Card::new(Suit::Hearts, Rank::King)),
+            ];
+            assert_eq!(trick_winner(&trick, None), 0);
+            assert_eq!(trick_winner(&trick, Some(Suit::Hearts)), 1);
+            assert_eq!(trick_winner(&trick, Some(Suit::Clubs)), 0);
+            assert_eq!(trick_winner(&[], None), 0);
+        }
+    }
+}
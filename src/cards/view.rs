@@ -0,0 +1,233 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use cards::card::Card;
+use connect4::button::{draw_text, is_under_mouse};
+use connect4::core::MyColor;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+
+///Constant width and height of a card
+pub const CARD_SIZE: (f32, f32) = (70.0, 100.0);
+
+///Constant widest horizontal distance between the cards of a fanned hand, used unless the hand is too wide to fit
+pub const HAND_SPACING: f32 = 42.0;
+
+///Constant height a card in a hand is raised when the mouse is over it
+pub const HOVER_LIFT: f32 = 15.0;
+
+///Constant height a card in a hand is raised when it is selected
+pub const SELECTED_LIFT: f32 = 30.0;
+
+///
+/// Method to draw a card with its top-left corner at a location
+///
+/// # Arguments
+/// * ctx    = Context to draw to
+/// * card   = Option of the Card to draw face up, or None to draw the back of a card
+/// * pos    = Location of the top-left corner of the card
+/// * dimmed = Boolean indicating if the card is grayed out, e.g. because it can't be played
+///
+pub fn draw_card(
+    ctx: &mut Context,
+    card: Option<Card>,
+    pos: (f32, f32),
+    dimmed: bool,
+) -> GameResult {
+    let rect = graphics::Rect::new(pos.0, pos.1, CARD_SIZE.0, CARD_SIZE.1);
+    let mut mb = graphics::MeshBuilder::new();
+    match card {
+        Some(_) => {
+            let face = if dimmed {
+                graphics::Color::from_rgba(170, 170, 170, 255)
+            } else {
+                graphics::WHITE
+            };
+            mb.rectangle(graphics::DrawMode::fill(), rect, face);
+        }
+        None => {
+            mb.rectangle(
+                graphics::DrawMode::fill(),
+                rect,
+                graphics::Color::from_rgba(30, 60, 150, 255),
+            );
+            mb.rectangle(
+                graphics::DrawMode::stroke(2.0),
+                graphics::Rect::new(
+                    pos.0 + 6.0,
+                    pos.1 + 6.0,
+                    CARD_SIZE.0 - 12.0,
+                    CARD_SIZE.1 - 12.0,
+                ),
+                graphics::WHITE,
+            );
+        }
+    }
+    mb.rectangle(graphics::DrawMode::stroke(1.0), rect, graphics::BLACK);
+    let mesh = mb.build(ctx)?;
+    graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))?;
+    if let Some(card) = card {
+        let color = if card.suit.is_red() {
+            graphics::Color::from_rgba(200, 0, 0, 255)
+        } else {
+            graphics::BLACK
+        };
+        draw_text(
+            ctx,
+            &card.rank.label(),
+            (pos.0 + 5.0, pos.1 + 3.0),
+            20.0,
+            color,
+        )?;
+        draw_text(
+            ctx,
+            card.suit.symbol(),
+            (pos.0 + 5.0, pos.1 + 22.0),
+            20.0,
+            color,
+        )?;
+        draw_text(
+            ctx,
+            card.suit.symbol(),
+            (
+                pos.0 + CARD_SIZE.0 / 2.0 - 8.0,
+                pos.1 + CARD_SIZE.1 / 2.0 - 5.0,
+            ),
+            36.0,
+            color,
+        )?;
+    }
+    Ok(())
+}
+
+///
+/// A struct that shows a hand of cards face up, fanned out so they overlap, and tracks the card under the mouse and
+/// the cards clicked to select them. Like a Button, it is checked against the mouse when it moves
+///
+/// # Fields
+/// * center_x        = X location of the center of the fanned hand
+/// * top_y           = Y location of the top of the cards that aren't raised
+/// * max_width       = Widest the fanned hand may be; cards overlap more once it would be wider
+/// * hovered         = Option of the index in the hand of the card under the mouse, or None
+/// * selected        = Vector of the selected cards, which are raised and outlined
+/// * max_selected    = Number of cards that can be selected at once
+/// * highlight_color = MyColor object representing the outline drawn around selected cards
+///
+pub struct HandView {
+    pub center_x: f32,
+    pub top_y: f32,
+    pub max_width: f32,
+    pub hovered: Option<usize>,
+    pub selected: Vec<Card>,
+    max_selected: usize,
+    highlight_color: MyColor,
+}
+
+impl HandView {
+    ///
+    /// Method to initialize and return a HandView with nothing hovered or selected
+    ///
+    /// # Arguments
+    /// * center_x     = X location of the center of the fanned hand
+    /// * top_y        = Y location of the top of the cards
+    /// * max_width    = Widest the fanned hand may be
+    /// * max_selected = Number of cards that can be selected at once, or 0 if cards are never selected
+    ///
+    pub fn new(center_x: f32, top_y: f32, max_width: f32, max_selected: usize) -> Self {
+        HandView {
+            center_x,
+            top_y,
+            max_width,
+            hovered: None,
+            selected: Vec::new(),
+            max_selected,
+            highlight_color: MyColor::Green,
+        }
+    }
+
+    ///Method to unselect every card and forget the card under the mouse, e.g. when a different hand is shown
+    pub fn clear(&mut self) {
+        self.selected.clear();
+        self.hovered = None;
+    }
+
+    ///Returns the location of the top-left corner of each card in a hand, raising selected and hovered cards
+    pub fn positions(&self, hand: &[Card]) -> Vec<(f32, f32)> {
+        let gaps = hand.len().max(1) - 1;
+        let spacing = if gaps == 0 {
+            0.0
+        } else {
+            HAND_SPACING.min((self.max_width - CARD_SIZE.0) / gaps as f32)
+        };
+        let left = self.center_x - (CARD_SIZE.0 + spacing * gaps as f32) / 2.0;
+        hand.iter()
+            .enumerate()
+            .map(|(i, card)| {
+                let lift = if self.selected.contains(card) {
+                    SELECTED_LIFT
+                } else if self.hovered == Some(i) {
+                    HOVER_LIFT
+                } else {
+                    0.0
+                };
+                (left + spacing * i as f32, self.top_y - lift)
+            })
+            .collect()
+    }
+
+    ///
+    /// Method to determine which card of a hand the mouse is over, hit-testing each card like a Button, and update the
+    /// hovered card accordingly. Cards overlap, so the top-most card is picked
+    ///
+    pub fn check_card_under_mouse(&mut self, ctx: &mut Context, hand: &[Card]) -> Option<usize> {
+        self.hovered = self.positions(hand).iter().rposition(|pos| {
+            is_under_mouse(
+                ctx,
+                graphics::Rect::new(pos.0, pos.1, CARD_SIZE.0, CARD_SIZE.1),
+            )
+        });
+        self.hovered
+    }
+
+    ///Method to select a card, or unselect it if it was selected. Returns false if no more cards can be selected
+    pub fn toggle_selected(&mut self, card: Card) -> bool {
+        if let Some(i) = self.selected.iter().position(|c| *c == card) {
+            self.selected.remove(i);
+        } else if self.selected.len() < self.max_selected {
+            self.selected.push(card);
+        } else {
+            return false;
+        }
+        true
+    }
+
+    ///
+    /// Draw method for rendering a hand, outlining the selected cards
+    ///
+    /// # Arguments
+    /// * ctx      = Context to draw to
+    /// * hand     = Cards to draw, in the order they are fanned from left to right
+    /// * playable = Option of the cards that can be played, where every other card is grayed out, or None
+    ///
+    pub fn draw(&self, ctx: &mut Context, hand: &[Card], playable: Option<&[Card]>) -> GameResult {
+        for (card, pos) in hand.iter().zip(self.positions(hand)) {
+            let dimmed = playable.is_some_and(|playable| !playable.contains(card));
+            draw_card(ctx, Some(*card), pos, dimmed)?;
+            if self.selected.contains(card) {
+                let outline = graphics::Mesh::new_rectangle(
+                    ctx,
+                    graphics::DrawMode::stroke(3.0),
+                    graphics::Rect::new(pos.0, pos.1, CARD_SIZE.0, CARD_SIZE.1),
+                    self.highlight_color.get_draw_color(),
+                )?;
+                graphics::draw(ctx, &outline, (Point2 { x: 0.0, y: 0.0 },))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+//NOTE: Tests not included since testing requires usage of mouse and other UI features
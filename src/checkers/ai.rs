@@ -0,0 +1,259 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use checkers::board::{Board, Move, BOARD_SIDE, SQUARES};
+use connect4::core::other_team;
+
+///Constant deepest the AI searches, in moves by either team, whatever its difficulty
+pub const MAX_SEARCH_DEPTH: i32 = 7;
+
+///Constant score of a won game, well above any score from counting pieces
+const WIN_SCORE: i32 = 100_000;
+
+///Constant value of a man
+const MAN_VALUE: i32 = 100;
+
+///Constant value of a king, which can move both ways
+const KING_VALUE: i32 = 160;
+
+///Constant bonus for each row a man has moved towards being crowned
+const ADVANCE_WEIGHT: i32 = 3;
+
+///Constant bonus for each man still guarding its own back row, which keeps the other team from being crowned
+const BACK_ROW_WEIGHT: i32 = 8;
+
+///
+/// Returns the score of a board from a team's point of view: the value of its pieces less the other team's, with
+/// bonuses for men moving towards the far row and for men left guarding the back row
+///
+/// # Arguments
+/// * board = Board to score
+/// * team  = Integer value (1 or 2) of the team to score for
+///
+pub fn evaluate(board: &Board, team: i32) -> i32 {
+    let mut score = 0;
+    for square in 0..SQUARES {
+        if let Some(piece) = board.piece(square) {
+            let row = board.position(square).y;
+            //Rows moved from the team's own back row
+            let advanced = if piece.team == 1 {
+                BOARD_SIDE - 1 - row
+            } else {
+                row
+            };
+            let value = if piece.king {
+                KING_VALUE
+            } else if advanced == 0 {
+                MAN_VALUE + BACK_ROW_WEIGHT
+            } else {
+                MAN_VALUE + ADVANCE_WEIGHT * advanced
+            };
+            if piece.team == team {
+                score += value;
+            } else {
+                score -= value;
+            }
+        }
+    }
+    score
+}
+
+///Returns the legal moves for a team, longest captures first so alpha-beta pruning cuts off sooner
+fn ordered_moves(board: &Board, team: i32) -> Vec<Move> {
+    let mut moves = board.legal_moves(team);
+    moves.sort_by_key(|mv| std::cmp::Reverse(mv.captures.len()));
+    moves
+}
+
+///
+/// Returns the score of the board from the team to move's point of view, searching depth moves ahead with alpha-beta
+/// pruning. A team with no legal move has lost, and losses found later score higher so the AI holds out longest
+///
+/// # Arguments
+/// * board = Board to search from
+/// * team  = Integer value (1 or 2) of the team to move
+/// * depth = Number of moves left to search
+/// * alpha = Lowest score the team to move is already sure of
+/// * beta  = Highest score the other team will allow
+///
+fn alpha_beta(board: &Board, team: i32, depth: i32, mut alpha: i32, beta: i32) -> i32 {
+    let moves = ordered_moves(board, team);
+    if moves.is_empty() {
+        return -WIN_SCORE - depth;
+    }
+    if depth == 0 {
+        return evaluate(board, team);
+    }
+    for mv in moves {
+        let mut next = *board;
+        next.play(&mv);
+        let score = -alpha_beta(&next, other_team(team), depth - 1, -beta, -alpha);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    alpha
+}
+
+///
+/// A struct representing an AI or bot player for checkers which has methods to pick moves
+///
+/// # Fields
+/// * team            = Integer value (1 or 2) of the team the AI plays for
+/// * difficulty      = Integer value for how many moves ahead the AI searches
+/// * last_move_frame = Integer used to track when the AI picked its move. Set to -1 until it picks each turn
+///
+pub struct AI {
+    pub team: i32,
+    difficulty: i32,
+    pub last_move_frame: i32,
+}
+
+impl AI {
+    ///
+    /// Method to initialize and return an AI object
+    ///
+    /// # Arguments
+    /// * team       = Integer value (1 or 2) of the team the AI plays for
+    /// * difficulty = Integer value for how many moves ahead the AI searches
+    ///
+    pub fn new(team: i32, difficulty: i32) -> Self {
+        AI {
+            team,
+            difficulty,
+            last_move_frame: -1,
+        }
+    }
+
+    ///Returns how many moves ahead the AI searches: two more than its difficulty, up to MAX_SEARCH_DEPTH
+    fn search_depth(&self) -> i32 {
+        (self.difficulty + 2).clamp(1, MAX_SEARCH_DEPTH)
+    }
+
+    ///
+    /// Method to pick a whole move, including every jump of a multi-jump, searching the legal moves with alpha-beta
+    /// pruning. Ties go to the move searched first. Returns None if the AI has no legal move
+    ///
+    /// # Arguments
+    /// * board = Board with the AI's team to move
+    ///
+    pub fn pick_move(&self, board: &Board) -> Option<Move> {
+        let depth = self.search_depth();
+        let mut best: Option<(i32, Move)> = None;
+        for mv in ordered_moves(board, self.team) {
+            let mut next = *board;
+            next.play(&mv);
+            let alpha = best.as_ref().map_or(-WIN_SCORE * 2, |(score, _)| *score);
+            let score = -alpha_beta(
+                &next,
+                other_team(self.team),
+                depth - 1,
+                -WIN_SCORE * 2,
+                -alpha,
+            );
+            if best
+                .as_ref()
+                .is_none_or(|(best_score, _)| score > *best_score)
+            {
+                best = Some((score, mv));
+            }
+        }
+        best.map(|(_, mv)| mv)
+    }
+}
+
+#[cfg(test)]
+mod ai_tests {
+    use super::*;
+    use checkers::board::Piece;
+    use checkers::game::Game;
+    use connect4::core::GridPosition;
+
+    //Method to create a board from the team on each (column, row) and whether it is a king
+    fn create_test_board(pieces: &[((i32, i32), i32, bool)]) -> Board {
+        let empty = Board::from([None; SQUARES]);
+        let mut squares = [None; SQUARES];
+        for (pos, owner, king) in pieces {
+            let square = empty.square_at(GridPosition::new(pos.0, pos.1)).unwrap();
+            squares[square] = Some(Piece {
+                team: *owner,
+                king: *king,
+            });
+        }
+        Board::from(squares)
+    }
+
+    //Method to return the dark square at a (column, row) position
+    fn square(pos: (i32, i32)) -> usize {
+        Board::new()
+            .square_at(GridPosition::new(pos.0, pos.1))
+            .unwrap()
+    }
+
+    mod ai {
+        use super::*;
+
+        mod pick_move {
+            use super::*;
+
+            #[test]
+            fn should_avoid_stepping_into_a_capture() {
+                //Stepping to (4, 3) is jumped by the man on (5, 2); stepping to (2, 3) is safe
+                let board = create_test_board(&[
+                    ((3, 4), 1, false),
+                    ((5, 2), 2, false),
+                    ((0, 1), 2, false),
+                ]);
+                let mv = AI::new(1, 1).pick_move(&board).unwrap();
+                assert_eq!(mv.to(), square((2, 3)));
+            }
+
+            #[test]
+            fn should_pick_the_capture_that_takes_most() {
+                //Both captures are allowed, but the double jump from (0, 5) takes two pieces to one
+                let board = create_test_board(&[
+                    ((0, 5), 1, false),
+                    ((5, 6), 1, false),
+                    ((1, 4), 2, false),
+                    ((3, 2), 2, false),
+                    ((6, 5), 2, false),
+                ]);
+                let mv = AI::new(1, 1).pick_move(&board).unwrap();
+                assert_eq!(mv.to(), square((4, 1)));
+                assert_eq!(mv.captures.len(), 2);
+            }
+
+            #[test]
+            fn should_return_none_without_a_legal_move() {
+                let board = create_test_board(&[((1, 0), 1, false), ((7, 6), 2, false)]);
+                assert!(AI::new(1, 2).pick_move(&board).is_none());
+            }
+
+            #[test]
+            fn should_play_a_full_game_against_itself() {
+                let ais = [AI::new(1, 1), AI::new(2, 1)];
+                let mut game = Game::new();
+                while game.outcome.is_none() {
+                    let mv = ais[game.team as usize - 1].pick_move(&game.board).unwrap();
+                    assert!(game.legal_moves().contains(&mv));
+                    game.play(&mv);
+                }
+                assert_eq!(Game::replay(&game.hops).last(), Some(&game.board));
+            }
+        }
+    }
+
+    mod evaluate {
+        use super::*;
+
+        #[test]
+        fn should_value_kings_above_men() {
+            let board = create_test_board(&[((1, 4), 1, true), ((4, 3), 2, false)]);
+            assert!(evaluate(&board, 1) > 0);
+            assert_eq!(evaluate(&board, 1), -evaluate(&board, 2));
+            assert_eq!(evaluate(&Board::new(), 1), 0);
+        }
+    }
+}
@@ -0,0 +1,462 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::GridPosition;
+
+///Constant number of rows and columns of the board
+pub const BOARD_SIDE: i32 = 8;
+
+///Constant number of dark squares pieces are played on, four in each row
+pub const SQUARES: usize = 32;
+
+///Constant number of rows of pieces each team starts with
+const START_ROWS: usize = 3;
+
+///
+/// Returns the move recorded for one hop of a piece, from one square to the next. A multi-jump is recorded as one hop
+/// per jump, so every move of a game fits in a list of integers like a Connect 4 game
+///
+pub fn encode_hop(from: usize, to: usize) -> i32 {
+    (from * SQUARES + to) as i32
+}
+
+///Returns the squares a recorded hop is made from and to
+pub fn decode_hop(hop: i32) -> (usize, usize) {
+    (hop as usize / SQUARES, hop as usize % SQUARES)
+}
+
+///
+/// A struct representing a piece on the board
+///
+/// # Fields
+/// * team = Integer value (1 or 2) of the team the piece belongs to
+/// * king = Boolean indicating if the piece has been crowned and may move backwards
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Piece {
+    pub team: i32,
+    pub king: bool,
+}
+
+impl Piece {
+    ///Constructor - returns an uncrowned piece, called a man, for the team
+    pub fn man(team: i32) -> Self {
+        Piece { team, king: false }
+    }
+
+    ///Returns the rows the piece may move towards: up the board for team 1 and down for team 2, or both for a king
+    fn directions(&self) -> &'static [i32] {
+        if self.king {
+            &[-1, 1]
+        } else if self.team == 1 {
+            &[-1]
+        } else {
+            &[1]
+        }
+    }
+}
+
+///
+/// A struct representing a whole move: a piece's starting square followed by each square it lands on, and the squares
+/// of the pieces it captures along the way
+///
+/// # Fields
+/// * path     = Vector of squares, starting with the one the piece moves from
+/// * captures = Vector of the squares of captured pieces, in the order they are jumped
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Move {
+    pub path: Vec<usize>,
+    pub captures: Vec<usize>,
+}
+
+impl Move {
+    ///Returns the square the piece moves from
+    pub fn from(&self) -> usize {
+        self.path[0]
+    }
+
+    ///Returns the square the piece finishes on
+    pub fn to(&self) -> usize {
+        self.path[self.path.len() - 1]
+    }
+
+    ///Returns the move as recorded hops, see encode_hop
+    pub fn hops(&self) -> Vec<i32> {
+        self.path
+            .windows(2)
+            .map(|pair| encode_hop(pair[0], pair[1]))
+            .collect()
+    }
+}
+
+///
+/// A struct representing the checkers board. Only the 32 dark squares are stored, numbered row by row from the
+/// top-left. Team 1 starts on the bottom three rows and moves first, team 2 starts on the top three rows
+///
+/// # Fields
+/// * squares = Array of the piece on each dark square, or None if it is empty
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Board {
+    squares: [Option<Piece>; SQUARES],
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::new()
+    }
+}
+
+/// From trait converting an array of the piece on each dark square to a Board set up in that position.
+impl From<[Option<Piece>; SQUARES]> for Board {
+    fn from(squares: [Option<Piece>; SQUARES]) -> Self {
+        Board { squares }
+    }
+}
+
+impl Board {
+    ///Constructor - returns the starting position with twelve men for each team
+    pub fn new() -> Self {
+        let mut squares = [None; SQUARES];
+        for square in 0..START_ROWS * 4 {
+            squares[square] = Some(Piece::man(2));
+            squares[SQUARES - 1 - square] = Some(Piece::man(1));
+        }
+        Board { squares }
+    }
+
+    ///Returns the position of a dark square on the board, where x is its column and y its row
+    pub fn position(&self, square: usize) -> GridPosition {
+        let y = square as i32 / 4;
+        GridPosition::new(2 * (square as i32 % 4) + 1 - y % 2, y)
+    }
+
+    ///Returns the dark square at a position, or None if the position is a light square or off the board
+    pub fn square_at(&self, pos: GridPosition) -> Option<usize> {
+        if pos.x < 0 || pos.x >= BOARD_SIDE || pos.y < 0 || pos.y >= BOARD_SIDE {
+            return None;
+        }
+        if (pos.x + pos.y) % 2 == 0 {
+            return None;
+        }
+        Some((pos.y * 4 + pos.x / 2) as usize)
+    }
+
+    ///Returns the piece on a square, or None if it is empty
+    pub fn piece(&self, square: usize) -> Option<Piece> {
+        self.squares[square]
+    }
+
+    ///Returns the number of men and kings a team has on the board
+    pub fn count(&self, team: i32) -> (usize, usize) {
+        let mut counts = (0, 0);
+        for piece in self.squares.iter().flatten() {
+            if piece.team == team {
+                if piece.king {
+                    counts.1 += 1;
+                } else {
+                    counts.0 += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    ///Returns the squares next to a square along the diagonals the piece on it may move, with the square beyond each
+    fn neighbours(&self, square: usize, piece: Piece) -> Vec<(usize, Option<usize>)> {
+        let pos = self.position(square);
+        let mut neighbours = Vec::new();
+        for dy in piece.directions() {
+            for dx in [-1, 1].iter() {
+                if let Some(next) = self.square_at(GridPosition::new(pos.x + dx, pos.y + dy)) {
+                    let beyond = self.square_at(GridPosition::new(pos.x + 2 * dx, pos.y + 2 * dy));
+                    neighbours.push((next, beyond));
+                }
+            }
+        }
+        neighbours
+    }
+
+    ///Returns true if a piece of the team would be crowned on the square, which is on the far row from where it starts
+    fn is_king_row(&self, square: usize, team: i32) -> bool {
+        let row = self.position(square).y;
+        (team == 1 && row == 0) || (team == 2 && row == BOARD_SIDE - 1)
+    }
+
+    ///
+    /// Method to add every capture sequence for a piece to moves. The piece must keep jumping while it can, and a man
+    /// that reaches the far row is crowned and stops. A piece may not be jumped twice, so captured pieces stay on the
+    /// board until the sequence is over
+    ///
+    /// # Arguments
+    /// * piece = Piece making the jumps
+    /// * path  = Vector of squares so far, starting with the one the piece moved from
+    /// * taken = Vector of squares of the pieces captured so far
+    /// * moves = Vector the finished sequences are added to
+    ///
+    fn add_jumps(
+        &self,
+        piece: Piece,
+        path: &mut Vec<usize>,
+        taken: &mut Vec<usize>,
+        moves: &mut Vec<Move>,
+    ) {
+        let square = path[path.len() - 1];
+        let mut extended = false;
+        if taken.is_empty() || piece.king || !self.is_king_row(square, piece.team) {
+            for (next, beyond) in self.neighbours(square, piece) {
+                let landing = match beyond {
+                    Some(landing) => landing,
+                    None => continue,
+                };
+                let jumpable = match self.squares[next] {
+                    Some(other) => other.team != piece.team && !taken.contains(&next),
+                    None => false,
+                };
+                if jumpable && (self.squares[landing].is_none() || landing == path[0]) {
+                    extended = true;
+                    path.push(landing);
+                    taken.push(next);
+                    self.add_jumps(piece, path, taken, moves);
+                    path.pop();
+                    taken.pop();
+                }
+            }
+        }
+        if !extended && !taken.is_empty() {
+            moves.push(Move {
+                path: path.clone(),
+                captures: taken.clone(),
+            });
+        }
+    }
+
+    ///
+    /// Returns every legal move for a team. Captures are compulsory, so if any piece can jump only capture sequences
+    /// are returned, each carried on until no more jumps are possible. Otherwise every step of one square is returned
+    ///
+    /// # Arguments
+    /// * team = Integer value (1 or 2) of the team to move
+    ///
+    pub fn legal_moves(&self, team: i32) -> Vec<Move> {
+        let mut captures = Vec::new();
+        for square in 0..SQUARES {
+            if let Some(piece) = self.squares[square].filter(|piece| piece.team == team) {
+                self.add_jumps(piece, &mut vec![square], &mut Vec::new(), &mut captures);
+            }
+        }
+        if !captures.is_empty() {
+            return captures;
+        }
+        let mut steps = Vec::new();
+        for square in 0..SQUARES {
+            if let Some(piece) = self.squares[square].filter(|piece| piece.team == team) {
+                for (next, _) in self.neighbours(square, piece) {
+                    if self.squares[next].is_none() {
+                        steps.push(Move {
+                            path: vec![square, next],
+                            captures: Vec::new(),
+                        });
+                    }
+                }
+            }
+        }
+        steps
+    }
+
+    ///
+    /// Method to move a piece one hop, from a square to the next along its path, capturing the piece it jumps if the
+    /// hop is two rows long and crowning it if it reaches the far row. Returns true if a piece was crowned
+    ///
+    /// # Arguments
+    /// * from = Square the piece moves from
+    /// * to   = Square the piece lands on
+    ///
+    pub fn hop(&mut self, from: usize, to: usize) -> bool {
+        let mut piece = match self.squares[from].take() {
+            Some(piece) => piece,
+            None => return false,
+        };
+        let (start, end) = (self.position(from), self.position(to));
+        if (end.y - start.y).abs() == 2 {
+            let middle = GridPosition::new((start.x + end.x) / 2, (start.y + end.y) / 2);
+            if let Some(jumped) = self.square_at(middle) {
+                self.squares[jumped] = None;
+            }
+        }
+        let crowned = !piece.king && self.is_king_row(to, piece.team);
+        piece.king |= crowned;
+        self.squares[to] = Some(piece);
+        crowned
+    }
+
+    ///Method to make a whole move, hop by hop. Returns true if the moving piece was crowned
+    pub fn play(&mut self, mv: &Move) -> bool {
+        let mut crowned = false;
+        for pair in mv.path.windows(2) {
+            crowned |= self.hop(pair[0], pair[1]);
+        }
+        crowned
+    }
+}
+
+#[cfg(test)]
+mod board_tests {
+    use super::*;
+
+    //Method to create a board from 8 rows of text. 'b' and 'B' are a man and a king of team 1, 'w' and 'W' of team 2,
+    //and any other character on a dark square leaves it empty
+    fn create_test_board(rows: [&str; 8]) -> Board {
+        let empty = Board::from([None; SQUARES]);
+        let mut squares = [None; SQUARES];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if let Some(square) = empty.square_at(GridPosition::new(x as i32, y as i32)) {
+                    squares[square] = match c {
+                        'b' => Some(Piece::man(1)),
+                        'B' => Some(Piece {
+                            team: 1,
+                            king: true,
+                        }),
+                        'w' => Some(Piece::man(2)),
+                        'W' => Some(Piece {
+                            team: 2,
+                            king: true,
+                        }),
+                        _ => None,
+                    };
+                }
+            }
+        }
+        Board::from(squares)
+    }
+
+    //Method to return the dark square at a (column, row) position
+    fn square(pos: (i32, i32)) -> usize {
+        Board::new()
+            .square_at(GridPosition::new(pos.0, pos.1))
+            .unwrap()
+    }
+
+    mod board {
+        use super::*;
+
+        mod square_at {
+            use super::*;
+
+            #[test]
+            fn should_number_dark_squares_both_ways() {
+                let board = Board::new();
+                for square in 0..SQUARES {
+                    assert_eq!(board.square_at(board.position(square)), Some(square));
+                }
+                assert_eq!(board.square_at(GridPosition::new(0, 0)), None);
+                assert_eq!(board.square_at(GridPosition::new(1, 0)), Some(0));
+                assert_eq!(board.square_at(GridPosition::new(0, 1)), Some(4));
+                assert_eq!(board.square_at(GridPosition::new(8, 1)), None);
+            }
+        }
+
+        mod legal_moves {
+            use super::*;
+
+            #[test]
+            fn should_offer_seven_steps_at_start() {
+                let board = Board::new();
+                assert_eq!(board.legal_moves(1).len(), 7);
+                assert_eq!(board.legal_moves(2).len(), 7);
+                assert_eq!(board.count(1), (12, 0));
+            }
+
+            #[test]
+            fn should_make_captures_compulsory() {
+                let board = create_test_board([
+                    "........", "........", "........", "....w...", "...b....", "........",
+                    ".b......", "........",
+                ]);
+                let moves = board.legal_moves(1);
+                assert_eq!(moves.len(), 1);
+                assert_eq!(moves[0].path, vec![square((3, 4)), square((5, 2))]);
+                assert_eq!(moves[0].captures, vec![square((4, 3))]);
+            }
+
+            #[test]
+            fn should_carry_on_multi_jumps() {
+                let board = create_test_board([
+                    "........", "........", "...w....", "........", ".w......", "b.......",
+                    "........", "........",
+                ]);
+                let moves = board.legal_moves(1);
+                assert_eq!(moves.len(), 1);
+                assert_eq!(
+                    moves[0].path,
+                    vec![square((0, 5)), square((2, 3)), square((4, 1))]
+                );
+                assert_eq!(moves[0].captures.len(), 2);
+            }
+
+            #[test]
+            fn should_stop_jumping_when_crowned() {
+                //The man is crowned on the far row and may not jump back out as a king on the same move
+                let board = create_test_board([
+                    "........", "..w.w...", ".b......", "........", "........", "........",
+                    "........", "........",
+                ]);
+                let moves = board.legal_moves(1);
+                assert_eq!(moves.len(), 1);
+                assert_eq!(moves[0].path, vec![square((1, 2)), square((3, 0))]);
+            }
+
+            #[test]
+            fn should_let_kings_move_backwards() {
+                let board = create_test_board([
+                    "........", "........", "........", "....B...", "........", "........",
+                    "........", "........",
+                ]);
+                assert_eq!(board.legal_moves(1).len(), 4);
+                let board = create_test_board([
+                    "........", "........", "........", "....b...", "........", "........",
+                    "........", "........",
+                ]);
+                assert_eq!(board.legal_moves(1).len(), 2);
+            }
+        }
+
+        mod play {
+            use super::*;
+
+            #[test]
+            fn should_remove_captured_pieces_and_crown() {
+                let mut board = create_test_board([
+                    "........", "..w.....", ".b......", "........", "........", "........",
+                    "........", "........",
+                ]);
+                let mv = board.legal_moves(1).remove(0);
+                assert!(board.play(&mv));
+                assert_eq!(board.count(1), (0, 1));
+                assert_eq!(board.count(2), (0, 0));
+                assert_eq!(
+                    board.piece(square((3, 0))),
+                    Some(Piece {
+                        team: 1,
+                        king: true
+                    })
+                );
+            }
+
+            #[test]
+            fn should_record_one_hop_per_jump() {
+                let mv = Move {
+                    path: vec![21, 14, 7],
+                    captures: vec![17, 10],
+                };
+                let hops = mv.hops();
+                assert_eq!(hops.len(), 2);
+                assert_eq!(decode_hop(hops[0]), (21, 14));
+                assert_eq!(decode_hop(hops[1]), (14, 7));
+            }
+        }
+    }
+}
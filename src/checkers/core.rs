@@ -0,0 +1,674 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use checkers::ai::AI;
+use checkers::board::{Board, Move, BOARD_SIDE, SQUARES};
+use checkers::game::{Game, DRAW_MOVE_LIMIT};
+use connect4::button::{create_button, draw_text, mouse_location, Button};
+use connect4::core::{
+    GameAction, GridPosition, MyColor, PlayerInfo, TurnIndicator, BOARD_DISC_RADIUS,
+};
+use ggez::event::KeyCode;
+use ggez::input::mouse::MouseButton;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+use history::MatchRecord;
+use replay::ReplayStepper;
+use screen::GameScreen;
+use settings::Settings;
+use sound::{result_sound, SoundEffect};
+
+///Constant dimensions for the checkers window, fitting the board with a panel of players and buttons to its right
+pub const CHECKERS_SCREEN_SIZE: (f32, f32) = (900.0, 660.0);
+
+///Constant top-left corner of the board, below the turn indicator
+const BOARD_POSITION: (f32, f32) = (40.0, 120.0);
+
+///Constant width and height of each square of the board, the same as a Connect 4 cell
+const SQUARE_SIZE: f32 = 64.0;
+
+///Constant x location of the panel of players and buttons to the right of the board
+const PANEL_POS_X: f32 = 600.0;
+
+///Constant font size for the panel text and buttons
+const CHECKERS_FONT_SIZE: f32 = 24.0;
+
+///Constant piece colors named on the panel for each team, where PIECE_NAMES[i] is team i + 1's
+const PIECE_NAMES: [&str; 2] = ["Black", "White"];
+
+///
+/// A struct that contains the state of a checkers game. Team 1 plays black from the bottom of the board and moves
+/// first, team 2 plays white from the top. A person moves a piece by dragging it, or by clicking it and then each
+/// square it lands on
+///
+/// # Fields
+/// * game              = Game holding the board, the team to move, the hops played and the draw rules
+/// * team_names        = Vector of player names, where team_names[i] is the name for team i + 1
+/// * team_colors       = Vector of MyColor objects, where team_colors[i] is the color team i + 1's pieces are drawn in
+/// * turn_indicator    = TurnIndicator from Connect 4 showing whose turn it is and who won
+/// * ai_players        = Vector of AI objects, one for each team played by the AI
+/// * ai_difficulty     = Difficulty given to the AI players, recorded in the match history
+/// * ai_move_delay     = Number of frames an AI shows the move it picked before making it
+/// * aim               = Option of the move the AI is about to make, or None
+/// * frames            = Integer counter for the number of times the update method is called; times AI moves
+/// * path              = Vector of squares a person has picked so far: the piece to move, then each square it has
+///                       landed on during a multi-jump. Empty if no piece is picked
+/// * dragging          = Boolean indicating if the picked piece is held down with the mouse
+/// * mouse             = Location of the mouse, where a dragged piece is drawn
+/// * hover             = Option of the square under the mouse that the picked piece may land on next, or None
+/// * last_move         = Option of the path of the move made last, or None at the start of a game
+/// * message           = Message shown on the panel, such as why the game was drawn
+/// * replay_boards     = Vector of boards after each move of a finished game, rebuilt from its hops
+/// * replay            = ReplayStepper tracking which of replay_boards is shown once the game is over
+/// * main_menu_button  = Button drawn to return to the main menu
+/// * play_again_button = Button drawn to reset the board and start a new game with the same players
+/// * background_color  = Color drawn behind the board
+/// * sound_queue       = Vector of SoundEffect enums triggered since the main menu last played them
+/// * elapsed           = Seconds the current game has been played for
+/// * finished_match    = Option of a MatchRecord for a game that just ended, taken by the main menu to be saved
+///
+pub struct CheckersState {
+    game: Game,
+    team_names: Vec<String>,
+    team_colors: Vec<MyColor>,
+    turn_indicator: TurnIndicator,
+    ai_players: Vec<AI>,
+    ai_difficulty: i32,
+    ai_move_delay: i32,
+    aim: Option<Move>,
+    frames: usize,
+    path: Vec<usize>,
+    dragging: bool,
+    mouse: Point2<f32>,
+    hover: Option<usize>,
+    last_move: Option<Vec<usize>>,
+    message: String,
+    replay_boards: Vec<Board>,
+    replay: ReplayStepper,
+    main_menu_button: Button,
+    play_again_button: Button,
+    background_color: graphics::Color,
+    pub sound_queue: Vec<SoundEffect>,
+    elapsed: f32,
+    pub finished_match: Option<MatchRecord>,
+}
+
+impl CheckersState {
+    ///Constructor - player_info holds the name, color and whether the AI plays for team 1 (black) and team 2 (white),
+    ///in that order, and settings holds the AI and theme options. Teams without player info get the defaults
+    pub fn new(
+        ctx: &mut Context,
+        player_info: Vec<PlayerInfo>,
+        settings: &Settings,
+    ) -> CheckersState {
+        let mut team_names = Vec::new();
+        let mut team_colors = Vec::new();
+        let mut ai_players = Vec::new();
+        let given = player_info.len();
+        for (i, info) in player_info
+            .into_iter()
+            .chain(PlayerInfo::defaults().into_iter().skip(given))
+            .take(2)
+            .enumerate()
+        {
+            if info.is_ai {
+                ai_players.push(AI::new(i as i32 + 1, settings.ai_difficulty));
+            }
+            team_names.push(info.name);
+            team_colors.push(info.color);
+        }
+        //The turn indicator names team i with names[i], so names[0] is left unused
+        let mut indicator_names = vec![String::new()];
+        indicator_names.extend(team_names.iter().cloned());
+        let buttons_y = BOARD_POSITION.1 + SQUARE_SIZE * BOARD_SIDE as f32 - 45.0;
+        let mut state = CheckersState {
+            game: Game::new(),
+            team_names,
+            team_colors,
+            turn_indicator: TurnIndicator::new(indicator_names),
+            ai_players,
+            ai_difficulty: settings.ai_difficulty,
+            ai_move_delay: settings.ai_move_delay,
+            aim: None,
+            frames: 0,
+            path: Vec::new(),
+            dragging: false,
+            mouse: Point2 { x: 0.0, y: 0.0 },
+            hover: None,
+            last_move: None,
+            message: String::new(),
+            replay_boards: Vec::new(),
+            replay: ReplayStepper::default(),
+            main_menu_button: create_button(
+                ctx,
+                "Main Menu",
+                (PANEL_POS_X, buttons_y),
+                CHECKERS_FONT_SIZE,
+                (MyColor::Brown, MyColor::Green),
+            ),
+            play_again_button: create_button(
+                ctx,
+                "Play Again",
+                (PANEL_POS_X, buttons_y - 60.0),
+                CHECKERS_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            background_color: settings.theme.game_background(),
+            sound_queue: Vec::new(),
+            elapsed: 0.0,
+            finished_match: None,
+        };
+        state.reset_game();
+        state
+    }
+
+    ///Method to set up the starting position again with black to move
+    fn reset_game(&mut self) {
+        self.game = Game::new();
+        for ai in &mut self.ai_players {
+            ai.last_move_frame = -1;
+        }
+        self.aim = None;
+        self.path.clear();
+        self.dragging = false;
+        self.hover = None;
+        self.last_move = None;
+        self.message.clear();
+        self.replay_boards.clear();
+        self.replay = ReplayStepper::default();
+        self.elapsed = 0.0;
+        self.play_again_button.active = false;
+        self.turn_indicator.reset();
+        self.turn_indicator.change_team(1);
+    }
+
+    ///Returns true if the AI plays for the team
+    fn is_ai(&self, team: i32) -> bool {
+        self.ai_players.iter().any(|ai| ai.team == team)
+    }
+
+    ///Returns true if a person may pick and move a piece
+    fn accepts_input(&self) -> bool {
+        self.game.outcome.is_none() && !self.is_ai(self.game.team)
+    }
+
+    ///Returns the top-left corner of a square on the screen
+    fn square_corner(&self, square: usize) -> Point2<f32> {
+        let pos = self.game.board.position(square);
+        Point2 {
+            x: BOARD_POSITION.0 + pos.x as f32 * SQUARE_SIZE,
+            y: BOARD_POSITION.1 + pos.y as f32 * SQUARE_SIZE,
+        }
+    }
+
+    ///Returns the center of a square on the screen
+    fn square_center(&self, square: usize) -> Point2<f32> {
+        let corner = self.square_corner(square);
+        Point2 {
+            x: corner.x + SQUARE_SIZE / 2.0,
+            y: corner.y + SQUARE_SIZE / 2.0,
+        }
+    }
+
+    ///Returns the dark square under a point, or None if it is a light square or off the board
+    fn square_at(&self, point: Point2<f32>) -> Option<usize> {
+        let pos = GridPosition::new(
+            ((point.x - BOARD_POSITION.0) / SQUARE_SIZE).floor() as i32,
+            ((point.y - BOARD_POSITION.1) / SQUARE_SIZE).floor() as i32,
+        );
+        self.game.board.square_at(pos)
+    }
+
+    ///Returns the legal moves that start with the squares picked so far
+    fn matching_moves(&self) -> Vec<Move> {
+        self.game
+            .legal_moves()
+            .into_iter()
+            .filter(|mv| mv.path.starts_with(&self.path))
+            .collect()
+    }
+
+    ///Returns the squares the picked piece may land on next, or none if no piece is picked
+    fn next_landings(&self) -> Vec<usize> {
+        if self.path.is_empty() {
+            return Vec::new();
+        }
+        let mut landings: Vec<usize> = self
+            .matching_moves()
+            .iter()
+            .filter(|mv| mv.path.len() > self.path.len())
+            .map(|mv| mv.path[self.path.len()])
+            .collect();
+        landings.dedup();
+        landings
+    }
+
+    ///Returns the board as shown: a step of the replay once the game is over, or the game's board with any jumps of a
+    ///multi-jump picked so far already made
+    fn shown_board(&self) -> Board {
+        if self.game.outcome.is_some() && !self.replay_boards.is_empty() {
+            return self.replay_boards[self.replay.position()];
+        }
+        let mut board = self.game.board;
+        for pair in self.path.windows(2) {
+            board.hop(pair[0], pair[1]);
+        }
+        board
+    }
+
+    ///
+    /// Method to pick up a piece of the team to move. A piece may only be picked if it has a legal move, so while any
+    /// capture is possible only pieces that can capture are picked. A multi-jump under way can't be changed
+    ///
+    /// # Arguments
+    /// * square = Number of the dark square the piece is on
+    ///
+    fn pick(&mut self, square: usize) -> bool {
+        if self.path.len() > 1 {
+            return false;
+        }
+        if self.game.legal_moves().iter().any(|mv| mv.from() == square) {
+            self.path = vec![square];
+            true
+        } else {
+            false
+        }
+    }
+
+    ///
+    /// Method to land the picked piece on a square. The move is made once its path is complete; during a multi-jump
+    /// the piece waits on each square until the next is picked
+    ///
+    /// # Arguments
+    /// * square = Number of the dark square to land on
+    ///
+    fn land(&mut self, square: usize) {
+        if !self.next_landings().contains(&square) {
+            self.sound_queue.push(SoundEffect::InvalidMove);
+            return;
+        }
+        self.path.push(square);
+        let complete = self
+            .matching_moves()
+            .into_iter()
+            .find(|mv| mv.path == self.path);
+        match complete {
+            Some(mv) => self.play(&mv),
+            None => self.sound_queue.push(SoundEffect::Drop),
+        }
+    }
+
+    ///Method to make a whole move for the team to move, then end the game if the other team can't move or it is drawn
+    fn play(&mut self, mv: &Move) {
+        let team = self.game.team;
+        let crowned = self.game.play(mv);
+        self.sound_queue.push(SoundEffect::Drop);
+        let from = self.game.board.position(mv.from());
+        let to = self.game.board.position(mv.to());
+        println!(
+            "{} moves ({}, {}) to ({}, {}), capturing {}{}",
+            self.team_names[team as usize - 1],
+            from.x,
+            from.y,
+            to.x,
+            to.y,
+            mv.captures.len(),
+            if crowned { " and is crowned" } else { "" }
+        );
+        self.last_move = Some(mv.path.clone());
+        self.path.clear();
+        self.dragging = false;
+        self.hover = None;
+        match self.game.outcome {
+            Some(_) => self.end_game(),
+            None => self.turn_indicator.change_team(self.game.team),
+        }
+    }
+
+    ///Method to end the game, show why it ended and record it so it can be saved and replayed
+    fn end_game(&mut self) {
+        let outcome = match self.game.outcome {
+            Some(outcome) => outcome,
+            None => return,
+        };
+        let winner = outcome.winner();
+        self.message = outcome.describe();
+        println!("Game ends: {}", self.message);
+        self.turn_indicator.change_team(winner);
+        self.turn_indicator.game_ends();
+        self.play_again_button.active = true;
+        //Rebuild the game from its recorded hops, the same way a saved game is replayed
+        self.replay_boards = Game::replay(&self.game.hops);
+        self.replay = ReplayStepper::new(self.replay_boards.len() - 1);
+        self.finish_match(winner);
+    }
+
+    ///Method to play the end-of-game sound and record the finished game so the main menu can save it to the match history
+    fn finish_match(&mut self, winner: i32) {
+        let ai_teams: Vec<i32> = self.ai_players.iter().map(|ai| ai.team).collect();
+        self.sound_queue.push(result_sound(winner, &ai_teams));
+        self.finished_match = Some(MatchRecord::for_teams(
+            "Checkers",
+            &self.team_names,
+            &ai_teams,
+            self.ai_difficulty,
+            self.game.hops.clone(),
+            winner,
+            self.elapsed,
+        ));
+    }
+
+    ///
+    /// Method to add a piece to the MeshBuilder, in the style of a Connect 4 disc. A king is drawn with a yellow ring
+    /// inside it
+    ///
+    /// # Arguments
+    /// * mb     = MeshBuilder the piece is added to
+    /// * center = Location of the center of the piece
+    /// * team   = Integer value (1 or 2) of the team the piece belongs to
+    /// * king   = Boolean indicating if the piece has been crowned
+    ///
+    fn draw_piece<'a>(
+        &self,
+        mb: &'a mut graphics::MeshBuilder,
+        center: Point2<f32>,
+        team: i32,
+        king: bool,
+    ) -> &'a mut graphics::MeshBuilder {
+        let radius = BOARD_DISC_RADIUS as f32;
+        let color = self.team_colors[team as usize - 1].get_draw_color();
+        mb.circle(graphics::DrawMode::fill(), center, radius, 0.5, color);
+        mb.circle(
+            graphics::DrawMode::stroke(1.0),
+            center,
+            radius,
+            0.5,
+            graphics::BLACK,
+        );
+        if king {
+            mb.circle(
+                graphics::DrawMode::stroke(4.0),
+                center,
+                radius / 2.0,
+                0.5,
+                MyColor::Yellow.get_draw_color(),
+            );
+        }
+        mb
+    }
+
+    ///Method to draw the squares, the pieces, the moves a person may make and the last move made
+    fn draw_board(&self, ctx: &mut Context) -> GameResult {
+        let board = self.shown_board();
+        let mut mb = graphics::MeshBuilder::new();
+        for y in 0..BOARD_SIDE {
+            for x in 0..BOARD_SIDE {
+                let rect = graphics::Rect::new(
+                    BOARD_POSITION.0 + x as f32 * SQUARE_SIZE,
+                    BOARD_POSITION.1 + y as f32 * SQUARE_SIZE,
+                    SQUARE_SIZE,
+                    SQUARE_SIZE,
+                );
+                let color = if (x + y) % 2 == 0 {
+                    graphics::Color::from_rgba(235, 210, 160, 255)
+                } else {
+                    graphics::Color::from_rgba(120, 70, 40, 255)
+                };
+                mb.rectangle(graphics::DrawMode::fill(), rect, color);
+            }
+        }
+        //Mark the path of the last move, unless an earlier step of the replay is shown
+        let replaying = !self.replay.is_at_end();
+        if let Some(path) = self.last_move.as_ref().filter(|_| !replaying) {
+            for square in path {
+                mb.circle(
+                    graphics::DrawMode::fill(),
+                    self.square_center(*square),
+                    6.0,
+                    0.5,
+                    MyColor::Red.get_draw_color(),
+                );
+            }
+        }
+        //Draw every piece except one being dragged, which follows the mouse
+        let held = match (self.dragging, self.path.last()) {
+            (true, Some(square)) => Some(*square),
+            _ => None,
+        };
+        for square in 0..SQUARES {
+            if let Some(piece) = board.piece(square) {
+                if Some(square) != held {
+                    self.draw_piece(&mut mb, self.square_center(square), piece.team, piece.king);
+                }
+            }
+        }
+        //Dot the pieces a person may move, or the squares the picked piece may land on, and ring the picked piece, the
+        //square under the mouse and the squares of the AI's move
+        if self.accepts_input() {
+            let dots = if self.path.is_empty() {
+                let mut from: Vec<usize> =
+                    self.game.legal_moves().iter().map(|mv| mv.from()).collect();
+                from.dedup();
+                from
+            } else {
+                self.next_landings()
+            };
+            for square in dots {
+                mb.circle(
+                    graphics::DrawMode::fill(),
+                    self.square_center(square),
+                    8.0,
+                    0.5,
+                    graphics::Color::from_rgba(255, 255, 255, 120),
+                );
+            }
+        }
+        let mut rings: Vec<usize> = self.path.iter().cloned().chain(self.hover).collect();
+        if let Some(mv) = &self.aim {
+            rings.extend(mv.path.iter().cloned());
+        }
+        for square in rings {
+            mb.circle(
+                graphics::DrawMode::stroke(3.0),
+                self.square_center(square),
+                BOARD_DISC_RADIUS as f32 + 2.0,
+                0.5,
+                MyColor::Yellow.get_draw_color(),
+            );
+        }
+        if let Some(piece) = held.and_then(|square| board.piece(square)) {
+            self.draw_piece(&mut mb, self.mouse, piece.team, piece.king);
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))
+    }
+
+    ///Method to draw the players with their piece counts, the draw count or replay step and any message to the right
+    ///of the board
+    fn draw_panel(&self, ctx: &mut Context) -> GameResult {
+        draw_text(
+            ctx,
+            "Checkers",
+            (PANEL_POS_X, BOARD_POSITION.1),
+            CHECKERS_FONT_SIZE,
+            MyColor::White,
+        )?;
+        let board = self.shown_board();
+        for (i, name) in self.team_names.iter().enumerate() {
+            let team = i as i32 + 1;
+            let player = if self.is_ai(team) {
+                format!("{}: {} (AI)", PIECE_NAMES[i], name)
+            } else {
+                format!("{}: {}", PIECE_NAMES[i], name)
+            };
+            let y = BOARD_POSITION.1 + 50.0 + 70.0 * i as f32;
+            draw_text(
+                ctx,
+                &player,
+                (PANEL_POS_X, y),
+                CHECKERS_FONT_SIZE,
+                self.team_colors[i],
+            )?;
+            let (men, kings) = board.count(team);
+            draw_text(
+                ctx,
+                &format!("Men: {}  Kings: {}", men, kings),
+                (PANEL_POS_X + 20.0, y + 30.0),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        let mut lines = Vec::new();
+        if self.game.outcome.is_some() {
+            lines.push(format!(
+                "Move {} of {}",
+                self.replay.position(),
+                self.replay.moves()
+            ));
+            lines.push(String::from("Left/Right arrows replay"));
+        } else {
+            lines.push(format!(
+                "Moves to a draw: {}",
+                DRAW_MOVE_LIMIT - self.game.quiet_moves() / 2
+            ));
+            if self.path.len() > 1 {
+                lines.push(String::from("Keep jumping"));
+            }
+        }
+        if !self.message.is_empty() {
+            lines.push(self.message.clone());
+        }
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(
+                ctx,
+                line,
+                (PANEL_POS_X, BOARD_POSITION.1 + 200.0 + 30.0 * i as f32),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl GameScreen for CheckersState {
+    fn screen_size(&self) -> (f32, f32) {
+        CHECKERS_SCREEN_SIZE
+    }
+
+    ///Update method for checkers, which lets the AI pick a move, show it and make it once enough frames pass
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        self.frames += 1; //Timing mechanism for AI moves
+        if self.game.outcome.is_some() {
+            return Ok(());
+        }
+        self.elapsed += ggez::timer::delta(_ctx).as_secs_f32();
+        let team = self.game.team;
+        if let Some(i) = self.ai_players.iter().position(|ai| ai.team == team) {
+            if self.ai_players[i].last_move_frame < 0 {
+                self.aim = self.ai_players[i].pick_move(&self.game.board);
+                self.ai_players[i].last_move_frame = self.frames as i32;
+            } else if self.frames
+                > (self.ai_players[i].last_move_frame + self.ai_move_delay) as usize
+            {
+                self.ai_players[i].last_move_frame = -1;
+                if let Some(mv) = self.aim.take() {
+                    self.play(&mv);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    ///Draw method for checkers, which draws the turn indicator, the board, the player panel and the buttons
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::clear(ctx, self.background_color);
+        self.turn_indicator.draw(
+            ctx,
+            BOARD_POSITION.0 + SQUARE_SIZE * BOARD_SIDE as f32 / 2.0,
+        )?;
+        self.draw_board(ctx)?;
+        self.draw_panel(ctx)?;
+        self.main_menu_button.draw(ctx)?;
+        self.play_again_button.draw(ctx)?;
+        graphics::present(ctx)?;
+        ggez::timer::yield_now();
+        Ok(())
+    }
+
+    ///Method active whenever the mouse moves, which tracks where a dragged piece is drawn and the square under the
+    ///mouse the picked piece may land on
+    fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        self.mouse = mouse_location(ctx);
+        self.hover = if self.accepts_input() {
+            self.square_at(self.mouse)
+                .filter(|square| self.next_landings().contains(square))
+        } else {
+            None
+        };
+        self.main_menu_button.check_button_under_mouse(ctx);
+        self.play_again_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method active when a mouse button is pressed, which picks up a piece of the team to move so it can be dragged
+    fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton) {
+        self.mouse_motion_event(ctx);
+        if button != MouseButton::Left || !self.accepts_input() {
+            return;
+        }
+        if let Some(square) = self.square_at(self.mouse) {
+            self.dragging = self.pick(square) || self.path.last() == Some(&square);
+        }
+    }
+
+    ///Method to handle a click on the checkers screen, returning what the main menu should do next. Releasing a piece
+    ///on another square lands it there, and a right click puts a picked piece back
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton) -> GameAction {
+        self.mouse_motion_event(ctx);
+        let dragged = self.dragging;
+        self.dragging = false;
+        if self.main_menu_button.check_button_under_mouse(ctx) {
+            println!("Main Menu Button pressed; Main Menu should pop up");
+            self.sound_queue.push(SoundEffect::Click);
+            return GameAction::MainMenu;
+        }
+        if self.play_again_button.check_button_under_mouse(ctx) {
+            println!("Play Again button pressed; Board reset");
+            self.sound_queue.push(SoundEffect::Click);
+            self.reset_game();
+        } else if button == MouseButton::Right {
+            self.path.clear();
+            self.hover = None;
+        } else if button == MouseButton::Left && self.accepts_input() {
+            match (self.square_at(self.mouse), self.path.last()) {
+                //Letting go of a piece where it was picked up leaves it picked, to be moved by clicks
+                (Some(square), Some(last)) if square == *last => (),
+                (Some(square), Some(_)) => self.land(square),
+                (Some(_), None) => self.sound_queue.push(SoundEffect::InvalidMove),
+                (None, _) if dragged && self.path.len() == 1 => self.path.clear(),
+                (None, _) => (),
+            }
+            self.mouse_motion_event(ctx);
+        }
+        GameAction::None
+    }
+
+    ///Method active whenever a key is pressed. Once the game is over, the arrow keys step through its replay and Home
+    ///and End jump to either end
+    fn key_down_event(&mut self, keycode: KeyCode) {
+        self.replay.key_down_event(keycode);
+    }
+
+    fn drain_sounds(&mut self) -> Vec<SoundEffect> {
+        self.sound_queue.drain(..).collect()
+    }
+
+    fn take_finished_match(&mut self) -> Option<MatchRecord> {
+        self.finished_match.take()
+    }
+}
+
+//NOTE: Tests not included since testing requires usage of mouse and other UI features
@@ -0,0 +1,323 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use checkers::board::{decode_hop, Board, Move};
+use connect4::core::other_team;
+use std::collections::HashMap;
+
+///Constant number of moves each player may make in a row without a capture or a man moving before the game is drawn
+pub const DRAW_MOVE_LIMIT: usize = 40;
+
+///Constant number of times the same position, with the same team to move, must come up for the game to be drawn
+pub const REPETITION_DRAW_COUNT: usize = 3;
+
+///
+/// Enum representing how a game of checkers ended
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    /// The team won because the other team has no pieces left or no legal move
+    Win(i32),
+    /// The same position came up REPETITION_DRAW_COUNT times
+    Repetition,
+    /// Both players made DRAW_MOVE_LIMIT moves without a capture or a man moving
+    MoveLimit,
+}
+
+impl Outcome {
+    ///Returns the team that won, or 0 for a draw
+    pub fn winner(self) -> i32 {
+        match self {
+            Outcome::Win(team) => team,
+            _ => 0,
+        }
+    }
+
+    ///Returns why the game ended, e.g. "Draw by threefold repetition"
+    pub fn describe(self) -> String {
+        match self {
+            Outcome::Win(_) => String::from("No pieces or moves left"),
+            Outcome::Repetition => String::from("Draw by threefold repetition"),
+            Outcome::MoveLimit => format!("Draw by the {}-move rule", DRAW_MOVE_LIMIT),
+        }
+    }
+}
+
+///
+/// A struct tracking a game of checkers: the board, whose turn it is, the hops played and the counts the draw rules
+/// need
+///
+/// # Fields
+/// * board       = Board holding the pieces
+/// * team        = Integer value (1 or 2) of the team to move
+/// * hops        = Vector of hops played so far, in order, see encode_hop. Saved to the match history
+/// * quiet_moves = Number of moves in a row, by either team, with no capture and no man moving
+/// * positions   = HashMap of how many times each position has come up with each team to move since the last capture
+///   or man move
+/// * outcome     = Option of how the game ended, or None while it is being played
+///
+#[derive(Clone, Debug)]
+pub struct Game {
+    pub board: Board,
+    pub team: i32,
+    pub hops: Vec<i32>,
+    quiet_moves: usize,
+    positions: HashMap<(Board, i32), usize>,
+    pub outcome: Option<Outcome>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
+
+impl Game {
+    ///Constructor - returns a game at the starting position with team 1 to move
+    pub fn new() -> Self {
+        let mut game = Game {
+            board: Board::new(),
+            team: 1,
+            hops: Vec::new(),
+            quiet_moves: 0,
+            positions: HashMap::new(),
+            outcome: None,
+        };
+        game.positions.insert((game.board, 1), 1);
+        game
+    }
+
+    ///Returns the number of moves in a row, by either team, with no capture and no man moving
+    pub fn quiet_moves(&self) -> usize {
+        self.quiet_moves
+    }
+
+    ///Returns the legal moves for the team to move, or none once the game is over
+    pub fn legal_moves(&self) -> Vec<Move> {
+        if self.outcome.is_some() {
+            Vec::new()
+        } else {
+            self.board.legal_moves(self.team)
+        }
+    }
+
+    ///
+    /// Method to make a legal move for the team to move and pass the turn, then end the game if the other team can't
+    /// move or a draw rule applies. Returns true if the moving piece was crowned
+    ///
+    /// # Arguments
+    /// * mv = Move to make, which must be one of legal_moves
+    ///
+    pub fn play(&mut self, mv: &Move) -> bool {
+        let man_moved = self.board.piece(mv.from()).is_some_and(|piece| !piece.king);
+        let crowned = self.board.play(mv);
+        self.hops.extend(mv.hops());
+        if man_moved || !mv.captures.is_empty() {
+            //Neither can be undone, so no earlier position can come up again
+            self.quiet_moves = 0;
+            self.positions.clear();
+        } else {
+            self.quiet_moves += 1;
+        }
+        self.team = other_team(self.team);
+        let seen = self.positions.entry((self.board, self.team)).or_insert(0);
+        *seen += 1;
+        self.outcome = if self.board.legal_moves(self.team).is_empty() {
+            Some(Outcome::Win(other_team(self.team)))
+        } else if *seen >= REPETITION_DRAW_COUNT {
+            Some(Outcome::Repetition)
+        } else if self.quiet_moves >= 2 * DRAW_MOVE_LIMIT {
+            Some(Outcome::MoveLimit)
+        } else {
+            None
+        };
+        crowned
+    }
+
+    ///
+    /// Returns the boards reached by replaying recorded hops from the starting position: the starting board, then the
+    /// board after each whole move. A capture is carried on by the next hop while the piece that made it can still
+    /// jump. Replaying stops at the first hop that isn't part of a legal move
+    ///
+    /// # Arguments
+    /// * hops = Vector of hops, in order, as saved in the match history
+    ///
+    pub fn replay(hops: &[i32]) -> Vec<Board> {
+        let mut game = Game::new();
+        let mut boards = vec![game.board];
+        let mut index = 0;
+        while index < hops.len() {
+            let legal = game.legal_moves();
+            let (from, _) = decode_hop(hops[index]);
+            let mut path = vec![from];
+            //Follow the hops while they match the start of a legal move, until one is completed
+            let mut found = None;
+            while index < hops.len() {
+                let (hop_from, to) = decode_hop(hops[index]);
+                if hop_from != path[path.len() - 1] {
+                    break;
+                }
+                path.push(to);
+                index += 1;
+                found = legal.iter().find(|mv| mv.path == path);
+                let continues = legal
+                    .iter()
+                    .any(|mv| mv.path.len() > path.len() && mv.path.starts_with(&path));
+                if found.is_some() || !continues {
+                    break;
+                }
+            }
+            match found {
+                Some(mv) => {
+                    let mv = mv.clone();
+                    game.play(&mv);
+                    boards.push(game.board);
+                }
+                None => break,
+            }
+        }
+        boards
+    }
+}
+
+#[cfg(test)]
+mod game_tests {
+    use super::*;
+    use checkers::board::{Piece, SQUARES};
+    use connect4::core::GridPosition;
+
+    //Method to create a game from a position, given as the team on each (column, row) and whether it is a king
+    fn create_test_game(pieces: &[((i32, i32), i32, bool)], team: i32) -> Game {
+        let empty = Board::from([None; SQUARES]);
+        let mut squares = [None; SQUARES];
+        for (pos, owner, king) in pieces {
+            let square = empty.square_at(GridPosition::new(pos.0, pos.1)).unwrap();
+            squares[square] = Some(Piece {
+                team: *owner,
+                king: *king,
+            });
+        }
+        let mut game = Game::new();
+        game.board = Board::from(squares);
+        game.team = team;
+        game.positions.clear();
+        game.positions.insert((game.board, team), 1);
+        game
+    }
+
+    //Method to return the legal move of the game's team from one (column, row) position to another
+    fn find_move(game: &Game, from: (i32, i32), to: (i32, i32)) -> Move {
+        let from = game
+            .board
+            .square_at(GridPosition::new(from.0, from.1))
+            .unwrap();
+        let to = game.board.square_at(GridPosition::new(to.0, to.1)).unwrap();
+        game.legal_moves()
+            .into_iter()
+            .find(|mv| mv.from() == from && mv.to() == to)
+            .unwrap()
+    }
+
+    mod game {
+        use super::*;
+
+        mod play {
+            use super::*;
+
+            #[test]
+            fn should_win_when_the_other_team_has_no_pieces() {
+                let mut game = create_test_game(&[((3, 4), 1, false), ((4, 3), 2, false)], 1);
+                let mv = find_move(&game, (3, 4), (5, 2));
+                game.play(&mv);
+                assert_eq!(game.outcome, Some(Outcome::Win(1)));
+                assert!(game.legal_moves().is_empty());
+            }
+
+            #[test]
+            fn should_draw_by_threefold_repetition() {
+                let mut game = create_test_game(&[((0, 7), 1, true), ((7, 0), 2, true)], 1);
+                for _ in 0..2 {
+                    for (from, to) in [
+                        ((0, 7), (1, 6)),
+                        ((7, 0), (6, 1)),
+                        ((1, 6), (0, 7)),
+                        ((6, 1), (7, 0)),
+                    ]
+                    .iter()
+                    {
+                        assert_eq!(game.outcome, None);
+                        let mv = find_move(&game, *from, *to);
+                        game.play(&mv);
+                    }
+                }
+                assert_eq!(game.outcome, Some(Outcome::Repetition));
+                assert_eq!(game.outcome.unwrap().winner(), 0);
+            }
+
+            #[test]
+            fn should_draw_after_forty_moves_each_without_progress() {
+                let mut game = create_test_game(
+                    &[((0, 7), 1, true), ((7, 0), 2, true), ((2, 1), 2, false)],
+                    1,
+                );
+                game.quiet_moves = 2 * DRAW_MOVE_LIMIT - 2;
+                let mv = find_move(&game, (0, 7), (1, 6));
+                game.play(&mv);
+                assert_eq!(game.outcome, None);
+                //A man moving starts the count again
+                let mv = find_move(&game, (2, 1), (1, 2));
+                game.play(&mv);
+                assert_eq!(game.quiet_moves, 0);
+                game.quiet_moves = 2 * DRAW_MOVE_LIMIT - 1;
+                let mv = find_move(&game, (1, 6), (0, 7));
+                game.play(&mv);
+                assert_eq!(game.outcome, Some(Outcome::MoveLimit));
+                assert_eq!(game.outcome.unwrap().describe(), "Draw by the 40-move rule");
+            }
+        }
+
+        mod replay {
+            use super::*;
+
+            #[test]
+            fn should_rebuild_every_position_from_recorded_hops() {
+                let mut game = Game::new();
+                let mut boards = vec![game.board];
+                for _ in 0..30 {
+                    let legal = game.legal_moves();
+                    if legal.is_empty() {
+                        break;
+                    }
+                    //Prefer the longest capture so multi-jumps are replayed too
+                    let mv = legal
+                        .iter()
+                        .max_by_key(|mv| mv.captures.len())
+                        .unwrap()
+                        .clone();
+                    game.play(&mv);
+                    boards.push(game.board);
+                }
+                assert_eq!(Game::replay(&game.hops), boards);
+            }
+
+            #[test]
+            fn should_replay_a_multi_jump_as_one_move() {
+                let mut game = create_test_game(
+                    &[
+                        ((0, 5), 1, false),
+                        ((1, 4), 2, false),
+                        ((3, 2), 2, false),
+                        ((7, 0), 2, false),
+                    ],
+                    1,
+                );
+                let mv = find_move(&game, (0, 5), (4, 1));
+                assert_eq!(mv.hops().len(), 2);
+                game.play(&mv);
+                assert_eq!(game.board.count(2), (1, 0));
+                assert_eq!(game.hops.len(), 2);
+            }
+        }
+    }
+}
@@ -0,0 +1,481 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use connect4::core::MyColor;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+use std::fmt;
+
+///Constant font size for the clock text
+const CLOCK_FONT_SIZE: f32 = 24.0;
+
+///Constant dimensions for each team's clock box
+const CLOCK_BOX_SIZE: (f32, f32) = (160.0, 64.0);
+
+///Constant vertical spacing between clock boxes
+const CLOCK_BOX_SPACING: f32 = 10.0;
+
+///Constant number of seconds left below which a clock is drawn in red
+const CLOCK_LOW_TIME: f32 = 10.0;
+
+///
+/// A struct describing how much time each team gets. A chess-style clock gives each team total_seconds for the whole
+/// game plus increment_seconds after every move, while move_seconds limits how long a single move can take. Either
+/// or both can be used, and a value of 0 turns that part off
+///
+/// # Fields
+/// * total_seconds     = Seconds each team starts the game with, or 0 for no game clock
+/// * increment_seconds = Seconds added to a team's game clock after each of their moves
+/// * move_seconds      = Seconds each team has to make a single move, or 0 for no per-move limit
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeControl {
+    pub total_seconds: u32,
+    pub increment_seconds: u32,
+    pub move_seconds: u32,
+}
+
+impl Default for TimeControl {
+    fn default() -> Self {
+        TimeControl::off()
+    }
+}
+
+impl TimeControl {
+    ///Returns a TimeControl with no game clock and no per-move limit
+    pub fn off() -> Self {
+        TimeControl {
+            total_seconds: 0,
+            increment_seconds: 0,
+            move_seconds: 0,
+        }
+    }
+
+    ///Returns a chess-style TimeControl of the given minutes per game plus increment seconds per move
+    pub fn game(minutes: u32, increment_seconds: u32) -> Self {
+        TimeControl {
+            total_seconds: minutes * 60,
+            increment_seconds,
+            move_seconds: 0,
+        }
+    }
+
+    ///Returns a TimeControl that only limits each move to the given seconds
+    pub fn per_move(seconds: u32) -> Self {
+        TimeControl {
+            total_seconds: 0,
+            increment_seconds: 0,
+            move_seconds: seconds,
+        }
+    }
+
+    ///Returns the time controls that can be picked on the settings screen, in the order they are cycled through
+    pub fn presets() -> Vec<TimeControl> {
+        vec![
+            TimeControl::off(),
+            TimeControl::game(1, 0),
+            TimeControl::game(3, 2),
+            TimeControl::game(5, 0),
+            TimeControl::game(10, 5),
+            TimeControl::per_move(10),
+            TimeControl::per_move(30),
+        ]
+    }
+
+    ///Returns true if either the game clock or the per-move limit is used
+    pub fn is_enabled(self) -> bool {
+        self.total_seconds > 0 || self.move_seconds > 0
+    }
+
+    ///
+    /// Method to return the preset before or after this one, wrapping around. Time controls that are not presets (e.g.
+    /// typed into the settings file) move to the first preset
+    ///
+    /// # Arguments
+    /// * up = Boolean indicating if the next (true) or previous (false) preset should be returned
+    ///
+    pub fn step(self, up: bool) -> TimeControl {
+        let presets = TimeControl::presets();
+        let len = presets.len();
+        match presets.iter().position(|preset| *preset == self) {
+            Some(i) if up => presets[(i + 1) % len],
+            Some(i) => presets[(i + len - 1) % len],
+            None => presets[0],
+        }
+    }
+
+    ///Returns a copy of the time control with every value moved into its valid range
+    pub fn clamped(self) -> TimeControl {
+        TimeControl {
+            total_seconds: self.total_seconds.min(90 * 60),
+            increment_seconds: self.increment_seconds.min(60),
+            move_seconds: self.move_seconds.min(300),
+        }
+    }
+}
+
+//Displays in chess notation, e.g. "3+2" for 3 minutes plus 2 seconds per move, or "10s/move" for a per-move limit
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.is_enabled() {
+            return write!(f, "Off");
+        }
+        let mut parts = Vec::new();
+        if self.total_seconds > 0 {
+            if self.total_seconds.is_multiple_of(60) {
+                parts.push(format!(
+                    "{}+{}",
+                    self.total_seconds / 60,
+                    self.increment_seconds
+                ));
+            } else {
+                parts.push(format!(
+                    "{}+{}",
+                    format_time(self.total_seconds as f32),
+                    self.increment_seconds
+                ));
+            }
+        }
+        if self.move_seconds > 0 {
+            parts.push(format!("{}s/move", self.move_seconds));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+///Function to format a number of seconds as minutes and seconds (e.g. "2:05"), rounding partial seconds up
+pub fn format_time(seconds: f32) -> String {
+    let whole = seconds.max(0.0).ceil() as u32;
+    format!("{}:{:02}", whole / 60, whole % 60)
+}
+
+///
+/// A struct tracking the time left for each team during a game
+///
+/// # Fields
+/// * control      = TimeControl the clock was started with
+/// * remaining    = Vector of seconds left on each team's game clock, where remaining[i] is for team i (index 0 is unused)
+/// * move_elapsed = Seconds the current move has taken so far
+/// * flagged      = Team whose time ran out, or 0 if no team has run out of time
+///
+pub struct GameClock {
+    control: TimeControl,
+    remaining: Vec<f32>,
+    move_elapsed: f32,
+    pub flagged: i32,
+}
+
+impl GameClock {
+    ///
+    /// Method to initialize and return a GameClock object
+    ///
+    /// # Arguments
+    /// * control = TimeControl used by every team
+    /// * teams   = Number of teams in the game
+    ///
+    pub fn new(control: TimeControl, teams: usize) -> Self {
+        GameClock {
+            control,
+            remaining: vec![control.total_seconds as f32; teams + 1],
+            move_elapsed: 0.0,
+            flagged: 0,
+        }
+    }
+
+    ///Returns true if the clock is counting down time
+    pub fn is_enabled(&self) -> bool {
+        self.control.is_enabled()
+    }
+
+    ///
+    /// Method to count down time for the team making a move. Returns true if the team ran out of time, which is
+    /// recorded in flagged
+    ///
+    /// # Arguments
+    /// * team    = Team whose turn it is
+    /// * seconds = Seconds passed since the last call
+    ///
+    pub fn tick(&mut self, team: i32, seconds: f32) -> bool {
+        if !self.is_enabled() || self.flagged != 0 || team <= 0 {
+            return self.flagged != 0;
+        }
+        self.move_elapsed += seconds;
+        if self.control.total_seconds > 0 {
+            self.remaining[team as usize] -= seconds;
+        }
+        if self.time_left(team).is_some_and(|left| left <= 0.0) {
+            self.flagged = team;
+        }
+        self.flagged != 0
+    }
+
+    ///Method to end the given team's move, adding the increment to their game clock and restarting the move limit
+    pub fn end_move(&mut self, team: i32) {
+        if self.control.total_seconds > 0 && team > 0 {
+            self.remaining[team as usize] += self.control.increment_seconds as f32;
+        }
+        self.move_elapsed = 0.0;
+    }
+
+    ///Returns the seconds the team has left to make their current move, or None if the clock is off
+    pub fn time_left(&self, team: i32) -> Option<f32> {
+        if team <= 0 || team as usize >= self.remaining.len() {
+            return None;
+        }
+        let game_left = if self.control.total_seconds > 0 {
+            Some(self.remaining[team as usize])
+        } else {
+            None
+        };
+        let move_left = if self.control.move_seconds > 0 {
+            Some(self.control.move_seconds as f32 - self.move_elapsed)
+        } else {
+            None
+        };
+        match (game_left, move_left) {
+            (Some(game), Some(mv)) => Some(game.min(mv).max(0.0)),
+            (Some(left), None) | (None, Some(left)) => Some(left.max(0.0)),
+            (None, None) => None,
+        }
+    }
+
+    ///Returns the text shown on a team's clock: the game clock, the per-move limit, or both
+    pub fn display(&self, team: i32, active_team: i32) -> String {
+        let mut text = String::new();
+        if self.control.total_seconds > 0 {
+            text = format_time(self.remaining[team as usize]);
+        }
+        if self.control.move_seconds > 0 {
+            let move_left = if team == active_team {
+                self.control.move_seconds as f32 - self.move_elapsed
+            } else {
+                self.control.move_seconds as f32
+            };
+            if text.is_empty() {
+                text = format_time(move_left);
+            } else {
+                text = format!("{} ({})", text, format_time(move_left));
+            }
+        }
+        text
+    }
+
+    ///Method to restart every team's clock
+    pub fn reset(&mut self) {
+        for remaining in &mut self.remaining {
+            *remaining = self.control.total_seconds as f32;
+        }
+        self.move_elapsed = 0.0;
+        self.flagged = 0;
+    }
+
+    ///
+    /// Draw method for rendering one box per team, stacked downwards. The team whose turn it is gets a white outline and
+    /// clocks with little time left are drawn in red
+    ///
+    /// # Arguments
+    /// * position    = Top-left location of the first box
+    /// * names       = Vector of player names, where names[i] is for team i (index 0 is unused)
+    /// * colors      = Vector of disc colors, where colors[i] is for team i (index 0 is unused)
+    /// * active_team = Team whose turn it is, or 0 if the game is over
+    ///
+    pub fn draw(
+        &self,
+        ctx: &mut Context,
+        position: (f32, f32),
+        names: &[String],
+        colors: &[MyColor],
+        active_team: i32,
+    ) -> GameResult<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        for team in 1..self.remaining.len() {
+            let outline = graphics::Rect::new(
+                position.0,
+                position.1 + (CLOCK_BOX_SIZE.1 + CLOCK_BOX_SPACING) * (team - 1) as f32,
+                CLOCK_BOX_SIZE.0,
+                CLOCK_BOX_SIZE.1,
+            );
+            let mut mb = graphics::MeshBuilder::new();
+            mb.rectangle(
+                graphics::DrawMode::fill(),
+                outline,
+                graphics::Color::from_rgba(205, 133, 63, 255),
+            );
+            mb.rectangle(
+                graphics::DrawMode::fill(),
+                graphics::Rect::new(outline.x, outline.y, 8.0, outline.h),
+                colors[team].get_draw_color(),
+            );
+            if team as i32 == active_team {
+                mb.rectangle(graphics::DrawMode::stroke(3.0), outline, graphics::WHITE);
+            }
+            let mesh = mb.build(ctx)?;
+            graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))?;
+
+            let name = graphics::Text::new((
+                names[team].as_str(),
+                graphics::Font::default(),
+                CLOCK_FONT_SIZE * 0.75,
+            ));
+            graphics::draw(
+                ctx,
+                &name,
+                (Point2 {
+                    x: outline.x + 14.0,
+                    y: outline.y + 6.0,
+                },),
+            )?;
+            let low = self
+                .time_left(team as i32)
+                .is_some_and(|left| left < CLOCK_LOW_TIME);
+            let time_color = if self.flagged == team as i32 || (low && team as i32 == active_team) {
+                MyColor::Red.get_draw_color()
+            } else {
+                graphics::WHITE
+            };
+            let time = graphics::Text::new((
+                self.display(team as i32, active_team),
+                graphics::Font::default(),
+                CLOCK_FONT_SIZE,
+            ));
+            graphics::draw(
+                ctx,
+                &time,
+                (
+                    Point2 {
+                        x: outline.x + 14.0,
+                        y: outline.y + 10.0 + CLOCK_FONT_SIZE * 0.75,
+                    },
+                    time_color,
+                ),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod clock_tests {
+    use super::*;
+
+    mod time_control {
+        use super::*;
+
+        mod fmt {
+            use super::*;
+
+            #[test]
+            fn should_use_chess_notation() {
+                assert_eq!(TimeControl::off().to_string(), "Off");
+                assert_eq!(TimeControl::game(3, 2).to_string(), "3+2");
+                assert_eq!(TimeControl::per_move(10).to_string(), "10s/move");
+                let both = TimeControl {
+                    total_seconds: 90,
+                    increment_seconds: 0,
+                    move_seconds: 15,
+                };
+                assert_eq!(both.to_string(), "1:30+0, 15s/move");
+            }
+        }
+
+        mod step {
+            use super::*;
+
+            #[test]
+            fn should_cycle_presets_both_ways() {
+                let presets = TimeControl::presets();
+                assert_eq!(TimeControl::off().step(true), presets[1]);
+                assert_eq!(TimeControl::off().step(false), presets[presets.len() - 1]);
+                assert_eq!(presets[presets.len() - 1].step(true), TimeControl::off());
+            }
+
+            #[test]
+            fn should_move_custom_values_to_first_preset() {
+                assert_eq!(TimeControl::game(7, 7).step(true), TimeControl::off());
+            }
+        }
+    }
+
+    mod format_time {
+        use super::*;
+
+        #[test]
+        fn should_round_partial_seconds_up() {
+            assert_eq!(format_time(125.0), "2:05");
+            assert_eq!(format_time(0.2), "0:01");
+            assert_eq!(format_time(-3.0), "0:00");
+        }
+    }
+
+    mod game_clock {
+        use super::*;
+
+        mod tick {
+            use super::*;
+
+            #[test]
+            fn should_only_count_down_active_team() {
+                let mut clock = GameClock::new(TimeControl::game(1, 0), 2);
+                clock.tick(1, 15.0);
+                assert_eq!(clock.time_left(1), Some(45.0));
+                assert_eq!(clock.time_left(2), Some(60.0));
+            }
+
+            #[test]
+            fn should_flag_team_when_game_clock_runs_out() {
+                let mut clock = GameClock::new(TimeControl::game(1, 0), 2);
+                assert!(!clock.tick(2, 59.0));
+                assert!(clock.tick(2, 1.5));
+                assert_eq!(clock.flagged, 2);
+                assert_eq!(clock.time_left(2), Some(0.0));
+            }
+
+            #[test]
+            fn should_flag_team_when_move_limit_runs_out() {
+                let mut clock = GameClock::new(TimeControl::per_move(10), 2);
+                assert!(!clock.tick(1, 6.0));
+                clock.end_move(1);
+                assert!(!clock.tick(2, 6.0));
+                assert!(clock.tick(2, 4.0));
+                assert_eq!(clock.flagged, 2);
+            }
+
+            #[test]
+            fn should_do_nothing_if_off() {
+                let mut clock = GameClock::new(TimeControl::off(), 2);
+                assert!(!clock.tick(1, 1000.0));
+                assert_eq!(clock.time_left(1), None);
+            }
+        }
+
+        mod end_move {
+            use super::*;
+
+            #[test]
+            fn should_add_increment_and_restart_move_limit() {
+                let control = TimeControl {
+                    total_seconds: 60,
+                    increment_seconds: 2,
+                    move_seconds: 20,
+                };
+                let mut clock = GameClock::new(control, 2);
+                clock.tick(1, 10.0);
+                assert_eq!(clock.time_left(1), Some(10.0));
+                clock.end_move(1);
+                assert_eq!(clock.time_left(1), Some(20.0));
+                clock.tick(2, 50.0);
+                assert_eq!(clock.flagged, 2);
+                clock.reset();
+                assert_eq!(clock.flagged, 0);
+                assert_eq!(clock.time_left(1), Some(20.0));
+                assert_eq!(clock.display(1, 1), "1:00 (0:20)");
+            }
+        }
+    }
+}
@@ -3,9 +3,13 @@
 // Please see the file LICENSE in the source
 // distribution of this software for license terms.
 
-use connect4::core::{Board, GridPosition, MyColor, BOARD_SIZE};
+use connect4::core::{decode_move, Board, GridPosition, MyColor};
+use connect4::pop10::{Pop10Phase, Pop10State};
 use std::cmp::Ordering;
 
+/// Constant definition for the highest difficulty (search depth) an AI can be given.
+pub const MAX_DIFFICULTY: i32 = 6;
+
 ///
 /// A struct representing a potential future move on a given board. Utilized by the AI struct to
 /// determine future moves and win probabilities
@@ -13,14 +17,16 @@ use std::cmp::Ordering;
 /// # Fields
 /// * team  = Integer value (1-2) representing team that is making the move
 /// * board = Board object representing grid state after a move is made
-/// * run   = Array of runs for the given team from the location of the move for this object. runs[0] is # of runs
+/// * run   = Vector of runs for the given team from the location of the move for this object. runs[0] is # of runs
 ///              of length 1, runs[1] is # of runs of length 2, etc. Runs are often duplicates (i.e. a contiguous run of
 ///              3 in the vertical direction is counted as both a run of 3 in the up and down direction)
+/// * gives_win = Boolean indicating if the move is a pop that completes a winning run for another team only
 ///
 pub struct MoveCheck {
     team: i32,
     board: Board,
-    runs: [i32; 4],
+    runs: Vec<i32>,
+    gives_win: bool,
 }
 
 impl MoveCheck {
@@ -28,11 +34,15 @@ impl MoveCheck {
     /// Method to initialize and return a MoveCheck object
     ///
     /// # Arguments
-    /// * board    = Board struct representing the state of the board prior to the move being made
-    /// * move_col = Index of column the disc is dropped in to make the move
-    /// * team     = Integer value represent the team number of the disc being placed for the move
+    /// * board = Board struct representing the state of the board prior to the move being made
+    /// * mv    = Move to make, the index of the column the disc is dropped in or pop_move(column) for a pop
+    /// * team  = Integer value represent the team number of the disc being placed for the move
     ///
-    fn new(board: Board, move_col: i32, team: i32) -> Self {
+    fn new(board: Board, mv: i32, team: i32) -> Self {
+        let (move_col, pop) = decode_move(mv);
+        if pop {
+            return MoveCheck::new_pop(board, move_col, team);
+        }
         let mut new_board = board.clone();
         let runs = new_board.get_runs_from_point(
             GridPosition::new(
@@ -46,14 +56,50 @@ impl MoveCheck {
             team,
             board: new_board,
             runs,
+            gives_win: false,
         }
     }
 
     ///
-    /// Method returning a boolean indicating if the move produces a run of 4 for the given team
+    /// Method to initialize a MoveCheck for popping a disc out of a column. Every disc in the column moves, so the runs
+    /// kept are the best ones through any of the team's discs left in the column
+    ///
+    /// # Arguments
+    /// * board    = Board struct representing the state of the board prior to the move being made
+    /// * move_col = Index of column the disc is popped from
+    /// * team     = Integer value represent the team number of the disc being popped
+    ///
+    fn new_pop(board: Board, move_col: i32, team: i32) -> Self {
+        let mut new_board = board;
+        new_board.pop(move_col, team);
+        let mut runs = vec![0; new_board.config().connect as usize];
+        for row in 0..new_board.get_column_height(move_col as usize) as i32 {
+            let pos = GridPosition::new(move_col, row);
+            if new_board.get_cell_team(pos) == team {
+                let cell_runs = new_board.get_runs_from_point(pos, team);
+                if cell_runs.iter().rev().gt(runs.iter().rev()) {
+                    runs = cell_runs;
+                }
+            }
+        }
+        let teams = new_board.config().teams;
+        let gives_win = runs[runs.len() - 1] == 0
+            && (1..=teams)
+                .filter(|other| *other != team)
+                .any(|other| new_board.column_connects(move_col, other));
+        MoveCheck {
+            team,
+            board: new_board,
+            runs,
+            gives_win,
+        }
+    }
+
+    ///
+    /// Method returning a boolean indicating if the move produces a winning run (4 on the standard board) for the given team
     ///
     fn has_end_result(&self) -> bool {
-        self.runs[3] > 0
+        self.runs[self.runs.len() - 1] > 0
     }
 
     ///
@@ -65,10 +111,12 @@ impl MoveCheck {
     ///
     fn get_win_probability(&self, team: i32) -> f32 {
         let mut prob = 0f32;
+        let longest = self.runs.len() as i32 - 1;
         for i in 0..self.runs.len() {
-            //Formula is 1/2^(i-3) * runs[i], so each run[3] has a prob of 1 (since it corresponds to a run of 3),
-            //each run[2] has a prob of 0.5, etc. All of this is divided by 2 since runs duplicate in opposite directionss
-            prob += (2.0f32.powi((i as i32) - 3) * (self.runs[i] as f32)) / 2.0;
+            //Formula is 1/2^(i-3) * runs[i] on the standard board, so each run[3] has a prob of 1 (since it corresponds to a run of 3),
+            //each run[2] has a prob of 0.5, etc. All of this is divided by 2 since runs duplicate in opposite directionss.
+            //Longer or shorter connect lengths shift the formula so the longest run always has a prob of 1
+            prob += (2.0f32.powi((i as i32) - longest) * (self.runs[i] as f32)) / 2.0;
         }
         //If teams match, return probability (but don't go over prob of 1.0)
         if team == self.team {
@@ -83,7 +131,7 @@ impl MoveCheck {
 //Ordering implementation based on documentation example (https://doc.rust-lang.org/std/cmp/trait.Ord.html), tailored to compare MoveCheck's runs
 impl Ord for MoveCheck {
     fn cmp(&self, other: &Self) -> Ordering {
-        for i in (0..4).rev() {
+        for i in (0..self.runs.len()).rev() {
             if self.runs[i] != other.runs[i] {
                 if (i as i32) * (self.runs[i] - other.runs[i]) < 0 {
                     return Ordering::Less;
@@ -110,6 +158,28 @@ impl PartialEq for MoveCheck {
 
 impl Eq for MoveCheck {}
 
+///
+/// Enum representing the AI's evaluation of a move. Moves are counted for the team making the move, so WinIn(1) wins
+/// right away and LossIn(1) lets the other team win on their next move
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Evaluation {
+    WinIn(i32),
+    LossIn(i32),
+    Score(f32),
+}
+
+impl Evaluation {
+    ///Returns the short text drawn for the evaluation, e.g. "Win 2", "Loss 1" or "62%"
+    pub fn label(self) -> String {
+        match self {
+            Evaluation::WinIn(n) => format!("Win {}", n),
+            Evaluation::LossIn(n) => format!("Loss {}", n),
+            Evaluation::Score(prob) => format!("{:.0}%", prob * 100.0),
+        }
+    }
+}
+
 ///
 /// A struct representing an AI or bot player for Connect4 which has methods to determine "ideal" moves
 ///
@@ -146,28 +216,74 @@ impl AI {
     /// the next disc
     ///
     /// # Arguments
-    /// * board    = Board struct representing the current state of the board
+    /// * board     = Board struct representing the current state of the board
+    /// * time_left = Option of seconds the AI has left for its move, or None if there is no clock
+    ///
+    pub fn pick_optimal_move(&self, board: Board, time_left: Option<f32>) -> i32 {
+        self.pick_move_at_depth(board, self.search_depth(time_left))
+    }
+
+    ///
+    /// Method to return the depth the AI can search to with the time it has left. Searches get shallower as the clock
+    /// runs low, but never deeper than the AI's difficulty
+    ///
+    /// # Arguments
+    /// * time_left = Option of seconds the AI has left for its move, or None if there is no clock
+    ///
+    pub fn search_depth(&self, time_left: Option<f32>) -> i32 {
+        let max_depth = match time_left {
+            None => self.difficulty,
+            Some(t) if t < 2.0 => 1,
+            Some(t) if t < 5.0 => 2,
+            Some(t) if t < 15.0 => 3,
+            Some(t) if t < 30.0 => 4,
+            Some(_) => self.difficulty,
+        };
+        self.difficulty.min(max_depth)
+    }
+
+    ///
+    /// Method to determine the "optimal" move by searching a given number of moves ahead. Returns an integer value
+    /// represnting the column to place the next disc. Games with more than two teams use a paranoid search, where
+    /// every other team is assumed to play against the AI
+    ///
+    /// # Arguments
+    /// * board = Board struct representing the current state of the board
+    /// * depth = Integer value for how deep the recursive search for a move will go
     ///
-    pub fn pick_optimal_move(&self, board: Board) -> i32 {
+    pub fn pick_move_at_depth(&self, board: Board, depth: i32) -> i32 {
         let mut best_move = -1;
         let mut best_prob = 0f32;
-        for i in 0..BOARD_SIZE.1 {
-            //For each valid move, create a MoveCheck to evaluate immediate move options
-            if !board.is_column_full(i as usize) {
-                let next_move = MoveCheck::new(board.clone(), i, self.team);
-                //If move will win game, make move
-                if next_move.has_end_result() {
-                    return i;
-                } else {
-                    //Otherwise, find win probability after move has been made to see if it is better than other possible moves
-                    let curr_prob = self.find_win_probability(next_move.board, 1, self.difficulty);
-                    if curr_prob == 1f32 {
-                        return i;
-                    } else if curr_prob >= best_prob {
-                        best_prob = curr_prob;
-                        best_move = i;
-                    }
-                }
+        let mut found = false;
+        //For each valid move, create a MoveCheck to evaluate immediate move options
+        let next_moves: Vec<(i32, MoveCheck)> = board
+            .legal_moves(self.team)
+            .into_iter()
+            .map(|i| (i, MoveCheck::new(board.clone(), i, self.team)))
+            .collect();
+        //If a move will win game, make move. Every move is checked first so a winning pop isn't passed over for a drop
+        //that only wins later
+        if let Some((i, _)) = next_moves.iter().find(|(_, m)| m.has_end_result()) {
+            return *i;
+        }
+        for (i, next_move) in next_moves {
+            //Otherwise, find win probability after move has been made to see if it is better than other possible moves.
+            //A pop that hands the other team a win is only made if nothing else can be
+            let teams = board.config().teams;
+            let curr_prob = if next_move.gives_win {
+                0f32
+            } else if teams > 2 {
+                let next_team = board.config().next_team(self.team);
+                self.find_paranoid_probability(next_move.board, next_team, depth, 0f32, 1f32)
+            } else {
+                self.find_win_probability(next_move.board, 1, depth)
+            };
+            if curr_prob == 1f32 {
+                return i;
+            } else if !found || curr_prob >= best_prob {
+                best_prob = curr_prob;
+                best_move = i;
+                found = true;
             }
         }
         best_move
@@ -184,26 +300,25 @@ impl AI {
     ///
     fn find_win_probability(&self, board: Board, curr_move: i32, last_move: i32) -> f32 {
         let mut moves = Vec::new();
-        for i in 0..BOARD_SIZE.1 {
-            if !board.is_column_full(i as usize) {
-                let board = board.clone();
-                //This will always make a MoveCheck where the "team" is self.team if curr_move%2 == 0 and the opposite team if curr_move%2 == 1
-                //Assumes only two teams, 1 and 2
-                let move_check = MoveCheck::new(board, i, (self.team + curr_move + 1) % 2 + 1);
-                //If move produces end result, return an absolute probability of 1 (if current move_check is for team) or 0 (for opp)
-                if move_check.has_end_result() {
-                    return (1 - (self.team - move_check.team).abs() % 2) as f32;
-                //If curr_move is not last move, recurse on subsequent moves from the current move_check and add result to a list
-                } else if curr_move < last_move {
-                    moves.push(self.find_win_probability(
-                        move_check.board,
-                        curr_move + 1,
-                        last_move,
-                    ));
-                //Base case - this is the last move, so just add the current probability of win for this move_check to the list
-                } else {
-                    moves.push(move_check.get_win_probability(self.team));
-                }
+        //This will always make a MoveCheck where the "team" is self.team if curr_move%2 == 0 and the opposite team if curr_move%2 == 1
+        //Assumes only two teams, 1 and 2; games with more teams use find_paranoid_probability
+        let team = (self.team + curr_move + 1) % 2 + 1;
+        for i in board.legal_moves(team) {
+            let board = board.clone();
+            let move_check = MoveCheck::new(board, i, team);
+            //A pop that completes a run for the other team only is never worth making, so it is left out
+            if move_check.gives_win {
+                continue;
+            }
+            //If move produces end result, return an absolute probability of 1 (if current move_check is for team) or 0 (for opp)
+            if move_check.has_end_result() {
+                return (1 - (self.team - move_check.team).abs() % 2) as f32;
+            //If curr_move is not last move, recurse on subsequent moves from the current move_check and add result to a list
+            } else if curr_move < last_move {
+                moves.push(self.find_win_probability(move_check.board, curr_move + 1, last_move));
+            //Base case - this is the last move, so just add the current probability of win for this move_check to the list
+            } else {
+                moves.push(move_check.get_win_probability(self.team));
             }
         }
         //Edge case - no moves to make, return 0 probability (can't win)
@@ -218,12 +333,234 @@ impl AI {
             moves.iter().sum::<f32>() / (moves.len() as f32)
         }
     }
+
+    ///
+    /// Method to recursively find the win probability for a board state in a game with more than two teams, using a
+    /// paranoid search: the AI picks its best move and every other team picks the move that is worst for the AI, as if
+    /// they were all on one team. This makes it a two-sided search, so alpha-beta pruning can skip moves that can't
+    /// change the result. Returns a value between 1.0 and 0.0
+    ///
+    /// # Arguments
+    /// * board = Board struct representing the current state of the board
+    /// * team  = Integer value of the team about to move
+    /// * depth = Integer value for how many more moves (counting every team) to search before making a best guess
+    /// * alpha = Lowest probability the AI is already sure of getting from an earlier move
+    /// * beta  = Highest probability the other teams are already sure of holding the AI to from an earlier move
+    ///
+    fn find_paranoid_probability(
+        &self,
+        board: Board,
+        team: i32,
+        depth: i32,
+        mut alpha: f32,
+        mut beta: f32,
+    ) -> f32 {
+        let ai_turn = team == self.team;
+        let next_team = board.config().next_team(team);
+        let mut best = None;
+        for i in board.legal_moves(team) {
+            let move_check = MoveCheck::new(board.clone(), i, team);
+            //A pop that completes a run for another team only is never worth making, so it is left out
+            if move_check.gives_win {
+                continue;
+            }
+            //Any other team's win is a loss for the AI
+            let prob = if move_check.has_end_result() {
+                if ai_turn {
+                    1f32
+                } else {
+                    0f32
+                }
+            } else if depth > 1 {
+                self.find_paranoid_probability(move_check.board, next_team, depth - 1, alpha, beta)
+            } else {
+                move_check.get_win_probability(self.team)
+            };
+            if ai_turn {
+                best = Some(best.map_or(prob, |b: f32| b.max(prob)));
+                alpha = alpha.max(prob);
+            } else {
+                best = Some(best.map_or(prob, |b: f32| b.min(prob)));
+                beta = beta.min(prob);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+        //Edge case - no moves to make, return 0 probability (can't win)
+        best.unwrap_or(0f32)
+    }
+
+    ///
+    /// Method to pick a move in a game of Pop 10. Pop 10 games are too long for the search used by pick_optimal_move,
+    /// so moves are picked greedily: drops build the best runs, and pops collect a disc if they can, otherwise leave
+    /// the AI the most discs to collect compared to the other team. Returns -1 if there is no move to make
+    ///
+    /// # Arguments
+    /// * board = Board struct representing the current state of the board
+    /// * state = Pop10State for the collected discs and the part of the turn the AI is in
+    ///
+    pub fn pick_pop10_move(&self, board: &Board, state: &Pop10State) -> i32 {
+        let moves = state.legal_moves(board, self.team);
+        let best = if state.phase == Pop10Phase::Popping {
+            let other = self.team % 2 + 1;
+            let collectable = |board: &Board, team: i32| {
+                (0..board.column_count())
+                    .filter(|col| Pop10State::collects(board, *col, team))
+                    .count() as i32
+            };
+            moves.into_iter().max_by_key(|mv| {
+                let (col, _) = decode_move(*mv);
+                let collects = Pop10State::collects(board, col, self.team);
+                let mut next_board = board.clone();
+                next_board.pop(col, self.team);
+                (
+                    collects,
+                    collectable(&next_board, self.team) - collectable(&next_board, other),
+                )
+            })
+        } else {
+            moves.into_iter().max_by(|a, b| {
+                MoveCheck::new(board.clone(), *a, self.team).cmp(&MoveCheck::new(
+                    board.clone(),
+                    *b,
+                    self.team,
+                ))
+            })
+        };
+        best.unwrap_or(-1)
+    }
+
+    ///
+    /// Method to evaluate dropping a disc in each column for the AI's team. Moves that force a win or loss within the
+    /// search depth are reported as WinIn/LossIn, and other moves get the win probability the AI uses to pick moves.
+    /// Returns a vector where output[i] is the evaluation for column i, or None if the column is full. Pops are not
+    /// evaluated, though the search after each drop includes them. Only two-team games are evaluated
+    ///
+    /// # Arguments
+    /// * board = Board struct representing the current state of the board
+    /// * depth = Integer value for how many moves (counting both teams) ahead the search goes
+    ///
+    pub fn evaluate_columns(&self, board: &Board, depth: i32) -> Vec<Option<Evaluation>> {
+        let mut output = Vec::new();
+        for i in 0..board.column_count() {
+            if board.is_column_full(i as usize) {
+                output.push(None);
+                continue;
+            }
+            let eval = match self.forced_result(board, i, depth) {
+                Some(eval) => eval,
+                None => {
+                    let move_check = MoveCheck::new(board.clone(), i, self.team);
+                    Evaluation::Score(self.find_win_probability(move_check.board, 1, depth))
+                }
+            };
+            output.push(Some(eval));
+        }
+        output
+    }
+
+    ///
+    /// Method to search for a forced result after the AI's team makes a move. Returns Some(WinIn(n)) or
+    /// Some(LossIn(n)), counted in moves of the AI's team, or None if neither team can force a win within the depth.
+    /// A pop that completes a run for the other team only counts as LossIn(1)
+    ///
+    /// # Arguments
+    /// * board = Board struct representing the current state of the board
+    /// * mv    = Index of the column the disc is dropped in, or pop_move(column) for a pop. The move must be legal
+    /// * depth = Integer value for how many moves (counting both teams) ahead the search goes
+    ///
+    pub fn forced_result(&self, board: &Board, mv: i32, depth: i32) -> Option<Evaluation> {
+        let move_check = MoveCheck::new(board.clone(), mv, self.team);
+        if move_check.has_end_result() {
+            return Some(Evaluation::WinIn(1));
+        }
+        if move_check.gives_win {
+            return Some(Evaluation::LossIn(1));
+        }
+        match self.find_forced_result(&move_check.board, self.team % 2 + 1, depth - 1) {
+            Some(Evaluation::WinIn(n)) => Some(Evaluation::LossIn(n)),
+            Some(Evaluation::LossIn(n)) => Some(Evaluation::WinIn(n + 1)),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Method to find how quickly the AI's team, about to move, can force a win. Returns Some(n) if the team can win
+    /// on its n-th move whatever the other team does, or None if there is no forced win within max_moves moves
+    ///
+    /// # Arguments
+    /// * board     = Board struct representing the current state of the board
+    /// * max_moves = Integer value for the most moves of the AI's team the win may take
+    ///
+    pub fn forced_win(&self, board: &Board, max_moves: i32) -> Option<i32> {
+        match self.find_forced_result(board, self.team, 2 * max_moves - 1) {
+            Some(Evaluation::WinIn(n)) => Some(n),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Method to recursively search for a forced result for the team about to move. Returns Some(WinIn(n)) if the team
+    /// can win on their n-th move whatever the other team does, Some(LossIn(n)) if the other team can force a win on
+    /// their n-th move, or None if neither can be forced within the depth
+    ///
+    /// # Arguments
+    /// * board = Board struct representing the current state of the board
+    /// * team  = Integer value (1 or 2) of the team about to move
+    /// * depth = Integer value for how many more moves (counting both teams) can be searched
+    ///
+    fn find_forced_result(&self, board: &Board, team: i32, depth: i32) -> Option<Evaluation> {
+        if depth <= 0 {
+            return None;
+        }
+        let mut children = Vec::new();
+        let mut gives_win = false;
+        for i in board.legal_moves(team) {
+            let move_check = MoveCheck::new(board.clone(), i, team);
+            if move_check.has_end_result() {
+                return Some(Evaluation::WinIn(1));
+            }
+            //Pops that hand the other team a win are only made if there is nothing else to do
+            if move_check.gives_win {
+                gives_win = true;
+            } else {
+                children.push(move_check.board);
+            }
+        }
+        //A full board is a draw, which is not a forced win for either team, unless the only moves left lose
+        if children.is_empty() {
+            return if gives_win {
+                Some(Evaluation::LossIn(1))
+            } else {
+                None
+            };
+        }
+        let mut fastest_win = None;
+        let mut slowest_loss = Some(0);
+        for child in children {
+            match self.find_forced_result(&child, team % 2 + 1, depth - 1) {
+                //The other team loses on their n-th move, so this team wins on its (n + 1)-th move
+                Some(Evaluation::LossIn(n)) => {
+                    fastest_win = Some(fastest_win.map_or(n + 1, |w: i32| w.min(n + 1)));
+                }
+                Some(Evaluation::WinIn(n)) => slowest_loss = slowest_loss.map(|l: i32| l.max(n)),
+                _ => slowest_loss = None,
+            }
+        }
+        //Pick the fastest win if there is one, otherwise the loss only counts if every move loses
+        match (fastest_win, slowest_loss) {
+            (Some(n), _) => Some(Evaluation::WinIn(n)),
+            (None, Some(n)) => Some(Evaluation::LossIn(n)),
+            (None, None) => None,
+        }
+    }
 }
 
 #[cfg(test)]
 mod ai_tests {
     use super::*;
-    use connect4::core::Board;
+    use connect4::core::{pop_move, Board, BoardConfig, Variant, BOARD_SIZE};
 
     //Method to create a board state from a set of vectors, where 0 is empty and 1 or 2 team tokens
     //Note that input is board[column][row], so if you want to add a team 1 token in column 4, row 0, then
@@ -246,6 +583,18 @@ mod ai_tests {
         output
     }
 
+    //Method to create a PopOut board from a set of columns, each listing its discs from the bottom up
+    fn create_pop_out_board(board: Vec<Vec<i32>>) -> Board {
+        let config = BoardConfig::standard().with_variant(Variant::PopOut);
+        let mut output = Board::with_config(GridPosition::new(0, 0), config);
+        for (i, col) in board.iter().enumerate() {
+            for val in col {
+                output.insert(i as i32, *val, MyColor::White);
+            }
+        }
+        output
+    }
+
     mod move_check {
         use super::*;
         use connect4::ai::MoveCheck;
@@ -300,10 +649,199 @@ mod ai_tests {
                 let mut board = create_test_board(data);
                 let test_ai = AI::new(1, 1);
                 //Should prioritize col 1 over col 5 even though both win
-                assert_eq!(test_ai.pick_optimal_move(board.clone()), 1i32);
+                assert_eq!(test_ai.pick_optimal_move(board.clone(), None), 1i32);
                 //Insert enemy token to block col 1, now col 5 should be found
                 board.insert(1, 2, MyColor::White);
-                assert_eq!(test_ai.pick_optimal_move(board.clone()), 5i32);
+                assert_eq!(test_ai.pick_optimal_move(board.clone(), None), 5i32);
+            }
+        }
+
+        mod pick_move_at_depth {
+            use super::*;
+
+            #[test]
+            fn should_use_board_connect_length() {
+                //Team 1 has two in a row on a Connect 3 board, so the third disc wins
+                let mut board =
+                    Board::with_config(GridPosition::new(0, 0), BoardConfig::new(5, 4, 3));
+                board.insert(1, 1, MyColor::White);
+                board.insert(1, 2, MyColor::White);
+                board.insert(2, 1, MyColor::White);
+                board.insert(2, 2, MyColor::White);
+                let ai = AI::new(1, 2);
+                let col = ai.pick_move_at_depth(board, 2);
+                assert!(col == 0 || col == 3);
+            }
+
+            #[test]
+            fn should_pop_to_win() {
+                //Popping column 3 drops its 1 into the second row, next to three more
+                let board =
+                    create_pop_out_board(vec![vec![1, 1], vec![2, 1], vec![2, 1], vec![1, 2, 1]]);
+                let ai = AI::new(1, 2);
+                assert_eq!(ai.pick_move_at_depth(board.clone(), 2), pop_move(3));
+                assert_eq!(
+                    ai.forced_result(&board, pop_move(3), 2),
+                    Some(Evaluation::WinIn(1))
+                );
+            }
+
+            #[test]
+            fn should_not_pop_to_give_opponent_a_win() {
+                //Popping column 3 drops a 2 into the bottom row, completing four for team 2
+                let board =
+                    create_pop_out_board(vec![vec![2], vec![2, 1], vec![2, 1], vec![1, 2, 1]]);
+                let ai = AI::new(1, 2);
+                assert_ne!(ai.pick_move_at_depth(board.clone(), 2), pop_move(3));
+                assert_eq!(
+                    ai.forced_result(&board, pop_move(3), 2),
+                    Some(Evaluation::LossIn(1))
+                );
+            }
+        }
+
+        mod find_paranoid_probability {
+            use super::*;
+
+            #[test]
+            fn should_block_team_that_moves_later() {
+                //Team 3 has three across the bottom right, so column 5 must be blocked even though team 2 moves first
+                let config = BoardConfig::standard().with_teams(3).played();
+                let mut board = Board::with_config(GridPosition::new(0, 0), config);
+                board.insert(0, 1, MyColor::White);
+                board.insert(1, 2, MyColor::White);
+                for col in 6..9 {
+                    board.insert(col, 3, MyColor::White);
+                }
+                let ai = AI::new(1, 2);
+                assert_eq!(ai.pick_move_at_depth(board.clone(), 2), 5);
+                assert_eq!(ai.find_paranoid_probability(board, 2, 2, 0.0, 1.0), 0.0);
+            }
+
+            #[test]
+            fn should_count_any_other_team_win_as_loss() {
+                //Team 2 moves next and can win in column 0 whatever team 3 does after
+                let config = BoardConfig::standard().with_teams(4).played();
+                let mut board = Board::with_config(GridPosition::new(0, 0), config);
+                for _ in 0..3 {
+                    board.insert(0, 2, MyColor::White);
+                }
+                let ai = AI::new(1, 1);
+                assert_eq!(
+                    ai.find_paranoid_probability(board.clone(), 2, 1, 0.0, 1.0),
+                    0.0
+                );
+                board.insert(0, 1, MyColor::White);
+                assert!(ai.find_paranoid_probability(board, 2, 1, 0.0, 1.0) > 0.0);
+            }
+        }
+
+        mod pick_pop10_move {
+            use super::*;
+
+            #[test]
+            fn should_pop_disc_that_collects() {
+                let config = BoardConfig::standard().with_variant(Variant::Pop10);
+                let mut board = Board::with_config(GridPosition::new(0, 0), config);
+                let a = [1, 2, 1, 2, 1, 2];
+                for col in 0..7 {
+                    for val in a.iter() {
+                        //Columns 3 to 6 start with team 1, making four across the bottom row from column 3
+                        let team = if col < 3 { 3 - val } else { *val };
+                        board.insert(col, team, MyColor::White);
+                    }
+                }
+                let mut state = Pop10State::new();
+                state.phase = Pop10Phase::Popping;
+                let mv = AI::new(1, 1).pick_pop10_move(&board, &state);
+                let (col, pop) = decode_move(mv);
+                assert!(pop && col >= 3);
+            }
+
+            #[test]
+            fn should_fill_lowest_row() {
+                let config = BoardConfig::standard().with_variant(Variant::Pop10);
+                let mut board = Board::with_config(GridPosition::new(0, 0), config);
+                board.insert(0, 1, MyColor::White);
+                let state = Pop10State::new();
+                let mv = AI::new(2, 1).pick_pop10_move(&board, &state);
+                assert!((1..=6).contains(&mv));
+            }
+        }
+
+        mod search_depth {
+            use super::*;
+
+            #[test]
+            fn should_use_difficulty_without_clock() {
+                let test_ai = AI::new(2, 5);
+                assert_eq!(test_ai.search_depth(None), 5);
+                assert_eq!(test_ai.search_depth(Some(120.0)), 5);
+            }
+
+            #[test]
+            fn should_search_shallower_when_time_is_low() {
+                let test_ai = AI::new(2, 5);
+                assert_eq!(test_ai.search_depth(Some(20.0)), 4);
+                assert_eq!(test_ai.search_depth(Some(3.0)), 2);
+                assert_eq!(test_ai.search_depth(Some(0.5)), 1);
+                //Never searches deeper than its difficulty
+                assert_eq!(AI::new(2, 1).search_depth(Some(10.0)), 1);
+            }
+        }
+
+        mod evaluate_columns {
+            use super::*;
+
+            #[test]
+            fn should_find_win_in_1_and_skip_full_columns() {
+                let data = vec![vec![1, 2, 1, 2, 1, 2], vec![1, 1, 1, 0, 0, 0]];
+                let evals = AI::new(1, 3).evaluate_columns(&create_test_board(data), 2);
+                assert_eq!(evals[0], None);
+                assert_eq!(evals[1], Some(Evaluation::WinIn(1)));
+            }
+
+            #[test]
+            fn should_find_loss_in_1_if_threat_not_blocked() {
+                let data = vec![vec![2, 2, 2, 0, 0, 0], vec![1, 0, 0, 0, 0, 0]];
+                let evals = AI::new(1, 3).evaluate_columns(&create_test_board(data), 2);
+                assert_eq!(evals[3], Some(Evaluation::LossIn(1)));
+                assert!(!matches!(evals[0], Some(Evaluation::LossIn(_))));
+            }
+
+            #[test]
+            fn should_find_win_in_2_from_open_three() {
+                let data = vec![
+                    vec![0, 0, 0, 0, 0, 0],
+                    vec![1, 0, 0, 0, 0, 0],
+                    vec![1, 0, 0, 0, 0, 0],
+                    vec![0, 0, 0, 0, 0, 0],
+                    vec![0, 0, 0, 0, 0, 0],
+                    vec![0, 0, 0, 0, 0, 0],
+                    vec![2, 2, 0, 0, 0, 0],
+                ];
+                let evals = AI::new(1, 3).evaluate_columns(&create_test_board(data), 3);
+                assert_eq!(evals[3], Some(Evaluation::WinIn(2)));
+            }
+        }
+
+        mod forced_win {
+            use super::*;
+
+            #[test]
+            fn should_find_fastest_forced_win_within_limit() {
+                let data = vec![
+                    vec![0, 0, 0, 0, 0, 0],
+                    vec![1, 0, 0, 0, 0, 0],
+                    vec![1, 0, 0, 0, 0, 0],
+                    vec![0, 0, 0, 0, 0, 0],
+                    vec![0, 0, 0, 0, 0, 0],
+                    vec![0, 0, 0, 0, 0, 0],
+                    vec![2, 2, 0, 0, 0, 0],
+                ];
+                let board = create_test_board(data);
+                assert_eq!(AI::new(1, 3).forced_win(&board, 2), Some(2));
+                assert_eq!(AI::new(1, 3).forced_win(&board, 1), None);
             }
         }
 
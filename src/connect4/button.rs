@@ -15,6 +15,67 @@ pub const BUTTON_PADDING: (f32, f32) = (10.0, 10.0);
 ///Constant dimmesions for spacing between distinct buttons
 pub const BUTTON_SPACING: (f32, f32) = (50.0, 50.0);
 
+///
+/// Returns the location of the mouse in screen coordinates. ggez reports the mouse in window pixels, which only match
+/// the screen coordinates that are drawn in while the window is unscaled
+///
+pub fn mouse_location(ctx: &Context) -> Point2<f32> {
+    let loc = mouse::position(ctx);
+    let screen = graphics::screen_coordinates(ctx);
+    let (width, height) = graphics::drawable_size(ctx);
+    if width <= 0.0 || height <= 0.0 {
+        return loc;
+    }
+    Point2 {
+        x: screen.x + loc.x * screen.w / width,
+        y: screen.y + loc.y * screen.h / height,
+    }
+}
+
+///Returns true if the mouse is inside an outline, which is how buttons and other clickable shapes are hit-tested
+pub fn is_under_mouse(ctx: &Context, outline: graphics::Rect) -> bool {
+    outline.contains(mouse_location(ctx))
+}
+
+///
+/// Returns a button sized to fit its label, with its top-left corner at the given location
+///
+/// # Arguments
+/// * ctx    = Context used to measure the label
+/// * label  = Text drawn on the button
+/// * pos    = Location of the top-left corner of the button
+/// * size   = Font size of the label
+/// * colors = Background color of the button, then its color when highlighted or selected
+///
+pub fn create_button(
+    ctx: &mut Context,
+    label: &str,
+    pos: (f32, f32),
+    size: f32,
+    colors: (MyColor, MyColor),
+) -> Button {
+    let text = graphics::Text::new((label, graphics::Font::default(), size));
+    let dim = (
+        2.0 * BUTTON_PADDING.0 + text.width(ctx) as f32,
+        2.0 * BUTTON_PADDING.1 + text.height(ctx) as f32,
+    );
+    let mut button = Button::new(text, graphics::Rect::new(pos.0, pos.1, dim.0, dim.1));
+    button.set_colors(colors.0, colors.1);
+    button
+}
+
+///Method to draw a line of text at a location in the given color, either a MyColor or a graphics::Color
+pub fn draw_text<C: Into<graphics::Color>>(
+    ctx: &mut Context,
+    text: &str,
+    pos: (f32, f32),
+    size: f32,
+    color: C,
+) -> GameResult {
+    let text = graphics::Text::new((text, graphics::Font::default(), size));
+    graphics::draw(ctx, &text, (Point2 { x: pos.0, y: pos.1 }, color.into()))
+}
+
 ///
 /// A struct representing a button object on a menu or a game
 ///
@@ -90,12 +151,7 @@ impl Button {
 
     ///Method to determine if mouse if hovering over button, updates highlighted state accordingly
     pub fn check_button_under_mouse(&mut self, ctx: &mut Context) -> bool {
-        let mouse_loc = mouse::position(ctx);
-        if self.active && self.outline.contains(mouse_loc) {
-            self.highlighted = true;
-        } else {
-            self.highlighted = false;
-        }
+        self.highlighted = self.active && is_under_mouse(ctx, self.outline);
         self.highlighted
     }
 }
@@ -0,0 +1,112 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use super::button::mouse_location;
+use super::core::MyColor;
+use ggez::graphics;
+use ggez::{Context, GameResult};
+
+///Constant dimmesions for each color swatch in the picker
+pub const SWATCH_SIZE: f32 = 40.0;
+///Constant dimmesion for spacing between swatches
+const SWATCH_SPACING: f32 = 10.0;
+
+///
+/// A struct representing a row of color swatches from which one color can be selected
+///
+/// # Fields
+/// * position    = Top-left location of the first swatch
+/// * colors      = Vector of MyColor objects that can be picked, drawn left to right
+/// * selected    = Index into colors of the currently picked color
+/// * highlighted = Index into colors of the swatch under the mouse, or -1 if no swatch is under the mouse
+///
+pub struct ColorPicker {
+    position: (f32, f32),
+    colors: Vec<MyColor>,
+    selected: usize,
+    highlighted: i32,
+}
+
+impl ColorPicker {
+    ///
+    /// Method to initialize and return a ColorPicker object
+    ///
+    /// # Arguments
+    /// * position = Top-left location of the first swatch
+    /// * colors   = Vector of MyColor objects that can be picked
+    /// * initial  = Color selected at start. Falls back to the first color if it is not one of colors
+    ///
+    pub fn new(position: (f32, f32), colors: Vec<MyColor>, initial: MyColor) -> Self {
+        let selected = colors.iter().position(|c| *c == initial).unwrap_or(0);
+        ColorPicker {
+            position,
+            colors,
+            selected,
+            highlighted: -1,
+        }
+    }
+
+    ///Returns the outline of the swatch for colors[index]
+    fn swatch_outline(&self, index: usize) -> graphics::Rect {
+        graphics::Rect::new(
+            self.position.0 + (SWATCH_SIZE + SWATCH_SPACING) * index as f32,
+            self.position.1,
+            SWATCH_SIZE,
+            SWATCH_SIZE,
+        )
+    }
+
+    ///Draw method for rendering all swatches, with the selected one outlined in white and the highlighted one in gray
+    pub fn draw(&self, ctx: &mut Context) -> GameResult<()> {
+        let mut mb = graphics::MeshBuilder::new();
+        for (i, color) in self.colors.iter().enumerate() {
+            let outline = self.swatch_outline(i);
+            mb.rectangle(graphics::DrawMode::fill(), outline, color.get_draw_color());
+            if i == self.selected {
+                mb.rectangle(graphics::DrawMode::stroke(4.0), outline, graphics::WHITE);
+            } else if i as i32 == self.highlighted {
+                mb.rectangle(
+                    graphics::DrawMode::stroke(2.0),
+                    outline,
+                    graphics::Color::from_rgba(150, 150, 150, 255),
+                );
+            }
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (ggez::mint::Point2 { x: 0.0, y: 0.0 },))?;
+        Ok(())
+    }
+
+    ///Method to find which swatch the mouse is over, updates highlighted state accordingly and returns it (-1 if none)
+    pub fn check_under_mouse(&mut self, ctx: &mut Context) -> i32 {
+        let loc = mouse_location(ctx);
+        self.highlighted = -1;
+        for i in 0..self.colors.len() {
+            if self.swatch_outline(i).contains(loc) {
+                self.highlighted = i as i32;
+            }
+        }
+        self.highlighted
+    }
+
+    ///Method to pick the swatch under the mouse (if any). Returns true if the selection changed
+    pub fn click(&mut self, ctx: &mut Context) -> bool {
+        let index = self.check_under_mouse(ctx);
+        if index >= 0 && index as usize != self.selected {
+            self.selected = index as usize;
+            true
+        } else {
+            false
+        }
+    }
+
+    ///Returns the currently picked color
+    pub fn selected_color(&self) -> MyColor {
+        self.colors[self.selected]
+    }
+}
+
+//NOTE: Tests not included since testing requires usage of mouse and other UI features
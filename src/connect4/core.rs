@@ -4,27 +4,61 @@
 // distribution of this software for license terms.
 extern crate ggez;
 
-use connect4::ai::AI;
-use connect4::button::Button;
-use ggez::input::mouse;
+use clock::{GameClock, TimeControl};
+use connect4::ai::{Evaluation, AI};
+use connect4::button::{mouse_location, Button};
+use connect4::pop10::{Pop10Phase, Pop10State, POP10_TARGET};
+use connect4::review::ReviewScreen;
+use ggez::event::KeyCode;
 use ggez::input::mouse::MouseButton;
 use ggez::mint::Point2;
 use ggez::{graphics, Context, GameResult};
+use history::{MatchRecord, PlayerRecord};
+use screen::GameScreen;
+use settings::Settings;
+use sound::SoundEffect;
 
 /// Constant definition for the connect4 board size: 6x7 cells, row x column.
 pub const BOARD_SIZE: (i32, i32) = (6, 7);
 
+/// Constant definition for the number of discs in a row needed to win on the standard board.
+pub const CONNECT_LENGTH: i32 = 4;
+
+/// Constant definition for the smallest and largest number of rows a board may have.
+pub const BOARD_ROWS_RANGE: (i32, i32) = (4, 9);
+
+/// Constant definition for the smallest and largest number of columns a board may have.
+pub const BOARD_COLUMNS_RANGE: (i32, i32) = (4, 10);
+
+/// Constant definition for the shortest and longest run that may be needed to win.
+pub const CONNECT_LENGTH_RANGE: (i32, i32) = (3, 6);
+
+/// Constant definition for the fewest and most players (teams) a game may have.
+pub const TEAM_COUNT_RANGE: (i32, i32) = (2, 4);
+
+/// Constant definition for the smallest board (row x column) games with more than two players are played on.
+pub const MULTIPLAYER_BOARD_SIZE: (i32, i32) = (7, 9);
+
+/// Constant definition for the board sizes (row x column) offered on the settings screen, smallest first.
+pub const BOARD_SIZE_PRESETS: [(i32, i32); 6] = [(4, 4), (5, 4), (6, 5), (6, 7), (7, 8), (9, 7)];
+
 /// Constant definition for the pixel size for each square tiles: 32x32 pixels.
 const BOARD_CELL_SIZE: (i32, i32) = (64, 64);
 
 /// Constant definition for the radius of each playing disc: 14px.
-const BOARD_DISC_RADIUS: i32 = 28;
+pub const BOARD_DISC_RADIUS: i32 = 28;
+
+/// Constant definition for how many times the same position must come up in a game with pops for it to be drawn.
+pub const REPETITION_DRAW_COUNT: usize = 3;
+
+/// Constant definition for the Five-in-a-Row board: 6x9 cells, row x column, with both outer columns filled at the start.
+pub const FIVE_IN_A_ROW_SIZE: (i32, i32) = (6, 9);
 
 /// Constant definition for the border size of the board.
 const BOARD_BORDER_SIZE: i32 = 32;
 
 /// Constant definition for dimensions of the board
-const BOARD_TOTAL_SIZE: (f32, f32) = (
+pub const BOARD_TOTAL_SIZE: (f32, f32) = (
     ((BOARD_SIZE.1 * BOARD_CELL_SIZE.0) + BOARD_BORDER_SIZE) as f32,
     ((BOARD_SIZE.0 * BOARD_CELL_SIZE.0) + BOARD_BORDER_SIZE) as f32,
 );
@@ -41,19 +75,47 @@ const COLUMN_SELECTION_INDICATOR_POS_OFFSET: (i32, i32) = (
     10 + TURN_INDICATOR_POS_OFFSET.1 + TURN_INDICATOR_BOX_SIZE_OFFSET.1 + TURN_INDICATOR_FONT_SIZE,
 );
 
-const BOARD_POS_OFFSET: (i32, i32) = (
+pub const BOARD_POS_OFFSET: (i32, i32) = (
     10,
     10 + COLUMN_SELECTION_INDICATOR_POS_OFFSET.1 + BOARD_CELL_SIZE.1,
 );
 
 const RESET_BUTTON_OFFSET: (i32, i32) = (10, 10);
 
-/// Constant definition for the screen size of the game window.
+/// Constant definition for how many pixels a falling disc moves per frame at an animation speed of 1.0.
+const DISC_DROP_SPEED: f32 = 12.0;
+
+/// Constant definition for the seconds left on an AI's clock below which it skips the move delay.
+const AI_HURRY_TIME: f32 = 3.0;
+
+/// Constant definition for the width of the panel right of the board, which holds the game clocks.
+const SIDE_PANEL_WIDTH: i32 = 180;
+
+/// Constant definition for the y location of the buttons in the side panel, below the game clocks.
+const SIDE_BUTTON_POS_Y: f32 = 170.0;
+
+/// Constant definition for the size of the buttons in the side panel.
+const SIDE_BUTTON_SIZE: (f32, f32) = (160.0, 40.0);
+
+/// Constant definition for the vertical space between buttons in the side panel, leaving room for a line of text below each.
+const SIDE_BUTTON_SPACING: f32 = 30.0;
+
+/// Constant definition for the screen size of the game window with the standard board.
 pub const SCREEN_SIZE: (f32, f32) = (
-    BOARD_TOTAL_SIZE.0 + (BOARD_POS_OFFSET.0 as f32),
+    BOARD_TOTAL_SIZE.0 + (BOARD_POS_OFFSET.0 + SIDE_PANEL_WIDTH) as f32,
     BOARD_TOTAL_SIZE.1 + (BOARD_POS_OFFSET.1 as f32),
 );
 
+///
+/// Enum representing what the main menu should do after a click on the game screen
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameAction {
+    None,
+    MainMenu,
+    Review,
+}
+
 /// Enums defining some color presets. Call `get_draw_color()` to get the ggez graphics Color object equivalent.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 pub enum MyColor {
@@ -62,6 +124,11 @@ pub enum MyColor {
     Red,
     Green,
     Brown,
+    Yellow,
+    Purple,
+    Orange,
+    Cyan,
+    Pink,
 }
 
 impl MyColor {
@@ -75,8 +142,85 @@ impl MyColor {
             MyColor::Red => graphics::Color::from_rgba(255, 0, 0, 255),
             MyColor::Green => graphics::Color::from_rgba(0, 255, 0, 255),
             MyColor::Brown => graphics::Color::from_rgba(205, 133, 63, 255),
+            MyColor::Yellow => graphics::Color::from_rgba(255, 215, 0, 255),
+            MyColor::Purple => graphics::Color::from_rgba(148, 0, 211, 255),
+            MyColor::Orange => graphics::Color::from_rgba(255, 140, 0, 255),
+            MyColor::Cyan => graphics::Color::from_rgba(0, 206, 209, 255),
+            MyColor::Pink => graphics::Color::from_rgba(255, 105, 180, 255),
+        }
+    }
+
+    ///
+    /// Method that returns the colors a player may pick for their discs. White and Brown are left out
+    /// since they are used for empty cells and the board itself
+    ///
+    pub fn disc_colors() -> Vec<MyColor> {
+        vec![
+            MyColor::Red,
+            MyColor::Blue,
+            MyColor::Green,
+            MyColor::Yellow,
+            MyColor::Purple,
+            MyColor::Orange,
+            MyColor::Cyan,
+            MyColor::Pink,
+        ]
+    }
+}
+
+/// From trait converting MyColor to the ggez graphics Color it is drawn in.
+impl From<MyColor> for graphics::Color {
+    fn from(color: MyColor) -> Self {
+        color.get_draw_color()
+    }
+}
+
+///Returns the team (1 or 2) that plays against the given team in a two-team game
+pub fn other_team(team: i32) -> i32 {
+    3 - team
+}
+
+///
+/// A struct holding the name and disc color a player picked before the game started
+///
+/// # Fields
+/// * name  = Name shown in the turn indicator, win message and logs
+/// * color = MyColor object used to draw the player's discs
+/// * is_ai = Boolean indicating if the player's moves are made by the AI
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PlayerInfo {
+    pub name: String,
+    pub color: MyColor,
+    pub is_ai: bool,
+}
+
+impl PlayerInfo {
+    /// Constructor for PlayerInfo, for a human player.
+    pub fn new(name: &str, color: MyColor) -> Self {
+        PlayerInfo {
+            name: name.to_string(),
+            color,
+            is_ai: false,
         }
     }
+
+    /// Returns a copy of the player info with its moves made by the AI.
+    pub fn with_ai(mut self) -> Self {
+        self.is_ai = true;
+        self
+    }
+
+    /// Returns the names and colors used when no setup was done, one per team up to the most a game may have:
+    /// "Player 1" in red, "Player 2" in blue, "Player 3" in green and "Player 4" in yellow.
+    pub fn defaults() -> Vec<PlayerInfo> {
+        vec![
+            PlayerInfo::new("Player 1", MyColor::Red),
+            PlayerInfo::new("Player 2", MyColor::Blue),
+            PlayerInfo::new("Player 3", MyColor::Green),
+            PlayerInfo::new("Player 4", MyColor::Yellow),
+        ]
+    }
 }
 
 /// Struct representing position on the board
@@ -101,6 +245,238 @@ impl From<(i32, i32)> for GridPosition {
     }
 }
 
+///
+/// Returns the move recorded when a disc is popped out of the bottom of a column. Drops are recorded as the column
+/// index and pops as -(column + 1), so every move of a game fits in one integer
+///
+pub fn pop_move(col: i32) -> i32 {
+    -(col + 1)
+}
+
+///Returns the column a recorded move is made in, and true if the move pops a disc out of it rather than dropping one
+pub fn decode_move(mv: i32) -> (i32, bool) {
+    if mv < 0 {
+        (-mv - 1, true)
+    } else {
+        (mv, false)
+    }
+}
+
+/// Enum for the rules a Connect 4 game is played with
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Variant {
+    /// Discs can only be dropped into the board
+    #[default]
+    Standard,
+    /// Players may instead pop one of their own discs out of the bottom of a column, shifting the column down
+    PopOut,
+    /// Five discs in a row win on a 6x9 board whose outer columns start filled with discs of both teams
+    FiveInARow,
+    /// The board is filled, then players pop their own discs out, collecting those that were part of four in a row
+    Pop10,
+}
+
+impl Variant {
+    ///Returns every variant in the order they are stepped through on the settings screen
+    pub fn all() -> Vec<Variant> {
+        vec![
+            Variant::Standard,
+            Variant::PopOut,
+            Variant::FiveInARow,
+            Variant::Pop10,
+        ]
+    }
+
+    ///Returns the next (up is true) or previous variant, wrapping around at either end
+    pub fn step(self, up: bool) -> Variant {
+        let all = Variant::all();
+        let i = all.iter().position(|v| *v == self).unwrap_or(0);
+        let next = if up { i + 1 } else { i + all.len() - 1 };
+        all[next % all.len()]
+    }
+
+    ///Returns the name shown on the settings screen and in the names of recorded games
+    pub fn name(self) -> &'static str {
+        match self {
+            Variant::Standard => "Standard",
+            Variant::PopOut => "PopOut",
+            Variant::FiveInARow => "Five-in-a-Row",
+            Variant::Pop10 => "Pop 10",
+        }
+    }
+
+    ///Returns true if the rules fix the size of the board, the length of a winning run and the number of players
+    pub fn fixes_board(self) -> bool {
+        matches!(self, Variant::FiveInARow | Variant::Pop10)
+    }
+}
+
+///
+/// A struct describing the shape of a board, how many discs in a row win and which rules are played, so variants such
+/// as Connect 3 on a 5x4 board, Connect 5 on a 9x7 board, PopOut or three- and four-player games can be played
+///
+/// # Fields
+/// * rows    = Number of cells in each column
+/// * columns = Number of columns
+/// * connect = Number of discs in a row needed to win
+/// * variant = Variant enum for the rules played on the board; missing from older settings files, so it has a default
+/// * teams   = Number of players taking turns, numbered 1 to teams; missing from older settings files, so it defaults to 2
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BoardConfig {
+    pub rows: i32,
+    pub columns: i32,
+    pub connect: i32,
+    #[serde(default)]
+    pub variant: Variant,
+    #[serde(default = "default_teams")]
+    pub teams: i32,
+}
+
+///Returns the number of teams in a game when none is set: the usual two players
+fn default_teams() -> i32 {
+    TEAM_COUNT_RANGE.0
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        BoardConfig::standard()
+    }
+}
+
+impl BoardConfig {
+    ///Constructor - rows and columns give the size of the board and connect is the length of a winning run
+    pub fn new(rows: i32, columns: i32, connect: i32) -> Self {
+        BoardConfig {
+            rows,
+            columns,
+            connect,
+            variant: Variant::Standard,
+            teams: default_teams(),
+        }
+    }
+
+    ///Returns a copy of the config for a game with the given number of players
+    pub fn with_teams(mut self, teams: i32) -> Self {
+        self.teams = teams;
+        self
+    }
+
+    ///Returns the team that moves after the given one, going back to team 1 after the last team
+    pub fn next_team(&self, team: i32) -> i32 {
+        team % self.teams + 1
+    }
+
+    ///Returns a copy of the config played with the given rules
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    ///Returns true if players may pop their own discs out of the bottom of a column
+    pub fn allows_pops(&self) -> bool {
+        matches!(self.variant, Variant::PopOut | Variant::Pop10)
+    }
+
+    ///Returns the config a game is played with. Five-in-a-Row and Pop 10 are always two-player games played on their
+    ///own board with their own run length, whatever is set. Games with more players get at least MULTIPLAYER_BOARD_SIZE
+    pub fn played(self) -> Self {
+        match self.variant {
+            Variant::FiveInARow => BoardConfig::new(FIVE_IN_A_ROW_SIZE.0, FIVE_IN_A_ROW_SIZE.1, 5)
+                .with_variant(self.variant),
+            Variant::Pop10 => BoardConfig::standard().with_variant(self.variant),
+            _ if self.teams > 2 => BoardConfig {
+                rows: self.rows.max(MULTIPLAYER_BOARD_SIZE.0),
+                columns: self.columns.max(MULTIPLAYER_BOARD_SIZE.1),
+                ..self
+            },
+            _ => self,
+        }
+    }
+
+    ///Returns the standard 6x7 Connect 4 board
+    pub fn standard() -> Self {
+        BoardConfig::new(BOARD_SIZE.0, BOARD_SIZE.1, CONNECT_LENGTH)
+    }
+
+    ///Returns a copy of the config with the size and run length moved into their valid ranges. The run length is
+    ///kept short enough to fit on the board
+    pub fn clamped(self) -> Self {
+        let rows = self.rows.clamp(BOARD_ROWS_RANGE.0, BOARD_ROWS_RANGE.1);
+        let columns = self
+            .columns
+            .clamp(BOARD_COLUMNS_RANGE.0, BOARD_COLUMNS_RANGE.1);
+        let longest = CONNECT_LENGTH_RANGE.1.min(rows.max(columns));
+        BoardConfig::new(
+            rows,
+            columns,
+            self.connect.clamp(CONNECT_LENGTH_RANGE.0, longest),
+        )
+        .with_variant(self.variant)
+        .with_teams(self.teams.clamp(TEAM_COUNT_RANGE.0, TEAM_COUNT_RANGE.1))
+    }
+
+    ///Returns the name of the game played on the board, e.g. "Connect 4" for the standard board, "Connect 5 (9x7)",
+    ///"PopOut Connect 4" or "Connect 4 (7x9), 3 players"
+    pub fn label(&self) -> String {
+        let game = if (self.rows, self.columns, self.connect)
+            == (BOARD_SIZE.0, BOARD_SIZE.1, CONNECT_LENGTH)
+        {
+            format!("Connect {}", self.connect)
+        } else {
+            format!("Connect {} ({}x{})", self.connect, self.rows, self.columns)
+        };
+        let game = match self.variant {
+            Variant::Standard => game,
+            Variant::PopOut => format!("{} {}", self.variant.name(), game),
+            variant => variant.name().to_string(),
+        };
+        if self.teams > 2 {
+            format!("{}, {} players", game, self.teams)
+        } else {
+            game
+        }
+    }
+
+    ///Returns the pixel dimensions of the board, including its border
+    pub fn total_size(&self) -> (f32, f32) {
+        (
+            ((self.columns * BOARD_CELL_SIZE.0) + BOARD_BORDER_SIZE) as f32,
+            ((self.rows * BOARD_CELL_SIZE.1) + BOARD_BORDER_SIZE) as f32,
+        )
+    }
+
+    ///Returns the width left of the side panel. Boards narrower than the standard board are centered in the same width
+    ///so the turn indicator still fits above them
+    fn play_area_width(&self) -> f32 {
+        self.total_size().0.max(BOARD_TOTAL_SIZE.0)
+    }
+
+    ///Returns the top-left location of the board in the game window
+    pub fn board_position(&self) -> GridPosition {
+        let margin = (self.play_area_width() - self.total_size().0) / 2.0;
+        GridPosition::new(BOARD_POS_OFFSET.0 + margin as i32, BOARD_POS_OFFSET.1)
+    }
+
+    ///Returns the x location of the center of the turn indicator, above the middle of the board
+    pub fn turn_indicator_x(&self) -> f32 {
+        BOARD_POS_OFFSET.0 as f32 + self.play_area_width() / 2.0
+    }
+
+    ///Returns the x location of the clocks and buttons in the panel right of the board
+    pub fn side_panel_x(&self) -> f32 {
+        self.play_area_width() + (BOARD_POS_OFFSET.0 + 10) as f32
+    }
+
+    ///Returns the screen size of the game window. Windows are never smaller than the one for the standard board
+    pub fn screen_size(&self) -> (f32, f32) {
+        (
+            self.play_area_width() + (BOARD_POS_OFFSET.0 + SIDE_PANEL_WIDTH) as f32,
+            (self.total_size().1 + BOARD_POS_OFFSET.1 as f32).max(SCREEN_SIZE.1),
+        )
+    }
+}
+
 ///
 /// A struct a single cell in the board
 ///
@@ -188,13 +564,13 @@ struct Column {
 }
 
 impl Column {
-    ///Constructor for Column
-    pub fn new(pos: GridPosition) -> Self {
+    ///Constructor for Column - rows is the number of cells in the column
+    pub fn new(pos: GridPosition, rows: i32) -> Self {
         Column {
             position: pos,
             // Adapted from: https://stackoverflow.com/questions/48021408/how-to-init-a-rust-vector-with-a-generator-function
             // Rev() method from https://stackoverflow.com/questions/25170091/how-to-make-a-reverse-ordered-for-loop-in-rust; used because columns drawn from top down
-            cells: (0..BOARD_SIZE.0)
+            cells: (0..rows)
                 .rev()
                 .map(|y| Cell::new((pos.x, pos.y + (BOARD_CELL_SIZE.0 * y)).into()))
                 .collect(),
@@ -217,7 +593,7 @@ impl Column {
 
     /// Checks whether the column is full.
     pub fn is_full(&self) -> bool {
-        self.height >= BOARD_SIZE.0 as usize
+        self.height >= self.cells.len()
     }
 
     /// Method to determine if a location (presumed to be the mouse) is inside the column or one cell above (for drop)
@@ -226,7 +602,7 @@ impl Column {
             self.position.x as f32,
             (self.position.y - (BOARD_CELL_SIZE.1 * 4 / 3)) as f32,
             BOARD_CELL_SIZE.0 as f32,
-            8.0 * BOARD_CELL_SIZE.1 as f32,
+            (self.cells.len() + 2) as f32 * BOARD_CELL_SIZE.1 as f32,
         )
         .contains(loc)
     }
@@ -244,6 +620,22 @@ impl Column {
         }
     }
 
+    /// Removes the disc at the bottom of the column, moving every disc above it down one cell
+    /// Returns true if a disc was removed
+    /// Returns false if column is empty
+    pub fn pop(&mut self) -> bool {
+        if self.height == 0 {
+            return false;
+        }
+        for i in 1..self.height {
+            let above = self.cells[i];
+            self.cells[i - 1].fill(above.team, above.color);
+        }
+        self.height -= 1;
+        self.cells[self.height].fill(0, MyColor::White);
+        true
+    }
+
     /// Resets the column.
     /// Changes the cells in the column to their 'empty' state.
     pub fn reset(&mut self) {
@@ -260,19 +652,27 @@ impl Column {
 /// # Fields
 /// * position = GridPosition struct used to determine the top-left position of the Board in the game window
 /// * columns  = Vector of columns representing all columns in the board. cells[0] is the left-most column, cells[5] is the right-most           
+/// * config   = BoardConfig giving the size of the board and the length of a winning run
 ///
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Board {
     position: GridPosition,
     columns: Vec<Column>,
+    config: BoardConfig,
 }
 
 impl Board {
-    ///Constructor for Board
+    ///Constructor for a standard 6x7 Board
     pub fn new(pos: GridPosition) -> Self {
-        Board {
+        Board::with_config(pos, BoardConfig::standard())
+    }
+
+    ///Constructor for a Board with the size and winning run length in config. Five-in-a-Row boards start with their outer
+    ///columns filled
+    pub fn with_config(pos: GridPosition, config: BoardConfig) -> Self {
+        let mut board = Board {
             position: pos,
-            columns: (0..BOARD_SIZE.1)
+            columns: (0..config.columns)
                 .map(|x| {
                     Column::new(
                         (
@@ -280,22 +680,72 @@ impl Board {
                             pos.y + (BOARD_BORDER_SIZE / 2),
                         )
                             .into(),
+                        config.rows,
                     )
                 })
                 .collect(),
+            config,
+        };
+        board.prefill();
+        board
+    }
+
+    ///
+    /// Method to fill the outer columns of a Five-in-a-Row board with discs of alternating teams, starting from team 1
+    /// at the bottom of the left column and team 2 at the bottom of the right column. The discs are drawn in the
+    /// default team colors until set_team_colors is called. Other boards are left empty
+    ///
+    fn prefill(&mut self) {
+        if self.config.variant != Variant::FiveInARow {
+            return;
+        }
+        let defaults = PlayerInfo::defaults();
+        let last = self.config.columns - 1;
+        for row in 0..self.config.rows {
+            for &(col, first) in &[(0, 1), (last, 2)] {
+                let team = if row % 2 == 0 { first } else { 3 - first };
+                self.insert(col, team, defaults[team as usize - 1].color);
+            }
+        }
+    }
+
+    ///Method to redraw every disc on the board in its team's color, where colors[i] is the color for team i
+    pub fn set_team_colors(&mut self, colors: &[MyColor]) {
+        for column in &mut self.columns {
+            for cell in &mut column.cells {
+                if cell.team > 0 && (cell.team as usize) < colors.len() {
+                    cell.color = colors[cell.team as usize];
+                }
+            }
         }
     }
 
+    ///Returns the BoardConfig the board was made with
+    pub fn config(&self) -> BoardConfig {
+        self.config
+    }
+
+    ///Returns the number of columns in the board
+    pub fn column_count(&self) -> i32 {
+        self.config.columns
+    }
+
+    ///Returns true if every column in the board is full
+    pub fn is_full(&self) -> bool {
+        self.columns.iter().all(|column| column.is_full())
+    }
+
     /// Builds Board's rect mesh and add it to the `MeshBuilder` passed in and calls column's draw function.
     /// Returns the MeshBuilder (with added board and columns meshes).
-    fn draw<'a>(&self, mb: &'a mut graphics::MeshBuilder) -> &'a mut graphics::MeshBuilder {
+    pub fn draw<'a>(&self, mb: &'a mut graphics::MeshBuilder) -> &'a mut graphics::MeshBuilder {
+        let total_size = self.config.total_size();
         mb.rectangle(
             graphics::DrawMode::fill(),
             graphics::Rect {
                 x: self.position.x as f32,
                 y: self.position.y as f32,
-                w: BOARD_TOTAL_SIZE.0 as f32,
-                h: BOARD_TOTAL_SIZE.1 as f32,
+                w: total_size.0,
+                h: total_size.1,
             },
             graphics::WHITE,
         );
@@ -304,8 +754,8 @@ impl Board {
             graphics::Rect {
                 x: self.position.x as f32,
                 y: self.position.y as f32,
-                w: BOARD_TOTAL_SIZE.0 as f32,
-                h: BOARD_TOTAL_SIZE.1 as f32,
+                w: total_size.0,
+                h: total_size.1,
             },
             graphics::Color::from_rgba(0, 255, 0, 255),
         );
@@ -317,6 +767,23 @@ impl Board {
         mb
     }
 
+    /// Method to get the center of a cell on the board, where its disc is drawn
+    pub fn cell_center(&self, pos: GridPosition) -> Point2<f32> {
+        let cell = &self.columns[pos.x as usize].cells[pos.y as usize];
+        Point2 {
+            x: (cell.position.x + (BOARD_CELL_SIZE.0 / 2)) as f32,
+            y: (cell.position.y + (BOARD_CELL_SIZE.1 / 2)) as f32,
+        }
+    }
+
+    /// Method to get the center of the hover disc drawn above a column, where a disc waits before it is dropped
+    pub fn hover_center(&self, col: usize) -> Point2<f32> {
+        Point2 {
+            x: (self.columns[col].position.x + (BOARD_CELL_SIZE.0 / 2)) as f32,
+            y: (self.position.y - (BOARD_CELL_SIZE.1 / 2)) as f32,
+        }
+    }
+
     /// Method to get the index of the column that is under the mouse (loc), or -1 if no column is highlighted
     pub fn get_highlighted_column(&self, loc: Point2<f32>) -> i32 {
         for i in 0..self.columns.len() {
@@ -329,7 +796,7 @@ impl Board {
 
     ///Method to determine if a GridPosition represents a valid location on the Board
     pub fn on_board(&self, pos: GridPosition) -> bool {
-        pos.x >= 0 && pos.x < BOARD_SIZE.1 && pos.y >= 0 && pos.y < BOARD_SIZE.0
+        pos.x >= 0 && pos.x < self.config.columns && pos.y >= 0 && pos.y < self.config.rows
     }
 
     ///Method to get the height of a column in the grid
@@ -363,10 +830,10 @@ impl Board {
     /// Accounts for runs towards and away from direction, but allows one space between tiles of the target team in
     /// target direction but no spaces in reverse direction.
     ///
-    /// The min value is 1; the max value returned is 4 even if a run is longer. If a space is used, the max returned value is 3
-    /// (as the space presumably prevents an actual run of 4). Cases with a run of 4 prior to space will return 4, except for edge
-    /// case where run goes from start and then completely in reverse direction. This can be caught by calling this method with reverse
-    /// direction
+    /// The min value is 1; the max value returned is the board's connect length N (4 on the standard board) even if a run is
+    /// longer. If a space is used, the max returned value is N - 1 (as the space presumably prevents an actual run of N). Cases
+    /// with a run of N prior to space will return N, except for edge case where run goes from start and then completely in
+    /// reverse direction. This can be caught by calling this method with reverse direction
     ///
     /// # Arguments
     /// * start = GridPosition struct representing the starting point to start counting runs from. Assumes that this position in the Board
@@ -376,6 +843,7 @@ impl Board {
     /// * team  = Integer value (1 or 2) representing team. Must match value of cell corresponding to start parameter
     ///
     fn get_run_in_direction(&self, start: GridPosition, dir: GridPosition, team: i32) -> i32 {
+        let connect = self.config.connect;
         let mut dir_active = true;
         let mut rev_active = true;
         let mut dir_spaces_used = 0;
@@ -383,12 +851,12 @@ impl Board {
         let mut run_len = 1i32; //Start with dropped token
         let mut potential_len = 1; //Assume potential length starts at 1 for dropped token
         let mut i = 1; //Start one beyond dropped token
-        while run_len <= 4 && (dir_active || rev_active) {
+        while run_len <= connect && (dir_active || rev_active) {
             dir_active = dir_active
                 && self.on_board(GridPosition::new(start.x + i * dir.x, start.y + i * dir.y));
             rev_active = rev_active
                 && self.on_board(GridPosition::new(start.x - i * dir.x, start.y - i * dir.y));
-            //Do reverse case first for edge case of AASA_A is treated as a run of 4 and not 3 with a space
+            //Do reverse case first for edge case of AASA_A is treated as a run of N and not N - 1 with a space
             if rev_active {
                 let val =
                     self.get_cell_team(GridPosition::new(start.x - i * dir.x, start.y - i * dir.y));
@@ -416,9 +884,9 @@ impl Board {
                     dir_active = false;
                 //If 0 or 1 spaces in target direction used, either add to run_len and/or potential_run depending on if cell is empty or matches team
                 } else if dir_spaces_used <= 1 {
-                    //If you have a contiguous run of 4 with no spaces, immediately return because a winning run has been found!
-                    if run_len >= 4 && dir_spaces_used == 0 {
-                        return 4i32;
+                    //If you have a contiguous run of N with no spaces, immediately return because a winning run has been found!
+                    if run_len >= connect && dir_spaces_used == 0 {
+                        return connect;
                     } else if val == team {
                         run_len += 1;
                     } else {
@@ -432,20 +900,20 @@ impl Board {
             }
             i += 1;
         }
-        //If the potential of the run is not 4 or more, return 0 because it is not a viable run
-        if potential_len < 4 {
+        //If the potential of the run is not N or more, return 0 because it is not a viable run
+        if potential_len < connect {
             0i32
-        //Otherwise, return the minimum of the run_len and 4 (if no spaces) or 3 (if one space used)
+        //Otherwise, return the minimum of the run_len and N (if no spaces) or N - 1 (if one space used)
         } else if dir_spaces_used > 0 {
-            run_len.min(3)
+            run_len.min(connect - 1)
         } else {
-            run_len.min(4)
+            run_len.min(connect)
         }
     }
 
     ///
-    /// Method to return an array of runs from a start location for a given team, where array[i] returns the number of runs
-    /// of length i-1. Accounts for all eight directions, but may have false duplicates (e.g. a run 21112 will return have two
+    /// Method to return a vector of runs from a start location for a given team, where output[i] returns the number of runs
+    /// of length i+1, up to the board's connect length. Accounts for all eight directions, but may have false duplicates (e.g. a run 21112 will return have two
     /// runs of length 3 for team 1 even though technically its the same run)
     ///
     /// # Arguments
@@ -453,8 +921,8 @@ impl Board {
     ///           is filled and matches the team parameter of this method
     /// * team  = Integer value (1 or 2) representing team. Must match value of cell corresponding to start parameter
     ///
-    pub fn get_runs_from_point(&self, start: GridPosition, team: i32) -> [i32; 4] {
-        let mut output = [0i32; 4];
+    pub fn get_runs_from_point(&self, start: GridPosition, team: i32) -> Vec<i32> {
+        let mut output = vec![0i32; self.config.connect as usize];
         let directions = vec![(1, 0), (1, 1), (0, 1), (-1, 1)];
         for dir in directions {
             let a = self.get_run_in_direction(start, GridPosition::new(dir.0, dir.1), team) - 1;
@@ -469,6 +937,32 @@ impl Board {
         output
     }
 
+    ///
+    /// Method to determine if a team's disc at a location completes a winning run of the board's connect length
+    ///
+    /// # Arguments
+    /// * start = GridPosition struct of the disc, which is usually the cell a disc is about to be dropped into
+    /// * team  = Integer value (1 or 2) representing team
+    ///
+    pub fn connects(&self, start: GridPosition, team: i32) -> bool {
+        self.get_runs_from_point(start, team)[self.config.connect as usize - 1] > 0
+    }
+
+    ///
+    /// Method to determine if any of a team's discs in a column is part of a winning run. Used after a pop, which moves
+    /// every disc in the column, so any new run must go through one of them
+    ///
+    /// # Arguments
+    /// * col  = Index of the column to check
+    /// * team = Integer value (1 or 2) representing team
+    ///
+    pub fn column_connects(&self, col: i32, team: i32) -> bool {
+        (0..self.get_column_height(col as usize) as i32).any(|row| {
+            let pos = GridPosition::new(col, row);
+            self.get_cell_team(pos) == team && self.connects(pos, team)
+        })
+    }
+
     /// Inserts a team's disc of a particular color into a cell
     /// Returns true if disc successfully inserted
     /// Returns false if column is full
@@ -476,11 +970,70 @@ impl Board {
         self.columns[position as usize].insert(team, color)
     }
 
-    /// Calls the reset function of every columns in the Board.
+    ///Method to determine if a team may pop the disc at the bottom of a column, which must be one of their own
+    pub fn can_pop(&self, col: i32, team: i32) -> bool {
+        self.config.allows_pops()
+            && col >= 0
+            && col < self.config.columns
+            && self.get_column_height(col as usize) > 0
+            && self.get_cell_team(GridPosition::new(col, 0)) == team
+    }
+
+    /// Pops a team's disc out of the bottom of a column, moving the discs above it down
+    /// Returns true if the disc was popped
+    /// Returns false if pops are not allowed or the bottom disc is not the team's
+    pub fn pop(&mut self, col: i32, team: i32) -> bool {
+        self.can_pop(col, team) && self.columns[col as usize].pop()
+    }
+
+    ///
+    /// Method to make a recorded move, dropping a disc or popping one out depending on how the move is encoded
+    /// (see `pop_move`). Returns false if the move can't be made
+    ///
+    /// # Arguments
+    /// * mv    = Integer value of the move, a column index for a drop or pop_move(column) for a pop
+    /// * team  = Integer value (1 or 2) representing team
+    /// * color = MyColor of the team's discs
+    ///
+    pub fn play_move(&mut self, mv: i32, team: i32, color: MyColor) -> bool {
+        let (col, pop) = decode_move(mv);
+        if pop {
+            self.pop(col, team)
+        } else {
+            col < self.config.columns && self.insert(col, team, color)
+        }
+    }
+
+    ///Returns every move a team can make: a drop for each column that is not full, then a pop for each column the
+    ///team may pop from
+    pub fn legal_moves(&self, team: i32) -> Vec<i32> {
+        let mut moves: Vec<i32> = (0..self.config.columns)
+            .filter(|col| !self.is_column_full(*col as usize))
+            .collect();
+        if self.config.allows_pops() {
+            moves.extend(
+                (0..self.config.columns)
+                    .filter(|col| self.can_pop(*col, team))
+                    .map(pop_move),
+            );
+        }
+        moves
+    }
+
+    ///Returns the team of every cell on the board, column by column, used to spot positions that repeat
+    pub fn position_key(&self) -> Vec<i32> {
+        self.columns
+            .iter()
+            .flat_map(|column| column.cells.iter().map(|cell| cell.team))
+            .collect()
+    }
+
+    /// Calls the reset function of every columns in the Board, then fills the outer columns again on Five-in-a-Row boards.
     pub fn reset(&mut self) {
         for column in &mut self.columns {
             column.reset();
         }
+        self.prefill();
     }
 }
 
@@ -490,27 +1043,35 @@ impl Board {
 /// # Fields
 /// * gaemover = Boolean indicating that game is over
 /// * team     = Value from 0-2 indicating the team whose turn it is or 0 if the game is paused or completed           
+/// * names    = Vector of player names, where names[i] is the name for team i (names[0] is unused)
+/// * timeout  = Boolean indicating that the game ended because a team ran out of time
 ///
 pub struct TurnIndicator {
     gameover: bool,
     team: i32,
+    names: Vec<String>,
+    timeout: bool,
 }
 
 impl TurnIndicator {
-    ///Constructor
-    pub fn new() -> Self {
+    ///Constructor - names[i] is the name displayed for team i
+    pub fn new(names: Vec<String>) -> Self {
         TurnIndicator {
             gameover: false,
             team: 0,
+            names,
+            timeout: false,
         }
     }
 
     /// Draws the turn indicator onto the Context/ game window.
     /// Text displayed depends on the state of the `gameover` and `team` property
     /// team: 0 & gameover: false = Game Draw
-    /// team: 1 or 2 & gameover: true = Player 1 or 2 Wins!
-    /// team: 1 or 2 & gameover: false = Player 1 or 2's turn
-    fn draw(&self, ctx: &mut Context) -> GameResult<()> {
+    /// team: 1 or 2 & gameover: true = <name of team 1 or 2> wins! (or wins on time! if the other team ran out of time)
+    /// team: 1 or 2 & gameover: false = <name of team 1 or 2>'s turn
+    /// center_x is the x location the indicator is centered on, so other games such as Othello can place it over their
+    /// own boards.
+    pub fn draw(&self, ctx: &mut Context, center_x: f32) -> GameResult<()> {
         let text: graphics::Text;
         if self.gameover {
             if self.team == 0 {
@@ -519,9 +1080,15 @@ impl TurnIndicator {
                     graphics::Font::default(),
                     TURN_INDICATOR_FONT_SIZE as f32,
                 ));
+            } else if self.timeout {
+                text = graphics::Text::new((
+                    format!("{} wins on time!", self.names[self.team as usize]),
+                    graphics::Font::default(),
+                    TURN_INDICATOR_FONT_SIZE as f32,
+                ));
             } else {
                 text = graphics::Text::new((
-                    format!("Player {} wins!", self.team),
+                    format!("{} wins!", self.names[self.team as usize]),
                     graphics::Font::default(),
                     TURN_INDICATOR_FONT_SIZE as f32,
                 ));
@@ -534,7 +1101,7 @@ impl TurnIndicator {
             ));
         } else {
             text = graphics::Text::new((
-                format!("Player {}'s turn", self.team),
+                format!("{}'s turn", self.names[self.team as usize]),
                 graphics::Font::default(),
                 TURN_INDICATOR_FONT_SIZE as f32,
             ));
@@ -542,7 +1109,7 @@ impl TurnIndicator {
 
         let dim = &text.dimensions(ctx);
         let pos = Point2 {
-            x: TURN_INDICATOR_POS_OFFSET.0 as f32 - (dim.0 as f32 / 2.0) as f32,
+            x: center_x - (dim.0 as f32 / 2.0) as f32,
             y: TURN_INDICATOR_POS_OFFSET.1 as f32,
         };
 
@@ -579,13 +1146,35 @@ impl TurnIndicator {
         self.gameover = true;
     }
 
-    /// Resets the values of the team and gameover property of the turn indicator object to 0 and false.
+    /// Ends the game with a win for the given team because the other team ran out of time.
+    pub fn time_out(&mut self, winner: i32) {
+        self.team = winner;
+        self.gameover = true;
+        self.timeout = true;
+    }
+
+    /// Resets the values of the team, gameover and timeout property of the turn indicator object to 0, false and false.
     pub fn reset(&mut self) {
         self.team = 0;
         self.gameover = false;
+        self.timeout = false;
     }
 }
 
+///
+/// A struct for a disc being animated as it falls into a column. The disc is only inserted into the board once it lands
+///
+/// # Fields
+/// * column   = Index of the column the disc is falling into
+/// * y        = Current y location of the disc's center
+/// * target_y = y location of the center of the cell the disc lands in
+///
+struct FallingDisc {
+    column: i32,
+    y: f32,
+    target_y: f32,
+}
+
 ///
 /// A struct that contains the states for the connect 4 game
 ///
@@ -594,35 +1183,115 @@ impl TurnIndicator {
 /// * ai_players         = Vector of AI structs representing any AI players in the game
 /// * board              = Board struct representing current board state           
 /// * team_colors        = Vector of MyColor objects representing what color to draw discs for player i or the empty cell (for 0 index)           
+/// * team_names         = Vector of player names for player i (index 0 is unused, mirroring team_colors)
 /// * turn_indicator     = TurnIndicator object tracking turns         
 /// * highlighted_column = Integer from -1 to 6 representing column over which a disc is hovering (-1 means no column is being hovered)           
+/// * popping            = Boolean indicating if the bottom disc of the highlighted column is about to be popped instead of a disc dropped
 /// * mouse_disabled     = Boolean indicating if clicking is enabled       
 /// * gameover           = Boolean indicating if game is over  
 /// * reset_button       = Button drawn to allow board to be reset and game to be restarted           
 /// * main_menu_button   = Button drawn to allow return to main menu screen          
+/// * falling_disc       = Option of a FallingDisc being animated, or None if no disc is falling
+/// * ai_move_delay      = Number of frames an AI shows its chosen column before dropping a disc
+/// * animation_speed    = Multiplier for DISC_DROP_SPEED; discs are placed instantly if this is 0.0
+/// * background_color   = Color drawn behind the board
+/// * sound_queue        = Vector of SoundEffect enums triggered since the main menu last played them
+/// * clock              = GameClock tracking each team's time, drawn right of the turn indicator if a time control is set
+/// * ai_difficulty      = Difficulty given to the AI players, recorded in the match history
+/// * moves              = Vector of column indexes for each move made so far, in order
+/// * elapsed            = Seconds the current game has been played for
+/// * finished_match     = Option of a MatchRecord for a game that just ended, taken by the main menu to be saved
+/// * hint_button        = Button drawn in the side panel to show the AI's suggested move for the current team
+/// * hint_strength      = Integer value for how deep the AI searches when a hint is asked for
+/// * hint_column        = Integer from -1 to 6 representing the column suggested by the last hint (-1 means no hint is shown)
+/// * hint_pop           = Boolean indicating if the last hint is to pop a disc out of hint_column rather than drop one in
+/// * hints_used         = Vector of hint counts, where hints_used[i] is the number of hints team i asked for (index 0 is unused)
+/// * analysis_button    = Button drawn in the side panel to toggle the analysis overlay; shown as selected while it is on
+/// * analysis           = Vector of evaluations drawn above each column while the overlay is on (empty if they need refreshing)
+/// * review_button      = Button drawn in the side panel once the game is over to open the post-game review
+/// * scripted           = Boolean indicating if another screen (e.g. the tutorial) drives the game, hiding the side buttons
+/// * allowed_columns    = Option of the columns a player may click, or None if any column may be clicked
+/// * highlighted_cells  = Vector of GridPositions for cells drawn with a ring around them
+/// * positions          = Vector of positions reached so far (position_key then the team to move), used to draw games with pops that repeat
+/// * pop10              = Option of the Pop10State for a game of Pop 10, or None for other rules
 ///
 pub struct GameState {
     frames: usize,
     ai_players: Vec<AI>,
     pub board: Board,
     team_colors: Vec<MyColor>,
+    team_names: Vec<String>,
     pub turn_indicator: TurnIndicator,
     pub highlighted_column: i32,
+    popping: bool,
     mouse_disabled: bool,
     gameover: bool,
     pub reset_button: Button,
     pub main_menu_button: Button,
+    falling_disc: Option<FallingDisc>,
+    ai_move_delay: i32,
+    animation_speed: f32,
+    background_color: graphics::Color,
+    pub sound_queue: Vec<SoundEffect>,
+    clock: GameClock,
+    ai_difficulty: i32,
+    moves: Vec<i32>,
+    elapsed: f32,
+    pub finished_match: Option<MatchRecord>,
+    pub hint_button: Button,
+    hint_strength: i32,
+    hint_column: i32,
+    hint_pop: bool,
+    hints_used: Vec<u32>,
+    pub analysis_button: Button,
+    analysis: Vec<Option<Evaluation>>,
+    pub review_button: Button,
+    scripted: bool,
+    pub allowed_columns: Option<Vec<i32>>,
+    pub highlighted_cells: Vec<GridPosition>,
+    positions: Vec<Vec<i32>>,
+    pop10: Option<Pop10State>,
 }
 
 //Implementation based on structure in example from GGEZ repo (see https://github.com/ggez/ggez/blob/master/examples/02_hello_world.rs)
 impl GameState {
-    ///Constructor - players is the number of human players to be in the game
-    pub fn new(ctx: &mut Context, players: i32) -> GameState {
-        let board_pos = BOARD_POS_OFFSET;
+    ///Constructor - player_info holds the name, color and whether the AI plays for each team in team order, and
+    ///settings holds the board, AI, animation and theme options. Teams without player info get the defaults
+    pub fn new(ctx: &mut Context, player_info: Vec<PlayerInfo>, settings: &Settings) -> GameState {
+        let main_menu_btn_text =
+            graphics::Text::new(("Main Menu", graphics::Font::default(), 16f32));
+        let main_menu_text_size = (
+            main_menu_btn_text.width(ctx) as f32,
+            main_menu_btn_text.height(ctx) as f32,
+        );
+        GameState::with_text_size(main_menu_text_size, player_info, settings)
+    }
+
+    ///
+    /// Method to initialize and return a GameState once the size of the "Main Menu" button text is known, which the
+    /// Reset button is sized to as well. Measuring text needs a Context, so tests pass a size instead
+    ///
+    /// # Arguments
+    /// * main_menu_text_size = Width and height of the "Main Menu" button text
+    /// * player_info         = Vector of PlayerInfo for each team, in team order
+    /// * settings            = Settings holding the board, AI, animation and theme options
+    ///
+    fn with_text_size(
+        main_menu_text_size: (f32, f32),
+        player_info: Vec<PlayerInfo>,
+        settings: &Settings,
+    ) -> GameState {
+        let board_config = settings.board.played();
+        let teams = board_config.teams as usize;
+        let pop10 = if board_config.variant == Variant::Pop10 {
+            Some(Pop10State::new())
+        } else {
+            None
+        };
+        let side_panel_x = board_config.side_panel_x();
         let main_menu_btn_text =
             graphics::Text::new(("Main Menu", graphics::Font::default(), 16f32));
-        let main_menu_text_width = main_menu_btn_text.width(ctx) as f32;
-        let main_menu_text_height = main_menu_btn_text.height(ctx) as f32;
+        let (main_menu_text_width, main_menu_text_height) = main_menu_text_size;
         let main_menu_btn_outline = graphics::Rect::new(
             RESET_BUTTON_OFFSET.0 as f32,
             RESET_BUTTON_OFFSET.1 as f32 + main_menu_text_height,
@@ -640,132 +1309,871 @@ impl GameState {
         );
         let mut reset_btn = Button::new(reset_text, reset_outline);
 
+        let hint_text = graphics::Text::new(("Hint", graphics::Font::default(), 24f32));
+        let mut hint_btn = Button::new(
+            hint_text,
+            graphics::Rect::new(
+                side_panel_x,
+                SIDE_BUTTON_POS_Y,
+                SIDE_BUTTON_SIZE.0,
+                SIDE_BUTTON_SIZE.1,
+            ),
+        );
+
+        let analysis_text = graphics::Text::new(("Analysis", graphics::Font::default(), 24f32));
+        let mut analysis_btn = Button::new(
+            analysis_text,
+            graphics::Rect::new(
+                side_panel_x,
+                SIDE_BUTTON_POS_Y + SIDE_BUTTON_SIZE.1 + SIDE_BUTTON_SPACING,
+                SIDE_BUTTON_SIZE.0,
+                SIDE_BUTTON_SIZE.1,
+            ),
+        );
+
+        let review_text = graphics::Text::new(("Review", graphics::Font::default(), 24f32));
+        let mut review_btn = Button::new(
+            review_text,
+            graphics::Rect::new(
+                side_panel_x,
+                SIDE_BUTTON_POS_Y + (SIDE_BUTTON_SIZE.1 + SIDE_BUTTON_SPACING) * 2.0,
+                SIDE_BUTTON_SIZE.0,
+                SIDE_BUTTON_SIZE.1,
+            ),
+        );
+        review_btn.active = false;
+        //Pop 10 games are too long for the AI's search, so hints and analysis are left out. Analysis counts forced
+        //wins and losses between two teams, so it is left out of games with more players too
+        hint_btn.active = pop10.is_none();
+        analysis_btn.active = pop10.is_none() && teams == 2;
+
         reset_btn.set_colors(MyColor::Brown, MyColor::Red);
         main_menu_btn.set_colors(MyColor::Brown, MyColor::Green);
+        hint_btn.set_colors(MyColor::Brown, MyColor::Yellow);
+        analysis_btn.set_colors(MyColor::Brown, MyColor::Cyan);
+        review_btn.set_colors(MyColor::Brown, MyColor::Orange);
         let mut bots = Vec::<AI>::new();
-        for i in 0..players {
-            bots.push(AI::new(2 - i, 3));
+        let mut team_colors = vec![MyColor::White];
+        let mut team_names = vec![String::new()];
+        let defaults = PlayerInfo::defaults();
+        for (i, info) in player_info
+            .into_iter()
+            .chain(defaults.into_iter().skip(teams))
+            .take(teams)
+            .enumerate()
+        {
+            if info.is_ai {
+                bots.push(AI::new(i as i32 + 1, settings.ai_difficulty));
+            }
+            team_colors.push(info.color);
+            team_names.push(info.name);
         }
-        GameState {
+        //A clock can only hand the win to one other team, so games with more players are untimed
+        let time_control = if teams == 2 {
+            settings.time_control
+        } else {
+            TimeControl::off()
+        };
+        let mut board = Board::with_config(board_config.board_position(), board_config);
+        board.set_team_colors(&team_colors);
+        let mut state = GameState {
             frames: 0,
             ai_players: bots,
-            board: Board::new(board_pos.into()),
-            team_colors: vec![MyColor::White, MyColor::Red, MyColor::Blue],
-            turn_indicator: TurnIndicator::new(),
+            board,
+            turn_indicator: TurnIndicator::new(team_names.clone()),
+            team_colors,
+            team_names,
             highlighted_column: -1,
+            popping: false,
             mouse_disabled: false,
             gameover: false,
             reset_button: reset_btn,
             main_menu_button: main_menu_btn,
+            falling_disc: None,
+            ai_move_delay: settings.ai_move_delay,
+            animation_speed: settings.animation_speed,
+            background_color: settings.theme.game_background(),
+            sound_queue: Vec::new(),
+            clock: GameClock::new(time_control, teams),
+            ai_difficulty: settings.ai_difficulty,
+            moves: Vec::new(),
+            elapsed: 0.0,
+            finished_match: None,
+            hint_button: hint_btn,
+            hint_strength: settings.hint_strength,
+            hint_column: -1,
+            hint_pop: false,
+            hints_used: vec![0; teams + 1],
+            analysis_button: analysis_btn,
+            analysis: Vec::new(),
+            review_button: review_btn,
+            scripted: false,
+            allowed_columns: None,
+            highlighted_cells: Vec::new(),
+            positions: Vec::new(),
+            pop10,
+        };
+        //Team 1 always makes the first move
+        state.record_position(1);
+        state
+    }
+
+    ///Method to hide the reset, hint, analysis and review buttons, for screens that drive the game themselves
+    pub fn set_scripted(&mut self) {
+        self.scripted = true;
+        self.reset_button.active = false;
+        self.hint_button.active = false;
+        self.analysis_button.active = false;
+        self.review_button.active = false;
+    }
+
+    ///Method to clear the board and everything tracked about the game so far, starting again with team 1 to move
+    fn reset_game(&mut self) {
+        self.board.reset();
+        self.board.set_team_colors(&self.team_colors);
+        if self.pop10.is_some() {
+            self.pop10 = Some(Pop10State::new());
+        }
+        self.turn_indicator.reset();
+        self.turn_indicator.change_team(1);
+        self.gameover = false;
+        self.mouse_disabled = false;
+        self.falling_disc = None;
+        self.clock.reset();
+        self.moves.clear();
+        self.elapsed = 0.0;
+        self.hint_column = -1;
+        self.hints_used = vec![0; self.team_names.len()];
+        self.analysis.clear();
+        self.review_button.active = false;
+        self.popping = false;
+        self.positions.clear();
+        self.record_position(1);
+        for ai in &mut self.ai_players {
+            ai.last_move_frame = -1;
         }
     }
 
-    /// Update method - contains main game logic.
-    pub fn update(&mut self, _ctx: &mut Context) -> GameResult {
-        self.frames += 1; //Timing mechanism for bot moves
-        if !self.gameover {
-            //Draw state check
-            let mut full_column = 0;
-            for column_index in 0..self.board.columns.len() {
-                if !self.board.is_column_full(column_index) {
-                    break;
-                }
-                full_column += 1;
-            }
-            if full_column == 7 {
-                //All columns full - proceed to Gameover - Draw state
-                println!("All columns full; Game Draw!");
-                self.gameover = true;
-                self.mouse_disabled = true;
-                self.turn_indicator.change_team(0);
-                self.turn_indicator.game_ends();
-            }
-            //Check for AI actions
-            let mut bot_active = false;
-            for ai in &mut self.ai_players {
-                if ai.team == self.turn_indicator.team {
-                    bot_active = true;
-                    self.mouse_disabled = true;
-                    //Check if move selection process has started
-                    if ai.last_move_frame < 0 {
-                        self.highlighted_column = ai.pick_optimal_move(self.board.clone());
-                        ai.last_move_frame = self.frames as i32;
-                    //If enough frames have passed, make move
-                    } else if self.frames > (ai.last_move_frame + 100) as usize {
-                        if self.board.insert(
-                            self.highlighted_column,
-                            self.turn_indicator.team,
-                            self.team_colors[self.turn_indicator.team as usize],
-                        ) {
-                            println!(
-                                "AI Player {} drops token in col {}",
-                                ai.team, self.highlighted_column
-                            );
-
-                            //game state check
-                            let runs = self.board.get_runs_from_point(
-                                GridPosition::new(
-                                    self.highlighted_column,
-                                    self.board
-                                        .get_column_height(self.highlighted_column as usize)
-                                        as i32
-                                        - 1,
-                                ),
-                                ai.team,
-                            );
-                            if runs[3] > 0 {
-                                //Four Connected - Proceed to Gameover - Win/Loss state
-                                println!(
-                                    "4 Connected for player {}; Game ends",
-                                    self.turn_indicator.team
-                                );
-                                self.gameover = true;
-                                self.turn_indicator.game_ends();
-                            } else {
-                                self.turn_indicator.team = self.turn_indicator.team % 2 + 1; //Change to other team's turn
-                            }
-                        }
-                        //Reset check for a move so next move can be made
-                        ai.last_move_frame = -1;
-                    }
-                }
+    ///
+    /// Method to start the game again from the position reached by playing the given moves, with teams taking turns
+    /// from team 1. Moves that can't be made, such as drops into full columns, are skipped
+    ///
+    /// # Arguments
+    /// * moves = Vector of moves, in order, as column indexes for drops or pop_move(column) for pops
+    ///
+    pub fn load_moves(&mut self, moves: &[i32]) {
+        self.reset_game();
+        let mut team = 1;
+        for mv in moves {
+            if self
+                .board
+                .play_move(*mv, team, self.team_colors[team as usize])
+            {
+                self.moves.push(*mv);
+                team = self.board.config().next_team(team);
             }
-            self.mouse_disabled = bot_active;
         }
-        Ok(())
+        self.turn_indicator.change_team(team);
+        self.positions.clear();
+        self.record_position(team);
     }
 
-    ///Draw method to render the board, turn indicator, and other buttons
-    pub fn draw(&mut self, ctx: &mut Context) -> GameResult {
-        //Draw screen background
-        graphics::clear(ctx, graphics::BLACK);
-        let mut mb = graphics::MeshBuilder::new();
-        //Draw disc over current column
-        if self.highlighted_column >= 0 {
-            mb.circle(
-                graphics::DrawMode::fill(),
-                Point2 {
-                    x: (self.board.columns[self.highlighted_column as usize]
-                        .position
-                        .x
-                        + (BOARD_CELL_SIZE.0 / 2)) as f32,
-                    y: (self.board.position.y - (BOARD_CELL_SIZE.1 / 2)) as f32,
-                },
-                BOARD_DISC_RADIUS as f32,
-                2.0,
-                self.team_colors[self.turn_indicator.team as usize].get_draw_color(),
-            );
-        }
-        //Draw Board
-        let mesh = self.board.draw(&mut mb).build(ctx)?;
-        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))?;
+    ///Returns the number of moves made so far in the game
+    pub fn move_count(&self) -> usize {
+        self.moves.len()
+    }
+
+    ///Returns true if the game has been won or drawn
+    pub fn is_gameover(&self) -> bool {
+        self.gameover
+    }
+
+    ///Returns true if a disc is being animated as it falls into a column
+    pub fn is_disc_falling(&self) -> bool {
+        self.falling_disc.is_some()
+    }
+
+    ///
+    /// Method to start dropping the current team's disc into a column. If animations are off, the move is made right away,
+    /// otherwise a FallingDisc is animated and the move is made when it lands. Returns false if the column is full
+    ///
+    /// # Arguments
+    /// * col = Index of the column the disc is dropped in
+    ///
+    pub fn drop_disc(&mut self, col: i32) -> bool {
+        if col < 0 || self.board.is_column_full(col as usize) {
+            self.sound_queue.push(SoundEffect::InvalidMove);
+            return false;
+        }
+        if self.animation_speed <= 0.0 {
+            self.complete_move(col);
+        } else {
+            let column = &self.board.columns[col as usize];
+            let cell = &column.cells[column.get_height()];
+            self.falling_disc = Some(FallingDisc {
+                column: col,
+                y: (self.board.position.y - (BOARD_CELL_SIZE.1 / 2)) as f32,
+                target_y: (cell.position.y + (BOARD_CELL_SIZE.1 / 2)) as f32,
+            });
+            self.mouse_disabled = true;
+        }
+        true
+    }
+
+    ///
+    /// Method to pop the current team's disc out of the bottom of a column. Pops are made right away, without an
+    /// animation. Returns false if the team may not pop from the column
+    ///
+    /// # Arguments
+    /// * col = Index of the column the disc is popped from
+    ///
+    pub fn pop_disc(&mut self, col: i32) -> bool {
+        if !self.board.can_pop(col, self.turn_indicator.team) {
+            self.sound_queue.push(SoundEffect::InvalidMove);
+            return false;
+        }
+        self.complete_pop(col);
+        true
+    }
+
+    ///Method to drop a disc into or pop a disc out of a column, depending on pop. In Pop 10 the part of the turn
+    ///decides instead
+    fn make_move(&mut self, col: i32, pop: bool) -> bool {
+        if self.pop10.is_some() {
+            self.play_pop10(col)
+        } else if pop {
+            self.pop_disc(col)
+        } else {
+            self.drop_disc(col)
+        }
+    }
+
+    ///Returns the sound for a win by the given team: the losing sound if an AI beat a human player, otherwise the winning sound
+    fn game_end_sound(&self, winner: i32) -> SoundEffect {
+        let winner_is_ai = self.ai_players.iter().any(|ai| ai.team == winner);
+        if winner_is_ai && self.ai_players.len() < self.board.config().teams as usize {
+            SoundEffect::Lose
+        } else {
+            SoundEffect::Win
+        }
+    }
+
+    ///
+    /// Method to ask the AI for the best move for the current team, shown by drawing a hover disc over the suggested
+    /// column. Hints are only given to human players during their turn
+    ///
+    fn show_hint(&mut self) {
+        let team = self.turn_indicator.team;
+        let ai_turn = self.ai_players.iter().any(|ai| ai.team == team);
+        if self.gameover || team == 0 || ai_turn || self.falling_disc.is_some() {
+            return;
+        }
+        let (col, pop) = decode_move(
+            AI::new(team, self.hint_strength).pick_optimal_move(self.board.clone(), None),
+        );
+        self.hint_column = col;
+        self.hint_pop = pop;
+        self.hints_used[team as usize] += 1;
+        println!(
+            "Hint for {}: {} col {}",
+            self.team_names[team as usize],
+            if pop { "pop" } else { "drop in" },
+            self.hint_column
+        );
+    }
+
+    ///Method to turn the analysis overlay on or off. The evaluations are worked out on the next update
+    fn toggle_analysis(&mut self) {
+        self.analysis_button.selected = !self.analysis_button.selected;
+        self.analysis.clear();
+        println!(
+            "Analysis {}",
+            if self.analysis_button.selected {
+                "on"
+            } else {
+                "off"
+            }
+        );
+    }
+
+    ///
+    /// Method to refresh the evaluations drawn above each column for the current team, searching as deep as a hint
+    /// would. Nothing is shown once the game is over
+    ///
+    fn update_analysis(&mut self) {
+        let team = self.turn_indicator.team;
+        if !self.analysis_button.selected || self.gameover || team == 0 {
+            self.analysis.clear();
+        } else if self.analysis.is_empty() {
+            self.analysis =
+                AI::new(team, self.hint_strength).evaluate_columns(&self.board, self.hint_strength);
+        }
+    }
+
+    ///Method to move the falling disc (if any) down by one frame's distance, making the move once it lands
+    fn update_falling_disc(&mut self) {
+        let mut landed = None;
+        if let Some(ref mut disc) = self.falling_disc {
+            disc.y += DISC_DROP_SPEED * self.animation_speed;
+            if disc.y >= disc.target_y {
+                landed = Some(disc.column);
+            }
+        }
+        if let Some(col) = landed {
+            self.falling_disc = None;
+            self.complete_move(col);
+        }
+    }
+
+    ///
+    /// Method to insert the current team's disc into a column, then check for a win or hand the turn to the other team
+    ///
+    /// # Arguments
+    /// * col = Index of the column the disc is dropped in
+    ///
+    fn complete_move(&mut self, col: i32) {
+        let team = self.turn_indicator.team;
+        if self
+            .board
+            .insert(col, team, self.team_colors[team as usize])
+        {
+            self.sound_queue.push(SoundEffect::Drop);
+            self.clock.end_move(team);
+            self.moves.push(col);
+            self.hint_column = -1;
+            self.analysis.clear();
+            if self.ai_players.iter().any(|ai| ai.team == team) {
+                println!(
+                    "AI Player {} ({}) drops token in col {}",
+                    team, self.team_names[team as usize], col
+                );
+            } else {
+                println!(
+                    "Team {} ({}) drops token in col {}",
+                    team, self.team_names[team as usize], col
+                );
+            }
+            //game state check
+            let connected = self.board.connects(
+                GridPosition::new(col, self.board.get_column_height(col as usize) as i32 - 1),
+                team,
+            );
+            if connected {
+                self.win_game(team);
+            } else {
+                self.next_turn(team);
+            }
+        }
+        self.mouse_disabled = self.gameover;
+    }
+
+    ///
+    /// Method to pop the current team's disc out of a column, then check for a win by either team or hand the turn to
+    /// the other team. Popping moves every disc in the column, so a pop can complete a run for the other team too.
+    /// If both teams get a run, the team that popped wins
+    ///
+    /// # Arguments
+    /// * col = Index of the column the disc is popped from
+    ///
+    fn complete_pop(&mut self, col: i32) {
+        let team = self.turn_indicator.team;
+        if self.board.pop(col, team) {
+            self.sound_queue.push(SoundEffect::Drop);
+            self.clock.end_move(team);
+            self.moves.push(pop_move(col));
+            self.hint_column = -1;
+            self.analysis.clear();
+            println!(
+                "Team {} ({}) pops token from col {}",
+                team, self.team_names[team as usize], col
+            );
+            //Any other team the pop completes a run for wins, checked in turn order from the next team
+            let config = self.board.config();
+            let mut other = config.next_team(team);
+            while other != team && !self.board.column_connects(col, other) {
+                other = config.next_team(other);
+            }
+            if self.board.column_connects(col, team) {
+                self.win_game(team);
+            } else if other != team {
+                self.win_game(other);
+            } else {
+                self.next_turn(team);
+            }
+        }
+        self.popping = false;
+        self.mouse_disabled = self.gameover;
+    }
+
+    ///
+    /// Method to make the current team's move in a column in a game of Pop 10: a drop while the board is filled or a
+    /// popped disc is returned, otherwise a pop. Moves are made right away. Returns false if the move is not allowed
+    ///
+    /// # Arguments
+    /// * col = Index of the column the move is made in
+    ///
+    fn play_pop10(&mut self, col: i32) -> bool {
+        let team = self.turn_indicator.team;
+        let color = self.team_colors[team as usize];
+        let (result, mv, winner) = match self.pop10 {
+            Some(ref mut state) => {
+                let mv = if state.phase == Pop10Phase::Popping {
+                    pop_move(col)
+                } else {
+                    col
+                };
+                (
+                    state.play(&mut self.board, mv, team, color),
+                    mv,
+                    state.winner(),
+                )
+            }
+            None => return false,
+        };
+        self.popping = false;
+        match result {
+            None => {
+                self.sound_queue.push(SoundEffect::InvalidMove);
+                false
+            }
+            Some(passes) => {
+                self.sound_queue.push(SoundEffect::Drop);
+                self.moves.push(mv);
+                self.hint_column = -1;
+                println!(
+                    "Team {} ({}) {} col {}",
+                    team,
+                    self.team_names[team as usize],
+                    if mv < 0 {
+                        "pops token from"
+                    } else {
+                        "drops token in"
+                    },
+                    col
+                );
+                if winner > 0 {
+                    self.win_game(winner);
+                } else if passes {
+                    self.clock.end_move(team);
+                    self.next_turn(team);
+                }
+                self.mouse_disabled = self.gameover;
+                true
+            }
+        }
+    }
+
+    ///
+    /// Method to check if the team to move in a game of Pop 10 can't pop any disc. The turn is passed to the other
+    /// team, or the game is drawn if neither team can pop
+    ///
+    fn check_pop10_pass(&mut self) {
+        let team = self.turn_indicator.team;
+        let (stuck, other_stuck) = match self.pop10 {
+            Some(ref state) => (
+                state.legal_moves(&self.board, team).is_empty(),
+                state
+                    .legal_moves(&self.board, self.board.config().next_team(team))
+                    .is_empty(),
+            ),
+            None => return,
+        };
+        if stuck && other_stuck {
+            self.draw_game("Neither team can pop");
+        } else if stuck {
+            println!(
+                "{} has no disc to pop; turn passes",
+                self.team_names[team as usize]
+            );
+            self.next_turn(team);
+        }
+    }
+
+    ///Method to end the game with a win for a team that connected a run, or collected enough discs in Pop 10
+    fn win_game(&mut self, team: i32) {
+        //N Connected - Proceed to Gameover - Win/Loss state
+        println!("{} wins; Game ends", self.team_names[team as usize]);
+        self.gameover = true;
+        self.turn_indicator.change_team(team);
+        self.turn_indicator.game_ends();
+        self.sound_queue.push(self.game_end_sound(team));
+        self.finish_match(team, false);
+    }
+
+    ///Method to end the game in a draw, e.g. once the board is full
+    fn draw_game(&mut self, reason: &str) {
+        println!("{}; Game Draw!", reason);
+        self.gameover = true;
+        self.mouse_disabled = true;
+        self.turn_indicator.change_team(0);
+        self.turn_indicator.game_ends();
+        self.sound_queue.push(SoundEffect::Draw);
+        self.finish_match(0, false);
+    }
+
+    ///Method to hand the turn to the team after the one that just moved, drawing the game if the position it reaches has
+    ///come up too many times. Positions can only repeat in games with pops
+    fn next_turn(&mut self, team: i32) {
+        let next = self.board.config().next_team(team);
+        self.turn_indicator.team = next; //Change to next team's turn
+        if self.record_position(next) >= REPETITION_DRAW_COUNT {
+            self.draw_game("Position repeated");
+        }
+    }
 
-        //Draw turn indicator
-        self.turn_indicator.draw(ctx)?;
+    ///Method to add the current position, with the team to move, to the positions reached. Returns how many times the
+    ///position has been reached so far
+    fn record_position(&mut self, team: i32) -> usize {
+        let mut key = self.board.position_key();
+        key.push(team);
+        self.positions.push(key);
+        let last = &self.positions[self.positions.len() - 1];
+        self.positions.iter().filter(|pos| *pos == last).count()
+    }
+
+    ///Method to end the game with a loss for a team whose time ran out, giving the win to the other team. Only
+    ///two-player games are timed
+    fn flag_fall(&mut self, team: i32) {
+        let winner = self.board.config().next_team(team);
+        println!(
+            "{} ran out of time; Game ends",
+            self.team_names[team as usize]
+        );
+        self.gameover = true;
+        self.mouse_disabled = true;
+        self.highlighted_column = -1;
+        self.turn_indicator.time_out(winner);
+        self.sound_queue.push(self.game_end_sound(winner));
+        self.finish_match(winner, true);
+    }
+
+    ///
+    /// Method to create the MatchRecord for a game that just ended, so it can be added to the match history
+    ///
+    /// # Arguments
+    /// * winner  = Team number of the winner, or 0 for a draw
+    /// * on_time = Boolean indicating if the game was won because the other team ran out of time
+    ///
+    fn finish_match(&mut self, winner: i32, on_time: bool) {
+        //Scripted games, such as tutorial lessons, are not real matches
+        if self.scripted {
+            return;
+        }
+        let mut players = Vec::new();
+        for team in 1..self.team_names.len() {
+            let is_ai = self.ai_players.iter().any(|ai| ai.team == team as i32);
+            players.push(PlayerRecord::new(&self.team_names[team], is_ai));
+        }
+        let ai_difficulty = if self.ai_players.is_empty() {
+            0
+        } else {
+            self.ai_difficulty
+        };
+        let mut record = MatchRecord::new(
+            &self.board.config().label(),
+            players,
+            ai_difficulty,
+            self.moves.clone(),
+            winner,
+            self.elapsed,
+        );
+        record.on_time = on_time;
+        record.hints = self.hints_used[1..].to_vec();
+        self.finished_match = Some(record);
+    }
+
+    ///Method to draw the board, turn indicator, and other buttons without clearing or presenting the screen, so other
+    ///screens can draw on top of the game
+    pub fn draw_contents(&mut self, ctx: &mut Context) -> GameResult {
+        let mut mb = graphics::MeshBuilder::new();
+        //Draw disc over current column, unless its bottom disc is about to be popped
+        if self.highlighted_column >= 0 && self.falling_disc.is_none() && !self.popping {
+            mb.circle(
+                graphics::DrawMode::fill(),
+                Point2 {
+                    x: (self.board.columns[self.highlighted_column as usize]
+                        .position
+                        .x
+                        + (BOARD_CELL_SIZE.0 / 2)) as f32,
+                    y: (self.board.position.y - (BOARD_CELL_SIZE.1 / 2)) as f32,
+                },
+                BOARD_DISC_RADIUS as f32,
+                2.0,
+                self.team_colors[self.turn_indicator.team as usize].get_draw_color(),
+            );
+        }
+        //Draw a hover disc with a white ring over the hinted column
+        if self.hint_column >= 0 && self.falling_disc.is_none() && !self.hint_pop {
+            let center = Point2 {
+                x: (self.board.columns[self.hint_column as usize].position.x
+                    + (BOARD_CELL_SIZE.0 / 2)) as f32,
+                y: (self.board.position.y - (BOARD_CELL_SIZE.1 / 2)) as f32,
+            };
+            mb.circle(
+                graphics::DrawMode::fill(),
+                center,
+                BOARD_DISC_RADIUS as f32,
+                2.0,
+                self.team_colors[self.turn_indicator.team as usize].get_draw_color(),
+            );
+            mb.circle(
+                graphics::DrawMode::stroke(4.0),
+                center,
+                BOARD_DISC_RADIUS as f32,
+                2.0,
+                graphics::WHITE,
+            );
+        }
+        //Draw Board, then any falling disc on top of it
+        self.board.draw(&mut mb);
+        if let Some(ref disc) = self.falling_disc {
+            mb.circle(
+                graphics::DrawMode::fill(),
+                Point2 {
+                    x: (self.board.columns[disc.column as usize].position.x
+                        + (BOARD_CELL_SIZE.0 / 2)) as f32,
+                    y: disc.y.min(disc.target_y),
+                },
+                BOARD_DISC_RADIUS as f32,
+                2.0,
+                self.team_colors[self.turn_indicator.team as usize].get_draw_color(),
+            );
+        }
+        //Draw a white ring around the bottom disc of a column that is about to be popped or is hinted to be popped
+        let mut pop_columns = Vec::new();
+        if self.highlighted_column >= 0 && self.popping {
+            pop_columns.push(self.highlighted_column);
+        }
+        if self.hint_column >= 0 && self.hint_pop {
+            pop_columns.push(self.hint_column);
+        }
+        for col in pop_columns {
+            mb.circle(
+                graphics::DrawMode::stroke(4.0),
+                self.board.cell_center(GridPosition::new(col, 0)),
+                BOARD_DISC_RADIUS as f32,
+                2.0,
+                graphics::WHITE,
+            );
+        }
+        //Draw a ring around each highlighted cell
+        for pos in &self.highlighted_cells {
+            if self.board.on_board(*pos) {
+                mb.circle(
+                    graphics::DrawMode::stroke(4.0),
+                    self.board.cell_center(*pos),
+                    BOARD_DISC_RADIUS as f32 + 2.0,
+                    2.0,
+                    MyColor::Yellow.get_draw_color(),
+                );
+            }
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))?;
+
+        //Draw the evaluation for each column above it if the analysis overlay is on
+        if self.falling_disc.is_none() {
+            for (i, eval) in self.analysis.iter().enumerate() {
+                if let Some(eval) = eval {
+                    let color = match eval {
+                        Evaluation::WinIn(_) => MyColor::Green,
+                        Evaluation::LossIn(_) => MyColor::Red,
+                        Evaluation::Score(_) => MyColor::White,
+                    };
+                    let text =
+                        graphics::Text::new((eval.label(), graphics::Font::default(), 20f32));
+                    let text_size = (text.width(ctx) as f32, text.height(ctx) as f32);
+                    let center_x =
+                        (self.board.columns[i].position.x + (BOARD_CELL_SIZE.0 / 2)) as f32;
+                    graphics::draw(
+                        ctx,
+                        &text,
+                        graphics::DrawParam::default()
+                            .dest(Point2 {
+                                x: center_x - text_size.0 / 2.0,
+                                y: (self.board.position.y - BOARD_CELL_SIZE.1 / 2) as f32
+                                    - text_size.1 / 2.0,
+                            })
+                            .color(color.get_draw_color()),
+                    )?;
+                }
+            }
+        }
+
+        //Draw turn indicator, with the game clocks right of it
+        let board_config = self.board.config();
+        self.turn_indicator
+            .draw(ctx, board_config.turn_indicator_x())?;
+        let active_team = if self.gameover {
+            0
+        } else {
+            self.turn_indicator.team
+        };
+        self.clock.draw(
+            ctx,
+            (
+                board_config.side_panel_x(),
+                TURN_INDICATOR_POS_OFFSET.1 as f32,
+            ),
+            &self.team_names,
+            &self.team_colors,
+            active_team,
+        )?;
 
         //Draw reset button
         self.reset_button.draw(ctx)?;
         self.main_menu_button.draw(ctx)?;
+
+        //Draw how many discs each team has collected in Pop 10, and what the team to move does next
+        if let Some(ref state) = self.pop10 {
+            let mut lines = vec![(format!("Collected (of {})", POP10_TARGET), MyColor::White)];
+            for team in 1..self.team_names.len() {
+                lines.push((
+                    format!("{}: {}", self.team_names[team], state.collected[team]),
+                    self.team_colors[team],
+                ));
+            }
+            if !self.gameover {
+                lines.push((state.phase.instructions().to_string(), MyColor::Yellow));
+            }
+            for (i, (line, color)) in lines.iter().enumerate() {
+                let text = graphics::Text::new((line.as_str(), graphics::Font::default(), 20f32));
+                graphics::draw(
+                    ctx,
+                    &text,
+                    (
+                        Point2 {
+                            x: board_config.side_panel_x(),
+                            y: SIDE_BUTTON_POS_Y + 30.0 * i as f32,
+                        },
+                        color.get_draw_color(),
+                    ),
+                )?;
+            }
+        }
+
+        //Draw hint button and how many hints have been used
+        if self.hint_button.active {
+            self.hint_button.draw(ctx)?;
+            let hints_text = graphics::Text::new((
+                format!("Hints used: {}", self.hints_used.iter().sum::<u32>()),
+                graphics::Font::default(),
+                16f32,
+            ));
+            graphics::draw(
+                ctx,
+                &hints_text,
+                (Point2 {
+                    x: self.hint_button.outline.x,
+                    y: SIDE_BUTTON_POS_Y + SIDE_BUTTON_SIZE.1 + 6.0,
+                },),
+            )?;
+        }
+
+        //Draw analysis button, with the key that also toggles it
+        if self.analysis_button.active {
+            self.analysis_button.draw(ctx)?;
+            let analysis_key_text =
+                graphics::Text::new(("Press A to toggle", graphics::Font::default(), 16f32));
+            graphics::draw(
+                ctx,
+                &analysis_key_text,
+                (Point2 {
+                    x: self.analysis_button.outline.x,
+                    y: self.analysis_button.outline.y + SIDE_BUTTON_SIZE.1 + 6.0,
+                },),
+            )?;
+        }
+
+        //Draw review button, which is only active once the game is over
+        self.review_button.draw(ctx)
+    }
+}
+
+impl GameScreen for GameState {
+    fn screen_size(&self) -> (f32, f32) {
+        self.board.config().screen_size()
+    }
+
+    /// Update method - contains main game logic.
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        self.frames += 1; //Timing mechanism for bot moves
+        self.review_button.active = self.gameover
+            && !self.scripted
+            && self.pop10.is_none()
+            && self.board.config().teams == 2;
+        if !self.gameover && self.turn_indicator.team > 0 {
+            self.elapsed += ggez::timer::delta(_ctx).as_secs_f32();
+        }
+        if self.falling_disc.is_some() {
+            //Finish animating a falling disc before anything else happens
+            self.update_falling_disc();
+            return Ok(());
+        }
+        self.update_analysis();
+        //Count down the clock of the team whose turn it is
+        let team = self.turn_indicator.team;
+        if !self.gameover
+            && self
+                .clock
+                .tick(team, ggez::timer::delta(_ctx).as_secs_f32())
+        {
+            self.flag_fall(team);
+        }
+        if !self.gameover {
+            //Draw state check
+            if self.pop10.is_some() {
+                self.check_pop10_pass();
+            } else if self.board.is_full()
+                && self.board.legal_moves(self.turn_indicator.team).is_empty()
+            {
+                //All columns full and nothing to pop - proceed to Gameover - Draw state
+                self.draw_game("All columns full");
+            }
+            //Check for AI actions, unless the draw check just ended the game
+            let mut bot_active = false;
+            let team = if self.gameover {
+                0
+            } else {
+                self.turn_indicator.team
+            };
+            if let Some(i) = self.ai_players.iter().position(|ai| ai.team == team) {
+                bot_active = true;
+                self.mouse_disabled = true;
+                //Check if move selection process has started
+                let time_left = self.clock.time_left(team);
+                if self.ai_players[i].last_move_frame < 0 {
+                    //The AI searches less deeply if it is running out of time
+                    let mv = match self.pop10 {
+                        Some(ref state) => self.ai_players[i].pick_pop10_move(&self.board, state),
+                        None => self.ai_players[i].pick_optimal_move(self.board.clone(), time_left),
+                    };
+                    let (col, pop) = decode_move(mv);
+                    self.highlighted_column = col;
+                    self.popping = pop;
+                    self.ai_players[i].last_move_frame = self.frames as i32;
+                //If enough frames have passed (or the AI is short on time), make move
+                } else if self.frames
+                    > (self.ai_players[i].last_move_frame + self.ai_move_delay) as usize
+                    || time_left.is_some_and(|t| t < AI_HURRY_TIME)
+                {
+                    let col = self.highlighted_column;
+                    let pop = self.popping;
+                    self.make_move(col, pop);
+                    //Reset check for a move so next move can be made
+                    self.ai_players[i].last_move_frame = -1;
+                }
+            }
+            self.mouse_disabled = self.gameover || bot_active || self.falling_disc.is_some();
+        }
+        Ok(())
+    }
+
+    ///Draw method to render the board, turn indicator, and other buttons
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        //Draw screen background
+        graphics::clear(ctx, self.background_color);
+        self.draw_contents(ctx)?;
         graphics::present(ctx)?;
         ggez::timer::yield_now();
         Ok(())
@@ -773,10 +2181,14 @@ impl GameState {
 
     ///Method active whenever the mouse is moved (if mouse is not intentionally disabled). Changes the highlighted_column
     ///value based on mouse location
-    pub fn mouse_motion_event(&mut self, _ctx: &mut Context, _x: f32, _y: f32, _dx: f32, _dy: f32) {
+    fn mouse_motion_event(&mut self, _ctx: &mut Context) {
         if !self.mouse_disabled {
             let was_highlighted = self.highlighted_column;
-            self.highlighted_column = self.board.get_highlighted_column(mouse::position(_ctx));
+            self.highlighted_column = self.board.get_highlighted_column(mouse_location(_ctx));
+            //In Pop 10, every move while the board is full is a pop
+            if let Some(ref state) = self.pop10 {
+                self.popping = state.phase == Pop10Phase::Popping;
+            }
             //Log ONLY switches between columns (otherwise lot of logs to console)
             if was_highlighted != self.highlighted_column {
                 println!("Mouse moved to col {}", self.highlighted_column);
@@ -784,94 +2196,101 @@ impl GameState {
         }
         self.reset_button.check_button_under_mouse(_ctx);
         self.main_menu_button.check_button_under_mouse(_ctx);
+        self.hint_button.check_button_under_mouse(_ctx);
+        self.analysis_button.check_button_under_mouse(_ctx);
+        self.review_button.check_button_under_mouse(_ctx);
     }
 
     ///Method active whenever the mouse is pressed down (if mouse is not intentionally disabled). Changes the highlighted_column
-    ///value based on mouse location, combined with mouse_button_up_event to form a click
-    pub fn mouse_button_down_event(
-        &mut self,
-        _ctx: &mut Context,
-        _button: MouseButton,
-        _x: f32,
-        _y: f32,
-    ) {
+    ///value based on mouse location, combined with mouse_button_up_event to form a click. Holding the right button marks
+    ///the bottom disc of the column to be popped in games with pops
+    fn mouse_button_down_event(&mut self, _ctx: &mut Context, _button: MouseButton) {
         if !self.mouse_disabled {
-            self.highlighted_column = self.board.get_highlighted_column(mouse::position(_ctx));
+            self.highlighted_column = self.board.get_highlighted_column(mouse_location(_ctx));
+            self.popping = _button == MouseButton::Right && self.board.config().allows_pops();
         }
         self.reset_button.check_button_under_mouse(_ctx);
         self.main_menu_button.check_button_under_mouse(_ctx);
+        self.hint_button.check_button_under_mouse(_ctx);
+        self.analysis_button.check_button_under_mouse(_ctx);
+        self.review_button.check_button_under_mouse(_ctx);
     }
 
     ///Method active whenever thea pressed mouse button is released (if mouse is not intentionally disabled). Changes the highlighted_column
-    ///value based on mouse location, combined with mouse_button_up_event to form a click
-    pub fn mouse_button_up_event(
-        &mut self,
-        _ctx: &mut Context,
-        _button: MouseButton,
-        _x: f32,
-        _y: f32,
-    ) -> bool {
+    ///value based on mouse location, combined with mouse_button_up_event to form a click. A right click pops the team's disc out of
+    ///the bottom of the column in games with pops. Returns what the main menu should do next
+    fn mouse_button_up_event(&mut self, _ctx: &mut Context, _button: MouseButton) -> GameAction {
         if !self.mouse_disabled {
             let was_highlighted = self.highlighted_column;
-            self.highlighted_column = self.board.get_highlighted_column(mouse::position(_ctx));
+            self.highlighted_column = self.board.get_highlighted_column(mouse_location(_ctx));
             //TODO: Originally intended to only click if column highlihgted on button down matches highlighted column on mouse up. However,
             //mouse move check automatically updates state, so this will always click. TBD if change will be made to address this
             if was_highlighted == self.highlighted_column && self.highlighted_column >= 0 {
-                self.mouse_disabled = true;
-                if self.board.insert(
-                    self.highlighted_column,
-                    self.turn_indicator.team,
-                    self.team_colors[self.turn_indicator.team as usize],
-                ) {
-                    println!(
-                        "Team {} drops token in col {}",
-                        self.turn_indicator.team, self.highlighted_column
-                    );
-                    //game state check
-                    let runs = self.board.get_runs_from_point(
-                        GridPosition::new(
-                            self.highlighted_column,
-                            self.board
-                                .get_column_height(self.highlighted_column as usize)
-                                as i32
-                                - 1,
-                        ),
-                        self.turn_indicator.team,
-                    );
-                    if runs[3] > 0 {
-                        //Four Connected - Proceed to Gameover - Win/Loss state
-                        println!(
-                            "4 Connected for player {}; Game ends",
-                            self.turn_indicator.team
-                        );
-                        self.gameover = true;
-                        self.turn_indicator.game_ends();
-                    } else {
-                        self.turn_indicator.team = self.turn_indicator.team % 2 + 1; //Change to other team's turn
-                    }
-                }
-                if !self.gameover {
-                    self.mouse_disabled = false;
+                let col = self.highlighted_column;
+                let allowed = match self.allowed_columns {
+                    Some(ref columns) => columns.contains(&col),
+                    None => true,
+                };
+                let pop = _button == MouseButton::Right && self.board.config().allows_pops();
+                if allowed {
+                    self.make_move(col, pop);
+                } else {
+                    self.sound_queue.push(SoundEffect::InvalidMove);
                 }
             }
+            self.popping = false;
+        }
+        //Check hint button
+        if self.hint_button.check_button_under_mouse(_ctx) {
+            self.sound_queue.push(SoundEffect::Click);
+            self.show_hint();
+        }
+        //Check analysis button
+        if self.analysis_button.check_button_under_mouse(_ctx) {
+            self.sound_queue.push(SoundEffect::Click);
+            self.toggle_analysis();
         }
         //Check reset button
         if self.reset_button.check_button_under_mouse(_ctx) {
             println!("Reset button pressed; Board reset");
-            self.board.reset();
-            self.turn_indicator.reset();
-            self.turn_indicator.change_team(1);
-            self.gameover = false;
-            self.mouse_disabled = false;
+            self.sound_queue.push(SoundEffect::Click);
+            self.reset_game();
         }
         //Check main menu button
         if self.main_menu_button.check_button_under_mouse(_ctx) {
             println!("Main Menu Button pressed; Main Menu should pop up");
-            true
+            self.sound_queue.push(SoundEffect::Click);
+            GameAction::MainMenu
+        } else if self.review_button.check_button_under_mouse(_ctx) {
+            println!("Review button pressed; reviewing game");
+            self.sound_queue.push(SoundEffect::Click);
+            GameAction::Review
         } else {
-            false
+            GameAction::None
+        }
+    }
+
+    ///Method active whenever a key is pressed. The A key toggles the analysis overlay
+    fn key_down_event(&mut self, keycode: KeyCode) {
+        if keycode == KeyCode::A && self.analysis_button.active {
+            self.sound_queue.push(SoundEffect::Click);
+            self.toggle_analysis();
         }
     }
+
+    ///Method to set up the review screen for this game's board, then load its moves, names and colors
+    fn start_review(&self, review: &mut ReviewScreen) {
+        *review = ReviewScreen::new(self.board.config());
+        review.load(&self.moves, &self.team_names, &self.team_colors);
+    }
+
+    fn drain_sounds(&mut self) -> Vec<SoundEffect> {
+        self.sound_queue.drain(..).collect()
+    }
+
+    fn take_finished_match(&mut self) -> Option<MatchRecord> {
+        self.finished_match.take()
+    }
 }
 
 #[cfg(test)]
@@ -1283,6 +2702,303 @@ mod core_tests {
                     [0, 2, 4, 0]
                 );
             }
+
+            #[test]
+            fn should_count_up_to_connect_length() {
+                let mut board =
+                    Board::with_config(GridPosition::new(0, 0), BoardConfig::new(5, 4, 3));
+                board.insert(0, 1, MyColor::White);
+                board.insert(1, 1, MyColor::White);
+                let runs = board.get_runs_from_point(GridPosition::new(2, 0), 1);
+                assert_eq!(runs.len(), 3);
+                assert_eq!(runs[2], 1);
+            }
+        }
+
+        mod connects {
+            use super::*;
+
+            #[test]
+            fn should_need_connect_length_discs() {
+                let mut board =
+                    Board::with_config(GridPosition::new(0, 0), BoardConfig::new(9, 7, 5));
+                for col in 0..3 {
+                    board.insert(col, 1, MyColor::White);
+                }
+                assert!(!board.connects(GridPosition::new(3, 0), 1));
+                board.insert(3, 1, MyColor::White);
+                assert!(board.connects(GridPosition::new(4, 0), 1));
+                assert!(!board.connects(GridPosition::new(4, 0), 2));
+            }
+        }
+
+        mod is_full {
+            use super::*;
+
+            #[test]
+            fn should_check_every_column() {
+                let mut board =
+                    Board::with_config(GridPosition::new(0, 0), BoardConfig::new(4, 4, 3));
+                for col in 0..4 {
+                    for _ in 0..4 {
+                        assert!(!board.is_full());
+                        board.insert(col, 1, MyColor::White);
+                    }
+                }
+                assert!(board.is_full());
+                assert!(!board.insert(2, 1, MyColor::White));
+            }
+        }
+
+        mod with_config {
+            use super::*;
+
+            #[test]
+            fn should_prefill_outer_columns_for_five_in_a_row() {
+                let config = BoardConfig::standard()
+                    .with_variant(Variant::FiveInARow)
+                    .played();
+                let board = Board::with_config(GridPosition::new(0, 0), config);
+                assert_eq!(board.column_count(), 9);
+                assert_eq!(board.get_column_height(0), 6);
+                assert_eq!(board.get_column_height(8), 6);
+                assert_eq!(board.get_column_height(4), 0);
+                assert_eq!(board.get_cell_team(GridPosition::new(0, 0)), 1);
+                assert_eq!(board.get_cell_team(GridPosition::new(0, 1)), 2);
+                assert_eq!(board.get_cell_team(GridPosition::new(8, 0)), 2);
+            }
+        }
+
+        mod pop {
+            use super::*;
+
+            fn create_pop_out_board(board: Vec<Vec<i32>>) -> Board {
+                let config = BoardConfig::standard().with_variant(Variant::PopOut);
+                let mut output = Board::with_config(GridPosition::new(0, 0), config);
+                for (i, col) in board.iter().enumerate() {
+                    for val in col {
+                        output.insert(i as i32, *val, MyColor::White);
+                    }
+                }
+                output
+            }
+
+            #[test]
+            fn should_shift_column_down() {
+                let mut board = create_pop_out_board(vec![vec![1, 2, 1]]);
+                assert!(board.pop(0, 1));
+                assert_eq!(board.get_column_height(0), 2);
+                assert_eq!(board.get_cell_team(GridPosition::new(0, 0)), 2);
+                assert_eq!(board.get_cell_team(GridPosition::new(0, 1)), 1);
+                assert_eq!(board.get_cell_team(GridPosition::new(0, 2)), 0);
+            }
+
+            #[test]
+            fn should_only_pop_own_bottom_disc() {
+                let mut board = create_pop_out_board(vec![vec![1, 2], vec![]]);
+                assert!(!board.pop(0, 2));
+                assert!(!board.pop(1, 1));
+                let mut standard = create_test_board(vec![vec![1, 2]]);
+                assert!(!standard.pop(0, 1));
+                assert_eq!(standard.get_column_height(0), 2);
+            }
+
+            #[test]
+            fn should_find_runs_for_both_teams_after_pop() {
+                //Popping the 1 at the bottom of column 3 drops a 2 and a 1 into the bottom row
+                let mut board =
+                    create_pop_out_board(vec![vec![2], vec![2, 1], vec![2, 1], vec![1, 2, 1]]);
+                assert!(board.pop(3, 1));
+                assert!(board.column_connects(3, 2));
+                assert!(!board.column_connects(3, 1));
+            }
+        }
+
+        mod legal_moves {
+            use super::*;
+
+            #[test]
+            fn should_add_pops_for_own_bottom_discs() {
+                let config = BoardConfig::new(4, 4, 3).with_variant(Variant::PopOut);
+                let mut board = Board::with_config(GridPosition::new(0, 0), config);
+                board.insert(1, 1, MyColor::White);
+                board.insert(2, 2, MyColor::White);
+                for _ in 0..4 {
+                    board.insert(3, 1, MyColor::White);
+                }
+                assert_eq!(
+                    board.legal_moves(1),
+                    vec![0, 1, 2, pop_move(1), pop_move(3)]
+                );
+                assert_eq!(board.legal_moves(2), vec![0, 1, 2, pop_move(2)]);
+            }
+
+            #[test]
+            fn should_only_drop_in_standard_games() {
+                let board = create_test_board(vec![vec![1, 1, 1, 1, 1, 1], vec![2]]);
+                assert_eq!(board.legal_moves(1), vec![1, 2, 3, 4, 5, 6]);
+            }
+        }
+
+        mod play_move {
+            use super::*;
+
+            #[test]
+            fn should_decode_drops_and_pops() {
+                assert_eq!(decode_move(pop_move(0)), (0, true));
+                assert_eq!(decode_move(pop_move(6)), (6, true));
+                assert_eq!(decode_move(6), (6, false));
+                let config = BoardConfig::standard().with_variant(Variant::PopOut);
+                let mut board = Board::with_config(GridPosition::new(0, 0), config);
+                assert!(board.play_move(2, 1, MyColor::White));
+                assert!(!board.play_move(pop_move(2), 2, MyColor::White));
+                assert!(board.play_move(pop_move(2), 1, MyColor::White));
+                assert_eq!(board.get_column_height(2), 0);
+            }
+        }
+    }
+
+    mod board_config {
+        use super::*;
+
+        #[test]
+        fn should_clamp_size_and_connect_length() {
+            assert_eq!(
+                BoardConfig::new(2, 20, 9).clamped(),
+                BoardConfig::new(4, 10, 6)
+            );
+            assert_eq!(
+                BoardConfig::new(4, 4, 6).clamped(),
+                BoardConfig::new(4, 4, 4)
+            );
+            assert_eq!(BoardConfig::standard().clamped(), BoardConfig::standard());
+        }
+
+        #[test]
+        fn should_label_variants() {
+            assert_eq!(BoardConfig::standard().label(), "Connect 4");
+            assert_eq!(BoardConfig::new(9, 7, 5).label(), "Connect 5 (9x7)");
+            let pop_out = BoardConfig::standard().with_variant(Variant::PopOut);
+            assert_eq!(pop_out.label(), "PopOut Connect 4");
+            assert_eq!(pop_out.clamped(), pop_out);
+            let pop10 = BoardConfig::new(5, 4, 3).with_variant(Variant::Pop10);
+            assert_eq!(pop10.label(), "Pop 10");
+        }
+
+        #[test]
+        fn should_rotate_through_every_team() {
+            let config = BoardConfig::standard().with_teams(3);
+            assert_eq!(config.next_team(1), 2);
+            assert_eq!(config.next_team(3), 1);
+            assert_eq!(BoardConfig::standard().next_team(2), 1);
+            assert_eq!(BoardConfig::standard().with_teams(9).clamped().teams, 4);
+        }
+
+        #[test]
+        fn should_grow_board_for_more_players() {
+            let config = BoardConfig::new(5, 10, 4).with_teams(3);
+            assert_eq!(config.played(), BoardConfig::new(7, 10, 4).with_teams(3));
+            assert_eq!(config.played().label(), "Connect 4 (7x10), 3 players");
+            let five = config.with_variant(Variant::FiveInARow).played();
+            assert_eq!(five.teams, 2);
+        }
+
+        #[test]
+        fn should_fix_board_for_variant_rules() {
+            let small = BoardConfig::new(5, 4, 3);
+            assert_eq!(small.played(), small);
+            assert_eq!(
+                small.with_variant(Variant::FiveInARow).played(),
+                BoardConfig::new(6, 9, 5).with_variant(Variant::FiveInARow)
+            );
+            assert_eq!(
+                small.with_variant(Variant::Pop10).played(),
+                BoardConfig::standard().with_variant(Variant::Pop10)
+            );
+        }
+
+        #[test]
+        fn should_fit_window_around_board() {
+            assert_eq!(BoardConfig::standard().screen_size(), SCREEN_SIZE);
+            //Small boards are centered in the standard window
+            let small = BoardConfig::new(5, 4, 3);
+            assert_eq!(small.screen_size(), SCREEN_SIZE);
+            let small_width = small.total_size().0;
+            assert_eq!(
+                small.board_position().x as f32 * 2.0 + small_width,
+                BOARD_TOTAL_SIZE.0 + 2.0 * BOARD_POS_OFFSET.0 as f32
+            );
+            //Large boards grow the window
+            let large = BoardConfig::new(9, 10, 5).screen_size();
+            assert!(large.0 > SCREEN_SIZE.0 && large.1 > SCREEN_SIZE.1);
+        }
+    }
+
+    mod game_state {
+        use super::*;
+
+        //Method to create a game on the given board with team 1 to move, skipping the Context needed to measure text
+        fn create_test_state(player_info: Vec<PlayerInfo>, board: Board) -> GameState {
+            let settings = Settings {
+                board: board.config(),
+                ..Settings::default()
+            };
+            let mut state = GameState::with_text_size((80.0, 20.0), player_info, &settings);
+            state.board = board;
+            state.turn_indicator.change_team(1);
+            state
+        }
+
+        mod show_hint {
+            use super::*;
+
+            #[test]
+            fn should_hint_the_winning_drop_and_count_it() {
+                let board = create_test_board(vec![vec![1, 2], vec![1, 2], vec![1, 2]]);
+                let mut state = create_test_state(PlayerInfo::defaults(), board);
+                state.show_hint();
+                assert_eq!(state.hint_column, 3);
+                assert!(!state.hint_pop);
+                assert_eq!(state.hints_used, vec![0, 1, 0]);
+                state.show_hint();
+                assert_eq!(state.hints_used, vec![0, 2, 0]);
+            }
+
+            #[test]
+            fn should_hint_a_winning_pop() {
+                //Popping the 1 at the bottom of column 0 drops a 1 next to the three 1s in the second row
+                let config = BoardConfig::standard().with_variant(Variant::PopOut);
+                let mut board = Board::with_config(GridPosition::new(0, 0), config);
+                for (i, col) in [vec![1, 2, 1], vec![2, 1], vec![2, 1], vec![1, 1]]
+                    .iter()
+                    .enumerate()
+                {
+                    for val in col {
+                        board.insert(i as i32, *val, MyColor::White);
+                    }
+                }
+                let mut state = create_test_state(PlayerInfo::defaults(), board);
+                state.show_hint();
+                assert_eq!(state.hint_column, 0);
+                assert!(state.hint_pop);
+                assert_eq!(state.hints_used, vec![0, 1, 0]);
+            }
+
+            #[test]
+            fn should_not_hint_for_the_ai_or_a_finished_game() {
+                let board = create_test_board(vec![vec![1, 2], vec![1, 2], vec![1, 2]]);
+                let mut players = PlayerInfo::defaults();
+                players[0] = players[0].clone().with_ai();
+                let mut state = create_test_state(players, board.clone());
+                state.show_hint();
+                assert_eq!(state.hint_column, -1);
+                assert_eq!(state.hints_used, vec![0, 0, 0]);
+                let mut state = create_test_state(PlayerInfo::defaults(), board);
+                state.gameover = true;
+                state.show_hint();
+                assert_eq!(state.hint_column, -1);
+                assert_eq!(state.hints_used, vec![0, 0, 0]);
+            }
         }
     }
 }
@@ -5,4 +5,11 @@
 
 pub mod ai;
 pub mod button;
+pub mod color_picker;
 pub mod core;
+pub mod pop10;
+pub mod puzzle;
+pub mod review;
+pub mod setup;
+pub mod text_input;
+pub mod tutorial;
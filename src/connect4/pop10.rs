@@ -0,0 +1,284 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+use connect4::core::{decode_move, pop_move, Board, GridPosition, MyColor};
+
+///Constant number of discs a player must collect to win a game of Pop 10
+pub const POP10_TARGET: u32 = 10;
+
+///
+/// Enum for the part of a Pop 10 turn the team to move is in
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Pop10Phase {
+    /// The board is being filled row by row, so discs may only be dropped into the lowest row with an empty cell
+    Filling,
+    /// The team pops one of their own discs out of the bottom row
+    Popping,
+    /// The team popped a disc that was not part of four in a row and must drop it back into a column
+    Returning,
+}
+
+impl Pop10Phase {
+    ///Returns the instructions drawn for the team to move
+    pub fn instructions(self) -> &'static str {
+        match self {
+            Pop10Phase::Filling => "Fill the board",
+            Pop10Phase::Popping => "Pop a disc",
+            Pop10Phase::Returning => "Drop it back",
+        }
+    }
+}
+
+///
+/// A struct tracking the rules of a Pop 10 game that are not part of the board itself. Players first take turns filling
+/// the board. After that, a player pops one of their own discs out of the bottom row on their turn. A disc that was
+/// part of four (or more) in a row of their color is collected and the player goes again; any other disc must be
+/// dropped back into a column, ending the turn. The first player to collect POP10_TARGET discs wins
+///
+/// # Fields
+/// * phase     = Pop10Phase enum for what the team to move does next
+/// * collected = Vector of disc counts, where collected[i] is the number of discs team i collected (index 0 is unused)
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Pop10State {
+    pub phase: Pop10Phase,
+    pub collected: Vec<u32>,
+}
+
+impl Default for Pop10State {
+    fn default() -> Self {
+        Pop10State::new()
+    }
+}
+
+impl Pop10State {
+    ///Constructor for the start of a game, with an empty board to fill and no discs collected
+    pub fn new() -> Self {
+        Pop10State {
+            phase: Pop10Phase::Filling,
+            collected: vec![0; 3],
+        }
+    }
+
+    ///Returns true if a disc popped by the team from the bottom of a column would be collected
+    pub fn collects(board: &Board, col: i32, team: i32) -> bool {
+        board.can_pop(col, team) && board.connects(GridPosition::new(col, 0), team)
+    }
+
+    ///
+    /// Method to list the moves the team to move may make, as column indexes for drops or pop_move(column) for pops
+    ///
+    /// # Arguments
+    /// * board = Board struct representing the current state of the board
+    /// * team  = Integer value (1 or 2) of the team to move
+    ///
+    pub fn legal_moves(&self, board: &Board, team: i32) -> Vec<i32> {
+        let columns = 0..board.column_count();
+        match self.phase {
+            Pop10Phase::Filling => {
+                let lowest = columns
+                    .clone()
+                    .map(|col| board.get_column_height(col as usize))
+                    .min()
+                    .unwrap_or(0);
+                columns
+                    .filter(|col| board.get_column_height(*col as usize) == lowest)
+                    .filter(|col| !board.is_column_full(*col as usize))
+                    .collect()
+            }
+            Pop10Phase::Popping => columns
+                .filter(|col| board.can_pop(*col, team))
+                .map(pop_move)
+                .collect(),
+            Pop10Phase::Returning => columns
+                .filter(|col| !board.is_column_full(*col as usize))
+                .collect(),
+        }
+    }
+
+    ///
+    /// Method to make a move for the team to move. Returns None if the move is not allowed, otherwise Some(true) if
+    /// the turn passes to the other team or Some(false) if the same team moves again
+    ///
+    /// # Arguments
+    /// * board = Board struct the move is made on
+    /// * mv    = Integer value of the move, a column index for a drop or pop_move(column) for a pop
+    /// * team  = Integer value (1 or 2) of the team to move
+    /// * color = MyColor of the team's discs
+    ///
+    pub fn play(&mut self, board: &mut Board, mv: i32, team: i32, color: MyColor) -> Option<bool> {
+        if !self.legal_moves(board, team).contains(&mv) {
+            return None;
+        }
+        let (col, _) = decode_move(mv);
+        match self.phase {
+            Pop10Phase::Filling => {
+                board.insert(col, team, color);
+                if board.is_full() {
+                    self.phase = Pop10Phase::Popping;
+                }
+                Some(true)
+            }
+            Pop10Phase::Returning => {
+                board.insert(col, team, color);
+                self.phase = Pop10Phase::Popping;
+                Some(true)
+            }
+            Pop10Phase::Popping => {
+                let collected = Pop10State::collects(board, col, team);
+                board.pop(col, team);
+                if collected {
+                    self.collected[team as usize] += 1;
+                } else {
+                    self.phase = Pop10Phase::Returning;
+                }
+                Some(false)
+            }
+        }
+    }
+
+    ///Returns the team that has collected POP10_TARGET discs, or 0 if neither has yet
+    pub fn winner(&self) -> i32 {
+        self.collected
+            .iter()
+            .position(|count| *count >= POP10_TARGET)
+            .map_or(0, |team| team as i32)
+    }
+}
+
+#[cfg(test)]
+mod pop10_tests {
+    use super::*;
+    use connect4::core::{BoardConfig, Variant};
+
+    //Method to create a full Pop 10 board from a set of columns, each listing its discs from the bottom up
+    fn create_full_board(board: Vec<Vec<i32>>) -> Board {
+        let config = BoardConfig::standard().with_variant(Variant::Pop10);
+        let mut output = Board::with_config(GridPosition::new(0, 0), config);
+        for (i, col) in board.iter().enumerate() {
+            for val in col {
+                output.insert(i as i32, *val, MyColor::White);
+            }
+        }
+        output
+    }
+
+    //Column patterns that fill a standard board without any four in a row
+    fn no_run_board() -> Board {
+        let a = vec![1, 1, 2, 2, 1, 1];
+        let b = vec![2, 2, 1, 1, 2, 2];
+        create_full_board(vec![
+            a.clone(),
+            b.clone(),
+            a.clone(),
+            b.clone(),
+            a.clone(),
+            b.clone(),
+            a,
+        ])
+    }
+
+    mod pop10_state {
+        use super::*;
+
+        mod legal_moves {
+            use super::*;
+
+            #[test]
+            fn should_fill_lowest_row_first() {
+                let config = BoardConfig::standard().with_variant(Variant::Pop10);
+                let mut board = Board::with_config(GridPosition::new(0, 0), config);
+                let mut state = Pop10State::new();
+                assert_eq!(state.legal_moves(&board, 1), vec![0, 1, 2, 3, 4, 5, 6]);
+                assert_eq!(state.play(&mut board, 3, 1, MyColor::White), Some(true));
+                assert_eq!(state.play(&mut board, 3, 2, MyColor::White), None);
+                assert_eq!(state.legal_moves(&board, 2), vec![0, 1, 2, 4, 5, 6]);
+            }
+
+            #[test]
+            fn should_pop_own_bottom_discs_once_full() {
+                let board = no_run_board();
+                let mut state = Pop10State::new();
+                state.phase = Pop10Phase::Popping;
+                assert_eq!(
+                    state.legal_moves(&board, 1),
+                    vec![pop_move(0), pop_move(2), pop_move(4), pop_move(6)]
+                );
+            }
+        }
+
+        mod play {
+            use super::*;
+
+            #[test]
+            fn should_start_popping_once_board_is_full() {
+                let config = BoardConfig::new(4, 4, 4).with_variant(Variant::Pop10);
+                let mut board = Board::with_config(GridPosition::new(0, 0), config);
+                let mut state = Pop10State::new();
+                let mut team = 1;
+                for _ in 0..16 {
+                    let mv = state.legal_moves(&board, team)[0];
+                    assert_eq!(state.play(&mut board, mv, team, MyColor::White), Some(true));
+                    team = 3 - team;
+                }
+                assert_eq!(state.phase, Pop10Phase::Popping);
+            }
+
+            #[test]
+            fn should_return_disc_not_in_a_run() {
+                let mut board = no_run_board();
+                let mut state = Pop10State::new();
+                state.phase = Pop10Phase::Popping;
+                assert_eq!(
+                    state.play(&mut board, pop_move(0), 1, MyColor::White),
+                    Some(false)
+                );
+                assert_eq!(state.phase, Pop10Phase::Returning);
+                assert_eq!(state.collected[1], 0);
+                assert_eq!(state.legal_moves(&board, 1), vec![0]);
+                assert_eq!(state.play(&mut board, 0, 1, MyColor::White), Some(true));
+                assert_eq!(state.phase, Pop10Phase::Popping);
+            }
+
+            #[test]
+            fn should_collect_disc_in_a_run_and_go_again() {
+                let a = vec![1, 2, 1, 2, 1, 2];
+                let mut board = create_full_board(vec![
+                    a.clone(),
+                    a.clone(),
+                    a.clone(),
+                    a.clone(),
+                    vec![2, 1, 2, 1, 2, 1],
+                    vec![2, 1, 2, 1, 2, 1],
+                    vec![2, 1, 2, 1, 2, 1],
+                ]);
+                let mut state = Pop10State::new();
+                state.phase = Pop10Phase::Popping;
+                assert!(Pop10State::collects(&board, 0, 1));
+                assert_eq!(
+                    state.play(&mut board, pop_move(0), 1, MyColor::White),
+                    Some(false)
+                );
+                assert_eq!(state.collected[1], 1);
+                assert_eq!(state.phase, Pop10Phase::Popping);
+                assert_eq!(board.get_column_height(0), 5);
+            }
+        }
+
+        mod winner {
+            use super::*;
+
+            #[test]
+            fn should_need_target_discs() {
+                let mut state = Pop10State::new();
+                state.collected[2] = POP10_TARGET - 1;
+                assert_eq!(state.winner(), 0);
+                state.collected[2] = POP10_TARGET;
+                assert_eq!(state.winner(), 2);
+            }
+        }
+    }
+}
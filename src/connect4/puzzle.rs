@@ -0,0 +1,783 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+extern crate rand;
+extern crate toml;
+
+use connect4::ai::{Evaluation, AI, MAX_DIFFICULTY};
+use connect4::button::{mouse_location, Button};
+use connect4::core::{
+    Board, GridPosition, MyColor, PlayerInfo, BOARD_DISC_RADIUS, BOARD_POS_OFFSET, BOARD_SIZE,
+    BOARD_TOTAL_SIZE,
+};
+use ggez::mint::Point2;
+use ggez::{filesystem, graphics, Context, GameResult};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use self::rand::{Rng, SeedableRng, StdRng};
+
+///Constant resource path of the puzzles that ship with the game
+const PUZZLES_RESOURCE_PATH: &str = "/puzzles.toml";
+
+///Constant name of the file inside the user data directory that generated puzzles are written to
+const GENERATED_PUZZLES_FILE_NAME: &str = "generated_puzzles.toml";
+
+///Constant name of the puzzle progress file inside the user data directory
+const PROGRESS_FILE_NAME: &str = "puzzle_progress.toml";
+
+///Constant difficulty of the AI players in the self-play games puzzles are mined from
+const GENERATOR_AI_DIFFICULTY: i32 = 3;
+
+///Constant number of random moves made at the start of each self-play game so the games are not all the same
+const GENERATOR_OPENING_MOVES: usize = 4;
+
+///Constant font size for the text above the board
+const PUZZLE_FONT_SIZE: f32 = 20.0;
+
+///Constant x location of the buttons right of the board
+const PUZZLE_PANEL_X: f32 = BOARD_TOTAL_SIZE.0 + (BOARD_POS_OFFSET.0 + 10) as f32;
+
+///Constant size of the buttons right of the board
+const PUZZLE_BUTTON_SIZE: (f32, f32) = (160.0, 36.0);
+
+///Constant vertical spacing between buttons right of the board
+const PUZZLE_BUTTON_SPACING: f32 = 44.0;
+
+///
+/// A struct describing a "win in N" puzzle
+///
+/// # Fields
+/// * moves   = Moves played to reach the puzzle position, as column numbers from 1 to 7 (e.g. "4453")
+/// * to_move = Team number (1 or 2) of the side to move, who must find the forced win
+/// * win_in  = Number of moves the side to move needs to force a win
+///
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Puzzle {
+    pub moves: String,
+    pub to_move: i32,
+    pub win_in: i32,
+}
+
+impl Puzzle {
+    ///Constructor - moves are the column indexes (0 to 6) played to reach the position
+    pub fn new(moves: &[i32], to_move: i32, win_in: i32) -> Self {
+        Puzzle {
+            moves: moves.iter().map(|col| (col + 1).to_string()).collect(),
+            to_move,
+            win_in,
+        }
+    }
+
+    ///
+    /// Method to build the puzzle position and check it with the solver. Returns the Board, or an error explaining why
+    /// the puzzle cannot be played: a bad move string, the wrong side to move, a finished game, or a forced win that
+    /// does not take exactly win_in moves
+    ///
+    /// # Arguments
+    /// * team_colors = Vector of MyColor objects for team i's discs (index 0 is unused)
+    ///
+    pub fn setup(&self, team_colors: &[MyColor]) -> Result<Board, String> {
+        let mut board = Board::new(BOARD_POS_OFFSET.into());
+        let mut team = 1;
+        for (i, c) in self.moves.chars().enumerate() {
+            let col = match c.to_digit(10) {
+                Some(d) if (1..=BOARD_SIZE.1 as u32).contains(&d) => d as i32 - 1,
+                _ => return Err(format!("'{}' is not a column", c)),
+            };
+            if board.is_column_full(col as usize) {
+                return Err(format!("Move {} is in a full column", i + 1));
+            }
+            let row = board.get_column_height(col as usize) as i32;
+            if board.connects(GridPosition::new(col, row), team) {
+                return Err(format!("Move {} ends the game", i + 1));
+            }
+            board.insert(col, team, team_colors[team as usize]);
+            team = team % 2 + 1;
+        }
+        if self.to_move != team {
+            return Err(format!(
+                "Team {} is to move, not team {}",
+                team, self.to_move
+            ));
+        }
+        if self.win_in < 1 {
+            return Err(String::from("Puzzles must be a win in 1 or more"));
+        }
+        match AI::new(team, MAX_DIFFICULTY).forced_win(&board, self.win_in) {
+            Some(n) if n == self.win_in => Ok(board),
+            Some(n) => Err(format!("There is a faster win in {}", n)),
+            None => Err(format!("There is no forced win in {}", self.win_in)),
+        }
+    }
+}
+
+///
+/// A struct holding a list of puzzles, read from TOML
+///
+/// # Fields
+/// * puzzles = Vector of Puzzles in the order they are played
+///
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct PuzzleSet {
+    #[serde(default)]
+    pub puzzles: Vec<Puzzle>,
+}
+
+impl PuzzleSet {
+    ///Returns the path of the generated puzzles file inside the user data directory
+    pub fn generated_path(ctx: &Context) -> PathBuf {
+        filesystem::user_data_dir(ctx).join(GENERATED_PUZZLES_FILE_NAME)
+    }
+
+    ///
+    /// Method to read a puzzle set from a TOML string
+    ///
+    /// # Arguments
+    /// * text = TOML contents of a puzzle file
+    ///
+    pub fn from_toml(text: &str) -> Result<PuzzleSet, String> {
+        toml::from_str::<PuzzleSet>(text).map_err(|e| e.to_string())
+    }
+
+    ///Method to write the puzzle set as a TOML string
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).unwrap_or_default()
+    }
+
+    ///
+    /// Method to load the puzzles that ship in the resources directory, followed by any generated puzzles in the user
+    /// data directory. Files that are missing or invalid are skipped, and puzzles are only kept once
+    ///
+    pub fn load(ctx: &mut Context) -> PuzzleSet {
+        let mut texts = Vec::new();
+        let mut resource_text = String::new();
+        match filesystem::open(ctx, PUZZLES_RESOURCE_PATH) {
+            Ok(mut file) => match file.read_to_string(&mut resource_text) {
+                Ok(_) => texts.push(resource_text),
+                Err(e) => println!("Error reading puzzles: {:?}", e),
+            },
+            Err(e) => println!("Error opening puzzles: {:?}", e),
+        }
+        if let Ok(text) = fs::read_to_string(PuzzleSet::generated_path(ctx)) {
+            texts.push(text);
+        }
+        let mut set = PuzzleSet::default();
+        for text in texts {
+            match PuzzleSet::from_toml(&text) {
+                Ok(loaded) => {
+                    for puzzle in loaded.puzzles {
+                        if !set.puzzles.iter().any(|p| p.moves == puzzle.moves) {
+                            set.puzzles.push(puzzle);
+                        }
+                    }
+                }
+                Err(e) => println!("Error reading puzzles: {}", e),
+            }
+        }
+        set
+    }
+
+    ///
+    /// Method to save the puzzle set to a file, creating its directory if needed
+    ///
+    /// # Arguments
+    /// * path = Location of the puzzle file
+    ///
+    pub fn save(&self, path: &Path) -> GameResult {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+}
+
+///
+/// Function to mine puzzles from self-play games. Each game starts from a random opening and is played by two AI
+/// players; the first position in a game where the side to move can force a win in 2 to max_win_in moves becomes a
+/// puzzle. Returns up to count puzzles, with no position repeated
+///
+/// # Arguments
+/// * count      = Number of puzzles to find
+/// * max_win_in = Highest number of moves a puzzle's forced win may take
+/// * seed       = Seed for the random openings, so results can be repeated
+///
+pub fn generate_puzzles(count: usize, max_win_in: i32, seed: usize) -> Vec<Puzzle> {
+    let mut rng: StdRng = SeedableRng::from_seed(&[seed][..]);
+    let mut puzzles: Vec<Puzzle> = Vec::new();
+    //Give up eventually in case few games produce puzzles
+    for _ in 0..count * 20 {
+        if puzzles.len() >= count {
+            break;
+        }
+        let players = [
+            AI::new(1, GENERATOR_AI_DIFFICULTY),
+            AI::new(2, GENERATOR_AI_DIFFICULTY),
+        ];
+        let mut board = Board::new(GridPosition::new(0, 0));
+        let mut moves = Vec::new();
+        let mut team = 1;
+        for turn in 0..(BOARD_SIZE.0 * BOARD_SIZE.1) as usize {
+            if turn >= GENERATOR_OPENING_MOVES {
+                let solver = AI::new(team, MAX_DIFFICULTY);
+                if let Some(n) = solver.forced_win(&board, max_win_in) {
+                    if n >= 2 {
+                        let puzzle = Puzzle::new(&moves, team, n);
+                        if !puzzles.iter().any(|p| p.moves == puzzle.moves) {
+                            println!("Found puzzle: {:?}", puzzle);
+                            puzzles.push(puzzle);
+                        }
+                    }
+                    break;
+                }
+            }
+            let col = if turn < GENERATOR_OPENING_MOVES {
+                rng.gen_range(0, BOARD_SIZE.1)
+            } else {
+                players[team as usize - 1].pick_optimal_move(board.clone(), None)
+            };
+            if col < 0 || !board.insert(col, team, MyColor::White) {
+                break;
+            }
+            moves.push(col);
+            let row = board.get_column_height(col as usize) as i32 - 1;
+            if board.connects(GridPosition::new(col, row), team) {
+                break;
+            }
+            team = team % 2 + 1;
+        }
+    }
+    puzzles
+}
+
+///
+/// Function to pick the defender's reply in a puzzle: the move that holds out the longest against the forced win
+///
+/// # Arguments
+/// * board = Board struct representing the current state of the board
+/// * team  = Integer value (1 or 2) of the defending team
+/// * depth = Integer value for how many moves (counting both teams) ahead the search goes
+///
+fn pick_defence(board: &Board, team: i32, depth: i32) -> i32 {
+    let ai = AI::new(team, depth);
+    let mut best_move = -1;
+    let mut best_score = -1;
+    for i in 0..BOARD_SIZE.1 {
+        if board.is_column_full(i as usize) {
+            continue;
+        }
+        //Slower losses are better, and anything that is not a forced loss is better still
+        let score = match ai.forced_result(board, i, depth) {
+            Some(Evaluation::LossIn(n)) => n,
+            _ => BOARD_SIZE.0 * BOARD_SIZE.1,
+        };
+        if score > best_score {
+            best_score = score;
+            best_move = i;
+        }
+    }
+    best_move
+}
+
+///
+/// A struct for how often a puzzle has been solved or failed
+///
+/// # Fields
+/// * moves  = Move string of the puzzle, which identifies it
+/// * solved = Number of attempts where the puzzle was solved
+/// * failed = Number of attempts where the puzzle was failed
+///
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PuzzleRecord {
+    pub moves: String,
+    pub solved: u32,
+    pub failed: u32,
+}
+
+///
+/// A struct holding the result of every puzzle attempt. Stored as TOML in the user data directory
+///
+/// # Fields
+/// * records = Vector of PuzzleRecords, one per puzzle attempted
+///
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct PuzzleProgress {
+    #[serde(default)]
+    pub records: Vec<PuzzleRecord>,
+}
+
+impl PuzzleProgress {
+    ///Returns the path of the puzzle progress file inside the user data directory
+    pub fn path(ctx: &Context) -> PathBuf {
+        filesystem::user_data_dir(ctx).join(PROGRESS_FILE_NAME)
+    }
+
+    ///
+    /// Method to load puzzle progress from a file. If the file is missing or invalid, no progress is returned
+    ///
+    /// # Arguments
+    /// * path = Location of the puzzle progress file
+    ///
+    pub fn load(path: &Path) -> PuzzleProgress {
+        match fs::read_to_string(path) {
+            Ok(text) => match toml::from_str::<PuzzleProgress>(&text) {
+                Ok(progress) => progress,
+                Err(e) => {
+                    println!("Error reading puzzle progress {:?}: {}", path, e);
+                    PuzzleProgress::default()
+                }
+            },
+            Err(_) => PuzzleProgress::default(),
+        }
+    }
+
+    ///
+    /// Method to save puzzle progress to a file, creating its directory if needed
+    ///
+    /// # Arguments
+    /// * path = Location of the puzzle progress file
+    ///
+    pub fn save(&self, path: &Path) -> GameResult {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(self).unwrap_or_default())?;
+        Ok(())
+    }
+
+    ///Returns the record for a puzzle, or None if it has not been attempted
+    pub fn get(&self, moves: &str) -> Option<&PuzzleRecord> {
+        self.records.iter().find(|r| r.moves == moves)
+    }
+
+    ///
+    /// Method to count an attempt at a puzzle
+    ///
+    /// # Arguments
+    /// * moves  = Move string of the puzzle
+    /// * solved = Boolean indicating if the puzzle was solved
+    ///
+    pub fn add_attempt(&mut self, moves: &str, solved: bool) {
+        let index = match self.records.iter().position(|r| r.moves == moves) {
+            Some(index) => index,
+            None => {
+                self.records.push(PuzzleRecord {
+                    moves: moves.to_string(),
+                    solved: 0,
+                    failed: 0,
+                });
+                self.records.len() - 1
+            }
+        };
+        if solved {
+            self.records[index].solved += 1;
+        } else {
+            self.records[index].failed += 1;
+        }
+    }
+
+    ///Returns the number of different puzzles that have been solved at least once
+    pub fn solved_count(&self) -> usize {
+        self.records.iter().filter(|r| r.solved > 0).count()
+    }
+}
+
+///
+/// Enum representing the state of the puzzle being played
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum PuzzleStatus {
+    Playing,
+    Solved,
+    Failed,
+    Invalid,
+}
+
+///
+/// A struct for the puzzle screen, where the player looks for the forced win in each puzzle against an AI defender
+///
+/// # Fields
+/// * puzzles            = Vector of Puzzles that can be played
+/// * progress           = PuzzleProgress counting each puzzle's solved and failed attempts, saved after every attempt
+/// * index              = Index of the puzzle being played
+/// * board              = Board struct holding the current position of the puzzle
+/// * team_colors        = Vector of MyColor objects for team i's discs, or the empty cell (for index 0)
+/// * moves_left         = Number of moves the player has left to force the win
+/// * status             = PuzzleStatus of the puzzle being played
+/// * message            = Text shown under the puzzle's goal, explaining the last move
+/// * highlighted_column = Integer from -1 to 6 representing column over which a disc is hovering (-1 means no column is being hovered)
+/// * prev_button        = Button drawn to go to the previous puzzle
+/// * next_button        = Button drawn to go to the next puzzle
+/// * retry_button       = Button drawn to start the puzzle again
+/// * back_button        = Button drawn to return to the main menu
+///
+pub struct PuzzleScreen {
+    puzzles: Vec<Puzzle>,
+    progress: PuzzleProgress,
+    index: usize,
+    board: Board,
+    team_colors: Vec<MyColor>,
+    moves_left: i32,
+    status: PuzzleStatus,
+    message: String,
+    highlighted_column: i32,
+    prev_button: Button,
+    next_button: Button,
+    retry_button: Button,
+    back_button: Button,
+}
+
+impl PuzzleScreen {
+    ///Constructor - puzzles and progress are loaded at startup, and screen_size is the size of the window the screen is drawn in
+    pub fn new(
+        puzzles: Vec<Puzzle>,
+        progress: PuzzleProgress,
+        screen_size: (f32, f32),
+    ) -> PuzzleScreen {
+        //Buttons down the panel right of the board, one per row
+        let panel_button = |title: &str, row: usize| {
+            let mut button = Button::new(
+                graphics::Text::new((title, graphics::Font::default(), PUZZLE_FONT_SIZE)),
+                graphics::Rect::new(
+                    PUZZLE_PANEL_X,
+                    10.0 + PUZZLE_BUTTON_SPACING * row as f32,
+                    PUZZLE_BUTTON_SIZE.0,
+                    PUZZLE_BUTTON_SIZE.1,
+                ),
+            );
+            button.set_colors(MyColor::Brown, MyColor::Green);
+            button
+        };
+        let mut back_button = Button::new(
+            graphics::Text::new(("Back", graphics::Font::default(), PUZZLE_FONT_SIZE)),
+            graphics::Rect::new(
+                PUZZLE_PANEL_X,
+                screen_size.1 - PUZZLE_BUTTON_SIZE.1 - 10.0,
+                PUZZLE_BUTTON_SIZE.0,
+                PUZZLE_BUTTON_SIZE.1,
+            ),
+        );
+        back_button.set_colors(MyColor::Red, MyColor::Green);
+        let mut team_colors = vec![MyColor::White];
+        for info in PlayerInfo::defaults() {
+            team_colors.push(info.color);
+        }
+        let mut screen = PuzzleScreen {
+            puzzles,
+            progress,
+            index: 0,
+            board: Board::new(BOARD_POS_OFFSET.into()),
+            team_colors,
+            moves_left: 0,
+            status: PuzzleStatus::Invalid,
+            message: String::new(),
+            highlighted_column: -1,
+            prev_button: panel_button("Prev", 0),
+            next_button: panel_button("Next", 1),
+            retry_button: panel_button("Retry", 2),
+            back_button,
+        };
+        screen.start_puzzle(0);
+        screen
+    }
+
+    ///Method to set up the puzzle at the given index, checking it with the solver first
+    fn start_puzzle(&mut self, index: usize) {
+        self.index = index;
+        self.highlighted_column = -1;
+        let puzzle = match self.puzzles.get(index) {
+            Some(puzzle) => puzzle.clone(),
+            None => {
+                self.status = PuzzleStatus::Invalid;
+                self.message = String::from("No puzzles found");
+                return;
+            }
+        };
+        match puzzle.setup(&self.team_colors) {
+            Ok(board) => {
+                self.board = board;
+                self.moves_left = puzzle.win_in;
+                self.status = PuzzleStatus::Playing;
+                self.message = String::from("Find the forced win");
+            }
+            Err(e) => {
+                println!("Invalid puzzle {:?}: {}", puzzle, e);
+                self.board = Board::new(BOARD_POS_OFFSET.into());
+                self.status = PuzzleStatus::Invalid;
+                self.message = format!("Invalid puzzle: {}", e);
+            }
+        }
+    }
+
+    ///
+    /// Method to end the current attempt, counting it in the puzzle progress and saving it
+    ///
+    /// # Arguments
+    /// * solved = Boolean indicating if the puzzle was solved
+    /// * path   = Location of the puzzle progress file
+    ///
+    fn finish_attempt(&mut self, solved: bool, path: &Path) {
+        self.status = if solved {
+            PuzzleStatus::Solved
+        } else {
+            PuzzleStatus::Failed
+        };
+        self.highlighted_column = -1;
+        let moves = self.puzzles[self.index].moves.clone();
+        self.progress.add_attempt(&moves, solved);
+        if let Err(e) = self.progress.save(path) {
+            println!("Error saving puzzle progress: {:?}", e);
+        }
+    }
+
+    ///
+    /// Method to play the player's move. Moves that keep the forced win are answered by the AI defender, and any other
+    /// move fails the puzzle
+    ///
+    /// # Arguments
+    /// * col  = Index of the column the disc is dropped in
+    /// * path = Location of the puzzle progress file
+    ///
+    fn play_move(&mut self, col: i32, path: &Path) {
+        if self.board.is_column_full(col as usize) {
+            self.message = String::from("That column is full");
+            return;
+        }
+        let team = self.puzzles[self.index].to_move;
+        let defender = team % 2 + 1;
+        let result =
+            AI::new(team, MAX_DIFFICULTY).forced_result(&self.board, col, 2 * self.moves_left - 1);
+        self.board
+            .insert(col, team, self.team_colors[team as usize]);
+        match result {
+            Some(Evaluation::WinIn(1)) => {
+                self.message = String::from("Solved! Four in a row");
+                self.finish_attempt(true, path);
+            }
+            Some(Evaluation::WinIn(_)) => {
+                self.moves_left -= 1;
+                let reply = pick_defence(&self.board, defender, 2 * self.moves_left);
+                if reply >= 0 {
+                    self.board
+                        .insert(reply, defender, self.team_colors[defender as usize]);
+                }
+                self.message = format!("Good move! The defender plays column {}", reply + 1);
+            }
+            _ => {
+                self.message = String::from("That move lets the defender escape");
+                self.finish_attempt(false, path);
+            }
+        }
+    }
+
+    ///Function to draw one line of text at the given location
+    fn draw_text(ctx: &mut Context, text: &str, x: f32, y: f32, color: MyColor) -> GameResult {
+        let text = graphics::Text::new((text, graphics::Font::default(), PUZZLE_FONT_SIZE));
+        graphics::draw(ctx, &text, (Point2 { x, y }, color.get_draw_color()))
+    }
+
+    ///Draw method to render the puzzle's goal and progress, the board and the buttons
+    pub fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let title = format!("Puzzle {} of {}", self.index + 1, self.puzzles.len());
+        let title = graphics::Text::new((title, graphics::Font::default(), 32.0));
+        graphics::draw(ctx, &title, (Point2 { x: 10.0, y: 10.0 },))?;
+
+        if let Some(puzzle) = self.puzzles.get(self.index) {
+            let goal = format!(
+                "{:?} to move: win in {}    Solved {} of {} puzzles",
+                self.team_colors[puzzle.to_move as usize],
+                puzzle.win_in,
+                self.progress.solved_count(),
+                self.puzzles.len()
+            );
+            PuzzleScreen::draw_text(ctx, &goal, 10.0, 55.0, MyColor::White)?;
+            if let Some(record) = self.progress.get(&puzzle.moves) {
+                let line = format!(
+                    "This puzzle: solved {}, failed {}",
+                    record.solved, record.failed
+                );
+                PuzzleScreen::draw_text(ctx, &line, 10.0, 115.0, MyColor::White)?;
+            }
+        }
+        let message_color = match self.status {
+            PuzzleStatus::Solved => MyColor::Green,
+            PuzzleStatus::Failed | PuzzleStatus::Invalid => MyColor::Red,
+            PuzzleStatus::Playing => MyColor::Yellow,
+        };
+        PuzzleScreen::draw_text(ctx, &self.message, 10.0, 85.0, message_color)?;
+
+        let mut mb = graphics::MeshBuilder::new();
+        if self.highlighted_column >= 0 {
+            let team = self.puzzles[self.index].to_move;
+            mb.circle(
+                graphics::DrawMode::fill(),
+                self.board.hover_center(self.highlighted_column as usize),
+                BOARD_DISC_RADIUS as f32,
+                2.0,
+                self.team_colors[team as usize].get_draw_color(),
+            );
+        }
+        self.board.draw(&mut mb);
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))?;
+
+        self.prev_button.draw(ctx)?;
+        self.next_button.draw(ctx)?;
+        self.retry_button.draw(ctx)?;
+        self.back_button.draw(ctx)
+    }
+
+    ///Method to update the hovering disc and highlighted state of the buttons if mouse moves
+    pub fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        if self.status == PuzzleStatus::Playing {
+            self.highlighted_column = self.board.get_highlighted_column(mouse_location(ctx));
+        }
+        self.prev_button.check_button_under_mouse(ctx);
+        self.next_button.check_button_under_mouse(ctx);
+        self.retry_button.check_button_under_mouse(ctx);
+        self.back_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method to handle a click on the puzzle screen. Plays a move or changes puzzle, and returns true if the back button was clicked
+    pub fn mouse_button_up_event(&mut self, ctx: &mut Context) -> bool {
+        if self.status == PuzzleStatus::Playing {
+            let col = self.board.get_highlighted_column(mouse_location(ctx));
+            if col >= 0 {
+                self.play_move(col, &PuzzleProgress::path(ctx));
+            }
+        }
+        if !self.puzzles.is_empty() {
+            if self.prev_button.check_button_under_mouse(ctx) {
+                let index = (self.index + self.puzzles.len() - 1) % self.puzzles.len();
+                self.start_puzzle(index);
+            }
+            if self.next_button.check_button_under_mouse(ctx) {
+                let index = (self.index + 1) % self.puzzles.len();
+                self.start_puzzle(index);
+            }
+            if self.retry_button.check_button_under_mouse(ctx) {
+                let index = self.index;
+                self.start_puzzle(index);
+            }
+        }
+        self.back_button.check_button_under_mouse(ctx)
+    }
+}
+
+#[cfg(test)]
+mod puzzle_tests {
+    use super::*;
+
+    fn test_colors() -> Vec<MyColor> {
+        vec![MyColor::White; 3]
+    }
+
+    mod puzzle {
+        use super::*;
+
+        mod setup {
+            use super::*;
+
+            #[test]
+            fn should_accept_exact_forced_win() {
+                //Team 1 has 2 and 3 on the bottom row, so dropping in 4 leaves two ways to make four
+                let puzzle = Puzzle {
+                    moves: String::from("2737"),
+                    to_move: 1,
+                    win_in: 2,
+                };
+                assert!(puzzle.setup(&test_colors()).is_ok());
+            }
+
+            #[test]
+            fn should_reject_wrong_target_or_side() {
+                let faster = Puzzle {
+                    moves: String::from("273741"),
+                    to_move: 1,
+                    win_in: 2,
+                };
+                assert_eq!(
+                    faster.setup(&test_colors()),
+                    Err(String::from("There is a faster win in 1"))
+                );
+                let wrong_side = Puzzle {
+                    moves: String::from("2737"),
+                    to_move: 2,
+                    win_in: 2,
+                };
+                assert!(wrong_side.setup(&test_colors()).is_err());
+            }
+
+            #[test]
+            fn should_reject_bad_move_strings() {
+                let puzzle = Puzzle {
+                    moves: String::from("48"),
+                    to_move: 1,
+                    win_in: 1,
+                };
+                assert_eq!(
+                    puzzle.setup(&test_colors()),
+                    Err(String::from("'8' is not a column"))
+                );
+            }
+        }
+    }
+
+    mod puzzle_set {
+        use super::*;
+
+        #[test]
+        fn should_round_trip_through_to_toml() {
+            let set = PuzzleSet {
+                puzzles: vec![Puzzle::new(&[1, 6, 2, 6], 1, 2)],
+            };
+            assert_eq!(set.puzzles[0].moves, "2737");
+            assert_eq!(PuzzleSet::from_toml(&set.to_toml()), Ok(set));
+        }
+
+        #[test]
+        fn should_ship_only_valid_puzzles() {
+            let set = PuzzleSet::from_toml(include_str!("../../resources/puzzles.toml")).unwrap();
+            assert!(!set.puzzles.is_empty());
+            for puzzle in set.puzzles {
+                assert_eq!(puzzle.setup(&test_colors()).err(), None, "{:?}", puzzle);
+            }
+        }
+    }
+
+    mod pick_defence {
+        use super::*;
+
+        #[test]
+        fn should_block_immediate_threat() {
+            let mut board = Board::new(GridPosition::new(0, 0));
+            for (i, col) in [3, 6, 3, 6, 3].iter().enumerate() {
+                board.insert(*col, (i % 2) as i32 + 1, MyColor::White);
+            }
+            //Team 1 has three stacked in column 3, so blocking it is the only move that does not lose right away
+            assert_eq!(pick_defence(&board, 2, 2), 3);
+        }
+    }
+
+    mod puzzle_progress {
+        use super::*;
+
+        #[test]
+        fn should_count_attempts_per_puzzle() {
+            let mut progress = PuzzleProgress::default();
+            progress.add_attempt("2737", false);
+            progress.add_attempt("2737", true);
+            progress.add_attempt("4444", false);
+            assert_eq!(
+                progress.get("2737").map(|r| (r.solved, r.failed)),
+                Some((1, 1))
+            );
+            assert_eq!(progress.solved_count(), 1);
+            assert_eq!(progress.get("1"), None);
+        }
+    }
+}
@@ -0,0 +1,477 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use connect4::ai::{Evaluation, AI, MAX_DIFFICULTY};
+use connect4::button::Button;
+use connect4::core::{decode_move, Board, BoardConfig, MyColor, BOARD_SIZE};
+use ggez::event::KeyCode;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+use replay::ReplayStepper;
+
+///Constant font size for the move and verdict text above the board
+const REVIEW_FONT_SIZE: f32 = 20.0;
+
+///Constant size of the buttons right of the board
+const REVIEW_BUTTON_SIZE: (f32, f32) = (160.0, 36.0);
+
+///Constant vertical spacing between buttons right of the board
+const REVIEW_BUTTON_SPACING: f32 = 44.0;
+
+///Constant y location of the first flagged move button
+const FLAGGED_BUTTONS_Y: f32 = 100.0;
+
+///Constant max number of flagged moves shown as buttons
+const MAX_FLAGGED_ROWS: usize = 8;
+
+///
+/// Enum representing the theoretical result of a position for the team about to move, as far as the AI can see
+///
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Outcome {
+    Loss,
+    Unclear,
+    Win,
+}
+
+impl Outcome {
+    ///Returns the outcome of a move from the AI's forced result for it: a forced win, a forced loss or neither
+    pub fn from_evaluation(eval: Option<Evaluation>) -> Outcome {
+        match eval {
+            Some(Evaluation::WinIn(_)) => Outcome::Win,
+            Some(Evaluation::LossIn(_)) => Outcome::Loss,
+            _ => Outcome::Unclear,
+        }
+    }
+}
+
+///
+/// Enum representing how a move changed the theoretical result. A mistake gives up a forced win, while a blunder walks
+/// into a forced loss that could have been avoided
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveQuality {
+    Good,
+    Mistake,
+    Blunder,
+}
+
+impl MoveQuality {
+    ///Returns the text shown for the move's quality
+    pub fn label(self) -> &'static str {
+        match self {
+            MoveQuality::Good => "Good",
+            MoveQuality::Mistake => "Mistake",
+            MoveQuality::Blunder => "Blunder",
+        }
+    }
+
+    ///Returns the color the move's quality is drawn in
+    pub fn color(self) -> MyColor {
+        match self {
+            MoveQuality::Good => MyColor::White,
+            MoveQuality::Mistake => MyColor::Orange,
+            MoveQuality::Blunder => MyColor::Red,
+        }
+    }
+}
+
+///
+/// A struct holding the review of one move in a finished game
+///
+/// # Fields
+/// * team    = Integer value (1 or 2) of the team that made the move
+/// * column  = Index of the column the disc was dropped in, or pop_move(column) if a disc was popped out of it
+/// * best    = Outcome of the best move the team could have made
+/// * played  = Outcome of the move the team made
+/// * quality = MoveQuality of the move, found by comparing best and played
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MoveReview {
+    pub team: i32,
+    pub column: i32,
+    pub best: Outcome,
+    pub played: Outcome,
+    pub quality: MoveQuality,
+}
+
+impl MoveReview {
+    ///Returns the explanation drawn under the move, e.g. "Blunder: lets the opponent force a win"
+    pub fn describe(&self) -> String {
+        let reason = match (self.quality, self.best) {
+            (MoveQuality::Good, Outcome::Win) => "keeps a forced win",
+            (MoveQuality::Good, Outcome::Loss) => "every move loses",
+            (MoveQuality::Good, _) => "no forced result given up",
+            (MoveQuality::Mistake, _) => "misses a forced win",
+            (MoveQuality::Blunder, Outcome::Win) => "throws away a forced win",
+            (MoveQuality::Blunder, _) => "lets the opponent force a win",
+        };
+        format!("{}: {}", self.quality.label(), reason)
+    }
+}
+
+///
+/// Function to review a move by comparing its outcome to the best outcome the team could have had
+///
+/// # Arguments
+/// * board  = Board struct representing the state of the board before the move
+/// * team   = Integer value (1 or 2) of the team making the move
+/// * column = Index of the column the disc was dropped in, or pop_move(column) for a pop
+/// * depth  = Integer value for how many moves (counting both teams) ahead the search goes
+///
+pub fn review_move(board: &Board, team: i32, column: i32, depth: i32) -> MoveReview {
+    let ai = AI::new(team, depth);
+    let mut best = Outcome::Loss;
+    let mut played = Outcome::Unclear;
+    for i in board.legal_moves(team) {
+        let outcome = Outcome::from_evaluation(ai.forced_result(board, i, depth));
+        best = best.max(outcome);
+        if i == column {
+            played = outcome;
+        }
+    }
+    let quality = if played >= best {
+        MoveQuality::Good
+    } else if played == Outcome::Loss {
+        MoveQuality::Blunder
+    } else {
+        MoveQuality::Mistake
+    };
+    MoveReview {
+        team,
+        column,
+        best,
+        played,
+        quality,
+    }
+}
+
+///
+/// A struct for the post-game review screen, which replays a finished game and flags the moves that changed its
+/// theoretical result. Moves are reviewed one per update by the strongest AI so the screen stays responsive
+///
+/// # Fields
+/// * config          = BoardConfig of the board the game was played on
+/// * panel_x         = x location of the buttons right of the board
+/// * moves           = Vector of moves of the game, in order, as column indexes for drops or pop_move(column) for pops
+/// * team_names      = Vector of player names for team i (index 0 is unused)
+/// * team_colors     = Vector of MyColor objects for team i's discs, or the empty cell (for index 0)
+/// * reviews         = Vector of MoveReviews, where reviews[i] is for moves[i]. Shorter than moves until reviewing is done
+/// * review_board    = Board struct holding the position before the next move to be reviewed
+/// * replay          = ReplayStepper tracking the number of moves shown on the replay board
+/// * flagged_buttons = Vector of (move index, Button) pairs to jump to each mistake or blunder
+/// * prev_button     = Button drawn to step the replay back one move
+/// * next_button     = Button drawn to step the replay forward one move
+/// * back_button     = Button drawn to return to the game screen
+///
+pub struct ReviewScreen {
+    config: BoardConfig,
+    panel_x: f32,
+    moves: Vec<i32>,
+    team_names: Vec<String>,
+    team_colors: Vec<MyColor>,
+    reviews: Vec<MoveReview>,
+    review_board: Board,
+    replay: ReplayStepper,
+    flagged_buttons: Vec<(usize, Button)>,
+    prev_button: Button,
+    next_button: Button,
+    back_button: Button,
+}
+
+impl ReviewScreen {
+    ///Constructor - config is the board the reviewed game is played on, which sets the size of the window
+    pub fn new(config: BoardConfig) -> ReviewScreen {
+        let screen_size = config.screen_size();
+        let panel_x = config.side_panel_x();
+        let half_width = (REVIEW_BUTTON_SIZE.0 - 10.0) / 2.0;
+        let mut prev_button = Button::new(
+            graphics::Text::new(("Prev", graphics::Font::default(), REVIEW_FONT_SIZE)),
+            graphics::Rect::new(panel_x, 10.0, half_width, REVIEW_BUTTON_SIZE.1),
+        );
+        let mut next_button = Button::new(
+            graphics::Text::new(("Next", graphics::Font::default(), REVIEW_FONT_SIZE)),
+            graphics::Rect::new(
+                panel_x + half_width + 10.0,
+                10.0,
+                half_width,
+                REVIEW_BUTTON_SIZE.1,
+            ),
+        );
+        let mut back_button = Button::new(
+            graphics::Text::new(("Back", graphics::Font::default(), REVIEW_FONT_SIZE)),
+            graphics::Rect::new(
+                panel_x,
+                screen_size.1 - REVIEW_BUTTON_SIZE.1 - 10.0,
+                REVIEW_BUTTON_SIZE.0,
+                REVIEW_BUTTON_SIZE.1,
+            ),
+        );
+        prev_button.set_colors(MyColor::Brown, MyColor::Green);
+        next_button.set_colors(MyColor::Brown, MyColor::Green);
+        back_button.set_colors(MyColor::Red, MyColor::Green);
+        ReviewScreen {
+            config,
+            panel_x,
+            moves: Vec::new(),
+            team_names: Vec::new(),
+            team_colors: Vec::new(),
+            reviews: Vec::new(),
+            review_board: Board::with_config(config.board_position(), config),
+            replay: ReplayStepper::default(),
+            flagged_buttons: Vec::new(),
+            prev_button,
+            next_button,
+            back_button,
+        }
+    }
+
+    ///
+    /// Method to start reviewing a finished game, called when the screen is opened. The replay starts at the end of
+    /// the game
+    ///
+    /// # Arguments
+    /// * moves       = Vector of moves of the game, in order, as column indexes or pop_move(column) for pops
+    /// * team_names  = Vector of player names for team i (index 0 is unused)
+    /// * team_colors = Vector of MyColor objects for team i's discs, or the empty cell (for index 0)
+    ///
+    pub fn load(&mut self, moves: &[i32], team_names: &[String], team_colors: &[MyColor]) {
+        self.moves = moves.to_vec();
+        self.team_names = team_names.to_vec();
+        self.team_colors = team_colors.to_vec();
+        self.reviews.clear();
+        self.review_board = Board::with_config(self.config.board_position(), self.config);
+        self.replay = ReplayStepper::new(moves.len());
+        self.flagged_buttons.clear();
+    }
+
+    ///Returns the team that makes the move at the given index; team 1 always moves first
+    fn team_for_move(index: usize) -> i32 {
+        (index % 2) as i32 + 1
+    }
+
+    ///Returns a Board showing the game after the given number of moves
+    fn board_at(&self, position: usize) -> Board {
+        let mut board = Board::with_config(self.config.board_position(), self.config);
+        board.set_team_colors(&self.team_colors);
+        for (i, mv) in self.moves.iter().take(position).enumerate() {
+            let team = ReviewScreen::team_for_move(i);
+            board.play_move(*mv, team, self.team_colors[team as usize]);
+        }
+        board
+    }
+
+    ///Returns how deep the strongest AI searches each move. Boards wider than the standard board search one move less
+    ///so each move is still reviewed in about a second
+    fn review_depth(&self) -> i32 {
+        if self.config.columns > BOARD_SIZE.1 {
+            MAX_DIFFICULTY - 1
+        } else {
+            MAX_DIFFICULTY
+        }
+    }
+
+    ///Update method - reviews the next move of the game (if any are left), adding a button for it if it is flagged
+    pub fn update(&mut self) {
+        let index = self.reviews.len();
+        if index >= self.moves.len() {
+            return;
+        }
+        let team = ReviewScreen::team_for_move(index);
+        let mv = self.moves[index];
+        let review = review_move(&self.review_board, team, mv, self.review_depth());
+        self.review_board
+            .play_move(mv, team, self.team_colors[team as usize]);
+        self.reviews.push(review);
+        if review.quality != MoveQuality::Good && self.flagged_buttons.len() < MAX_FLAGGED_ROWS {
+            let label = format!("{}. {}", index + 1, review.quality.label());
+            let mut button = Button::new(
+                graphics::Text::new((label, graphics::Font::default(), REVIEW_FONT_SIZE)),
+                graphics::Rect::new(
+                    self.panel_x,
+                    FLAGGED_BUTTONS_Y + REVIEW_BUTTON_SPACING * self.flagged_buttons.len() as f32,
+                    REVIEW_BUTTON_SIZE.0,
+                    REVIEW_BUTTON_SIZE.1,
+                ),
+            );
+            button.set_colors(review.quality.color(), MyColor::Green);
+            self.flagged_buttons.push((index, button));
+        }
+    }
+
+    ///Function to draw one line of text at the given location
+    fn draw_text(ctx: &mut Context, text: &str, x: f32, y: f32, color: MyColor) -> GameResult {
+        let text = graphics::Text::new((text, graphics::Font::default(), REVIEW_FONT_SIZE));
+        graphics::draw(ctx, &text, (Point2 { x, y }, color.get_draw_color()))
+    }
+
+    ///Draw method to render the replay board, the move shown and its review, and the buttons
+    pub fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let title = graphics::Text::new(("Game Review", graphics::Font::default(), 32.0));
+        graphics::draw(ctx, &title, (Point2 { x: 10.0, y: 10.0 },))?;
+
+        //Progress while moves are still being reviewed, then a count of the flagged moves
+        let status = if self.reviews.len() < self.moves.len() {
+            format!(
+                "Reviewing move {} of {}...",
+                self.reviews.len() + 1,
+                self.moves.len()
+            )
+        } else {
+            let count = |quality| self.reviews.iter().filter(|r| r.quality == quality).count();
+            format!(
+                "Blunders: {}    Mistakes: {}",
+                count(MoveQuality::Blunder),
+                count(MoveQuality::Mistake)
+            )
+        };
+        ReviewScreen::draw_text(ctx, &status, 10.0, 55.0, MyColor::White)?;
+
+        //The last move shown on the board, and its review once it is ready
+        if self.replay.position() == 0 {
+            ReviewScreen::draw_text(ctx, "Start of the game", 10.0, 85.0, MyColor::White)?;
+        } else {
+            let index = self.replay.position() - 1;
+            let team = ReviewScreen::team_for_move(index);
+            let (col, pop) = decode_move(self.moves[index]);
+            let line = format!(
+                "Move {} of {}: {} {} column {}",
+                self.replay.position(),
+                self.moves.len(),
+                self.team_names[team as usize],
+                if pop { "pops from" } else { "drops in" },
+                col + 1
+            );
+            ReviewScreen::draw_text(ctx, &line, 10.0, 85.0, MyColor::White)?;
+            if let Some(review) = self.reviews.get(index) {
+                ReviewScreen::draw_text(
+                    ctx,
+                    &review.describe(),
+                    10.0,
+                    115.0,
+                    review.quality.color(),
+                )?;
+            }
+        }
+
+        let mut mb = graphics::MeshBuilder::new();
+        self.board_at(self.replay.position()).draw(&mut mb);
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))?;
+
+        self.prev_button.draw(ctx)?;
+        self.next_button.draw(ctx)?;
+        if !self.flagged_buttons.is_empty() {
+            ReviewScreen::draw_text(
+                ctx,
+                "Flagged moves",
+                self.panel_x,
+                FLAGGED_BUTTONS_Y - 30.0,
+                MyColor::Yellow,
+            )?;
+        }
+        for (_, button) in &self.flagged_buttons {
+            button.draw(ctx)?;
+        }
+        self.back_button.draw(ctx)
+    }
+
+    ///Method to update highlighted state of the buttons if mouse moves
+    pub fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        self.prev_button.check_button_under_mouse(ctx);
+        self.next_button.check_button_under_mouse(ctx);
+        self.back_button.check_button_under_mouse(ctx);
+        for (_, button) in &mut self.flagged_buttons {
+            button.check_button_under_mouse(ctx);
+        }
+    }
+
+    ///Method to handle a click on the review screen. Steps or jumps the replay and returns true if the back button was clicked
+    pub fn mouse_button_up_event(&mut self, ctx: &mut Context) -> bool {
+        if self.prev_button.check_button_under_mouse(ctx) {
+            self.replay.back();
+        }
+        if self.next_button.check_button_under_mouse(ctx) {
+            self.replay.forward();
+        }
+        let mut jump = None;
+        for (index, button) in &mut self.flagged_buttons {
+            if button.check_button_under_mouse(ctx) {
+                //Show the position right after the flagged move
+                jump = Some(*index + 1);
+            }
+        }
+        if let Some(position) = jump {
+            self.replay.show(position);
+        }
+        self.back_button.check_button_under_mouse(ctx)
+    }
+
+    ///Method active whenever a key is pressed. The arrow keys step through the replay, Home and End jump to either end
+    pub fn key_down_event(&mut self, keycode: KeyCode) {
+        self.replay.key_down_event(keycode);
+    }
+}
+
+#[cfg(test)]
+mod review_tests {
+    use super::*;
+    use connect4::core::GridPosition;
+    //Method to create a board state from a set of vectors, where 0 is empty and 1 or 2 team tokens
+    //Note that input is board[column][row], so if you want to add a team 1 token in column 4, row 0, then
+    //the board input should have board[4][0] = 1
+    fn create_test_board(board: Vec<Vec<i32>>) -> Board {
+        let mut output = Board::new(GridPosition { x: 0, y: 0 });
+        for (i, col) in board.iter().enumerate() {
+            for val in col {
+                if *val > 0 {
+                    output.insert(i as i32, *val, MyColor::White);
+                }
+            }
+        }
+        output
+    }
+
+    mod review_move {
+        use super::*;
+
+        #[test]
+        fn should_flag_blunder_if_threat_not_blocked() {
+            let data = vec![vec![2, 2, 2], vec![1]];
+            let review = review_move(&create_test_board(data), 1, 4, 2);
+            assert_eq!(review.best, Outcome::Unclear);
+            assert_eq!(review.played, Outcome::Loss);
+            assert_eq!(review.quality, MoveQuality::Blunder);
+        }
+
+        #[test]
+        fn should_flag_mistake_if_win_missed() {
+            let data = vec![vec![], vec![1, 1, 1], vec![2, 2]];
+            let review = review_move(&create_test_board(data), 1, 6, 2);
+            assert_eq!(review.best, Outcome::Win);
+            assert_eq!(review.played, Outcome::Unclear);
+            assert_eq!(review.quality, MoveQuality::Mistake);
+        }
+
+        #[test]
+        fn should_accept_winning_and_blocking_moves() {
+            let data = vec![vec![], vec![1, 1, 1], vec![2, 2]];
+            let review = review_move(&create_test_board(data), 1, 1, 2);
+            assert_eq!(review.quality, MoveQuality::Good);
+            let data = vec![vec![2, 2, 2], vec![1]];
+            let review = review_move(&create_test_board(data), 1, 0, 2);
+            assert_eq!(review.quality, MoveQuality::Good);
+        }
+
+        #[test]
+        fn should_not_flag_moves_in_lost_positions() {
+            let data = vec![vec![], vec![2, 2, 2], vec![], vec![2, 2, 2], vec![1, 1]];
+            let review = review_move(&create_test_board(data), 1, 6, 2);
+            assert_eq!(review.best, Outcome::Loss);
+            assert_eq!(review.quality, MoveQuality::Good);
+        }
+    }
+}
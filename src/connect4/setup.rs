@@ -0,0 +1,333 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use connect4::button::{Button, BUTTON_PADDING};
+use connect4::color_picker::{ColorPicker, SWATCH_SIZE};
+use connect4::core::{MyColor, PlayerInfo};
+use connect4::text_input::TextInput;
+use ggez::event::KeyCode;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+
+///Constant for the max number of characters in a player name
+const MAX_NAME_LENGTH: usize = 16;
+
+///Constant font size for labels and text inputs on the setup screen
+const SETUP_FONT_SIZE: f32 = 32.0;
+
+///Constant location of the first player row; each later row is up to SETUP_ROW_SPACING below the previous one
+const SETUP_ROW_OFFSET: (f32, f32) = (50.0, 110.0);
+
+///Constant vertical spacing between player rows, if there is room for it
+const SETUP_ROW_SPACING: f32 = 140.0;
+
+///Constant font size for the buttons that switch a player between a human and the AI
+const AI_TOGGLE_FONT_SIZE: f32 = 24.0;
+
+///Constant width of the name text inputs
+const NAME_INPUT_WIDTH: f32 = 360.0;
+
+///
+/// Enum representing what the main menu should do after a click on the setup screen
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SetupAction {
+    None,
+    Back,
+    Play,
+}
+
+///
+/// A struct for the pre-game screen where each player enters a name, picks a disc color and is set as a human or AI
+///
+/// # Fields
+/// * ai_buttons    = Vector of Buttons where ai_buttons[i] is selected if team i + 1 is played by the AI
+/// * name_inputs   = Vector of TextInputs where name_inputs[i] holds the name for team i + 1
+/// * color_pickers = Vector of ColorPickers where color_pickers[i] holds the disc color for team i + 1
+/// * back_button   = Button drawn to go back to the main menu
+/// * play_button   = Button drawn to start the game with the entered names and colors
+/// * message       = Text shown under the player rows, used to explain why the game cannot start
+/// * row_spacing   = Vertical spacing between player rows, smaller than SETUP_ROW_SPACING if needed to fit every team
+///
+pub struct PlayerSetup {
+    ai_buttons: Vec<Button>,
+    name_inputs: Vec<TextInput>,
+    color_pickers: Vec<ColorPicker>,
+    pub back_button: Button,
+    pub play_button: Button,
+    message: String,
+    row_spacing: f32,
+}
+
+///Returns the name a player starts with: "AI N" for the AI, otherwise the default name for the team
+fn default_name(team: usize, ai: bool) -> String {
+    if ai {
+        format!("AI {}", team)
+    } else {
+        PlayerInfo::defaults()[team - 1].name.clone()
+    }
+}
+
+impl PlayerSetup {
+    ///Constructor - teams is the number of players in the game and humans is how many of them start as human
+    ///players. The AI takes the highest team numbers
+    pub fn new(ctx: &mut Context, teams: i32, humans: i32, screen_size: (f32, f32)) -> PlayerSetup {
+        let back_text = graphics::Text::new(("Back", graphics::Font::default(), SETUP_FONT_SIZE));
+        let play_text = graphics::Text::new(("Play", graphics::Font::default(), SETUP_FONT_SIZE));
+        let button_dim = (
+            2.0 * BUTTON_PADDING.0 + back_text.width(ctx).max(play_text.width(ctx)) as f32,
+            2.0 * BUTTON_PADDING.1 + back_text.height(ctx) as f32,
+        );
+        let button_y = screen_size.1 - button_dim.1 - 20.0;
+        let row_spacing = SETUP_ROW_SPACING.min((button_y - SETUP_ROW_OFFSET.1) / teams as f32);
+
+        let mut ai_buttons = Vec::new();
+        let mut name_inputs = Vec::new();
+        let mut color_pickers = Vec::new();
+        for (i, info) in PlayerInfo::defaults()
+            .iter()
+            .take(teams as usize)
+            .enumerate()
+        {
+            let team = i as i32 + 1;
+            let ai = team > humans;
+            let row_y = SETUP_ROW_OFFSET.1 + row_spacing * i as f32;
+            let name = default_name(i + 1, ai);
+            name_inputs.push(TextInput::new(
+                &name,
+                graphics::Rect::new(
+                    SETUP_ROW_OFFSET.0,
+                    row_y + SETUP_FONT_SIZE + 10.0,
+                    NAME_INPUT_WIDTH,
+                    SETUP_FONT_SIZE + 16.0,
+                ),
+                SETUP_FONT_SIZE,
+                MAX_NAME_LENGTH,
+            ));
+            color_pickers.push(ColorPicker::new(
+                (
+                    SETUP_ROW_OFFSET.0 + NAME_INPUT_WIDTH + 40.0,
+                    row_y + SETUP_FONT_SIZE + 10.0 + (SETUP_FONT_SIZE + 16.0 - SWATCH_SIZE) / 2.0,
+                ),
+                MyColor::disc_colors(),
+                info.color,
+            ));
+            //The AI toggle sits right of the player's label
+            let label = graphics::Text::new((
+                format!("Player {}", team),
+                graphics::Font::default(),
+                SETUP_FONT_SIZE,
+            ));
+            let ai_text =
+                graphics::Text::new(("AI", graphics::Font::default(), AI_TOGGLE_FONT_SIZE));
+            let mut ai_button = Button::new(
+                ai_text.clone(),
+                graphics::Rect::new(
+                    SETUP_ROW_OFFSET.0 + label.width(ctx) as f32 + 20.0,
+                    row_y,
+                    2.0 * BUTTON_PADDING.0 + ai_text.width(ctx) as f32,
+                    ai_text.height(ctx) as f32 + BUTTON_PADDING.1,
+                ),
+            );
+            ai_button.set_colors(MyColor::Brown, MyColor::Green);
+            ai_button.selected = ai;
+            ai_buttons.push(ai_button);
+        }
+
+        let mut back_button = Button::new(
+            back_text,
+            graphics::Rect::new(SETUP_ROW_OFFSET.0, button_y, button_dim.0, button_dim.1),
+        );
+        back_button.set_colors(MyColor::Red, MyColor::Green);
+        let mut play_button = Button::new(
+            play_text,
+            graphics::Rect::new(
+                screen_size.0 - SETUP_ROW_OFFSET.0 - button_dim.0,
+                button_y,
+                button_dim.0,
+                button_dim.1,
+            ),
+        );
+        play_button.set_colors(MyColor::Blue, MyColor::Green);
+
+        PlayerSetup {
+            ai_buttons,
+            name_inputs,
+            color_pickers,
+            back_button,
+            play_button,
+            message: String::new(),
+            row_spacing,
+        }
+    }
+
+    ///Draw method to render the title, player rows, buttons and any message
+    pub fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let title = graphics::Text::new(("Player Setup", graphics::Font::default(), 48.0));
+        graphics::draw(
+            ctx,
+            &title,
+            (Point2 {
+                x: SETUP_ROW_OFFSET.0,
+                y: 20.0,
+            },),
+        )?;
+        for i in 0..self.name_inputs.len() {
+            let label = graphics::Text::new((
+                format!("Player {}", i + 1),
+                graphics::Font::default(),
+                SETUP_FONT_SIZE,
+            ));
+            graphics::draw(
+                ctx,
+                &label,
+                (Point2 {
+                    x: SETUP_ROW_OFFSET.0,
+                    y: SETUP_ROW_OFFSET.1 + self.row_spacing * i as f32,
+                },),
+            )?;
+            self.ai_buttons[i].draw(ctx)?;
+            self.name_inputs[i].draw(ctx)?;
+            self.color_pickers[i].draw(ctx)?;
+        }
+        if !self.message.is_empty() {
+            let message = graphics::Text::new((
+                self.message.as_str(),
+                graphics::Font::default(),
+                SETUP_FONT_SIZE * 0.75,
+            ));
+            graphics::draw(
+                ctx,
+                &message,
+                (
+                    Point2 {
+                        x: SETUP_ROW_OFFSET.0 + self.back_button.outline.w + 20.0,
+                        y: self.back_button.outline.y + BUTTON_PADDING.1,
+                    },
+                    MyColor::Yellow.get_draw_color(),
+                ),
+            )?;
+        }
+        self.back_button.draw(ctx)?;
+        self.play_button.draw(ctx)?;
+        Ok(())
+    }
+
+    ///Method to update highlighted state of all widgets if mouse moves
+    pub fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        for input in &mut self.name_inputs {
+            input.check_under_mouse(ctx);
+        }
+        for picker in &mut self.color_pickers {
+            picker.check_under_mouse(ctx);
+        }
+        for button in &mut self.ai_buttons {
+            button.check_button_under_mouse(ctx);
+        }
+        self.back_button.check_button_under_mouse(ctx);
+        self.play_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method to handle a click on the setup screen. Focuses the clicked text input, picks the clicked color, switches a
+    ///player between a human and the AI and returns what the main menu should do next
+    pub fn mouse_button_up_event(&mut self, ctx: &mut Context) -> SetupAction {
+        for i in 0..self.ai_buttons.len() {
+            if self.ai_buttons[i].check_button_under_mouse(ctx) {
+                let ai = !self.ai_buttons[i].selected;
+                self.ai_buttons[i].selected = ai;
+                //Names that were not changed follow the switch, so "AI 2" doesn't end up as a human player's name
+                if self.name_inputs[i].contents() == default_name(i + 1, !ai) {
+                    self.name_inputs[i].set_contents(&default_name(i + 1, ai));
+                }
+            }
+        }
+        for input in &mut self.name_inputs {
+            input.focused = input.check_under_mouse(ctx);
+        }
+        for picker in &mut self.color_pickers {
+            if picker.click(ctx) {
+                self.message.clear();
+            }
+        }
+        if self.back_button.check_button_under_mouse(ctx) {
+            println!("Back button pressed; returning to Main Menu");
+            SetupAction::Back
+        } else if self.play_button.check_button_under_mouse(ctx) {
+            if self.has_color_conflict() {
+                self.message = String::from("Players must pick different colors");
+                SetupAction::None
+            } else {
+                println!("Play button pressed; starting game");
+                SetupAction::Play
+            }
+        } else {
+            SetupAction::None
+        }
+    }
+
+    ///Method to forward a typed character to the focused text input
+    pub fn text_input_event(&mut self, character: char) {
+        for input in &mut self.name_inputs {
+            input.push_char(character);
+        }
+    }
+
+    ///Method to handle special keys: Backspace deletes a character and Tab moves focus to the next text input
+    pub fn key_down_event(&mut self, keycode: KeyCode) {
+        match keycode {
+            KeyCode::Back => {
+                for input in &mut self.name_inputs {
+                    input.backspace();
+                }
+            }
+            KeyCode::Tab => {
+                let focused = self.name_inputs.iter().position(|input| input.focused);
+                let next = match focused {
+                    Some(i) => (i + 1) % self.name_inputs.len(),
+                    None => 0,
+                };
+                for (i, input) in self.name_inputs.iter_mut().enumerate() {
+                    input.focused = i == next;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    ///Returns true if two players picked the same disc color
+    fn has_color_conflict(&self) -> bool {
+        for i in 0..self.color_pickers.len() {
+            for j in (i + 1)..self.color_pickers.len() {
+                if self.color_pickers[i].selected_color() == self.color_pickers[j].selected_color()
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    ///Returns the name and color entered for each team, and if the AI plays for it, in team order. Blank names fall
+    ///back to "Player N"
+    pub fn player_info(&self) -> Vec<PlayerInfo> {
+        let mut output = Vec::new();
+        for i in 0..self.name_inputs.len() {
+            let mut name = self.name_inputs[i].contents();
+            if name.is_empty() {
+                name = format!("Player {}", i + 1);
+            }
+            let info = PlayerInfo::new(&name, self.color_pickers[i].selected_color());
+            output.push(if self.ai_buttons[i].selected {
+                info.with_ai()
+            } else {
+                info
+            });
+        }
+        output
+    }
+}
+
+//NOTE: Tests not included since testing requires usage of mouse and other UI features
@@ -0,0 +1,191 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use super::button::is_under_mouse;
+use super::core::MyColor;
+use ggez::graphics;
+use ggez::mint::Point2;
+use ggez::{Context, GameResult};
+
+///Constant dimmesions for padding between input text and outline
+const TEXT_INPUT_PADDING: (f32, f32) = (10.0, 8.0);
+
+///
+/// A struct representing a single line text box the user can click on and type into
+///
+/// # Fields
+/// * contents      = String holding the text typed so far
+/// * outline       = Rect object representing background shape of the text box
+/// * font_size     = Size of the font used to render the contents
+/// * max_len       = Maximum number of characters that can be typed into the box
+/// * focused       = Boolean indicating if the text box was clicked and is receiving key presses
+/// * highlighted   = Boolean indicating if mouse is hovering over the text box
+///
+pub struct TextInput {
+    contents: String,
+    pub outline: graphics::Rect,
+    font_size: f32,
+    max_len: usize,
+    pub focused: bool,
+    pub highlighted: bool,
+}
+
+impl TextInput {
+    ///
+    /// Method to initialize and return a TextInput object
+    ///
+    /// # Arguments
+    /// * initial   = Text the box is pre-filled with
+    /// * outline   = Rect object representing background shape of the text box
+    /// * font_size = Size of the font used to render the contents
+    /// * max_len   = Maximum number of characters that can be typed into the box
+    ///
+    pub fn new(initial: &str, outline: graphics::Rect, font_size: f32, max_len: usize) -> Self {
+        TextInput {
+            contents: initial.chars().take(max_len).collect(),
+            outline,
+            font_size,
+            max_len,
+            focused: false,
+            highlighted: false,
+        }
+    }
+
+    ///Draw method for rendering the text box, with a trailing underscore as a cursor if it is focused
+    pub fn draw(&self, ctx: &mut Context) -> GameResult<()> {
+        let bg_color = if self.focused {
+            MyColor::White.get_draw_color()
+        } else if self.highlighted {
+            graphics::Color::from_rgba(230, 230, 230, 255)
+        } else {
+            graphics::Color::from_rgba(200, 200, 200, 255)
+        };
+        let textbox =
+            graphics::Mesh::new_rectangle(ctx, graphics::DrawMode::fill(), self.outline, bg_color)?;
+        graphics::draw(ctx, &textbox, (Point2 { x: 0.0, y: 0.0 },))?;
+
+        let shown = if self.focused {
+            format!("{}_", self.contents)
+        } else {
+            self.contents.clone()
+        };
+        let text = graphics::Text::new((shown, graphics::Font::default(), self.font_size));
+        graphics::draw(
+            ctx,
+            &text,
+            (
+                Point2 {
+                    x: self.outline.x + TEXT_INPUT_PADDING.0,
+                    y: self.outline.y + TEXT_INPUT_PADDING.1,
+                },
+                graphics::BLACK,
+            ),
+        )?;
+        Ok(())
+    }
+
+    ///Method to determine if mouse if hovering over the text box, updates highlighted state accordingly
+    pub fn check_under_mouse(&mut self, ctx: &mut Context) -> bool {
+        self.highlighted = is_under_mouse(ctx, self.outline);
+        self.highlighted
+    }
+
+    ///Method to add a typed character to the end of the contents. Control characters and characters past max_len are ignored
+    pub fn push_char(&mut self, character: char) {
+        if self.focused && !character.is_control() && self.contents.chars().count() < self.max_len {
+            self.contents.push(character);
+        }
+    }
+
+    ///Method to remove the last character of the contents, if the text box is focused
+    pub fn backspace(&mut self) {
+        if self.focused {
+            self.contents.pop();
+        }
+    }
+
+    ///Returns the current contents with surrounding whitespace removed
+    pub fn contents(&self) -> String {
+        self.contents.trim().to_string()
+    }
+
+    ///Method to replace the contents, cut down to max_len characters
+    pub fn set_contents(&mut self, text: &str) {
+        self.contents = text.chars().take(self.max_len).collect();
+    }
+}
+
+#[cfg(test)]
+mod text_input_tests {
+    use super::*;
+
+    fn create_test_input(initial: &str, max_len: usize) -> TextInput {
+        let mut input = TextInput::new(
+            initial,
+            graphics::Rect::new(0.0, 0.0, 100.0, 20.0),
+            16.0,
+            max_len,
+        );
+        input.focused = true;
+        input
+    }
+
+    mod text_input {
+        use super::*;
+
+        mod new {
+            use super::*;
+
+            #[test]
+            fn should_truncate_initial_to_max_len() {
+                let input = create_test_input("Player 1", 4);
+                assert_eq!(input.contents(), "Play");
+            }
+        }
+
+        mod push_char {
+            use super::*;
+
+            #[test]
+            fn should_append_until_max_len() {
+                let mut input = create_test_input("Ab", 3);
+                input.push_char('c');
+                input.push_char('d');
+                assert_eq!(input.contents(), "Abc");
+            }
+
+            #[test]
+            fn should_ignore_control_chars() {
+                let mut input = create_test_input("Ab", 10);
+                input.push_char('\u{8}');
+                input.push_char('\r');
+                assert_eq!(input.contents(), "Ab");
+            }
+
+            #[test]
+            fn should_ignore_input_if_not_focused() {
+                let mut input = create_test_input("Ab", 10);
+                input.focused = false;
+                input.push_char('c');
+                input.backspace();
+                assert_eq!(input.contents(), "Ab");
+            }
+        }
+
+        mod backspace {
+            use super::*;
+
+            #[test]
+            fn should_remove_last_char_and_handle_empty() {
+                let mut input = create_test_input("A", 10);
+                input.backspace();
+                assert_eq!(input.contents(), "");
+                input.backspace();
+                assert_eq!(input.contents(), "");
+            }
+        }
+    }
+}
@@ -0,0 +1,601 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+extern crate toml;
+
+use clock::TimeControl;
+use connect4::button::Button;
+use connect4::core::{
+    Board, BoardConfig, GameAction, GameState, GridPosition, MyColor, PlayerInfo, BOARD_POS_OFFSET,
+    BOARD_SIZE, BOARD_TOTAL_SIZE,
+};
+use ggez::event::MouseButton;
+use ggez::mint::Point2;
+use ggez::{filesystem, graphics, Context, GameResult};
+use screen::GameScreen;
+use settings::Settings;
+use std::io::Read;
+
+///Constant resource path of the tutorial script
+const TUTORIAL_RESOURCE_PATH: &str = "/tutorial.toml";
+
+///Constant x location of the text box and buttons right of the board
+const TUTORIAL_PANEL_X: f32 = BOARD_TOTAL_SIZE.0 + (BOARD_POS_OFFSET.0 + 10) as f32;
+
+///Constant width of the text box and buttons right of the board
+const TUTORIAL_PANEL_WIDTH: f32 = 160.0;
+
+///Constant y location of the step title, with the text box below it
+const TUTORIAL_PANEL_Y: f32 = 10.0;
+
+///Constant font size for the instructions in the text box
+const TUTORIAL_FONT_SIZE: f32 = 16.0;
+
+///Constant font size for the step title
+const TUTORIAL_TITLE_FONT_SIZE: f32 = 24.0;
+
+///Constant size of the Continue and Finish buttons
+const TUTORIAL_BUTTON_SIZE: (f32, f32) = (160.0, 36.0);
+
+///Constant padding between the edge of the text box and its text
+const TUTORIAL_TEXT_PADDING: f32 = 6.0;
+
+///
+/// Function to read a column number from 1 to 7 in a tutorial script, returning its column index
+///
+/// # Arguments
+/// * column = Column number as written in the script
+///
+fn column_index(column: i32) -> Result<i32, String> {
+    if column >= 1 && column <= BOARD_SIZE.1 {
+        Ok(column - 1)
+    } else {
+        Err(format!("{} is not a column", column))
+    }
+}
+
+///
+/// A struct describing one step of the tutorial script
+///
+/// # Fields
+/// * title     = Heading shown above the instructions
+/// * text      = Instructions shown in the text box
+/// * moves     = Moves played to set up the board, as column numbers from 1 to 7 (e.g. "4453")
+/// * column    = Option of the column number (1 to 7) the player must drop a disc in, or None for steps that are only read
+/// * reply     = Option of the column number the opponent drops a disc in after the player's move
+/// * highlight = Vector of [column, row] cells ringed on the board, numbered from 1 with row 1 at the bottom
+/// * done      = Text shown once the player's move (and any reply) is made
+///
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct TutorialStep {
+    pub title: String,
+    pub text: String,
+    #[serde(default)]
+    pub moves: String,
+    #[serde(default)]
+    pub column: Option<i32>,
+    #[serde(default)]
+    pub reply: Option<i32>,
+    #[serde(default)]
+    pub highlight: Vec<[i32; 2]>,
+    #[serde(default)]
+    pub done: String,
+}
+
+impl TutorialStep {
+    ///Method to read the setup moves as column indexes (0 to 6), or an error naming the first character that is not a column
+    pub fn setup_moves(&self) -> Result<Vec<i32>, String> {
+        self.moves
+            .chars()
+            .map(|c| match c.to_digit(10) {
+                Some(d) => column_index(d as i32).map_err(|_| format!("'{}' is not a column", c)),
+                None => Err(format!("'{}' is not a column", c)),
+            })
+            .collect()
+    }
+
+    ///Method to get the highlighted cells as GridPositions on the board
+    pub fn highlighted_cells(&self) -> Vec<GridPosition> {
+        self.highlight
+            .iter()
+            .map(|cell| GridPosition::new(cell[0] - 1, cell[1] - 1))
+            .collect()
+    }
+
+    ///
+    /// Method to check the step can be played: the setup moves must fit on the board without ending the game, the
+    /// player's column and the reply must have room for a disc, and highlighted cells must be on the board
+    ///
+    pub fn check(&self) -> Result<(), String> {
+        let mut board = Board::new(BOARD_POS_OFFSET.into());
+        let mut team = 1;
+        for (i, col) in self.setup_moves()?.into_iter().enumerate() {
+            if board.is_column_full(col as usize) {
+                return Err(format!("Move {} is in a full column", i + 1));
+            }
+            let row = board.get_column_height(col as usize) as i32;
+            if board.connects(GridPosition::new(col, row), team) {
+                return Err(format!("Move {} ends the game", i + 1));
+            }
+            board.insert(col, team, MyColor::White);
+            team = team % 2 + 1;
+        }
+        for pos in self.highlighted_cells() {
+            if !board.on_board(pos) {
+                return Err(format!(
+                    "Cell [{}, {}] is not on the board",
+                    pos.x + 1,
+                    pos.y + 1
+                ));
+            }
+        }
+        let column = match self.column {
+            Some(column) => column_index(column)?,
+            None if self.reply.is_some() => {
+                return Err(String::from("A reply needs a column for the player's move"))
+            }
+            None => return Ok(()),
+        };
+        if !board.insert(column, team, MyColor::White) {
+            return Err(format!("Column {} is full", column + 1));
+        }
+        if let Some(reply) = self.reply {
+            if !board.insert(column_index(reply)?, team % 2 + 1, MyColor::White) {
+                return Err(format!("Reply column {} is full", reply));
+            }
+        }
+        Ok(())
+    }
+}
+
+///
+/// A struct holding the steps of the tutorial, read from TOML
+///
+/// # Fields
+/// * steps = Vector of TutorialSteps in the order they are played
+///
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct TutorialScript {
+    #[serde(default)]
+    pub steps: Vec<TutorialStep>,
+}
+
+impl TutorialScript {
+    ///
+    /// Method to read a tutorial script from a TOML string
+    ///
+    /// # Arguments
+    /// * text = TOML contents of a tutorial script
+    ///
+    pub fn from_toml(text: &str) -> Result<TutorialScript, String> {
+        toml::from_str::<TutorialScript>(text).map_err(|e| e.to_string())
+    }
+
+    ///Method to load the tutorial script from the resources directory, or an empty script if it is missing or invalid
+    pub fn load(ctx: &mut Context) -> TutorialScript {
+        let mut text = String::new();
+        match filesystem::open(ctx, TUTORIAL_RESOURCE_PATH) {
+            Ok(mut file) => {
+                if let Err(e) = file.read_to_string(&mut text) {
+                    println!("Error reading tutorial: {:?}", e);
+                }
+            }
+            Err(e) => println!("Error opening tutorial: {:?}", e),
+        }
+        TutorialScript::from_toml(&text).unwrap_or_else(|e| {
+            println!("Error reading tutorial: {}", e);
+            TutorialScript::default()
+        })
+    }
+}
+
+///
+/// Enum for how far the player is through the current tutorial step
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum StepStatus {
+    Reading,
+    Waiting,
+    Replying,
+    Done,
+}
+
+///
+/// A struct for the tutorial screen, which plays each step of the script on a Connect4 game and shows its instructions in a text box
+///
+/// # Fields
+/// * script          = TutorialScript with the steps to play
+/// * index           = Index of the current step
+/// * status          = StepStatus of the current step
+/// * message         = Text shown in the text box
+/// * start_moves     = Number of moves on the board when the current step started
+/// * game            = Connect4 GameState the steps are played on, with both teams controlled by the player
+/// * continue_button = Button moving on to the next step once the current one is done
+/// * finish_button   = Button shown on the last step, returning to the main menu
+///
+pub struct TutorialScreen {
+    script: TutorialScript,
+    index: usize,
+    status: StepStatus,
+    message: String,
+    start_moves: usize,
+    pub game: GameState,
+    continue_button: Button,
+    finish_button: Button,
+}
+
+impl TutorialScreen {
+    ///Constructor - script is loaded at startup, and settings hold the animation and theme options for the game
+    pub fn new(ctx: &mut Context, script: TutorialScript, settings: &Settings) -> TutorialScreen {
+        let button_outline = graphics::Rect::new(
+            TUTORIAL_PANEL_X,
+            BOARD_POS_OFFSET.1 as f32 + BOARD_TOTAL_SIZE.1 - TUTORIAL_BUTTON_SIZE.1,
+            TUTORIAL_BUTTON_SIZE.0,
+            TUTORIAL_BUTTON_SIZE.1,
+        );
+        let mut continue_button = Button::new(
+            graphics::Text::new(("Continue", graphics::Font::default(), 20.0)),
+            button_outline,
+        );
+        continue_button.set_colors(MyColor::Brown, MyColor::Green);
+        let mut finish_button = Button::new(
+            graphics::Text::new(("Finish", graphics::Font::default(), 20.0)),
+            button_outline,
+        );
+        finish_button.set_colors(MyColor::Brown, MyColor::Green);
+        let mut screen = TutorialScreen {
+            script,
+            index: 0,
+            status: StepStatus::Reading,
+            message: String::new(),
+            start_moves: 0,
+            game: TutorialScreen::new_game(ctx, settings),
+            continue_button,
+            finish_button,
+        };
+        screen.start_step(0);
+        screen
+    }
+
+    ///Function to create the game the tutorial is played on, a standard board without clocks or the usual side buttons
+    fn new_game(ctx: &mut Context, settings: &Settings) -> GameState {
+        let mut settings = settings.clone();
+        settings.time_control = TimeControl::off();
+        settings.board = BoardConfig::standard();
+        let mut game = GameState::new(ctx, PlayerInfo::defaults(), &settings);
+        game.set_scripted();
+        game
+    }
+
+    ///Method to start the tutorial again from the first step
+    pub fn restart(&mut self, ctx: &mut Context, settings: &Settings) {
+        self.game = TutorialScreen::new_game(ctx, settings);
+        self.start_step(0);
+    }
+
+    ///Method to set up the board, highlighted cells and allowed column for the step at the given index
+    fn start_step(&mut self, index: usize) {
+        self.index = index;
+        let step = match self.script.steps.get(index) {
+            Some(step) => step.clone(),
+            None => {
+                self.status = StepStatus::Reading;
+                self.message = String::from("No tutorial steps found");
+                self.game.allowed_columns = Some(Vec::new());
+                return;
+            }
+        };
+        if let Err(e) = step.check() {
+            println!("Invalid tutorial step {:?}: {}", step, e);
+            self.game.load_moves(&[]);
+            self.game.highlighted_cells.clear();
+            self.game.allowed_columns = Some(Vec::new());
+            self.status = StepStatus::Reading;
+            self.message = format!("Invalid step: {}", e);
+            return;
+        }
+        self.game
+            .load_moves(&step.setup_moves().unwrap_or_default());
+        self.game.highlighted_cells = step.highlighted_cells();
+        self.start_moves = self.game.move_count();
+        self.message = step.text.clone();
+        match step.column {
+            Some(column) => {
+                self.game.allowed_columns = Some(vec![column - 1]);
+                self.status = StepStatus::Waiting;
+            }
+            None => {
+                self.game.allowed_columns = Some(Vec::new());
+                self.status = StepStatus::Reading;
+            }
+        }
+    }
+
+    ///Method to finish the current step, showing its closing text and stopping any more clicks on the board
+    fn finish_step(&mut self) {
+        self.status = StepStatus::Done;
+        self.game.allowed_columns = Some(Vec::new());
+        let done = &self.script.steps[self.index].done;
+        if !done.is_empty() {
+            self.message = done.clone();
+        }
+    }
+
+    ///Update method to run the game and move the step on once the player's move and any reply have landed
+    pub fn update(&mut self, ctx: &mut Context) -> GameResult {
+        self.game.update(ctx)?;
+        if self.game.is_disc_falling() {
+            return Ok(());
+        }
+        let moves_made = self.game.move_count() - self.start_moves;
+        match self.status {
+            StepStatus::Waiting if moves_made >= 1 => match self.script.steps[self.index].reply {
+                Some(reply) if !self.game.is_gameover() => {
+                    self.game.allowed_columns = Some(Vec::new());
+                    self.game.drop_disc(reply - 1);
+                    self.status = StepStatus::Replying;
+                }
+                _ => self.finish_step(),
+            },
+            StepStatus::Replying if moves_made >= 2 => self.finish_step(),
+            _ => (),
+        }
+        Ok(())
+    }
+
+    ///Returns true if the current step is the last one in the script
+    fn is_last_step(&self) -> bool {
+        self.index + 1 >= self.script.steps.len()
+    }
+
+    ///Returns true if the player can move on from the current step
+    fn can_continue(&self) -> bool {
+        self.status == StepStatus::Reading || self.status == StepStatus::Done
+    }
+
+    ///Draw method to render the game, then the step title, text box and Continue or Finish button right of the board
+    pub fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.game.draw_contents(ctx)?;
+
+        let title = match self.script.steps.get(self.index) {
+            Some(step) => step.title.clone(),
+            None => String::from("Tutorial"),
+        };
+        let title =
+            graphics::Text::new((title, graphics::Font::default(), TUTORIAL_TITLE_FONT_SIZE));
+        let title_height = title.height(ctx) as f32;
+        graphics::draw(
+            ctx,
+            &title,
+            (Point2 {
+                x: TUTORIAL_PANEL_X,
+                y: TUTORIAL_PANEL_Y,
+            },),
+        )?;
+        if !self.script.steps.is_empty() {
+            let progress = format!("Step {} of {}", self.index + 1, self.script.steps.len());
+            let progress =
+                graphics::Text::new((progress, graphics::Font::default(), TUTORIAL_FONT_SIZE));
+            graphics::draw(
+                ctx,
+                &progress,
+                (Point2 {
+                    x: TUTORIAL_PANEL_X,
+                    y: TUTORIAL_PANEL_Y + title_height + 4.0,
+                },),
+            )?;
+        }
+
+        //Wrap the instructions to fit the text box, which is drawn behind them
+        let mut text = graphics::Text::new((
+            self.message.as_str(),
+            graphics::Font::default(),
+            TUTORIAL_FONT_SIZE,
+        ));
+        text.set_bounds(
+            Point2 {
+                x: TUTORIAL_PANEL_WIDTH - 2.0 * TUTORIAL_TEXT_PADDING,
+                y: f32::INFINITY,
+            },
+            graphics::Align::Left,
+        );
+        let box_y = TUTORIAL_PANEL_Y + 2.0 * title_height + 16.0;
+        let text_box = graphics::Rect::new(
+            TUTORIAL_PANEL_X,
+            box_y,
+            TUTORIAL_PANEL_WIDTH,
+            text.height(ctx) as f32 + 2.0 * TUTORIAL_TEXT_PADDING,
+        );
+        let text_color = match self.status {
+            StepStatus::Done => MyColor::Green,
+            _ => MyColor::White,
+        };
+        let mesh = graphics::MeshBuilder::new()
+            .rectangle(
+                graphics::DrawMode::fill(),
+                text_box,
+                MyColor::Brown.get_draw_color(),
+            )
+            .rectangle(
+                graphics::DrawMode::stroke(2.0),
+                text_box,
+                text_color.get_draw_color(),
+            )
+            .build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))?;
+        graphics::draw(
+            ctx,
+            &text,
+            (
+                Point2 {
+                    x: TUTORIAL_PANEL_X + TUTORIAL_TEXT_PADDING,
+                    y: box_y + TUTORIAL_TEXT_PADDING,
+                },
+                text_color.get_draw_color(),
+            ),
+        )?;
+
+        if self.can_continue() {
+            if self.is_last_step() {
+                self.finish_button.draw(ctx)?;
+            } else {
+                self.continue_button.draw(ctx)?;
+            }
+        }
+        Ok(())
+    }
+
+    ///Method to update the hovering disc and highlighted state of the buttons if mouse moves
+    pub fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        self.game.mouse_motion_event(ctx);
+        self.continue_button.check_button_under_mouse(ctx);
+        self.finish_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method to update the hovering disc and highlighted state of the buttons if mouse is pressed down
+    pub fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton) {
+        self.game.mouse_button_down_event(ctx, button);
+        self.continue_button.check_button_under_mouse(ctx);
+        self.finish_button.check_button_under_mouse(ctx);
+    }
+
+    ///
+    /// Method to handle a click on the tutorial screen. Drops a disc if the step allows it or moves on to the next
+    /// step, and returns true if the main menu or Finish button was clicked
+    ///
+    pub fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton) -> bool {
+        if self.game.mouse_button_up_event(ctx, button) == GameAction::MainMenu {
+            return true;
+        }
+        if !self.can_continue() {
+            return false;
+        }
+        if self.is_last_step() {
+            self.finish_button.check_button_under_mouse(ctx)
+        } else {
+            if self.continue_button.check_button_under_mouse(ctx) {
+                let index = self.index + 1;
+                self.start_step(index);
+            }
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tutorial_tests {
+    use super::*;
+
+    fn test_step(moves: &str, column: Option<i32>) -> TutorialStep {
+        TutorialStep {
+            title: String::from("Test"),
+            text: String::from("Test step"),
+            moves: String::from(moves),
+            column,
+            ..TutorialStep::default()
+        }
+    }
+
+    mod tutorial_step {
+        use super::*;
+
+        mod setup_moves {
+            use super::*;
+
+            #[test]
+            fn should_convert_column_numbers_to_indexes() {
+                assert_eq!(test_step("1247", None).setup_moves(), Ok(vec![0, 1, 3, 6]));
+                assert_eq!(test_step("", None).setup_moves(), Ok(vec![]));
+            }
+
+            #[test]
+            fn should_reject_characters_that_are_not_columns() {
+                assert_eq!(
+                    test_step("408", None).setup_moves(),
+                    Err(String::from("'0' is not a column"))
+                );
+                assert_eq!(
+                    test_step("4x", None).setup_moves(),
+                    Err(String::from("'x' is not a column"))
+                );
+            }
+        }
+
+        mod check {
+            use super::*;
+
+            #[test]
+            fn should_accept_playable_steps() {
+                assert_eq!(test_step("", None).check(), Ok(()));
+                let mut step = test_step("112233", Some(4));
+                step.highlight = vec![[1, 1], [4, 1]];
+                assert_eq!(step.check(), Ok(()));
+            }
+
+            #[test]
+            fn should_reject_setup_that_ends_the_game() {
+                assert_eq!(
+                    test_step("1122334", Some(5)).check(),
+                    Err(String::from("Move 7 ends the game"))
+                );
+            }
+
+            #[test]
+            fn should_reject_full_or_missing_columns() {
+                assert_eq!(
+                    test_step("444444", Some(4)).check(),
+                    Err(String::from("Column 4 is full"))
+                );
+                assert_eq!(
+                    test_step("", Some(8)).check(),
+                    Err(String::from("8 is not a column"))
+                );
+                let mut step = test_step("", None);
+                step.reply = Some(3);
+                assert!(step.check().is_err());
+            }
+
+            #[test]
+            fn should_reject_highlights_off_the_board() {
+                let mut step = test_step("", None);
+                step.highlight = vec![[4, 7]];
+                assert_eq!(
+                    step.check(),
+                    Err(String::from("Cell [4, 7] is not on the board"))
+                );
+            }
+        }
+    }
+
+    mod tutorial_script {
+        use super::*;
+
+        #[test]
+        fn should_read_optional_fields_from_toml() {
+            let script = TutorialScript::from_toml(
+                "[[steps]]\ntitle = \"A\"\ntext = \"Read me\"\n\n\
+                 [[steps]]\ntitle = \"B\"\ntext = \"Click 4\"\ncolumn = 4\nhighlight = [[4, 1]]\n",
+            )
+            .unwrap();
+            assert_eq!(script.steps.len(), 2);
+            assert_eq!(script.steps[0].column, None);
+            assert_eq!(script.steps[1].column, Some(4));
+            assert_eq!(
+                script.steps[1].highlighted_cells(),
+                vec![GridPosition::new(3, 0)]
+            );
+        }
+
+        #[test]
+        fn should_ship_only_valid_steps() {
+            let script =
+                TutorialScript::from_toml(include_str!("../../resources/tutorial.toml")).unwrap();
+            assert!(!script.steps.is_empty());
+            for step in script.steps {
+                assert_eq!(step.check(), Ok(()), "{:?}", step);
+            }
+        }
+    }
+}
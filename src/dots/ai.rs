@@ -0,0 +1,459 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate rand;
+
+use self::rand::Rng;
+use connect4::core::other_team;
+use dots::board::Board;
+
+///Constant length from which a chain is long: giving one away lets the taker keep control by declining the last two
+///boxes
+pub const LONG_CHAIN: usize = 3;
+
+///Constant number of safe edges, at difficulty 3, below which the AI plays each one out to the end of the game before
+///picking. Each difficulty above 3 adds LOOKAHEAD_STEP more
+const LOOKAHEAD_EDGES: usize = 8;
+
+///Constant number of safe edges added to LOOKAHEAD_EDGES for each difficulty above 3
+const LOOKAHEAD_STEP: usize = 4;
+
+///
+/// A struct describing a group of open boxes joined by undrawn edges, which is given away as a whole once any edge of
+/// it is drawn
+///
+/// # Fields
+/// * boxes   = Number of boxes in the group
+/// * is_loop = Boolean indicating if the group closes on itself, so every edge of it is shared by two of its boxes
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Chain {
+    pub boxes: usize,
+    pub is_loop: bool,
+}
+
+impl Chain {
+    ///Returns true if the chain is a loop or a long chain, which the player in control takes all but a few boxes of
+    pub fn is_long(&self) -> bool {
+        self.is_loop || self.boxes >= LONG_CHAIN
+    }
+}
+
+///
+/// Returns the groups of open boxes joined by undrawn edges. Once no safe edge is left, each group is a chain or loop
+/// that will be given away whole
+///
+/// # Arguments
+/// * board = Board to find the chains on
+///
+pub fn chains(board: &Board) -> Vec<Chain> {
+    let boxes = board.config.boxes();
+    let mut seen = vec![false; boxes];
+    let mut chains = Vec::new();
+    for start in 0..boxes {
+        if seen[start] || board.owner(start) != 0 {
+            continue;
+        }
+        seen[start] = true;
+        let mut stack = vec![start];
+        let mut chain = Chain {
+            boxes: 0,
+            is_loop: true,
+        };
+        while let Some(square) = stack.pop() {
+            chain.boxes += 1;
+            chain.is_loop &= board.sides(square) == 2;
+            for edge in board.box_edges(square).iter() {
+                if board.edge(*edge) != 0 {
+                    continue;
+                }
+                let neighbours = board.edge_boxes(*edge);
+                //An undrawn edge on the outside of the grid leaves the group open at that end
+                chain.is_loop &= neighbours.len() == 2;
+                for next in neighbours {
+                    if !seen[next] {
+                        seen[next] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+        chain.is_loop &= chain.boxes >= 4;
+        chains.push(chain);
+    }
+    chains
+}
+
+///Returns the open boxes with three sides drawn, which can be claimed with the fourth
+pub fn capturable_boxes(board: &Board) -> Vec<usize> {
+    (0..board.config.boxes())
+        .filter(|square| board.owner(*square) == 0 && board.sides(*square) == 3)
+        .collect()
+}
+
+///Returns the undrawn edges that don't leave a box with three sides for the other team to claim
+pub fn safe_edges(board: &Board) -> Vec<usize> {
+    board
+        .undrawn_edges()
+        .into_iter()
+        .filter(|edge| {
+            board
+                .edge_boxes(*edge)
+                .iter()
+                .all(|square| board.sides(*square) < 2)
+        })
+        .collect()
+}
+
+///Returns the undrawn side of a box with three sides drawn
+fn missing_edge(board: &Board, square: usize) -> Option<usize> {
+    board
+        .box_edges(square)
+        .iter()
+        .cloned()
+        .find(|edge| board.edge(*edge) == 0)
+}
+
+///
+/// Returns the number of boxes the other team claims by taking every box it can after a team draws an edge. Only the
+/// boxes next to each claimed box are checked, so the whole chain is followed without scanning the grid
+///
+/// # Arguments
+/// * board = Board before the edge is drawn
+/// * edge  = Number of the edge to draw
+/// * team  = Integer value (1 or 2) of the team drawing the edge
+///
+fn giveaway(board: &Board, edge: usize, team: i32) -> usize {
+    let mut next = board.clone();
+    next.play(edge, team);
+    let taker = other_team(team);
+    let mut taken = 0;
+    let mut stack = next.edge_boxes(edge);
+    while let Some(square) = stack.pop() {
+        if next.owner(square) != 0 || next.sides(square) != 3 {
+            continue;
+        }
+        if let Some(side) = missing_edge(&next, square) {
+            taken += next.play(side, taker).unwrap_or(0);
+            stack.extend(next.edge_boxes(side));
+        }
+    }
+    taken
+}
+
+///
+/// Returns the edge that declines the last two boxes of a chain being taken, or None if taking them is better. With one
+/// box left to claim and one more box behind it, drawing the far side of that box hands both over with a single edge,
+/// so the other team has to open the next chain. This is only worth it while no safe edge is left and a long chain or
+/// loop remains to be given away
+///
+/// # Arguments
+/// * board = Board with the team to move able to claim a box
+/// * team  = Integer value (1 or 2) of the team to move
+///
+fn double_deal(board: &Board, team: i32) -> Option<usize> {
+    let capturable = capturable_boxes(board);
+    if capturable.len() != 1 || !safe_edges(board).is_empty() {
+        return None;
+    }
+    let first = capturable[0];
+    let shared = missing_edge(board, first)?;
+    let second = board
+        .edge_boxes(shared)
+        .into_iter()
+        .find(|square| *square != first && board.owner(*square) == 0)?;
+    if board.sides(second) != 2 {
+        return None;
+    }
+    let far = board
+        .box_edges(second)
+        .iter()
+        .cloned()
+        .find(|edge| *edge != shared && board.edge(*edge) == 0)?;
+    //The chain must end at the second box, or declining would hand over more than two boxes
+    let beyond = board
+        .edge_boxes(far)
+        .into_iter()
+        .find(|square| *square != second);
+    if beyond.is_some_and(|square| board.sides(square) >= 2) {
+        return None;
+    }
+    let mut rest = board.clone();
+    rest.play(far, team);
+    rest.play(shared, other_team(team));
+    if chains(&rest).iter().any(Chain::is_long) {
+        Some(far)
+    } else {
+        None
+    }
+}
+
+///
+/// Returns the move the chain strategy picks, or None once every edge is drawn. Boxes are claimed while any can be,
+/// except that the last two of a chain are declined to keep control when a long chain or loop is left. Otherwise the
+/// first safe edge is drawn, and once none is left the edge giving away the fewest boxes is drawn, preferring an edge
+/// that opens a short chain in its middle so it can't be declined
+///
+/// # Arguments
+/// * board = Board with the team to move
+/// * team  = Integer value (1 or 2) of the team to move
+///
+fn chain_move(board: &Board, team: i32) -> Option<usize> {
+    let capturable = capturable_boxes(board);
+    if !capturable.is_empty() {
+        return double_deal(board, team).or_else(|| missing_edge(board, capturable[0]));
+    }
+    if let Some(edge) = safe_edges(board).first() {
+        return Some(*edge);
+    }
+    board.undrawn_edges().into_iter().min_by_key(|edge| {
+        let opened = board
+            .edge_boxes(*edge)
+            .iter()
+            .filter(|square| board.sides(**square) == 2)
+            .count();
+        (giveaway(board, *edge, team), 2 - opened)
+    })
+}
+
+///
+/// Returns the number of boxes a team claims less the other team's from a board to the end of the game, with both
+/// teams playing the chain strategy
+///
+/// # Arguments
+/// * board = Board to play out
+/// * team  = Integer value (1 or 2) of the team to move
+/// * me    = Integer value (1 or 2) of the team to score for
+///
+fn play_out(board: &Board, mut team: i32, me: i32) -> i32 {
+    let mut board = board.clone();
+    let start = board.count(me) as i32 - board.count(other_team(me)) as i32;
+    while let Some(edge) = chain_move(&board, team) {
+        if board.play(edge, team) == Some(0) {
+            team = other_team(team);
+        }
+    }
+    board.count(me) as i32 - board.count(other_team(me)) as i32 - start
+}
+
+///
+/// A struct representing an AI or bot player for Dots and Boxes which has methods to pick edges
+///
+/// # Fields
+/// * team            = Integer value (1 or 2) of the team the AI plays for
+/// * difficulty      = Integer value for how well the AI plays chains, see pick_move
+/// * last_move_frame = Integer used to track when the AI picked its edge. Set to -1 until it picks each move
+///
+pub struct AI {
+    pub team: i32,
+    difficulty: i32,
+    pub last_move_frame: i32,
+}
+
+impl AI {
+    ///
+    /// Method to initialize and return an AI object
+    ///
+    /// # Arguments
+    /// * team       = Integer value (1 or 2) of the team the AI plays for
+    /// * difficulty = Integer value for how well the AI plays chains
+    ///
+    pub fn new(team: i32, difficulty: i32) -> Self {
+        AI {
+            team,
+            difficulty,
+            last_move_frame: -1,
+        }
+    }
+
+    ///Returns how many safe edges may be left for the AI to play each of them out before picking, or 0 if it doesn't
+    fn lookahead_edges(&self) -> usize {
+        if self.difficulty < 3 {
+            0
+        } else {
+            LOOKAHEAD_EDGES + LOOKAHEAD_STEP * (self.difficulty - 3) as usize
+        }
+    }
+
+    ///
+    /// Method to pick the edge to draw. Every AI claims any box it can and draws a safe edge at random while there is
+    /// one. At difficulty 1 it then draws any edge; from difficulty 2 it gives away the shortest chain and declines the
+    /// last two boxes of a chain to keep control, and from difficulty 3 it plays out every safe edge once few are left,
+    /// picking the one that wins the most boxes. Returns None once every edge is drawn
+    ///
+    /// # Arguments
+    /// * board = Board with the AI's team to move
+    /// * rng   = Random number generator used to pick between safe edges
+    ///
+    pub fn pick_move<R: Rng>(&self, board: &Board, rng: &mut R) -> Option<usize> {
+        let safe = safe_edges(board);
+        if !capturable_boxes(board).is_empty() || safe.is_empty() {
+            if self.difficulty >= 2 {
+                return chain_move(board, self.team);
+            }
+            return match capturable_boxes(board).first() {
+                Some(square) => missing_edge(board, *square),
+                None => {
+                    let edges = board.undrawn_edges();
+                    if edges.is_empty() {
+                        None
+                    } else {
+                        Some(edges[rng.gen_range(0, edges.len())])
+                    }
+                }
+            };
+        }
+        if safe.len() > self.lookahead_edges() {
+            return Some(safe[rng.gen_range(0, safe.len())]);
+        }
+        //Play each safe edge out to the end of the game, keeping the ones that win the most boxes
+        let mut best = Vec::new();
+        let mut best_score = i32::MIN;
+        for edge in safe {
+            let mut next = board.clone();
+            next.play(edge, self.team);
+            let score = play_out(&next, other_team(self.team), self.team);
+            if score > best_score {
+                best_score = score;
+                best.clear();
+            }
+            if score == best_score {
+                best.push(edge);
+            }
+        }
+        Some(best[rng.gen_range(0, best.len())])
+    }
+}
+
+#[cfg(test)]
+mod ai_tests {
+    use super::rand::{SeedableRng, StdRng};
+    use super::*;
+    use dots::board::DotsConfig;
+
+    //Method to create a board of the given size with the given edges drawn, all by team 1
+    fn create_test_board(rows: i32, columns: i32, edges: &[usize]) -> Board {
+        let mut board = Board::new(DotsConfig::new(rows, columns));
+        for edge in edges {
+            board.play(*edge, 1);
+        }
+        board
+    }
+
+    //Method to create a seeded random number generator so picks are repeatable
+    fn create_test_rng() -> StdRng {
+        SeedableRng::from_seed(&[7][..])
+    }
+
+    mod chains {
+        use super::*;
+
+        #[test]
+        fn should_find_a_loop_and_a_chain() {
+            //A 2x2 grid with only the four inner edges undrawn is a loop of four boxes
+            let board = create_test_board(2, 2, &[0, 1, 4, 5, 6, 8, 9, 11]);
+            assert_eq!(
+                chains(&board),
+                vec![Chain {
+                    boxes: 4,
+                    is_loop: true
+                }]
+            );
+            //A 1x3 row with its top and bottom drawn is a chain of three, open at both ends
+            let board = create_test_board(1, 3, &[0, 1, 2, 3, 4, 5]);
+            let found = chains(&board);
+            assert_eq!(found.len(), 1);
+            assert!(!found[0].is_loop && found[0].is_long());
+        }
+    }
+
+    mod ai {
+        use super::*;
+
+        mod pick_move {
+            use super::*;
+
+            #[test]
+            fn should_claim_a_box_with_three_sides() {
+                let board = create_test_board(2, 2, &[0, 2, 6]);
+                for difficulty in 1..4 {
+                    let edge = AI::new(2, difficulty).pick_move(&board, &mut create_test_rng());
+                    assert_eq!(edge, Some(7));
+                }
+            }
+
+            #[test]
+            fn should_draw_safe_edges_while_there_are_any() {
+                let board = create_test_board(3, 3, &[0, 1]);
+                let mut rng = create_test_rng();
+                for difficulty in 1..5 {
+                    let edge = AI::new(1, difficulty).pick_move(&board, &mut rng).unwrap();
+                    assert!(safe_edges(&board).contains(&edge));
+                }
+            }
+
+            #[test]
+            fn should_give_away_the_shortest_chain() {
+                //On a 2x2 grid, the top-left box is a chain of one and the other three boxes a chain of three. No edge
+                //is safe, and giving away the single box costs least
+                let board = create_test_board(2, 2, &[2, 5, 7, 8, 9, 11]);
+                assert!(safe_edges(&board).is_empty());
+                let edge = AI::new(1, 2)
+                    .pick_move(&board, &mut create_test_rng())
+                    .unwrap();
+                assert_eq!(giveaway(&board, edge, 1), 1);
+                assert_eq!(giveaway(&board, 3, 1), 3);
+            }
+
+            #[test]
+            fn should_decline_the_last_two_boxes_to_keep_control() {
+                //On a 2x4 grid, the two right-hand boxes are a chain the other team has opened at the top, and the
+                //other six boxes are one long chain winding through both rows
+                let board = create_test_board(2, 4, &[0, 1, 2, 3, 4, 5, 8, 9, 10, 15, 16, 20, 21]);
+                assert_eq!(capturable_boxes(&board), vec![3]);
+                assert!(safe_edges(&board).is_empty());
+                //Drawing the bottom of the lower box hands both boxes over instead of claiming them
+                let edge = AI::new(2, 2).pick_move(&board, &mut create_test_rng());
+                assert_eq!(edge, Some(11));
+                assert_eq!(giveaway(&board, 11, 2), 2);
+                //The weakest AI just claims the box
+                let edge = AI::new(2, 1).pick_move(&board, &mut create_test_rng());
+                assert_eq!(edge, Some(7));
+            }
+
+            #[test]
+            fn should_return_none_once_every_edge_is_drawn() {
+                let board = create_test_board(2, 2, &(0..12).collect::<Vec<usize>>());
+                assert!(board.is_over());
+                assert_eq!(
+                    AI::new(1, 3).pick_move(&board, &mut create_test_rng()),
+                    None
+                );
+            }
+
+            #[test]
+            fn should_beat_a_weaker_ai_on_a_small_grid() {
+                let mut rng = create_test_rng();
+                let mut wins = 0;
+                for game in 0..10 {
+                    //Take turns moving first
+                    let strong = 1 + game % 2;
+                    let difficulty = |team: i32| if team == strong { 3 } else { 1 };
+                    let ais = [AI::new(1, difficulty(1)), AI::new(2, difficulty(2))];
+                    let mut board = Board::new(DotsConfig::new(3, 3));
+                    let mut team = 1;
+                    while let Some(edge) = ais[team as usize - 1].pick_move(&board, &mut rng) {
+                        if board.play(edge, team) == Some(0) {
+                            team = other_team(team);
+                        }
+                    }
+                    if board.leader() == strong {
+                        wins += 1;
+                    }
+                }
+                assert!(wins >= 7);
+            }
+        }
+    }
+}
@@ -0,0 +1,320 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::GridPosition;
+
+///Constant smallest and largest number of rows or columns of boxes a grid may have
+pub const DOTS_SIZE_RANGE: (i32, i32) = (2, 9);
+
+///Constant grid sizes (rows, columns of boxes) offered on the settings screen, smallest first
+pub const DOTS_PRESETS: [(i32, i32); 6] = [(2, 2), (3, 3), (4, 4), (5, 5), (6, 6), (8, 8)];
+
+///
+/// A struct describing the grid of a Dots and Boxes game, counted in boxes. A grid of rows x columns boxes has
+/// (rows + 1) x (columns + 1) dots
+///
+/// # Fields
+/// * rows    = Number of rows of boxes
+/// * columns = Number of columns of boxes
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct DotsConfig {
+    pub rows: i32,
+    pub columns: i32,
+}
+
+impl Default for DotsConfig {
+    fn default() -> Self {
+        DotsConfig::new(3, 3)
+    }
+}
+
+impl DotsConfig {
+    ///Constructor - rows and columns give the number of boxes down and across the grid
+    pub fn new(rows: i32, columns: i32) -> Self {
+        DotsConfig { rows, columns }
+    }
+
+    ///Returns a copy of the config with the size moved into its valid range
+    pub fn clamped(self) -> Self {
+        DotsConfig::new(
+            self.rows.clamp(DOTS_SIZE_RANGE.0, DOTS_SIZE_RANGE.1),
+            self.columns.clamp(DOTS_SIZE_RANGE.0, DOTS_SIZE_RANGE.1),
+        )
+    }
+
+    ///
+    /// Returns the next larger or smaller preset, or the config itself if there is none in that direction. A custom
+    /// size from the settings file moves to the nearest preset in that direction
+    ///
+    /// # Arguments
+    /// * up = Boolean indicating if the grid should get larger (true) or smaller (false)
+    ///
+    pub fn step(self, up: bool) -> Self {
+        let boxes = |size: (i32, i32)| size.0 * size.1;
+        let current = boxes((self.rows, self.columns));
+        let next = if up {
+            DOTS_PRESETS.iter().find(|size| boxes(**size) > current)
+        } else {
+            DOTS_PRESETS
+                .iter()
+                .rev()
+                .find(|size| boxes(**size) < current)
+        };
+        next.map_or(self, |size| DotsConfig::new(size.0, size.1))
+    }
+
+    ///Returns the size shown on the settings screen, e.g. "3x3 boxes"
+    pub fn label(&self) -> String {
+        format!("{}x{} boxes", self.rows, self.columns)
+    }
+
+    ///Returns the number of boxes in the grid
+    pub fn boxes(&self) -> usize {
+        (self.rows * self.columns) as usize
+    }
+
+    ///Returns the number of horizontal edges, which are numbered before the vertical ones
+    fn horizontal_edges(&self) -> usize {
+        ((self.rows + 1) * self.columns) as usize
+    }
+
+    ///Returns the number of edges in the grid, horizontal and vertical
+    pub fn edges(&self) -> usize {
+        self.horizontal_edges() + (self.rows * (self.columns + 1)) as usize
+    }
+}
+
+///
+/// A struct representing a Dots and Boxes grid. Horizontal edges are numbered row by row from the top-left dot, then
+/// vertical edges are numbered the same way, which is how moves are recorded in the match history. Boxes are numbered
+/// row by row from the top-left corner
+///
+/// # Fields
+/// * config = DotsConfig for the size of the grid
+/// * edges  = Vector of the team (1 or 2) that drew each edge, or 0 if it hasn't been drawn
+/// * boxes  = Vector of the team (1 or 2) that claimed each box, or 0 if it is still open
+///
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Board {
+    pub config: DotsConfig,
+    edges: Vec<i32>,
+    boxes: Vec<i32>,
+}
+
+impl Board {
+    ///Constructor - returns a grid of the given size with no edges drawn
+    pub fn new(config: DotsConfig) -> Self {
+        Board {
+            config,
+            edges: vec![0; config.edges()],
+            boxes: vec![0; config.boxes()],
+        }
+    }
+
+    ///Returns true if the edge runs across, between two dots in the same row
+    pub fn is_horizontal(&self, edge: usize) -> bool {
+        edge < self.config.horizontal_edges()
+    }
+
+    ///Returns the dots at either end of an edge, where x is the dot's column and y its row
+    pub fn edge_ends(&self, edge: usize) -> (GridPosition, GridPosition) {
+        let columns = self.config.columns as usize;
+        if self.is_horizontal(edge) {
+            let (y, x) = ((edge / columns) as i32, (edge % columns) as i32);
+            (GridPosition::new(x, y), GridPosition::new(x + 1, y))
+        } else {
+            let edge = edge - self.config.horizontal_edges();
+            let (y, x) = ((edge / (columns + 1)) as i32, (edge % (columns + 1)) as i32);
+            (GridPosition::new(x, y), GridPosition::new(x, y + 1))
+        }
+    }
+
+    ///Returns the position of a box, where x is its column and y its row
+    pub fn box_position(&self, square: usize) -> GridPosition {
+        let columns = self.config.columns as usize;
+        GridPosition::new((square % columns) as i32, (square / columns) as i32)
+    }
+
+    ///Returns the four edges around a box: top, bottom, left and right
+    pub fn box_edges(&self, square: usize) -> [usize; 4] {
+        let columns = self.config.columns as usize;
+        let (row, column) = (square / columns, square % columns);
+        let left = self.config.horizontal_edges() + row * (columns + 1) + column;
+        [
+            row * columns + column,
+            (row + 1) * columns + column,
+            left,
+            left + 1,
+        ]
+    }
+
+    ///Returns the one or two boxes an edge is a side of
+    pub fn edge_boxes(&self, edge: usize) -> Vec<usize> {
+        let (rows, columns) = (self.config.rows, self.config.columns);
+        let (start, _) = self.edge_ends(edge);
+        let mut boxes = Vec::new();
+        if self.is_horizontal(edge) {
+            if start.y > 0 {
+                boxes.push(((start.y - 1) * columns + start.x) as usize);
+            }
+            if start.y < rows {
+                boxes.push((start.y * columns + start.x) as usize);
+            }
+        } else {
+            if start.x > 0 {
+                boxes.push((start.y * columns + start.x - 1) as usize);
+            }
+            if start.x < columns {
+                boxes.push((start.y * columns + start.x) as usize);
+            }
+        }
+        boxes
+    }
+
+    ///Returns the team that drew an edge, or 0 if it hasn't been drawn
+    pub fn edge(&self, edge: usize) -> i32 {
+        self.edges[edge]
+    }
+
+    ///Returns the team that claimed a box, or 0 if it is still open
+    pub fn owner(&self, square: usize) -> i32 {
+        self.boxes[square]
+    }
+
+    ///Returns how many of a box's four sides have been drawn
+    pub fn sides(&self, square: usize) -> usize {
+        self.box_edges(square)
+            .iter()
+            .filter(|edge| self.edges[**edge] != 0)
+            .count()
+    }
+
+    ///Returns every edge that hasn't been drawn yet
+    pub fn undrawn_edges(&self) -> Vec<usize> {
+        (0..self.edges.len())
+            .filter(|edge| self.edges[*edge] == 0)
+            .collect()
+    }
+
+    ///
+    /// Method to draw an edge for a team and claim every box it completes. The team moves again if it completed a box.
+    /// Returns the number of boxes claimed, or None if the edge is off the grid or already drawn
+    ///
+    /// # Arguments
+    /// * edge = Number of the edge to draw
+    /// * team = Integer value (1 or 2) of the team drawing the edge
+    ///
+    pub fn play(&mut self, edge: usize, team: i32) -> Option<usize> {
+        if edge >= self.edges.len() || self.edges[edge] != 0 {
+            return None;
+        }
+        self.edges[edge] = team;
+        let mut claimed = 0;
+        for square in self.edge_boxes(edge) {
+            if self.sides(square) == 4 {
+                self.boxes[square] = team;
+                claimed += 1;
+            }
+        }
+        Some(claimed)
+    }
+
+    ///Returns true once every edge has been drawn and so every box claimed
+    pub fn is_over(&self) -> bool {
+        self.edges.iter().all(|edge| *edge != 0)
+    }
+
+    ///Returns the number of boxes a team has claimed
+    pub fn count(&self, team: i32) -> usize {
+        self.boxes.iter().filter(|owner| **owner == team).count()
+    }
+
+    ///Returns the team with more boxes, or 0 if both have the same number
+    pub fn leader(&self) -> i32 {
+        let (first, second) = (self.count(1), self.count(2));
+        if first > second {
+            1
+        } else if second > first {
+            2
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod board_tests {
+    use super::*;
+
+    //Method to create a board of the given size with the given edges drawn by team 1
+    fn create_test_board(rows: i32, columns: i32, edges: &[usize]) -> Board {
+        let mut board = Board::new(DotsConfig::new(rows, columns));
+        for edge in edges {
+            board.play(*edge, 1);
+        }
+        board
+    }
+
+    mod dots_config {
+        use super::*;
+
+        #[test]
+        fn should_count_edges_and_boxes() {
+            let config = DotsConfig::new(2, 3);
+            assert_eq!(config.boxes(), 6);
+            assert_eq!(config.edges(), 3 * 3 + 2 * 4);
+        }
+
+        #[test]
+        fn should_step_through_presets_and_clamp() {
+            assert_eq!(DotsConfig::default().step(true), DotsConfig::new(4, 4));
+            assert_eq!(DotsConfig::new(2, 2).step(false), DotsConfig::new(2, 2));
+            assert_eq!(DotsConfig::new(3, 7).step(false), DotsConfig::new(4, 4));
+            assert_eq!(DotsConfig::new(1, 20).clamped(), DotsConfig::new(2, 9));
+            assert_eq!(DotsConfig::default().label(), "3x3 boxes");
+        }
+    }
+
+    mod board {
+        use super::*;
+
+        #[test]
+        fn should_agree_on_box_edges_and_edge_boxes() {
+            let board = Board::new(DotsConfig::new(3, 4));
+            for square in 0..board.config.boxes() {
+                for edge in board.box_edges(square).iter() {
+                    assert!(board.edge_boxes(*edge).contains(&square));
+                }
+            }
+            //Edges on the outside of the grid are a side of only one box
+            assert_eq!(board.edge_boxes(0), vec![0]);
+            assert_eq!(board.edge_boxes(5), vec![1, 5]);
+            assert_eq!(board.edge_boxes(16), vec![0]);
+            assert_eq!(
+                board.edge_ends(16),
+                (GridPosition::new(0, 0), GridPosition::new(0, 1))
+            );
+        }
+
+        #[test]
+        fn should_claim_completed_boxes_only() {
+            let mut board = create_test_board(2, 2, &[0, 2, 6]);
+            assert_eq!(board.sides(0), 3);
+            assert_eq!(board.play(7, 2), Some(1));
+            assert_eq!(board.owner(0), 2);
+            assert_eq!(board.count(2), 1);
+            assert_eq!(board.play(7, 1), None);
+        }
+
+        #[test]
+        fn should_claim_two_boxes_with_one_edge() {
+            //The middle vertical edge of a 1x2 grid is the last side of both boxes
+            let mut board = create_test_board(1, 2, &[0, 1, 2, 3, 4, 6]);
+            assert_eq!(board.play(5, 2), Some(2));
+            assert!(board.is_over());
+            assert_eq!(board.leader(), 2);
+        }
+    }
+}
@@ -0,0 +1,506 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+extern crate rand;
+
+use connect4::button::{create_button, draw_text, mouse_location, Button};
+use connect4::core::{other_team, GameAction, GridPosition, MyColor, PlayerInfo, TurnIndicator};
+use dots::ai::AI;
+use dots::board::Board;
+use ggez::input::mouse::MouseButton;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+use history::MatchRecord;
+use screen::GameScreen;
+use settings::Settings;
+use sound::{result_sound, SoundEffect};
+
+///Constant dimensions for the Dots and Boxes window, fitting the grid with a panel of players and buttons to its right
+pub const DOTS_SCREEN_SIZE: (f32, f32) = (900.0, 660.0);
+
+///Constant top-left corner of the grid, below the turn indicator
+const BOARD_POSITION: (f32, f32) = (60.0, 130.0);
+
+///Constant width and height of the area the grid is fitted in
+const BOARD_PIXELS: f32 = 480.0;
+
+///Constant x location of the panel of players and buttons to the right of the grid
+const PANEL_POS_X: f32 = 600.0;
+
+///Constant font size for the panel text and buttons
+const DOTS_FONT_SIZE: f32 = 24.0;
+
+///Constant fraction of the gap between dots within which the mouse picks an edge
+const EDGE_PICK_DISTANCE: f32 = 0.25;
+
+///Constant radius of each dot
+const DOT_RADIUS: f32 = 6.0;
+
+///Constant width of a drawn edge; the edge drawn last is drawn twice as wide
+const EDGE_WIDTH: f32 = 4.0;
+
+///Returns the distance from a point to the line segment between two others
+fn distance_to_segment(point: Point2<f32>, start: Point2<f32>, end: Point2<f32>) -> f32 {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let length = dx * dx + dy * dy;
+    let t = if length == 0.0 {
+        0.0
+    } else {
+        (((point.x - start.x) * dx + (point.y - start.y) * dy) / length).clamp(0.0, 1.0)
+    };
+    let (x, y) = (start.x + t * dx, start.y + t * dy);
+    ((point.x - x).powi(2) + (point.y - y).powi(2)).sqrt()
+}
+
+///
+/// A struct that contains the state of a Dots and Boxes game. Players take turns drawing an edge between two dots, and
+/// a player who completes a box claims it and draws again. Whoever has more boxes once every edge is drawn wins
+///
+/// # Fields
+/// * board             = Board holding the edges drawn and boxes claimed so far
+/// * team_names        = Vector of player names, where team_names[i] is the name for team i + 1
+/// * team_colors       = Vector of MyColor objects, where team_colors[i] is the color team i + 1's edges and boxes
+///                       are drawn in
+/// * team              = Integer value (1 or 2) of the team to move
+/// * turn_indicator    = TurnIndicator from Connect 4 showing whose turn it is and who won
+/// * ai_players        = Vector of AI objects, one for each team played by the AI
+/// * ai_difficulty     = Difficulty given to the AI players, recorded in the match history
+/// * ai_move_delay     = Number of frames an AI shows the edge it picked before drawing it
+/// * aim               = Option of the edge the AI is about to draw, or None
+/// * frames            = Integer counter for the number of times the update method is called; times AI moves
+/// * hover             = Option of the undrawn edge under the mouse during a human player's turn, or None
+/// * last_edge         = Option of the edge drawn last, or None at the start of a game
+/// * message           = Message shown on the panel, such as a player claiming boxes and moving again
+/// * game_over         = Boolean indicating if every edge has been drawn
+/// * winner            = Integer value (1 or 2) of the team with more boxes at the end, or 0 for a draw
+/// * main_menu_button  = Button drawn to return to the main menu
+/// * play_again_button = Button drawn to clear the grid and start a new game with the same players
+/// * background_color  = Color drawn behind the grid
+/// * sound_queue       = Vector of SoundEffect enums triggered since the main menu last played them
+/// * moves             = Vector of edges drawn so far, in order, see Board for how they are numbered
+/// * elapsed           = Seconds the current game has been played for
+/// * finished_match    = Option of a MatchRecord for a game that just ended, taken by the main menu to be saved
+///
+pub struct DotsState {
+    board: Board,
+    team_names: Vec<String>,
+    team_colors: Vec<MyColor>,
+    team: i32,
+    turn_indicator: TurnIndicator,
+    ai_players: Vec<AI>,
+    ai_difficulty: i32,
+    ai_move_delay: i32,
+    aim: Option<usize>,
+    frames: usize,
+    hover: Option<usize>,
+    last_edge: Option<usize>,
+    message: String,
+    game_over: bool,
+    winner: i32,
+    main_menu_button: Button,
+    play_again_button: Button,
+    background_color: graphics::Color,
+    pub sound_queue: Vec<SoundEffect>,
+    moves: Vec<i32>,
+    elapsed: f32,
+    pub finished_match: Option<MatchRecord>,
+}
+
+impl DotsState {
+    ///Constructor - player_info holds the name, color and whether the AI plays for team 1 and team 2, in that order, and
+    ///settings holds the grid size, AI and theme options. Teams without player info get the defaults
+    pub fn new(ctx: &mut Context, player_info: Vec<PlayerInfo>, settings: &Settings) -> DotsState {
+        let mut team_names = Vec::new();
+        let mut team_colors = Vec::new();
+        let mut ai_players = Vec::new();
+        let given = player_info.len();
+        for (i, info) in player_info
+            .into_iter()
+            .chain(PlayerInfo::defaults().into_iter().skip(given))
+            .take(2)
+            .enumerate()
+        {
+            if info.is_ai {
+                ai_players.push(AI::new(i as i32 + 1, settings.ai_difficulty));
+            }
+            team_names.push(info.name);
+            team_colors.push(info.color);
+        }
+        //The turn indicator names team i with names[i], so names[0] is left unused
+        let mut indicator_names = vec![String::new()];
+        indicator_names.extend(team_names.iter().cloned());
+        let buttons_y = BOARD_POSITION.1 + BOARD_PIXELS - 45.0;
+        let mut state = DotsState {
+            board: Board::new(settings.dots),
+            team_names,
+            team_colors,
+            team: 1,
+            turn_indicator: TurnIndicator::new(indicator_names),
+            ai_players,
+            ai_difficulty: settings.ai_difficulty,
+            ai_move_delay: settings.ai_move_delay,
+            aim: None,
+            frames: 0,
+            hover: None,
+            last_edge: None,
+            message: String::new(),
+            game_over: false,
+            winner: 0,
+            main_menu_button: create_button(
+                ctx,
+                "Main Menu",
+                (PANEL_POS_X, buttons_y),
+                DOTS_FONT_SIZE,
+                (MyColor::Brown, MyColor::Green),
+            ),
+            play_again_button: create_button(
+                ctx,
+                "Play Again",
+                (PANEL_POS_X, buttons_y - 60.0),
+                DOTS_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            background_color: settings.theme.game_background(),
+            sound_queue: Vec::new(),
+            moves: Vec::new(),
+            elapsed: 0.0,
+            finished_match: None,
+        };
+        state.reset_game();
+        state
+    }
+
+    ///Method to clear the grid with the first player to move
+    fn reset_game(&mut self) {
+        self.board = Board::new(self.board.config);
+        for ai in &mut self.ai_players {
+            ai.last_move_frame = -1;
+        }
+        self.team = 1;
+        self.aim = None;
+        self.hover = None;
+        self.last_edge = None;
+        self.message.clear();
+        self.game_over = false;
+        self.winner = 0;
+        self.moves.clear();
+        self.elapsed = 0.0;
+        self.play_again_button.active = false;
+        self.turn_indicator.reset();
+        self.turn_indicator.change_team(1);
+    }
+
+    ///Returns true if the AI plays for the team
+    fn is_ai(&self, team: i32) -> bool {
+        self.ai_players.iter().any(|ai| ai.team == team)
+    }
+
+    ///Returns the gap between neighbouring dots, fitting the grid in BOARD_PIXELS
+    fn spacing(&self) -> f32 {
+        let config = self.board.config;
+        BOARD_PIXELS / config.rows.max(config.columns) as f32
+    }
+
+    ///Returns the location of a dot on the screen, where x is its column and y its row
+    fn dot_point(&self, pos: GridPosition) -> Point2<f32> {
+        let spacing = self.spacing();
+        Point2 {
+            x: BOARD_POSITION.0 + pos.x as f32 * spacing,
+            y: BOARD_POSITION.1 + pos.y as f32 * spacing,
+        }
+    }
+
+    ///Returns the locations of the dots at either end of an edge
+    fn edge_points(&self, edge: usize) -> [Point2<f32>; 2] {
+        let (start, end) = self.board.edge_ends(edge);
+        [self.dot_point(start), self.dot_point(end)]
+    }
+
+    ///Returns the undrawn edge nearest a point, or None if no undrawn edge is close enough to pick
+    fn edge_at(&self, point: Point2<f32>) -> Option<usize> {
+        let reach = EDGE_PICK_DISTANCE * self.spacing();
+        self.board
+            .undrawn_edges()
+            .into_iter()
+            .map(|edge| {
+                let [start, end] = self.edge_points(edge);
+                (distance_to_segment(point, start, end), edge)
+            })
+            .filter(|(distance, _)| *distance <= reach)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, edge)| edge)
+    }
+
+    ///
+    /// Method to draw an edge for the team to move and claim any boxes it completes. The same team moves again if it
+    /// claimed a box, otherwise the turn passes. Returns false if the edge has already been drawn
+    ///
+    /// # Arguments
+    /// * edge = Number of the edge to draw, see Board for how edges are numbered
+    ///
+    fn play(&mut self, edge: usize) -> bool {
+        let claimed = match self.board.play(edge, self.team) {
+            Some(claimed) => claimed,
+            None => {
+                self.sound_queue.push(SoundEffect::InvalidMove);
+                return false;
+            }
+        };
+        self.moves.push(edge as i32);
+        self.sound_queue.push(SoundEffect::Drop);
+        let (start, end) = self.board.edge_ends(edge);
+        println!(
+            "{} draws ({}, {}) to ({}, {}) and claims {}",
+            self.team_names[self.team as usize - 1],
+            start.x,
+            start.y,
+            end.x,
+            end.y,
+            claimed
+        );
+        self.last_edge = Some(edge);
+        self.hover = None;
+        if self.board.is_over() {
+            self.end_game();
+        } else if claimed > 0 {
+            self.message = format!(
+                "{} claims {} and moves again",
+                self.team_names[self.team as usize - 1],
+                if claimed == 1 { "a box" } else { "two boxes" }
+            );
+        } else {
+            self.message.clear();
+            self.team = other_team(self.team);
+            self.turn_indicator.change_team(self.team);
+        }
+        true
+    }
+
+    ///Method to end the game with a win for the team with more boxes, or a draw if both have the same number
+    fn end_game(&mut self) {
+        self.game_over = true;
+        self.message.clear();
+        self.winner = self.board.leader();
+        println!(
+            "Game ends {} - {}",
+            self.board.count(1),
+            self.board.count(2)
+        );
+        self.turn_indicator.change_team(self.winner);
+        self.turn_indicator.game_ends();
+        self.play_again_button.active = true;
+        self.finish_match(self.winner);
+    }
+
+    ///Method to play the end-of-game sound and record the finished game so the main menu can save it to the match history
+    fn finish_match(&mut self, winner: i32) {
+        let ai_teams: Vec<i32> = self.ai_players.iter().map(|ai| ai.team).collect();
+        self.sound_queue.push(result_sound(winner, &ai_teams));
+        self.finished_match = Some(MatchRecord::for_teams(
+            "Dots and Boxes",
+            &self.team_names,
+            &ai_teams,
+            self.ai_difficulty,
+            self.moves.clone(),
+            winner,
+            self.elapsed,
+        ));
+    }
+
+    ///Method to draw the claimed boxes, the edges, the edge under the mouse or picked by the AI, and the dots
+    fn draw_board(&self, ctx: &mut Context) -> GameResult {
+        let spacing = self.spacing();
+        let mut mb = graphics::MeshBuilder::new();
+        //Fill each claimed box with a faded version of its owner's color
+        for square in 0..self.board.config.boxes() {
+            let owner = self.board.owner(square);
+            if owner == 0 {
+                continue;
+            }
+            let corner = self.dot_point(self.board.box_position(square));
+            let mut color = self.team_colors[owner as usize - 1].get_draw_color();
+            color.a = 0.5;
+            mb.rectangle(
+                graphics::DrawMode::fill(),
+                graphics::Rect::new(corner.x, corner.y, spacing, spacing),
+                color,
+            );
+        }
+        //Undrawn edges are drawn faintly so the grid can be seen, drawn edges in the color of the team that drew them
+        let config = self.board.config;
+        for edge in 0..config.edges() {
+            let team = self.board.edge(edge);
+            let (color, width) = if team == 0 {
+                (graphics::Color::from_rgba(128, 128, 128, 60), 1.0)
+            } else if self.last_edge == Some(edge) {
+                (
+                    self.team_colors[team as usize - 1].get_draw_color(),
+                    2.0 * EDGE_WIDTH,
+                )
+            } else {
+                (
+                    self.team_colors[team as usize - 1].get_draw_color(),
+                    EDGE_WIDTH,
+                )
+            };
+            mb.line(&self.edge_points(edge), width, color)?;
+        }
+        if let Some(edge) = self.hover.or(self.aim) {
+            mb.line(
+                &self.edge_points(edge),
+                EDGE_WIDTH,
+                MyColor::Yellow.get_draw_color(),
+            )?;
+        }
+        for y in 0..=config.rows {
+            for x in 0..=config.columns {
+                mb.circle(
+                    graphics::DrawMode::fill(),
+                    self.dot_point(GridPosition::new(x, y)),
+                    DOT_RADIUS,
+                    0.5,
+                    graphics::WHITE,
+                );
+            }
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))
+    }
+
+    ///Method to draw the players with their box counts, the boxes left and any message to the right of the grid
+    fn draw_panel(&self, ctx: &mut Context) -> GameResult {
+        draw_text(
+            ctx,
+            "Dots and Boxes",
+            (PANEL_POS_X, BOARD_POSITION.1),
+            DOTS_FONT_SIZE,
+            MyColor::White,
+        )?;
+        for (i, name) in self.team_names.iter().enumerate() {
+            let team = i as i32 + 1;
+            let player = if self.is_ai(team) {
+                format!("{} (AI)", name)
+            } else {
+                name.clone()
+            };
+            let y = BOARD_POSITION.1 + 50.0 + 70.0 * i as f32;
+            draw_text(
+                ctx,
+                &player,
+                (PANEL_POS_X, y),
+                DOTS_FONT_SIZE,
+                self.team_colors[i],
+            )?;
+            draw_text(
+                ctx,
+                &format!("Boxes: {}", self.board.count(team)),
+                (PANEL_POS_X + 20.0, y + 30.0),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        let open = self.board.config.boxes() - self.board.count(1) - self.board.count(2);
+        let mut lines = vec![format!("Boxes left: {}", open)];
+        if !self.message.is_empty() {
+            lines.push(self.message.clone());
+        }
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(
+                ctx,
+                line,
+                (PANEL_POS_X, BOARD_POSITION.1 + 200.0 + 30.0 * i as f32),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl GameScreen for DotsState {
+    fn screen_size(&self) -> (f32, f32) {
+        DOTS_SCREEN_SIZE
+    }
+
+    ///Update method for Dots and Boxes, which lets the AI pick an edge, show it and draw it once enough frames pass
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        self.frames += 1; //Timing mechanism for AI moves
+        if self.game_over {
+            return Ok(());
+        }
+        self.elapsed += ggez::timer::delta(_ctx).as_secs_f32();
+        let team = self.team;
+        if let Some(i) = self.ai_players.iter().position(|ai| ai.team == team) {
+            if self.ai_players[i].last_move_frame < 0 {
+                self.aim = self.ai_players[i].pick_move(&self.board, &mut rand::thread_rng());
+                self.ai_players[i].last_move_frame = self.frames as i32;
+            } else if self.frames
+                > (self.ai_players[i].last_move_frame + self.ai_move_delay) as usize
+            {
+                self.ai_players[i].last_move_frame = -1;
+                if let Some(edge) = self.aim.take() {
+                    self.play(edge);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    ///Draw method for Dots and Boxes, which draws the turn indicator, the grid, the player panel and the buttons
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::clear(ctx, self.background_color);
+        self.turn_indicator
+            .draw(ctx, BOARD_POSITION.0 + BOARD_PIXELS / 2.0)?;
+        self.draw_board(ctx)?;
+        self.draw_panel(ctx)?;
+        self.main_menu_button.draw(ctx)?;
+        self.play_again_button.draw(ctx)?;
+        graphics::present(ctx)?;
+        ggez::timer::yield_now();
+        Ok(())
+    }
+
+    ///Method active whenever the mouse moves, which tracks the undrawn edge under it during a human player's turn
+    fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        self.hover = if self.game_over || self.is_ai(self.team) {
+            None
+        } else {
+            self.edge_at(mouse_location(ctx))
+        };
+        self.main_menu_button.check_button_under_mouse(ctx);
+        self.play_again_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method to handle a click on the Dots and Boxes screen, returning what the main menu should do next
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton) -> GameAction {
+        self.mouse_motion_event(ctx);
+        if self.main_menu_button.check_button_under_mouse(ctx) {
+            println!("Main Menu Button pressed; Main Menu should pop up");
+            self.sound_queue.push(SoundEffect::Click);
+            return GameAction::MainMenu;
+        }
+        if self.play_again_button.check_button_under_mouse(ctx) {
+            println!("Play Again button pressed; Grid cleared");
+            self.sound_queue.push(SoundEffect::Click);
+            self.reset_game();
+        } else if button == MouseButton::Left {
+            if let Some(edge) = self.hover {
+                self.play(edge);
+                self.mouse_motion_event(ctx);
+            }
+        }
+        GameAction::None
+    }
+
+    fn drain_sounds(&mut self) -> Vec<SoundEffect> {
+        self.sound_queue.drain(..).collect()
+    }
+
+    fn take_finished_match(&mut self) -> Option<MatchRecord> {
+        self.finished_match.take()
+    }
+}
+
+//NOTE: Tests not included since testing requires usage of mouse and other UI features
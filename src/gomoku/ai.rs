@@ -0,0 +1,545 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::{other_team, GridPosition};
+use gomoku::rules::GomokuRules;
+use mnk::board::{Board, DIRECTIONS};
+
+///Constant score of a line of connect cells holding n of a team's stones and none of the other team's, as
+///LINE_SCORES[n]. A stone is scored by every line it would join
+const LINE_SCORES: [i32; 7] = [0, 1, 8, 64, 512, 4_096, 32_768];
+
+///Constant share, in percent, of the score for blocking the other team that is added to the score for attacking
+const DEFENSE_WEIGHT: i32 = 90;
+
+///Constant most threats the AI tries at each step of a threat-space search, best first
+const THREAT_MOVES: usize = 12;
+
+///
+/// Returns the empty cells worth considering: every cell within two cells of a stone, or the center of an empty
+/// board
+///
+/// # Arguments
+/// * board = Board to pick cells on
+///
+fn nearby_cells(board: &Board) -> Vec<usize> {
+    if board.marks() == 0 {
+        let center = GridPosition::new(board.config.columns / 2, board.config.rows / 2);
+        return vec![board.index(center)];
+    }
+    board
+        .empty_cells()
+        .into_iter()
+        .filter(|cell| {
+            let pos = board.position(*cell);
+            (-2..3).any(|dy| {
+                (-2..3).any(|dx| board.get(GridPosition::new(pos.x + dx, pos.y + dy)) != 0)
+            })
+        })
+        .collect()
+}
+
+///
+/// Returns how much a stone in an empty cell would add to a team's lines: the LINE_SCORES of every line of connect
+/// cells through the cell that the other team has no stones in, counting the new stone
+///
+/// # Arguments
+/// * board = Board with the cell empty
+/// * cell  = Number of the cell scored
+/// * team  = Integer value (1 or 2) of the team the stone would be for
+///
+fn line_score(board: &Board, cell: usize, team: i32) -> i32 {
+    let connect = board.config.connect;
+    let pos = board.position(cell);
+    let mut score = 0;
+    for dir in DIRECTIONS.iter() {
+        for start in 0..connect {
+            let mut own = 1;
+            let mut open = true;
+            for i in 0..connect {
+                let step = i - start;
+                let target = GridPosition::new(pos.x + step * dir.0, pos.y + step * dir.1);
+                if step == 0 {
+                    continue;
+                }
+                if !board.on_board(target) || board.get(target) == other_team(team) {
+                    open = false;
+                    break;
+                }
+                if board.get(target) == team {
+                    own += 1;
+                }
+            }
+            if open {
+                score += LINE_SCORES[(own as usize).min(LINE_SCORES.len() - 1)];
+            }
+        }
+    }
+    score
+}
+
+///Returns the score of a stone in an empty cell for a team, adding the lines it builds to those it blocks
+fn cell_score(board: &Board, cell: usize, team: i32) -> i32 {
+    line_score(board, cell, team) + line_score(board, cell, other_team(team)) * DEFENSE_WEIGHT / 100
+}
+
+///
+/// Returns the moves that make a four for a team, followed by those that make an open three if threes is true, best
+/// scoring first. Moves forbidden to the team are left out
+///
+/// # Arguments
+/// * board  = Board to search, which is changed while searching and left as it was
+/// * rules  = GomokuRules the game is played with
+/// * team   = Integer value (1 or 2) of the team making threats
+/// * threes = Boolean indicating if open threes are included as well as fours
+///
+fn threat_moves(board: &mut Board, rules: GomokuRules, team: i32, threes: bool) -> Vec<usize> {
+    let mut fours = Vec::new();
+    let mut open_threes = Vec::new();
+    for cell in nearby_cells(board) {
+        let score = cell_score(board, cell, team);
+        board.place(cell, team);
+        if rules.foul(board, cell).is_none() {
+            if rules.fours(board, cell) > 0 {
+                fours.push((score, cell));
+            } else if threes && rules.open_threes(board, cell) > 0 {
+                open_threes.push((score, cell));
+            }
+        }
+        board.clear(cell);
+    }
+    fours.sort_by_key(|&(score, _)| -score);
+    open_threes.sort_by_key(|&(score, _)| -score);
+    fours
+        .into_iter()
+        .chain(open_threes)
+        .take(THREAT_MOVES)
+        .map(|(_, cell)| cell)
+        .collect()
+}
+
+///
+/// Returns the cells where the defender could stop an open three made by the stone in a cell: any empty cell near it
+/// after which the attacker has neither a four nor an open three through it
+///
+/// # Arguments
+/// * board    = Board with the attacker's stone placed, which is changed while searching and left as it was
+/// * rules    = GomokuRules the game is played with
+/// * cell     = Number of the cell holding the stone that made the three
+/// * defender = Integer value (1 or 2) of the team defending
+///
+fn three_defenses(board: &mut Board, rules: GomokuRules, cell: usize, defender: i32) -> Vec<usize> {
+    let pos = board.position(cell);
+    let reach = board.config.connect - 1;
+    let mut defenses = Vec::new();
+    for dir in DIRECTIONS.iter() {
+        for steps in (-reach..=reach).filter(|steps| *steps != 0) {
+            let target = GridPosition::new(pos.x + steps * dir.0, pos.y + steps * dir.1);
+            if !board.on_board(target) || board.get(target) != 0 {
+                continue;
+            }
+            let defense = board.index(target);
+            if defenses.contains(&defense) || !rules.is_legal(board, defense, defender) {
+                continue;
+            }
+            board.place(defense, defender);
+            if rules.fours(board, cell) == 0 && rules.open_threes(board, cell) == 0 {
+                defenses.push(defense);
+            }
+            board.clear(defense);
+        }
+    }
+    defenses
+}
+
+///
+/// Returns true if the attacker wins after the defender places a stone in a cell, by going on with threats. The
+/// defender loses if the cell is forbidden to them, and the attacker gives up if the defender's stone wins or makes a
+/// four of their own
+///
+/// # Arguments
+/// * board    = Board to search, which is changed while searching and left as it was
+/// * rules    = GomokuRules the game is played with
+/// * attacker = Integer value (1 or 2) of the team making threats
+/// * defense  = Number of the cell the defender places their stone in
+/// * depth    = Number of threats the attacker may still make
+/// * threes   = Number of those threats that may be open threes rather than fours
+///
+fn wins_after_defense(
+    board: &mut Board,
+    rules: GomokuRules,
+    attacker: i32,
+    defense: usize,
+    depth: i32,
+    threes: i32,
+) -> bool {
+    let defender = other_team(attacker);
+    if !rules.is_legal(board, defense, defender) {
+        return true;
+    }
+    board.place(defense, defender);
+    let wins = !rules.is_five(board, defense)
+        && rules.fours(board, defense) == 0
+        && threat_search(board, rules, attacker, depth, threes).is_some();
+    board.clear(defense);
+    wins
+}
+
+///
+/// Returns the first stone of a sequence of threats that wins for the attacker however the defender answers, or None
+/// if none is found. A four must be blocked where it would win, and an open three at one of the cells that stop it.
+/// The defender's own counter-threats are only checked where they win or make a four on the spot
+///
+/// # Arguments
+/// * board    = Board to search, which is changed while searching and left as it was
+/// * rules    = GomokuRules the game is played with, which must place one stone a turn
+/// * attacker = Integer value (1 or 2) of the team making threats
+/// * depth    = Number of threats the attacker may make
+/// * threes   = Number of those threats that may be open threes rather than fours. With none, this is a search for a
+///   victory by continuous fours
+///
+pub fn threat_search(
+    board: &mut Board,
+    rules: GomokuRules,
+    attacker: i32,
+    depth: i32,
+    threes: i32,
+) -> Option<usize> {
+    if depth <= 0 {
+        return None;
+    }
+    let defender = other_team(attacker);
+    for cell in threat_moves(board, rules, attacker, threes > 0) {
+        board.place(cell, attacker);
+        let wins = if rules.is_five(board, cell) {
+            true
+        } else {
+            let completions = rules.winning_cells(board, attacker);
+            match completions.len() {
+                0 => {
+                    let defenses = three_defenses(board, rules, cell, defender);
+                    defenses.iter().all(|defense| {
+                        wins_after_defense(board, rules, attacker, *defense, depth - 1, threes - 1)
+                    })
+                }
+                1 => wins_after_defense(board, rules, attacker, completions[0], depth - 1, threes),
+                _ => true,
+            }
+        };
+        board.clear(cell);
+        if wins {
+            return Some(cell);
+        }
+    }
+    None
+}
+
+///
+/// A struct representing an AI or bot player for Gomoku, Renju and Connect6 which has methods to pick its stones
+///
+/// # Fields
+/// * team            = Integer value (1 or 2) of the team the AI plays for
+/// * difficulty      = Integer value for how hard the AI looks for threats; see pick_stones
+/// * last_move_frame = Integer used to track when the AI placed its last stone. Set to -1 until it picks each turn
+///
+pub struct AI {
+    pub team: i32,
+    difficulty: i32,
+    pub last_move_frame: i32,
+}
+
+impl AI {
+    ///
+    /// Method to initialize and return an AI object
+    ///
+    /// # Arguments
+    /// * team       = Integer value (1 or 2) of the team the AI plays for
+    /// * difficulty = Integer value for how hard the AI looks for threats
+    ///
+    pub fn new(team: i32, difficulty: i32) -> Self {
+        AI {
+            team,
+            difficulty,
+            last_move_frame: -1,
+        }
+    }
+
+    ///Returns the most threats searched for a win (depth) and how many of them may be open threes (threes)
+    fn search_limits(&self) -> (i32, i32) {
+        match self.difficulty {
+            d if d <= 1 => (0, 0),
+            2 => (8, 0),
+            3 => (10, 1),
+            4 => (12, 2),
+            _ => (14, 3),
+        }
+    }
+
+    ///
+    /// Method to pick the stones to place this turn, in order. Each stone wins if it can and blocks a win for the other
+    /// team otherwise. Under Gomoku and Renju, the AI then looks for a winning sequence of threats (fours only from
+    /// difficulty 2, open threes too from 3) and blocks the other team's from difficulty 3, before falling back on the
+    /// cell that builds and blocks the most lines. Connect6 moves use the win, block and line checks for each stone
+    ///
+    /// # Arguments
+    /// * board  = Board with the AI's team to move
+    /// * rules  = GomokuRules the game is played with
+    /// * stones = Number of stones to place this turn
+    ///
+    pub fn pick_stones(&self, board: &Board, rules: GomokuRules, stones: usize) -> Vec<usize> {
+        let mut board = board.clone();
+        let mut picks = Vec::new();
+        for left in (1..=stones).rev() {
+            let cell = if rules == GomokuRules::Connect6 {
+                self.pick_connect6_stone(&mut board, left)
+            } else {
+                self.pick_stone(&mut board, rules)
+            };
+            match cell {
+                Some(cell) => {
+                    board.place(cell, self.team);
+                    picks.push(cell);
+                    if rules.is_five(&board, cell) {
+                        break;
+                    }
+                }
+                None => break,
+            }
+        }
+        picks
+    }
+
+    ///Returns the legal nearby cell with the best cell_score for the AI, or None if there is nowhere to play
+    fn best_scoring(&self, board: &mut Board, rules: GomokuRules) -> Option<usize> {
+        let mut best: Option<(i32, usize)> = None;
+        for cell in nearby_cells(board) {
+            if !rules.is_legal(board, cell, self.team) {
+                continue;
+            }
+            let score = cell_score(board, cell, self.team);
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, cell));
+            }
+        }
+        best.map(|(_, cell)| cell).or_else(|| {
+            board
+                .empty_cells()
+                .into_iter()
+                .find(|cell| rules.is_legal(board, *cell, self.team))
+        })
+    }
+
+    ///Returns the stone to place under Gomoku or Renju, where each turn is one stone
+    fn pick_stone(&self, board: &mut Board, rules: GomokuRules) -> Option<usize> {
+        let other = other_team(self.team);
+        if let Some(cell) = rules.winning_cells(board, self.team).into_iter().next() {
+            return Some(cell);
+        }
+        let threats = rules.winning_cells(board, other);
+        if let Some(cell) = threats
+            .iter()
+            .find(|cell| rules.is_legal(board, **cell, self.team))
+        {
+            return Some(*cell);
+        }
+        let (depth, threes) = self.search_limits();
+        if threats.is_empty() {
+            if let Some(cell) = threat_search(board, rules, self.team, depth, threes) {
+                return Some(cell);
+            }
+        }
+        //Take the key point of the other team's winning sequence before they can play it
+        if threes > 0 {
+            if let Some(cell) = threat_search(board, rules, other, depth, threes)
+                .filter(|cell| rules.is_legal(board, *cell, self.team))
+            {
+                return Some(cell);
+            }
+        }
+        self.best_scoring(board, rules)
+    }
+
+    ///
+    /// Returns the next stone to place under Connect6. A line of six holding only the AI's stones and needing no more
+    /// than the stones left this turn is completed, then any line where the other team could make six next turn is
+    /// blocked, covering as many of those lines as one stone can
+    ///
+    /// # Arguments
+    /// * board = Board with the stones already picked this turn placed
+    /// * left  = Number of stones left to place this turn, counting this one
+    ///
+    fn pick_connect6_stone(&self, board: &mut Board, left: usize) -> Option<usize> {
+        let other = other_team(self.team);
+        let windows = board.windows();
+        let count = |board: &Board, window: &[usize], team: i32| {
+            window.iter().filter(|c| board.cell(**c) == team).count()
+        };
+        let connect = board.config.connect as usize;
+        for window in &windows {
+            if count(board, window, other) == 0 && count(board, window, self.team) + left >= connect
+            {
+                return window.iter().find(|c| board.cell(**c) == 0).cloned();
+            }
+        }
+        //The other team places two stones next turn, so any line they hold four of is a threat
+        let threats: Vec<&Vec<usize>> = windows
+            .iter()
+            .filter(|window| {
+                count(board, window, self.team) == 0 && count(board, window, other) + 2 >= connect
+            })
+            .collect();
+        let mut best: Option<(usize, i32, usize)> = None;
+        for threat in &threats {
+            for cell in threat.iter().filter(|c| board.cell(**c) == 0) {
+                let covered = threats.iter().filter(|t| t.contains(cell)).count();
+                let score = cell_score(board, *cell, self.team);
+                if best.is_none_or(|(c, s, _)| (covered, score) > (c, s)) {
+                    best = Some((covered, score, *cell));
+                }
+            }
+        }
+        best.map(|(_, _, cell)| cell)
+            .or_else(|| self.best_scoring(board, GomokuRules::Connect6))
+    }
+}
+
+#[cfg(test)]
+mod ai_tests {
+    use super::*;
+
+    //Method to create a board for the rules with stones for team 1 and team 2 at the given (column, row) positions
+    fn create_test_board(rules: GomokuRules, ones: &[(i32, i32)], twos: &[(i32, i32)]) -> Board {
+        let mut board = Board::new(rules.config());
+        for (team, stones) in [(1, ones), (2, twos)].iter() {
+            for pos in stones.iter() {
+                let cell = board.index(GridPosition::new(pos.0, pos.1));
+                board.place(cell, *team);
+            }
+        }
+        board
+    }
+
+    //Method to return the number of the cell at a (column, row) position
+    fn cell(board: &Board, pos: (i32, i32)) -> usize {
+        board.index(GridPosition::new(pos.0, pos.1))
+    }
+
+    mod threat_search {
+        use super::*;
+
+        #[test]
+        fn should_find_victory_by_continuous_fours() {
+            //Fours at (5, 2) and (5, 3), in either order, end in a double four
+            let rules = GomokuRules::FreeStyle;
+            let mut board = create_test_board(
+                rules,
+                &[
+                    (2, 2),
+                    (3, 2),
+                    (4, 2),
+                    (2, 3),
+                    (3, 3),
+                    (4, 3),
+                    (5, 4),
+                    (5, 5),
+                ],
+                &[(1, 2), (1, 3), (5, 6), (1, 1), (6, 6), (2, 1)],
+            );
+            assert_eq!(threat_search(&mut board, rules, 1, 1, 0), None);
+            let start = threat_search(&mut board, rules, 1, 2, 0).unwrap();
+            assert!(start == cell(&board, (5, 2)) || start == cell(&board, (5, 3)));
+            board.place(start, 1);
+            assert_eq!(rules.fours(&mut board, start), 1);
+        }
+
+        #[test]
+        fn should_only_win_with_double_three_when_searching_threes() {
+            let rules = GomokuRules::FreeStyle;
+            let mut board = create_test_board(rules, &[(5, 7), (6, 7), (7, 5), (7, 6)], &[(0, 0)]);
+            assert_eq!(threat_search(&mut board, rules, 1, 3, 0), None);
+            assert_eq!(
+                threat_search(&mut board, rules, 1, 3, 1),
+                Some(cell(&board, (7, 7)))
+            );
+            //Under Renju the same point is forbidden to black
+            let rules = GomokuRules::Renju;
+            assert_eq!(threat_search(&mut board, rules, 1, 3, 1), None);
+        }
+    }
+
+    mod ai {
+        use super::*;
+
+        mod pick_stones {
+            use super::*;
+
+            #[test]
+            fn should_win_before_blocking() {
+                let rules = GomokuRules::FreeStyle;
+                let board = create_test_board(
+                    rules,
+                    &[(3, 3), (4, 3), (5, 3), (6, 3)],
+                    &[(3, 5), (4, 5), (5, 5), (6, 5), (2, 3)],
+                );
+                assert_eq!(
+                    AI::new(2, 1).pick_stones(&board, rules, 1),
+                    vec![cell(&board, (2, 5))]
+                );
+                assert_eq!(
+                    AI::new(1, 1).pick_stones(&board, rules, 1),
+                    vec![cell(&board, (7, 3))]
+                );
+            }
+
+            #[test]
+            fn should_block_a_four() {
+                let rules = GomokuRules::FreeStyle;
+                let board =
+                    create_test_board(rules, &[(3, 3), (4, 3), (5, 3), (6, 3)], &[(2, 3), (8, 8)]);
+                assert_eq!(
+                    AI::new(2, 3).pick_stones(&board, rules, 1),
+                    vec![cell(&board, (7, 3))]
+                );
+            }
+
+            #[test]
+            fn should_never_play_a_forbidden_point_under_renju() {
+                //(7, 7) is the best point for black but would make a double three
+                let rules = GomokuRules::Renju;
+                let mut board =
+                    create_test_board(rules, &[(5, 7), (6, 7), (7, 5), (7, 6)], &[(9, 9)]);
+                let picks = AI::new(1, 1).pick_stones(&board, rules, 1);
+                assert_eq!(picks.len(), 1);
+                assert_ne!(picks[0], cell(&board, (7, 7)));
+                assert!(rules.is_legal(&mut board, picks[0], 1));
+            }
+
+            #[test]
+            fn should_start_in_center_of_empty_board() {
+                let rules = GomokuRules::Connect6;
+                let board = Board::new(rules.config());
+                assert_eq!(
+                    AI::new(1, 3).pick_stones(&board, rules, 1),
+                    vec![cell(&board, (9, 9))]
+                );
+            }
+
+            #[test]
+            fn should_place_two_stones_to_finish_or_block_connect6() {
+                let rules = GomokuRules::Connect6;
+                let row: Vec<(i32, i32)> = (3..7).map(|x| (x, 9)).collect();
+                let board = create_test_board(rules, &row, &[(2, 9), (12, 12), (13, 12)]);
+                let mut wins = AI::new(1, 2).pick_stones(&board, rules, 2);
+                wins.sort();
+                assert_eq!(wins, vec![cell(&board, (7, 9)), cell(&board, (8, 9))]);
+                let blocks = AI::new(2, 2).pick_stones(&board, rules, 2);
+                assert_eq!(blocks.len(), 2);
+                assert!(
+                    blocks.contains(&cell(&board, (7, 9)))
+                        || blocks.contains(&cell(&board, (8, 9)))
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,590 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use connect4::button::{create_button, draw_text, mouse_location, Button};
+use connect4::core::{other_team, GameAction, GridPosition, MyColor, PlayerInfo};
+use ggez::input::mouse::MouseButton;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+use gomoku::ai::AI;
+use gomoku::rules::GomokuRules;
+use history::MatchRecord;
+use mnk::board::Board;
+use screen::GameScreen;
+use settings::Settings;
+use sound::{result_sound, SoundEffect};
+
+///Constant dimensions for the Gomoku window, fitting the board with a panel of players and buttons to its right
+pub const GOMOKU_SCREEN_SIZE: (f32, f32) = (900.0, 660.0);
+
+///Constant top-left corner of the square the board is drawn in
+const BOARD_POSITION: (f32, f32) = (40.0, 90.0);
+
+///Constant width and height of the square the board is drawn in. The lines are closer together on larger boards
+const BOARD_PIXELS: f32 = 540.0;
+
+///Constant x location of the panel of players and buttons to the right of the board
+const PANEL_POS_X: f32 = 620.0;
+
+///Constant font size for the panel text and buttons
+const GOMOKU_FONT_SIZE: f32 = 24.0;
+
+///Constant stone names for each team, where STONE_NAMES[i] is team i + 1's
+const STONE_NAMES: [&str; 2] = ["Black", "White"];
+
+///
+/// A struct that contains the state of a game of Gomoku, Renju or Connect6. Stones are placed on the points where the
+/// lines of the board cross, and team 1 (black) moves first
+///
+/// # Fields
+/// * rules             = GomokuRules the game is played with
+/// * board             = Board holding the stones placed so far, with one cell for each point
+/// * team_names        = Vector of player names, where team_names[i] is the name for team i + 1
+/// * team_colors       = Vector of MyColor objects, where team_colors[i] is the color team i + 1's stones are drawn in
+/// * team              = Integer value (1 or 2) of the team to move
+/// * stones_left       = Number of stones the team still has to place this turn
+/// * forbidden         = Vector of the points black may not play under Renju, found at the start of black's turn
+/// * ai_players        = Vector of AI objects, one for each team played by the AI
+/// * ai_difficulty     = Difficulty given to the AI players, recorded in the match history
+/// * ai_move_delay     = Number of frames an AI shows the points it picked before placing each stone
+/// * aims              = Vector of the points the AI is about to place stones on, in order
+/// * frames            = Integer counter for the number of times the update method is called; times AI moves
+/// * hover             = Option of the empty point under the mouse during a human player's turn, or None
+/// * game_over         = Boolean indicating if the game has been won or drawn
+/// * winner            = Integer value (1 or 2) of the team that won, or 0 if there is no winner
+/// * winning_run       = Vector of the points in the winning run, circled once the game is won
+/// * message           = Text shown under the players, e.g. why a point is forbidden
+/// * main_menu_button  = Button drawn to return to the main menu
+/// * play_again_button = Button drawn to clear the board and start a new game with the same players
+/// * background_color  = Color drawn behind the board
+/// * sound_queue       = Vector of SoundEffect enums triggered since the main menu last played them
+/// * moves             = Vector of points played so far, in order, each stored as row * size + column
+/// * elapsed           = Seconds the current game has been played for
+/// * finished_match    = Option of a MatchRecord for a game that just ended, taken by the main menu to be saved
+///
+pub struct GomokuState {
+    rules: GomokuRules,
+    board: Board,
+    team_names: Vec<String>,
+    team_colors: Vec<MyColor>,
+    team: i32,
+    stones_left: usize,
+    forbidden: Vec<usize>,
+    ai_players: Vec<AI>,
+    ai_difficulty: i32,
+    ai_move_delay: i32,
+    aims: Vec<usize>,
+    frames: usize,
+    hover: Option<usize>,
+    game_over: bool,
+    winner: i32,
+    winning_run: Vec<usize>,
+    message: String,
+    main_menu_button: Button,
+    play_again_button: Button,
+    background_color: graphics::Color,
+    pub sound_queue: Vec<SoundEffect>,
+    moves: Vec<i32>,
+    elapsed: f32,
+    pub finished_match: Option<MatchRecord>,
+}
+
+impl GomokuState {
+    ///Constructor - player_info holds the name, color and whether the AI plays for team 1 (black) and team 2 (white),
+    ///in that order, and settings holds the rules, AI and theme options. Teams without player info get the defaults
+    pub fn new(
+        ctx: &mut Context,
+        player_info: Vec<PlayerInfo>,
+        settings: &Settings,
+    ) -> GomokuState {
+        let mut team_names = Vec::new();
+        let mut team_colors = Vec::new();
+        let mut ai_players = Vec::new();
+        let given = player_info.len();
+        for (i, info) in player_info
+            .into_iter()
+            .chain(PlayerInfo::defaults().into_iter().skip(given))
+            .take(2)
+            .enumerate()
+        {
+            if info.is_ai {
+                ai_players.push(AI::new(i as i32 + 1, settings.ai_difficulty));
+            }
+            team_names.push(info.name);
+            team_colors.push(info.color);
+        }
+        let buttons_y = BOARD_POSITION.1 + BOARD_PIXELS - 45.0;
+        let rules = settings.gomoku_rules;
+        let mut state = GomokuState {
+            rules,
+            board: Board::new(rules.config()),
+            team_names,
+            team_colors,
+            team: 1,
+            stones_left: 1,
+            forbidden: Vec::new(),
+            ai_players,
+            ai_difficulty: settings.ai_difficulty,
+            ai_move_delay: settings.ai_move_delay,
+            aims: Vec::new(),
+            frames: 0,
+            hover: None,
+            game_over: false,
+            winner: 0,
+            winning_run: Vec::new(),
+            message: String::new(),
+            main_menu_button: create_button(
+                ctx,
+                "Main Menu",
+                (PANEL_POS_X, buttons_y),
+                GOMOKU_FONT_SIZE,
+                (MyColor::Brown, MyColor::Green),
+            ),
+            play_again_button: create_button(
+                ctx,
+                "Play Again",
+                (PANEL_POS_X, buttons_y - 60.0),
+                GOMOKU_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            background_color: settings.theme.game_background(),
+            sound_queue: Vec::new(),
+            moves: Vec::new(),
+            elapsed: 0.0,
+            finished_match: None,
+        };
+        state.reset_game();
+        state
+    }
+
+    ///Method to clear the board and start again with black to move
+    fn reset_game(&mut self) {
+        self.board = Board::new(self.rules.config());
+        for ai in &mut self.ai_players {
+            ai.last_move_frame = -1;
+        }
+        self.aims.clear();
+        self.hover = None;
+        self.game_over = false;
+        self.winner = 0;
+        self.winning_run.clear();
+        self.message.clear();
+        self.moves.clear();
+        self.elapsed = 0.0;
+        self.play_again_button.active = false;
+        self.start_turn(1);
+    }
+
+    ///Returns true if the AI plays for the team
+    fn is_ai(&self, team: i32) -> bool {
+        self.ai_players.iter().any(|ai| ai.team == team)
+    }
+
+    ///Method to pass the turn to a team, working out how many stones they place and which points are forbidden to them
+    fn start_turn(&mut self, team: i32) {
+        self.team = team;
+        self.stones_left = self.rules.stones_per_turn(self.board.marks());
+        let rules = self.rules;
+        let board = &mut self.board;
+        self.forbidden = if rules == GomokuRules::Renju && team == 1 {
+            board
+                .empty_cells()
+                .into_iter()
+                .filter(|cell| !rules.is_legal(board, *cell, team))
+                .collect()
+        } else {
+            Vec::new()
+        };
+    }
+
+    ///Returns the distance between neighbouring lines of the board
+    fn spacing(&self) -> f32 {
+        BOARD_PIXELS / self.board.config.rows as f32
+    }
+
+    ///Returns the location of the point where the lines through a cell cross
+    fn point_center(&self, cell: usize) -> Point2<f32> {
+        let pos = self.board.position(cell);
+        let spacing = self.spacing();
+        Point2 {
+            x: BOARD_POSITION.0 + (pos.x as f32 + 0.5) * spacing,
+            y: BOARD_POSITION.1 + (pos.y as f32 + 0.5) * spacing,
+        }
+    }
+
+    ///Returns the point nearest a location, or None if it is off the board
+    fn point_at(&self, loc: Point2<f32>) -> Option<usize> {
+        let spacing = self.spacing();
+        let pos = GridPosition::new(
+            ((loc.x - BOARD_POSITION.0) / spacing).floor() as i32,
+            ((loc.y - BOARD_POSITION.1) / spacing).floor() as i32,
+        );
+        if self.board.on_board(pos) {
+            Some(self.board.index(pos))
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Method to place a stone for the team to move, then end the game if it won or filled the board, or pass the turn
+    /// once the team has placed all its stones. Returns false if the point is taken or forbidden
+    ///
+    /// # Arguments
+    /// * cell = Number of the point to place a stone on, counted row by row from the top-left corner
+    ///
+    fn play(&mut self, cell: usize) -> bool {
+        if self.forbidden.contains(&cell) {
+            //Say why the point is forbidden
+            self.board.place(cell, self.team);
+            let reason = self
+                .rules
+                .foul(&mut self.board, cell)
+                .unwrap_or("forbidden");
+            self.board.clear(cell);
+            self.message = format!("Forbidden: {}", reason);
+            self.sound_queue.push(SoundEffect::InvalidMove);
+            return false;
+        }
+        if !self.board.place(cell, self.team) {
+            self.sound_queue.push(SoundEffect::InvalidMove);
+            return false;
+        }
+        self.message.clear();
+        self.moves.push(cell as i32);
+        self.sound_queue.push(SoundEffect::Drop);
+        let name = self.team_names[self.team as usize - 1].clone();
+        let pos = self.board.position(cell);
+        println!("{} places a stone at ({}, {})", name, pos.x, pos.y);
+        if self.rules.is_five(&self.board, cell) {
+            println!("{} wins; Game ends", name);
+            self.game_over = true;
+            self.winner = self.team;
+            self.winning_run = self.board.winning_run(cell).unwrap_or_default();
+            self.finish_match(self.team);
+        } else if self.board.is_full() {
+            println!("Board is full; Game ends in a draw");
+            self.game_over = true;
+            self.finish_match(0);
+        } else {
+            self.stones_left -= 1;
+            if self.stones_left == 0 {
+                self.start_turn(other_team(self.team));
+            }
+        }
+        self.hover = None;
+        self.play_again_button.active = self.game_over;
+        true
+    }
+
+    ///Method to play the end-of-game sound and record the finished game so the main menu can save it to the match history
+    fn finish_match(&mut self, winner: i32) {
+        let ai_teams: Vec<i32> = self.ai_players.iter().map(|ai| ai.team).collect();
+        self.sound_queue.push(result_sound(winner, &ai_teams));
+        self.finished_match = Some(MatchRecord::for_teams(
+            self.rules.name(),
+            &self.team_names,
+            &ai_teams,
+            self.ai_difficulty,
+            self.moves.clone(),
+            winner,
+            self.elapsed,
+        ));
+    }
+
+    ///Method to draw the lines, star points, stones and markers of the board
+    fn draw_board(&self, ctx: &mut Context) -> GameResult {
+        let size = self.board.config.rows;
+        let spacing = self.spacing();
+        let first = 0.5 * spacing;
+        let last = (size as f32 - 0.5) * spacing;
+        let mut mb = graphics::MeshBuilder::new();
+        mb.rectangle(
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(
+                BOARD_POSITION.0,
+                BOARD_POSITION.1,
+                BOARD_PIXELS,
+                BOARD_PIXELS,
+            ),
+            graphics::Color::from_rgba(220, 180, 110, 255),
+        );
+        for i in 0..size {
+            let offset = (i as f32 + 0.5) * spacing;
+            mb.line(
+                &[
+                    Point2 {
+                        x: BOARD_POSITION.0 + offset,
+                        y: BOARD_POSITION.1 + first,
+                    },
+                    Point2 {
+                        x: BOARD_POSITION.0 + offset,
+                        y: BOARD_POSITION.1 + last,
+                    },
+                ],
+                1.0,
+                graphics::BLACK,
+            )?;
+            mb.line(
+                &[
+                    Point2 {
+                        x: BOARD_POSITION.0 + first,
+                        y: BOARD_POSITION.1 + offset,
+                    },
+                    Point2 {
+                        x: BOARD_POSITION.0 + last,
+                        y: BOARD_POSITION.1 + offset,
+                    },
+                ],
+                1.0,
+                graphics::BLACK,
+            )?;
+        }
+        //Star points sit four lines in from each edge and in the center
+        let star = 3;
+        for y in [star, size / 2, size - 1 - star].iter() {
+            for x in [star, size / 2, size - 1 - star].iter() {
+                let cell = self.board.index(GridPosition::new(*x, *y));
+                mb.circle(
+                    graphics::DrawMode::fill(),
+                    self.point_center(cell),
+                    3.0,
+                    0.5,
+                    graphics::BLACK,
+                );
+            }
+        }
+        let radius = spacing * 0.45;
+        for cell in &self.forbidden {
+            let center = self.point_center(*cell);
+            let arm = radius * 0.4;
+            for sign in [-1.0, 1.0].iter() {
+                mb.line(
+                    &[
+                        Point2 {
+                            x: center.x - arm,
+                            y: center.y - sign * arm,
+                        },
+                        Point2 {
+                            x: center.x + arm,
+                            y: center.y + sign * arm,
+                        },
+                    ],
+                    2.0,
+                    MyColor::Red.get_draw_color(),
+                )?;
+            }
+        }
+        for cell in 0..self.board.config.cells() {
+            let team = self.board.cell(cell);
+            if team == 0 {
+                continue;
+            }
+            let center = self.point_center(cell);
+            mb.circle(
+                graphics::DrawMode::fill(),
+                center,
+                radius,
+                0.5,
+                self.team_colors[team as usize - 1].get_draw_color(),
+            );
+            mb.circle(
+                graphics::DrawMode::stroke(1.0),
+                center,
+                radius,
+                0.5,
+                graphics::BLACK,
+            );
+        }
+        //Mark the last stone, circle the winning run, and outline the point the player or AI is about to play
+        if let Some(last_move) = self.moves.last() {
+            mb.circle(
+                graphics::DrawMode::fill(),
+                self.point_center(*last_move as usize),
+                radius * 0.25,
+                0.5,
+                MyColor::Red.get_draw_color(),
+            );
+        }
+        for cell in &self.winning_run {
+            mb.circle(
+                graphics::DrawMode::stroke(3.0),
+                self.point_center(*cell),
+                radius + 2.0,
+                0.5,
+                MyColor::Yellow.get_draw_color(),
+            );
+        }
+        for cell in self.hover.iter().chain(self.aims.iter()) {
+            mb.circle(
+                graphics::DrawMode::stroke(2.0),
+                self.point_center(*cell),
+                radius,
+                0.5,
+                MyColor::Yellow.get_draw_color(),
+            );
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))
+    }
+
+    ///Method to draw the rules, the players, the number of stones played and any message to the right of the board
+    fn draw_panel(&self, ctx: &mut Context) -> GameResult {
+        draw_text(
+            ctx,
+            self.rules.name(),
+            (PANEL_POS_X, BOARD_POSITION.1),
+            GOMOKU_FONT_SIZE,
+            MyColor::White,
+        )?;
+        for (i, name) in self.team_names.iter().enumerate() {
+            let player = if self.is_ai(i as i32 + 1) {
+                format!("{}: {} (AI)", STONE_NAMES[i], name)
+            } else {
+                format!("{}: {}", STONE_NAMES[i], name)
+            };
+            draw_text(
+                ctx,
+                &player,
+                (PANEL_POS_X, BOARD_POSITION.1 + 50.0 + 35.0 * i as f32),
+                GOMOKU_FONT_SIZE,
+                self.team_colors[i],
+            )?;
+        }
+        let mut lines = vec![
+            format!("Get {} in a row to win", self.board.config.connect),
+            format!("Stones: {}", self.moves.len()),
+        ];
+        if self.rules == GomokuRules::Renju {
+            lines.push(String::from("Black may not play on a red X"));
+        }
+        if !self.message.is_empty() {
+            lines.push(self.message.clone());
+        }
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(
+                ctx,
+                line,
+                (PANEL_POS_X, BOARD_POSITION.1 + 150.0 + 30.0 * i as f32),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl GameScreen for GomokuState {
+    fn screen_size(&self) -> (f32, f32) {
+        GOMOKU_SCREEN_SIZE
+    }
+
+    ///Update method for Gomoku, which lets the AI pick its stones for the turn, show them, then place one each delay
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        self.frames += 1; //Timing mechanism for AI moves
+        if self.game_over {
+            return Ok(());
+        }
+        self.elapsed += ggez::timer::delta(_ctx).as_secs_f32();
+        let team = self.team;
+        if let Some(i) = self.ai_players.iter().position(|ai| ai.team == team) {
+            if self.ai_players[i].last_move_frame < 0 {
+                self.aims =
+                    self.ai_players[i].pick_stones(&self.board, self.rules, self.stones_left);
+                self.ai_players[i].last_move_frame = self.frames as i32;
+            } else if self.frames
+                > (self.ai_players[i].last_move_frame + self.ai_move_delay) as usize
+            {
+                if self.aims.is_empty() {
+                    self.ai_players[i].last_move_frame = -1;
+                } else {
+                    let cell = self.aims.remove(0);
+                    self.play(cell);
+                    //Pick again next frame once the turn's stones are placed
+                    self.ai_players[i].last_move_frame = if self.aims.is_empty() {
+                        -1
+                    } else {
+                        self.frames as i32
+                    };
+                }
+            }
+        }
+        Ok(())
+    }
+
+    ///Draw method for Gomoku, which draws the board, the stones, the player panel and the buttons
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::clear(ctx, self.background_color);
+        let name = &self.team_names[self.team as usize - 1];
+        let status = if !self.game_over {
+            let stones = if self.stones_left == 1 {
+                String::from("a stone")
+            } else {
+                format!("{} stones", self.stones_left)
+            };
+            format!("{} to place {}", name, stones)
+        } else if self.winner == 0 {
+            String::from("It's a draw!")
+        } else {
+            format!("{} wins!", name)
+        };
+        let status_color = if self.game_over && self.winner == 0 {
+            MyColor::White
+        } else {
+            self.team_colors[self.team as usize - 1]
+        };
+        draw_text(ctx, &status, (BOARD_POSITION.0, 25.0), 40.0, status_color)?;
+        self.draw_board(ctx)?;
+        self.draw_panel(ctx)?;
+        self.main_menu_button.draw(ctx)?;
+        self.play_again_button.draw(ctx)?;
+        graphics::present(ctx)?;
+        ggez::timer::yield_now();
+        Ok(())
+    }
+
+    ///Method active whenever the mouse moves, which tracks the empty point under it during a human player's turn
+    fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        self.hover = if self.game_over || self.is_ai(self.team) {
+            None
+        } else {
+            self.point_at(mouse_location(ctx))
+                .filter(|cell| self.board.cell(*cell) == 0)
+        };
+        self.main_menu_button.check_button_under_mouse(ctx);
+        self.play_again_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method to handle a click on the Gomoku screen, returning what the main menu should do next
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton) -> GameAction {
+        self.mouse_motion_event(ctx);
+        if self.main_menu_button.check_button_under_mouse(ctx) {
+            println!("Main Menu Button pressed; Main Menu should pop up");
+            self.sound_queue.push(SoundEffect::Click);
+            return GameAction::MainMenu;
+        }
+        if self.play_again_button.check_button_under_mouse(ctx) {
+            println!("Play Again button pressed; Board reset");
+            self.sound_queue.push(SoundEffect::Click);
+            self.reset_game();
+        } else if button == MouseButton::Left {
+            if let Some(cell) = self.hover {
+                self.play(cell);
+            }
+        }
+        GameAction::None
+    }
+
+    fn drain_sounds(&mut self) -> Vec<SoundEffect> {
+        self.sound_queue.drain(..).collect()
+    }
+
+    fn take_finished_match(&mut self) -> Option<MatchRecord> {
+        self.finished_match.take()
+    }
+}
+
+//NOTE: Tests not included since testing requires usage of mouse and other UI features
@@ -0,0 +1,8 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+pub mod ai;
+pub mod core;
+pub mod rules;
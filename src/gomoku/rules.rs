@@ -0,0 +1,416 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::GridPosition;
+use mnk::board::{Board, MnkConfig, DIRECTIONS};
+
+/// Enum for the rules a Gomoku game is played with. Team 1 (black) always moves first
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum GomokuRules {
+    /// Five or more stones in a row win on a 15x15 board
+    #[default]
+    FreeStyle,
+    /// As free-style, but black only wins with exactly five and may not make an overline, double four or double three
+    Renju,
+    /// Six or more stones in a row win on a 19x19 board. Black places one stone to start, then each turn is two stones
+    Connect6,
+}
+
+///Returns the position reached by moving steps cells from a position in a direction
+fn offset(pos: GridPosition, dir: (i32, i32), steps: i32) -> GridPosition {
+    GridPosition::new(pos.x + steps * dir.0, pos.y + steps * dir.1)
+}
+
+impl GomokuRules {
+    ///Returns every set of rules in the order they are stepped through on the settings screen
+    pub fn all() -> Vec<GomokuRules> {
+        vec![
+            GomokuRules::FreeStyle,
+            GomokuRules::Renju,
+            GomokuRules::Connect6,
+        ]
+    }
+
+    ///Returns the next (up is true) or previous rules, wrapping around at either end
+    pub fn step(self, up: bool) -> GomokuRules {
+        let all = GomokuRules::all();
+        let i = all.iter().position(|r| *r == self).unwrap_or(0);
+        let next = if up { i + 1 } else { i + all.len() - 1 };
+        all[next % all.len()]
+    }
+
+    ///Returns the name shown on the settings screen and in the names of recorded games
+    pub fn name(self) -> &'static str {
+        match self {
+            GomokuRules::FreeStyle => "Gomoku",
+            GomokuRules::Renju => "Renju",
+            GomokuRules::Connect6 => "Connect6",
+        }
+    }
+
+    ///Returns the size of the board and the length of a winning run
+    pub fn config(self) -> MnkConfig {
+        match self {
+            GomokuRules::FreeStyle | GomokuRules::Renju => MnkConfig::new(15, 15, 5),
+            GomokuRules::Connect6 => MnkConfig::new(19, 19, 6),
+        }
+    }
+
+    ///Returns the number of stones to place in a turn, given the number of stones already on the board
+    pub fn stones_per_turn(self, stones: usize) -> usize {
+        if self == GomokuRules::Connect6 && stones > 0 {
+            2
+        } else {
+            1
+        }
+    }
+
+    ///Returns true if a run of the given length wins for the team. Under Renju, black's run must be exactly five
+    fn wins_with(self, team: i32, run: i32) -> bool {
+        let connect = self.config().connect;
+        if self == GomokuRules::Renju && team == 1 {
+            run == connect
+        } else {
+            run >= connect
+        }
+    }
+
+    ///Returns true if the stone in a cell completes a winning run
+    pub fn is_five(self, board: &Board, cell: usize) -> bool {
+        let team = board.cell(cell);
+        let pos = board.position(cell);
+        team != 0
+            && DIRECTIONS
+                .iter()
+                .any(|dir| self.wins_with(team, board.run_length(pos, *dir, team)))
+    }
+
+    ///
+    /// Returns how many steps along one line from a stone each empty cell is that would complete a winning run through
+    /// it, in order from the negative end of the line. The line holds a four if there is at least one
+    ///
+    /// # Arguments
+    /// * board = Board to check, which is changed while checking and left as it was
+    /// * pos   = GridPosition of the stone
+    /// * dir   = One of DIRECTIONS, giving the line checked
+    ///
+    fn completions(self, board: &mut Board, pos: GridPosition, dir: (i32, i32)) -> Vec<i32> {
+        let team = board.get(pos);
+        let reach = self.config().connect - 1;
+        let mut completions = Vec::new();
+        for steps in (-reach..=reach).filter(|steps| *steps != 0) {
+            let target = offset(pos, dir, steps);
+            if !board.on_board(target) || board.get(target) != 0 {
+                continue;
+            }
+            let cell = board.index(target);
+            board.place(cell, team);
+            if self.wins_with(team, board.run_length(pos, dir, team)) {
+                completions.push(steps);
+            }
+            board.clear(cell);
+        }
+        completions
+    }
+
+    ///
+    /// Returns the number of fours through the stone in a cell, i.e. groups of stones one more stone would make win.
+    /// Each cell completing a winning run is its own four, except that the two ends of a straight four are one four,
+    /// so a line such as X _ X X X _ X holds two
+    ///
+    /// # Arguments
+    /// * board = Board to check, which is changed while checking and left as it was
+    /// * cell  = Number of the cell holding the stone to check
+    ///
+    pub fn fours(self, board: &mut Board, cell: usize) -> usize {
+        let pos = board.position(cell);
+        let connect = self.config().connect;
+        DIRECTIONS
+            .iter()
+            .map(|dir| {
+                let completions = self.completions(board, pos, *dir);
+                //Completions a winning run apart are the two ends of the same straight four
+                let straight = completions
+                    .windows(2)
+                    .filter(|pair| pair[1] - pair[0] == connect)
+                    .count();
+                completions.len() - straight
+            })
+            .sum()
+    }
+
+    ///
+    /// Returns true if the line through a stone is an open three: one more stone on the line makes a straight four,
+    /// which wins at either end. Whether that stone would itself be forbidden under Renju is not checked
+    ///
+    /// # Arguments
+    /// * board = Board to check, which is changed while checking and left as it was
+    /// * pos   = GridPosition of the stone
+    /// * dir   = One of DIRECTIONS, giving the line checked
+    ///
+    fn is_open_three(self, board: &mut Board, pos: GridPosition, dir: (i32, i32)) -> bool {
+        let team = board.get(pos);
+        let reach = self.config().connect - 1;
+        for steps in (-reach..=reach).filter(|steps| *steps != 0) {
+            let target = offset(pos, dir, steps);
+            if !board.on_board(target) || board.get(target) != 0 {
+                continue;
+            }
+            let cell = board.index(target);
+            board.place(cell, team);
+            let run = board.run_length(pos, dir, team);
+            let straight =
+                run == self.config().connect - 1 && self.completions(board, pos, dir).len() == 2;
+            board.clear(cell);
+            if straight {
+                return true;
+            }
+        }
+        false
+    }
+
+    ///Returns the number of lines through the stone in a cell that are open threes and not already fours
+    pub fn open_threes(self, board: &mut Board, cell: usize) -> usize {
+        let pos = board.position(cell);
+        DIRECTIONS
+            .iter()
+            .filter(|dir| {
+                self.completions(board, pos, **dir).is_empty()
+                    && self.is_open_three(board, pos, **dir)
+            })
+            .count()
+    }
+
+    ///
+    /// Returns why the stone in a cell is forbidden, or None if it may be played. Only black's stones under Renju can
+    /// be forbidden, and a stone that makes exactly five is always allowed
+    ///
+    /// # Arguments
+    /// * board = Board with the stone placed in the cell, which is changed while checking and left as it was
+    /// * cell  = Number of the cell holding the stone to check
+    ///
+    pub fn foul(self, board: &mut Board, cell: usize) -> Option<&'static str> {
+        if self != GomokuRules::Renju || board.cell(cell) != 1 || self.is_five(board, cell) {
+            return None;
+        }
+        let pos = board.position(cell);
+        if DIRECTIONS
+            .iter()
+            .any(|dir| board.run_length(pos, *dir, 1) > self.config().connect)
+        {
+            Some("overline")
+        } else if self.fours(board, cell) >= 2 {
+            Some("double four")
+        } else if self.open_threes(board, cell) >= 2 {
+            Some("double three")
+        } else {
+            None
+        }
+    }
+
+    ///Returns true if the team may place a stone in an empty cell
+    pub fn is_legal(self, board: &mut Board, cell: usize, team: i32) -> bool {
+        if !board.place(cell, team) {
+            return false;
+        }
+        let legal = self.foul(board, cell).is_none();
+        board.clear(cell);
+        legal
+    }
+
+    ///Returns the empty cells where one more stone would win for the team
+    pub fn winning_cells(self, board: &mut Board, team: i32) -> Vec<usize> {
+        let mut cells = Vec::new();
+        for cell in board.empty_cells() {
+            board.place(cell, team);
+            if self.is_five(board, cell) {
+                cells.push(cell);
+            }
+            board.clear(cell);
+        }
+        cells
+    }
+}
+
+#[cfg(test)]
+mod rules_tests {
+    use super::*;
+
+    //Method to create a board for the rules with stones for team 1 and team 2 at the given (column, row) positions
+    fn create_test_board(rules: GomokuRules, ones: &[(i32, i32)], twos: &[(i32, i32)]) -> Board {
+        let mut board = Board::new(rules.config());
+        for (team, stones) in [(1, ones), (2, twos)].iter() {
+            for pos in stones.iter() {
+                let cell = board.index(GridPosition::new(pos.0, pos.1));
+                board.place(cell, *team);
+            }
+        }
+        board
+    }
+
+    //Method to return the number of the cell at a (column, row) position
+    fn cell(board: &Board, pos: (i32, i32)) -> usize {
+        board.index(GridPosition::new(pos.0, pos.1))
+    }
+
+    mod gomoku_rules {
+        use super::*;
+
+        mod step {
+            use super::*;
+
+            #[test]
+            fn should_wrap_around_both_ways() {
+                assert_eq!(GomokuRules::FreeStyle.step(false), GomokuRules::Connect6);
+                assert_eq!(GomokuRules::Connect6.step(true), GomokuRules::FreeStyle);
+                assert_eq!(GomokuRules::FreeStyle.step(true), GomokuRules::Renju);
+            }
+        }
+
+        mod is_five {
+            use super::*;
+
+            #[test]
+            fn should_only_let_black_win_with_exactly_five_under_renju() {
+                let row: Vec<(i32, i32)> = (2..8).map(|x| (x, 7)).collect();
+                for team in 1..3 {
+                    let (ones, twos) = if team == 1 {
+                        (&row[..], &[][..])
+                    } else {
+                        (&[][..], &row[..])
+                    };
+                    let board = create_test_board(GomokuRules::Renju, ones, twos);
+                    assert_eq!(
+                        GomokuRules::Renju.is_five(&board, cell(&board, (4, 7))),
+                        team == 2
+                    );
+                    let board = create_test_board(GomokuRules::FreeStyle, ones, twos);
+                    assert!(GomokuRules::FreeStyle.is_five(&board, cell(&board, (4, 7))));
+                }
+            }
+
+            #[test]
+            fn should_need_six_for_connect6() {
+                let row: Vec<(i32, i32)> = (2..7).map(|x| (x, 9)).collect();
+                let mut board = create_test_board(GomokuRules::Connect6, &row, &[]);
+                assert!(!GomokuRules::Connect6.is_five(&board, cell(&board, (2, 9))));
+                let extra = cell(&board, (7, 9));
+                board.place(extra, 1);
+                assert!(GomokuRules::Connect6.is_five(&board, extra));
+            }
+        }
+
+        mod stones_per_turn {
+            use super::*;
+
+            #[test]
+            fn should_place_two_after_first_stone_in_connect6() {
+                assert_eq!(GomokuRules::Connect6.stones_per_turn(0), 1);
+                assert_eq!(GomokuRules::Connect6.stones_per_turn(1), 2);
+                assert_eq!(GomokuRules::FreeStyle.stones_per_turn(8), 1);
+            }
+        }
+
+        mod fours {
+            use super::*;
+
+            #[test]
+            fn should_count_broken_and_blocked_fours() {
+                //X X _ X X is a four along the row, and blocked X X X X with white at one end is one too
+                let mut board = create_test_board(
+                    GomokuRules::FreeStyle,
+                    &[(3, 3), (4, 3), (6, 3), (7, 3), (7, 4), (7, 5), (7, 6)],
+                    &[(7, 7)],
+                );
+                let corner = cell(&board, (7, 3));
+                assert_eq!(GomokuRules::FreeStyle.fours(&mut board, corner), 2);
+                assert_eq!(
+                    GomokuRules::FreeStyle.winning_cells(&mut board, 1),
+                    vec![cell(&board, (7, 2)), cell(&board, (5, 3))]
+                );
+            }
+
+            #[test]
+            fn should_count_two_fours_on_one_line_but_one_straight_four() {
+                //X _ X X X _ X is two fours on one line, but _ X X X X _ is only one
+                let rules = GomokuRules::Renju;
+                let mut board = create_test_board(rules, &[(1, 5), (3, 5), (5, 5), (7, 5)], &[]);
+                let point = cell(&board, (4, 5));
+                board.place(point, 1);
+                assert_eq!(rules.fours(&mut board, point), 2);
+                assert_eq!(rules.foul(&mut board, point), Some("double four"));
+                let mut board = create_test_board(rules, &[(3, 5), (5, 5), (6, 5)], &[]);
+                board.place(point, 1);
+                assert_eq!(rules.fours(&mut board, point), 1);
+                assert_eq!(rules.foul(&mut board, point), None);
+            }
+        }
+
+        mod open_threes {
+            use super::*;
+
+            #[test]
+            fn should_find_open_and_split_threes_but_not_blocked_ones() {
+                let rules = GomokuRules::FreeStyle;
+                let mut board = create_test_board(rules, &[(5, 5), (6, 5), (7, 5)], &[]);
+                let middle = cell(&board, (6, 5));
+                assert_eq!(rules.open_threes(&mut board, middle), 1);
+                let mut board = create_test_board(rules, &[(5, 5), (6, 5), (8, 5)], &[]);
+                assert_eq!(rules.open_threes(&mut board, middle), 1);
+                let mut board = create_test_board(rules, &[(5, 5), (6, 5), (7, 5)], &[(4, 5)]);
+                assert_eq!(rules.open_threes(&mut board, middle), 0);
+                //A three against the edge of the board can't become a straight four
+                let mut board = create_test_board(rules, &[(0, 5), (1, 5), (2, 5)], &[]);
+                let edge = cell(&board, (1, 5));
+                assert_eq!(rules.open_threes(&mut board, edge), 0);
+            }
+        }
+
+        mod foul {
+            use super::*;
+
+            #[test]
+            fn should_forbid_double_three_for_black_under_renju() {
+                let stones = [(5, 7), (6, 7), (7, 5), (7, 6)];
+                let mut board = create_test_board(GomokuRules::Renju, &stones, &[]);
+                let point = cell(&board, (7, 7));
+                assert!(!GomokuRules::Renju.is_legal(&mut board, point, 1));
+                board.place(point, 1);
+                assert_eq!(
+                    GomokuRules::Renju.foul(&mut board, point),
+                    Some("double three")
+                );
+                board.clear(point);
+                assert!(GomokuRules::Renju.is_legal(&mut board, point, 2));
+                assert!(GomokuRules::FreeStyle.is_legal(&mut board, point, 1));
+            }
+
+            #[test]
+            fn should_forbid_double_four_and_overline_but_allow_five() {
+                let rules = GomokuRules::Renju;
+                let mut board = create_test_board(
+                    rules,
+                    &[(3, 7), (4, 7), (5, 7), (7, 4), (7, 5), (7, 6)],
+                    &[(2, 7), (7, 3)],
+                );
+                let point = cell(&board, (7, 7));
+                board.place(point, 1);
+                assert_eq!(rules.foul(&mut board, point), Some("double four"));
+                let mut board =
+                    create_test_board(rules, &[(2, 2), (3, 2), (4, 2), (6, 2), (7, 2)], &[]);
+                let point = cell(&board, (5, 2));
+                board.place(point, 1);
+                assert_eq!(rules.foul(&mut board, point), Some("overline"));
+                let mut board = create_test_board(
+                    rules,
+                    &[(3, 7), (4, 7), (5, 7), (6, 7), (7, 4), (7, 5), (7, 6)],
+                    &[(7, 3)],
+                );
+                let point = cell(&board, (7, 7));
+                board.place(point, 1);
+                assert_eq!(rules.foul(&mut board, point), None);
+            }
+        }
+    }
+}
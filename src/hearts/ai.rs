@@ -0,0 +1,277 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate rand;
+
+use self::rand::Rng;
+use cards::card::{Card, Rank, Suit};
+use cards::hand::count_suit;
+use cards::trick::lead_suit;
+use hearts::game::{points, HeartsGame, PASS_SIZE, QUEEN_OF_SPADES};
+
+///Returns true for the spades that can be forced to take the queen of spades: the king and ace
+fn is_high_spade(card: Card) -> bool {
+    card.suit == Suit::Spades && card.rank > QUEEN_OF_SPADES.rank
+}
+
+///
+/// Returns how much a player wants to get rid of a card when passing. The queen of spades and the spades above it are
+/// passed first, then high hearts, then high cards of short suits, which helps to empty a suit and discard points on it
+///
+/// # Arguments
+/// * card = Card to rate
+/// * hand = Cards held, including the card
+///
+fn pass_danger(card: Card, hand: &[Card]) -> i32 {
+    let suit_count = count_suit(hand, card.suit);
+    if card == QUEEN_OF_SPADES {
+        100
+    } else if is_high_spade(card) {
+        80 + card.rank.value()
+    } else if card.suit == Suit::Hearts {
+        20 + 2 * card.rank.value()
+    } else if suit_count <= 2 {
+        10 + card.rank.value()
+    } else {
+        card.rank.value()
+    }
+}
+
+///
+/// A struct representing an AI or bot player for Hearts which has methods to pick cards to pass and play
+///
+/// # Fields
+/// * seat            = Seat the AI plays for
+/// * difficulty      = Integer value that determines how "smart" the AI is: 1 plays any legal card, 2+ avoids points
+/// * last_move_frame = Integer used to track when the AI picked its card. Set to -1 until it picks each turn
+///
+pub struct AI {
+    pub seat: usize,
+    difficulty: i32,
+    pub last_move_frame: i32,
+}
+
+impl AI {
+    ///
+    /// Method to initialize and return an AI object
+    ///
+    /// # Arguments
+    /// * seat       = Seat the AI plays for
+    /// * difficulty = Integer value that determines how "smart" the AI is
+    ///
+    pub fn new(seat: usize, difficulty: i32) -> Self {
+        AI {
+            seat,
+            difficulty,
+            last_move_frame: -1,
+        }
+    }
+
+    ///
+    /// Method to pick the three cards to pass from a hand
+    ///
+    /// # Arguments
+    /// * hand = Cards held by the AI
+    /// * rng  = Random number generator used by the weakest AI to pick any cards
+    ///
+    pub fn pick_pass<R: Rng>(&self, hand: &[Card], rng: &mut R) -> Vec<Card> {
+        let mut cards = hand.to_vec();
+        if self.difficulty <= 1 {
+            rng.shuffle(&mut cards);
+        } else {
+            cards.sort_by_key(|card| -pass_danger(*card, hand));
+        }
+        cards.truncate(PASS_SIZE);
+        cards
+    }
+
+    ///
+    /// Method to pick the card to play to the current trick. Stronger AIs lead low, duck under the winning card when
+    /// they can, and dump the queen of spades, high spades and hearts when they can't follow suit
+    ///
+    /// # Arguments
+    /// * game = HeartsGame with the AI to play
+    /// * rng  = Random number generator used by the weakest AI to pick any legal card
+    ///
+    pub fn pick_play<R: Rng>(&self, game: &HeartsGame, rng: &mut R) -> Card {
+        let mut legal = game.legal_plays(self.seat);
+        legal.sort_by_key(|card| card.rank);
+        if self.difficulty <= 1 {
+            return legal[rng.gen_range(0, legal.len())];
+        }
+        //Cards other than the queen of spades, which is only played when there is no choice or it can be dumped
+        let safe: Vec<Card> = legal
+            .iter()
+            .filter(|c| **c != QUEEN_OF_SPADES)
+            .cloned()
+            .collect();
+        let lead = match lead_suit(&game.trick) {
+            //Lead the lowest card, keeping the queen of spades and the spades above it back
+            None => {
+                return legal
+                    .iter()
+                    .find(|card| **card != QUEEN_OF_SPADES && !is_high_spade(**card))
+                    .cloned()
+                    .unwrap_or(legal[0]);
+            }
+            Some(suit) => suit,
+        };
+        if legal[0].suit != lead {
+            //Void in the suit led, so discard the most dangerous card held
+            return *legal
+                .iter()
+                .max_by_key(|card| {
+                    if **card == QUEEN_OF_SPADES {
+                        1000
+                    } else if is_high_spade(**card) {
+                        500 + card.rank.value()
+                    } else if card.suit == Suit::Hearts {
+                        100 + card.rank.value()
+                    } else {
+                        card.rank.value()
+                    }
+                })
+                .unwrap_or(&legal[0]);
+        }
+        let winning = game
+            .trick
+            .iter()
+            .filter(|(_, card)| card.suit == lead)
+            .map(|(_, card)| card.rank)
+            .max()
+            .unwrap_or(Rank::Two);
+        //Play the highest card that still loses the trick
+        if let Some(card) = legal.iter().rev().find(|card| card.rank < winning) {
+            return *card;
+        }
+        let trick_points: i32 = game.trick.iter().map(|(_, card)| points(*card)).sum();
+        let last_to_play = game.trick.len() == game.hands.len() - 1;
+        let choices = if safe.is_empty() { &legal } else { &safe };
+        if last_to_play && trick_points == 0 {
+            //Taking a pointless trick anyway, so get rid of the highest card
+            choices[choices.len() - 1]
+        } else {
+            choices[0]
+        }
+    }
+}
+
+#[cfg(test)]
+mod ai_tests {
+    use super::rand::{SeedableRng, StdRng};
+    use super::*;
+    use hearts::game::HeartsPhase;
+
+    //Method to create a game in play where seat 0 has the given hand and the trick so far has the given cards, played by
+    //the seats before seat 0
+    fn create_test_game(hand: Vec<Card>, trick: Vec<Card>) -> HeartsGame {
+        let mut rng = create_test_rng();
+        let mut game = HeartsGame::new(&mut rng);
+        game.phase = HeartsPhase::Playing;
+        game.tricks_played = 1;
+        game.hearts_broken = true;
+        game.hands[0] = hand;
+        let start = 4 - trick.len();
+        game.trick = trick
+            .into_iter()
+            .enumerate()
+            .map(|(i, card)| (start + i, card))
+            .collect();
+        game.turn = 0;
+        game
+    }
+
+    //Method to create a seeded random number generator so picks are repeatable
+    fn create_test_rng() -> StdRng {
+        SeedableRng::from_seed(&[7][..])
+    }
+
+    //Method to create a card from a suit and rank, kept short for building test hands
+    fn c(suit: Suit, rank: i32) -> Card {
+        Card::new(suit, Rank::all()[rank as usize - 2])
+    }
+
+    mod ai {
+        use super::*;
+
+        mod pick_pass {
+            use super::*;
+
+            #[test]
+            fn should_pass_queen_and_high_spades() {
+                let hand = vec![
+                    c(Suit::Clubs, 3),
+                    c(Suit::Clubs, 14),
+                    c(Suit::Diamonds, 9),
+                    c(Suit::Spades, 12),
+                    c(Suit::Spades, 14),
+                    c(Suit::Hearts, 13),
+                    c(Suit::Hearts, 2),
+                ];
+                let mut rng = create_test_rng();
+                let pass = AI::new(0, 3).pick_pass(&hand, &mut rng);
+                assert_eq!(
+                    pass,
+                    vec![QUEEN_OF_SPADES, c(Suit::Spades, 14), c(Suit::Hearts, 13)]
+                );
+                let pass = AI::new(0, 1).pick_pass(&hand, &mut rng);
+                assert_eq!(pass.len(), PASS_SIZE);
+                assert!(pass.iter().all(|card| hand.contains(card)));
+            }
+        }
+
+        mod pick_play {
+            use super::*;
+
+            #[test]
+            fn should_duck_under_winning_card() {
+                let mut rng = create_test_rng();
+                let hand = vec![
+                    c(Suit::Diamonds, 4),
+                    c(Suit::Diamonds, 9),
+                    c(Suit::Diamonds, 13),
+                ];
+                let game = create_test_game(hand, vec![c(Suit::Diamonds, 10), c(Suit::Hearts, 3)]);
+                assert_eq!(
+                    AI::new(0, 3).pick_play(&game, &mut rng),
+                    c(Suit::Diamonds, 9)
+                );
+            }
+
+            #[test]
+            fn should_dump_queen_when_void() {
+                let mut rng = create_test_rng();
+                let hand = vec![c(Suit::Hearts, 14), QUEEN_OF_SPADES, c(Suit::Clubs, 5)];
+                let game = create_test_game(hand, vec![c(Suit::Diamonds, 10)]);
+                assert_eq!(AI::new(0, 3).pick_play(&game, &mut rng), QUEEN_OF_SPADES);
+            }
+
+            #[test]
+            fn should_take_pointless_trick_with_highest_card_when_last() {
+                let mut rng = create_test_rng();
+                let hand = vec![c(Suit::Clubs, 11), c(Suit::Clubs, 14)];
+                let trick = vec![c(Suit::Clubs, 2), c(Suit::Clubs, 5), c(Suit::Clubs, 8)];
+                let game = create_test_game(hand, trick);
+                assert_eq!(AI::new(0, 3).pick_play(&game, &mut rng), c(Suit::Clubs, 14));
+            }
+
+            #[test]
+            fn should_lead_low_without_high_spades() {
+                let mut rng = create_test_rng();
+                let hand = vec![
+                    c(Suit::Spades, 13),
+                    c(Suit::Diamonds, 7),
+                    c(Suit::Hearts, 9),
+                ];
+                let game = create_test_game(hand, Vec::new());
+                assert_eq!(
+                    AI::new(0, 3).pick_play(&game, &mut rng),
+                    c(Suit::Diamonds, 7)
+                );
+                let card = AI::new(0, 1).pick_play(&game, &mut rng);
+                assert!(game.legal_plays(0).contains(&card));
+            }
+        }
+    }
+}
@@ -0,0 +1,672 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+extern crate rand;
+
+use cards::card::Card;
+use cards::view::{draw_card, HandView, CARD_SIZE};
+use connect4::button::{create_button, draw_text, Button};
+use connect4::core::{GameAction, MyColor, PlayerInfo};
+use ggez::input::mouse::MouseButton;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+use hearts::ai::AI;
+use hearts::game::{points, HeartsGame, HeartsPhase, PassDirection, PASS_SIZE, PLAYERS};
+use history::MatchRecord;
+use screen::GameScreen;
+use settings::Settings;
+use sound::SoundEffect;
+
+///Constant dimensions for the Hearts window, fitting a hand along the bottom and the other players around the table
+pub const HEARTS_SCREEN_SIZE: (f32, f32) = (960.0, 720.0);
+
+///Constant y location of the top of the cards in the hand along the bottom of the screen
+const HAND_POS_Y: f32 = 520.0;
+
+///Constant center of the table, where tricks are played
+const TABLE_CENTER: (f32, f32) = (480.0, 300.0);
+
+///Constant offsets from the table center of the card played by each seat, starting from the bottom and going clockwise
+const TRICK_OFFSETS: [(f32, f32); 4] = [(0.0, 75.0), (-90.0, 0.0), (0.0, -75.0), (90.0, 0.0)];
+
+///Constant locations of the name label for each seat, starting from the bottom and going clockwise
+const LABEL_POSITIONS: [(f32, f32); 4] =
+    [(40.0, 480.0), (40.0, 190.0), (360.0, 60.0), (730.0, 190.0)];
+
+///Constant font size for the status line, player labels and messages
+const HEARTS_FONT_SIZE: f32 = 22.0;
+
+///Constant y location of the row of buttons along the bottom of the screen
+const BUTTON_POS_Y: f32 = 655.0;
+
+///
+/// A struct that contains the state of a game of Hearts. The hand of the human player to act is shown along the
+/// bottom of the screen, with the other seats around the table in playing order. When several people share the screen,
+/// a hand-off screen hides the cards whenever a different person has to act
+///
+/// # Fields
+/// * game              = HeartsGame holding the rules and the cards
+/// * names             = Vector of player names, where names[i] is the name for seat i
+/// * colors            = Vector of MyColor objects, where colors[i] is the color seat i's name is shown in
+/// * ai_players        = Vector of AI objects, one for each seat played by the AI
+/// * ai_difficulty     = Difficulty given to the AI players, recorded in the match history
+/// * ai_move_delay     = Number of frames an AI waits before playing a card, and a full trick stays on the table
+/// * frames            = Integer counter for the number of times the update method is called; times AI plays
+/// * trick_frame       = Frame the current trick was completed on, or -1 if it isn't complete
+/// * viewer            = Seat whose hand is shown along the bottom of the screen
+/// * hand_off          = Boolean indicating if the hand-off screen is hiding the cards until the next player is ready
+/// * hand_view         = HandView showing the viewer's hand, with the card under the mouse and the cards picked to pass
+/// * message           = Text shown above the hand, e.g. who took the last trick
+/// * main_menu_button  = Button drawn to return to the main menu
+/// * pass_button       = Button drawn while passing once three cards are picked
+/// * next_hand_button  = Button drawn after a hand is scored to deal the next one
+/// * ready_button      = Button drawn on the hand-off screen to show the next player's cards
+/// * play_again_button = Button drawn once the game is over to start a new game with the same players
+/// * background_color  = Color drawn behind the table
+/// * sound_queue       = Vector of SoundEffect enums triggered since the main menu last played them
+/// * moves             = Vector of cards played so far, in order, each stored as its Card::index
+/// * elapsed           = Seconds the current game has been played for
+/// * finished_match    = Option of a MatchRecord for a game that just ended, taken by the main menu to be saved
+///
+pub struct HeartsState {
+    game: HeartsGame,
+    names: Vec<String>,
+    colors: Vec<MyColor>,
+    ai_players: Vec<AI>,
+    ai_difficulty: i32,
+    ai_move_delay: i32,
+    frames: usize,
+    trick_frame: i32,
+    viewer: usize,
+    hand_off: bool,
+    hand_view: HandView,
+    message: String,
+    main_menu_button: Button,
+    pass_button: Button,
+    next_hand_button: Button,
+    ready_button: Button,
+    play_again_button: Button,
+    background_color: graphics::Color,
+    pub sound_queue: Vec<SoundEffect>,
+    moves: Vec<i32>,
+    elapsed: f32,
+    pub finished_match: Option<MatchRecord>,
+}
+
+impl HeartsState {
+    ///Constructor - player_info holds the name, color and whether the AI plays for each seat, in playing order, and
+    ///settings holds the AI and theme options. Seats without player info get the defaults
+    pub fn new(
+        ctx: &mut Context,
+        player_info: Vec<PlayerInfo>,
+        settings: &Settings,
+    ) -> HeartsState {
+        let mut names = Vec::new();
+        let mut colors = Vec::new();
+        let mut ai_players = Vec::new();
+        let given = player_info.len();
+        for (seat, info) in player_info
+            .into_iter()
+            .chain(PlayerInfo::defaults().into_iter().skip(given))
+            .take(PLAYERS)
+            .enumerate()
+        {
+            if info.is_ai {
+                ai_players.push(AI::new(seat, settings.ai_difficulty));
+            }
+            names.push(info.name);
+            colors.push(info.color);
+        }
+        let right = HEARTS_SCREEN_SIZE.0 - 250.0;
+        let mut state = HeartsState {
+            game: HeartsGame::new(&mut rand::thread_rng()),
+            names,
+            colors,
+            ai_players,
+            ai_difficulty: settings.ai_difficulty,
+            ai_move_delay: settings.ai_move_delay,
+            frames: 0,
+            trick_frame: -1,
+            viewer: 0,
+            hand_off: false,
+            hand_view: HandView::new(
+                HEARTS_SCREEN_SIZE.0 / 2.0,
+                HAND_POS_Y,
+                HEARTS_SCREEN_SIZE.0 - 80.0,
+                PASS_SIZE,
+            ),
+            message: String::new(),
+            main_menu_button: create_button(
+                ctx,
+                "Main Menu",
+                (40.0, BUTTON_POS_Y),
+                HEARTS_FONT_SIZE,
+                (MyColor::Brown, MyColor::Green),
+            ),
+            pass_button: create_button(
+                ctx,
+                "Pass",
+                (right, BUTTON_POS_Y),
+                HEARTS_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            next_hand_button: create_button(
+                ctx,
+                "Next Hand",
+                (right, BUTTON_POS_Y),
+                HEARTS_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            ready_button: create_button(
+                ctx,
+                "Ready",
+                (right, BUTTON_POS_Y),
+                HEARTS_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            play_again_button: create_button(
+                ctx,
+                "Play Again",
+                (right, BUTTON_POS_Y),
+                HEARTS_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            background_color: settings.theme.game_background(),
+            sound_queue: Vec::new(),
+            moves: Vec::new(),
+            elapsed: 0.0,
+            finished_match: None,
+        };
+        state.viewer = (0..PLAYERS).find(|seat| !state.is_ai(*seat)).unwrap_or(0);
+        state.start_hand();
+        state
+    }
+
+    ///Returns true if the AI plays for the seat
+    fn is_ai(&self, seat: usize) -> bool {
+        self.ai_players.iter().any(|ai| ai.seat == seat)
+    }
+
+    ///Returns the number of seats played by people
+    fn human_count(&self) -> usize {
+        PLAYERS - self.ai_players.len()
+    }
+
+    ///Method to start a new game with every score at zero
+    fn reset_game(&mut self) {
+        self.game = HeartsGame::new(&mut rand::thread_rng());
+        self.moves.clear();
+        self.elapsed = 0.0;
+        self.message.clear();
+        self.start_hand();
+    }
+
+    ///Method to start a freshly dealt hand: the AI picks its passes, and the first person still to pass is shown
+    fn start_hand(&mut self) {
+        let mut rng = rand::thread_rng();
+        for ai in &mut self.ai_players {
+            ai.last_move_frame = -1;
+        }
+        for i in 0..self.ai_players.len() {
+            let seat = self.ai_players[i].seat;
+            let pass = self.ai_players[i].pick_pass(&self.game.hands[seat], &mut rng);
+            self.game.set_pass(seat, pass);
+        }
+        self.trick_frame = -1;
+        if self.game.phase == HeartsPhase::Passing {
+            let seat = (0..PLAYERS)
+                .find(|seat| self.game.passes[*seat].is_none())
+                .unwrap_or(0);
+            self.set_viewer(seat);
+        } else {
+            self.show_received_cards();
+        }
+        self.update_buttons();
+    }
+
+    ///Method to show a seat's hand along the bottom. The hand-off screen hides it first if several people are playing
+    fn set_viewer(&mut self, seat: usize) {
+        if seat != self.viewer && self.human_count() > 1 {
+            self.hand_off = true;
+            self.message.clear();
+        }
+        self.viewer = seat;
+        self.hand_view.clear();
+        self.show_received_cards();
+    }
+
+    ///Method to tell the viewer which cards were passed to them, while the first trick of a hand is played
+    fn show_received_cards(&mut self) {
+        let direction = self.game.pass_direction();
+        if self.game.phase != HeartsPhase::Playing
+            || self.game.tricks_played > 0
+            || direction == PassDirection::Hold
+        {
+            return;
+        }
+        if let Some(Some(cards)) = (0..PLAYERS)
+            .find(|seat| direction.target(*seat) == self.viewer)
+            .map(|seat| self.game.passes[seat].clone())
+        {
+            let labels: Vec<String> = cards.iter().map(|card| card.to_string()).collect();
+            self.message = format!("You received {}", labels.join(", "));
+        }
+    }
+
+    ///Method to show only the buttons that can be used in the current part of the game
+    fn update_buttons(&mut self) {
+        let phase = self.game.phase;
+        self.ready_button.active = self.hand_off;
+        self.pass_button.active = !self.hand_off
+            && phase == HeartsPhase::Passing
+            && self.hand_view.selected.len() == PASS_SIZE;
+        self.next_hand_button.active = phase == HeartsPhase::HandOver;
+        self.play_again_button.active = phase == HeartsPhase::GameOver;
+    }
+
+    ///Method for the viewer to pass the cards they picked, moving on to the next person still to pass
+    fn pass_selected(&mut self) {
+        let cards = self.hand_view.selected.clone();
+        if !self.game.set_pass(self.viewer, cards) {
+            self.sound_queue.push(SoundEffect::InvalidMove);
+            return;
+        }
+        println!("{} passes {} cards", self.names[self.viewer], PASS_SIZE);
+        self.hand_view.clear();
+        if let Some(seat) = (0..PLAYERS).find(|seat| self.game.passes[*seat].is_none()) {
+            self.set_viewer(seat);
+        } else {
+            self.show_received_cards();
+        }
+    }
+
+    ///
+    /// Method for the seat to act to play a card, recording it and playing its sound. Returns false if it can't be played
+    ///
+    /// # Arguments
+    /// * card = Card played
+    ///
+    fn play_card(&mut self, card: Card) -> bool {
+        let seat = self.game.turn;
+        if !self.game.play(seat, card) {
+            self.sound_queue.push(SoundEffect::InvalidMove);
+            return false;
+        }
+        println!("{} plays {}", self.names[seat], card);
+        self.moves.push(card.index());
+        self.sound_queue.push(SoundEffect::Drop);
+        self.hand_view.hovered = None;
+        if self.game.is_trick_complete() {
+            self.trick_frame = self.frames as i32;
+        }
+        true
+    }
+
+    ///Method to give the full trick on the table to its winner, then score the hand or the game if it is over
+    fn collect_trick(&mut self) {
+        let winner = self.game.collect_trick();
+        self.trick_frame = -1;
+        self.message = format!("{} takes the trick", self.names[winner]);
+        match self.game.phase {
+            HeartsPhase::HandOver => {
+                self.message = self.hand_summary();
+                self.sound_queue.push(SoundEffect::Click);
+            }
+            HeartsPhase::GameOver => {
+                let leaders = self.game.leaders();
+                let names: Vec<String> = leaders
+                    .iter()
+                    .map(|seat| self.names[*seat].clone())
+                    .collect();
+                println!("{} wins; Game ends", names.join(" and "));
+                self.message = self.hand_summary();
+                //Cheer if a person won, unless everyone at the table is a person
+                let human_won = leaders.iter().any(|seat| !self.is_ai(*seat));
+                self.sound_queue
+                    .push(if human_won || self.human_count() == 0 {
+                        SoundEffect::Win
+                    } else {
+                        SoundEffect::Lose
+                    });
+                self.finish_match(leaders[0] as i32 + 1);
+            }
+            _ => (),
+        }
+    }
+
+    ///Returns the message shown after a hand is scored, naming anyone who shot the moon
+    fn hand_summary(&self) -> String {
+        match self.game.moon_shooter {
+            Some(seat) => format!("{} shot the moon!", self.names[seat]),
+            None => String::from("Hand over"),
+        }
+    }
+
+    ///
+    /// Method to create the MatchRecord for a game that just ended, so it can be added to the match history
+    ///
+    /// # Arguments
+    /// * winner = Seat number + 1 of the winner with the lowest score (the first one if the lowest score is shared)
+    ///
+    fn finish_match(&mut self, winner: i32) {
+        let ai_teams: Vec<i32> = self
+            .ai_players
+            .iter()
+            .map(|ai| ai.seat as i32 + 1)
+            .collect();
+        self.finished_match = Some(MatchRecord::for_teams(
+            "Hearts",
+            &self.names,
+            &ai_teams,
+            self.ai_difficulty,
+            self.moves.clone(),
+            winner,
+            self.elapsed,
+        ));
+    }
+
+    ///Returns the location on screen of a seat, counted clockwise from the viewer's seat at the bottom
+    fn screen_slot(&self, seat: usize) -> usize {
+        (seat + PLAYERS - self.viewer) % PLAYERS
+    }
+
+    ///Method to draw the status line, the players around the table, the trick, the viewer's hand and the scores
+    fn draw_table(&self, ctx: &mut Context) -> GameResult {
+        let game = &self.game;
+        let name = &self.names[self.viewer];
+        let status = match game.phase {
+            HeartsPhase::Passing => {
+                let direction = game.pass_direction();
+                format!(
+                    "{}: pick {} cards to pass {} to {}",
+                    name,
+                    PASS_SIZE,
+                    direction.label(),
+                    self.names[direction.target(self.viewer)]
+                )
+            }
+            HeartsPhase::Playing if game.is_trick_complete() => String::from("Trick complete"),
+            HeartsPhase::Playing if self.is_ai(game.turn) => {
+                format!("{} is playing...", self.names[game.turn])
+            }
+            HeartsPhase::Playing => format!("{}: your turn", name),
+            HeartsPhase::HandOver => format!("Hand {} over", game.hand_number),
+            HeartsPhase::GameOver => {
+                let names: Vec<String> = game
+                    .leaders()
+                    .iter()
+                    .map(|seat| self.names[*seat].clone())
+                    .collect();
+                format!("{} wins!", names.join(" and "))
+            }
+        };
+        draw_text(ctx, &status, (40.0, 15.0), 36.0, graphics::WHITE)?;
+
+        //Players around the table, with their scores and the points taken in this hand
+        for seat in 0..PLAYERS {
+            let slot = self.screen_slot(seat);
+            let taken: i32 = game.taken[seat].iter().map(|card| points(*card)).sum();
+            let marker = if game.phase == HeartsPhase::Playing && game.turn == seat {
+                "> "
+            } else {
+                ""
+            };
+            draw_text(
+                ctx,
+                &format!(
+                    "{}{}: {} ({} this hand)",
+                    marker, self.names[seat], game.scores[seat], taken
+                ),
+                LABEL_POSITIONS[slot],
+                HEARTS_FONT_SIZE,
+                self.colors[seat].get_draw_color(),
+            )?;
+            if slot != 0 && !game.hands[seat].is_empty() {
+                let pos = LABEL_POSITIONS[slot];
+                draw_card(ctx, None, (pos.0, pos.1 + 30.0), false)?;
+                draw_text(
+                    ctx,
+                    &format!("{} cards", game.hands[seat].len()),
+                    (pos.0 + CARD_SIZE.0 + 10.0, pos.1 + 70.0),
+                    HEARTS_FONT_SIZE,
+                    graphics::WHITE,
+                )?;
+            }
+        }
+
+        //The trick on the table
+        for (seat, card) in &game.trick {
+            let offset = TRICK_OFFSETS[self.screen_slot(*seat)];
+            draw_card(
+                ctx,
+                Some(*card),
+                (
+                    TABLE_CENTER.0 + offset.0 - CARD_SIZE.0 / 2.0,
+                    TABLE_CENTER.1 + offset.1 - CARD_SIZE.1 / 2.0,
+                ),
+                false,
+            )?;
+        }
+
+        //The viewer's hand, with the cards that can't be played grayed out on their turn
+        let legal = if game.phase == HeartsPhase::Playing
+            && game.turn == self.viewer
+            && !game.is_trick_complete()
+        {
+            Some(game.legal_plays(self.viewer))
+        } else {
+            None
+        };
+        self.hand_view
+            .draw(ctx, &game.hands[self.viewer], legal.as_deref())?;
+
+        if game.phase == HeartsPhase::HandOver || game.phase == HeartsPhase::GameOver {
+            self.draw_scores(ctx)?;
+        }
+        draw_text(
+            ctx,
+            &self.message,
+            (TABLE_CENTER.0 - 150.0, HAND_POS_Y - 70.0),
+            HEARTS_FONT_SIZE,
+            graphics::WHITE,
+        )
+    }
+
+    ///Method to draw the points every player scored in the last hand and their totals, once a hand is over
+    fn draw_scores(&self, ctx: &mut Context) -> GameResult {
+        let (x, y) = (TABLE_CENTER.0 - 170.0, TABLE_CENTER.1 - 90.0);
+        let mut mb = graphics::MeshBuilder::new();
+        mb.rectangle(
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(x - 15.0, y - 15.0, 370.0, 190.0),
+            graphics::Color::from_rgba(0, 0, 0, 200),
+        );
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))?;
+        draw_text(ctx, "Player", (x, y), HEARTS_FONT_SIZE, graphics::WHITE)?;
+        draw_text(
+            ctx,
+            "Hand",
+            (x + 200.0, y),
+            HEARTS_FONT_SIZE,
+            graphics::WHITE,
+        )?;
+        draw_text(
+            ctx,
+            "Total",
+            (x + 275.0, y),
+            HEARTS_FONT_SIZE,
+            graphics::WHITE,
+        )?;
+        for seat in 0..PLAYERS {
+            let row_y = y + 35.0 * (seat + 1) as f32;
+            let color = self.colors[seat].get_draw_color();
+            draw_text(ctx, &self.names[seat], (x, row_y), HEARTS_FONT_SIZE, color)?;
+            draw_text(
+                ctx,
+                &format!("+{}", self.game.hand_points[seat]),
+                (x + 200.0, row_y),
+                HEARTS_FONT_SIZE,
+                color,
+            )?;
+            draw_text(
+                ctx,
+                &self.game.scores[seat].to_string(),
+                (x + 275.0, row_y),
+                HEARTS_FONT_SIZE,
+                color,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl GameScreen for HeartsState {
+    fn screen_size(&self) -> (f32, f32) {
+        HEARTS_SCREEN_SIZE
+    }
+
+    /// Update method - collects finished tricks and plays the AI's cards after a delay
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        self.frames += 1; //Timing mechanism for AI plays and full tricks
+        let phase = self.game.phase;
+        if phase != HeartsPhase::GameOver && !self.hand_off {
+            self.elapsed += ggez::timer::delta(_ctx).as_secs_f32();
+        }
+        if phase != HeartsPhase::Playing || self.hand_off {
+            self.update_buttons();
+            return Ok(());
+        }
+        if self.game.is_trick_complete() {
+            //Leave the full trick on the table for a moment before it is taken
+            if self.frames > (self.trick_frame + self.ai_move_delay) as usize {
+                self.collect_trick();
+            }
+        } else {
+            let seat = self.game.turn;
+            if let Some(i) = self.ai_players.iter().position(|ai| ai.seat == seat) {
+                if self.ai_players[i].last_move_frame < 0 {
+                    self.ai_players[i].last_move_frame = self.frames as i32;
+                } else if self.frames
+                    > (self.ai_players[i].last_move_frame + self.ai_move_delay) as usize
+                {
+                    let card = self.ai_players[i].pick_play(&self.game, &mut rand::thread_rng());
+                    self.ai_players[i].last_move_frame = -1;
+                    self.play_card(card);
+                }
+            } else if seat != self.viewer {
+                self.set_viewer(seat);
+            }
+        }
+        self.update_buttons();
+        Ok(())
+    }
+
+    ///Draw method to render the table, the viewer's hand, messages and buttons, or the hand-off screen
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::clear(ctx, self.background_color);
+        if self.hand_off {
+            draw_text(
+                ctx,
+                &format!("Pass to {}", self.names[self.viewer]),
+                (40.0, 250.0),
+                48.0,
+                self.colors[self.viewer].get_draw_color(),
+            )?;
+            draw_text(
+                ctx,
+                &format!(
+                    "{}, click Ready when no one else can see your cards",
+                    self.names[self.viewer]
+                ),
+                (40.0, 330.0),
+                HEARTS_FONT_SIZE,
+                graphics::WHITE,
+            )?;
+        } else {
+            self.draw_table(ctx)?;
+        }
+        self.main_menu_button.draw(ctx)?;
+        self.pass_button.draw(ctx)?;
+        self.next_hand_button.draw(ctx)?;
+        self.ready_button.draw(ctx)?;
+        self.play_again_button.draw(ctx)?;
+        graphics::present(ctx)?;
+        ggez::timer::yield_now();
+        Ok(())
+    }
+
+    ///Method to update the hovered card and the highlighted buttons when the mouse moves
+    fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        if self.hand_off {
+            self.hand_view.hovered = None;
+        } else {
+            self.hand_view
+                .check_card_under_mouse(ctx, &self.game.hands[self.viewer]);
+        }
+        self.main_menu_button.check_button_under_mouse(ctx);
+        self.pass_button.check_button_under_mouse(ctx);
+        self.next_hand_button.check_button_under_mouse(ctx);
+        self.ready_button.check_button_under_mouse(ctx);
+        self.play_again_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method to handle a click on the Hearts screen, returning what the main menu should do next
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton) -> GameAction {
+        self.mouse_motion_event(ctx);
+        if self.main_menu_button.check_button_under_mouse(ctx) {
+            println!("Main Menu Button pressed; Main Menu should pop up");
+            self.sound_queue.push(SoundEffect::Click);
+            return GameAction::MainMenu;
+        }
+        if self.ready_button.check_button_under_mouse(ctx) {
+            self.sound_queue.push(SoundEffect::Click);
+            self.hand_off = false;
+        } else if self.pass_button.check_button_under_mouse(ctx) {
+            self.sound_queue.push(SoundEffect::Click);
+            self.pass_selected();
+        } else if self.next_hand_button.check_button_under_mouse(ctx) {
+            self.sound_queue.push(SoundEffect::Click);
+            self.message.clear();
+            self.game.deal(&mut rand::thread_rng());
+            self.start_hand();
+        } else if self.play_again_button.check_button_under_mouse(ctx) {
+            println!("Play Again button pressed; Scores reset");
+            self.sound_queue.push(SoundEffect::Click);
+            self.reset_game();
+        } else if button == MouseButton::Left && !self.hand_off {
+            if let Some(i) = self.hand_view.hovered {
+                let card = self.game.hands[self.viewer][i];
+                match self.game.phase {
+                    //Pick or unpick a card to pass, up to three
+                    HeartsPhase::Passing => {
+                        let picked = self.hand_view.toggle_selected(card);
+                        if !picked {
+                            self.sound_queue.push(SoundEffect::InvalidMove);
+                        }
+                    }
+                    HeartsPhase::Playing
+                        if self.game.turn == self.viewer && !self.is_ai(self.viewer) =>
+                    {
+                        self.play_card(card);
+                    }
+                    _ => (),
+                }
+            }
+        }
+        self.update_buttons();
+        GameAction::None
+    }
+
+    fn drain_sounds(&mut self) -> Vec<SoundEffect> {
+        self.sound_queue.drain(..).collect()
+    }
+
+    fn take_finished_match(&mut self) -> Option<MatchRecord> {
+        self.finished_match.take()
+    }
+}
+
+//NOTE: Tests not included since testing requires usage of mouse and other UI features
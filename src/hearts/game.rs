@@ -0,0 +1,538 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate rand;
+
+use self::rand::Rng;
+use cards::card::{Card, Rank, Suit};
+use cards::deck::Deck;
+use cards::hand::{count_suit, sort_hand};
+use cards::trick::{follow_suit, lead_suit, trick_winner};
+
+///Constant number of players in a game of Hearts
+pub const PLAYERS: usize = 4;
+
+///Constant number of cards dealt to each player
+pub const HAND_SIZE: usize = 13;
+
+///Constant number of cards each player passes before a hand is played
+pub const PASS_SIZE: usize = 3;
+
+///Constant score that ends the game once a player reaches it
+pub const GAME_END_SCORE: i32 = 100;
+
+///Constant number of points in a hand, given to every other player when one player takes them all
+pub const MOON_POINTS: i32 = 26;
+
+///Constant card that leads the first trick of every hand
+pub const TWO_OF_CLUBS: Card = Card {
+    suit: Suit::Clubs,
+    rank: Rank::Two,
+};
+
+///Constant card worth 13 points
+pub const QUEEN_OF_SPADES: Card = Card {
+    suit: Suit::Spades,
+    rank: Rank::Queen,
+};
+
+///Returns the points a card is worth when taken in a trick: 1 for each heart and 13 for the queen of spades
+pub fn points(card: Card) -> i32 {
+    if card.suit == Suit::Hearts {
+        1
+    } else if card == QUEEN_OF_SPADES {
+        13
+    } else {
+        0
+    }
+}
+
+///
+/// Enum for which player each player passes their cards to before a hand. Seats are numbered in playing order, so the
+/// player on a seat's left plays after them
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PassDirection {
+    Left,
+    Right,
+    Across,
+    Hold,
+}
+
+impl PassDirection {
+    ///Returns the direction for a hand: the passes go left, right, across and then are held, over and over
+    pub fn for_hand(hand_number: usize) -> PassDirection {
+        match hand_number % 4 {
+            0 => PassDirection::Left,
+            1 => PassDirection::Right,
+            2 => PassDirection::Across,
+            _ => PassDirection::Hold,
+        }
+    }
+
+    ///Returns the seat that the player at a seat passes to
+    pub fn target(self, seat: usize) -> usize {
+        match self {
+            PassDirection::Left => (seat + 1) % PLAYERS,
+            PassDirection::Right => (seat + PLAYERS - 1) % PLAYERS,
+            PassDirection::Across => (seat + 2) % PLAYERS,
+            PassDirection::Hold => seat,
+        }
+    }
+
+    ///Returns the direction's name, shown while passing
+    pub fn label(self) -> &'static str {
+        match self {
+            PassDirection::Left => "left",
+            PassDirection::Right => "right",
+            PassDirection::Across => "across",
+            PassDirection::Hold => "hold",
+        }
+    }
+}
+
+///
+/// Enum for the part of a game of Hearts being played
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HeartsPhase {
+    /// Every player picks three cards to pass
+    Passing,
+    /// Players take turns playing cards to tricks
+    Playing,
+    /// Every trick of the hand has been taken and the points scored
+    HandOver,
+    /// A player reached the end score after a hand
+    GameOver,
+}
+
+///
+/// A struct holding the rules and state of a game of Hearts, apart from how it is shown
+///
+/// # Fields
+/// * hands         = Vector of the cards each seat holds, kept sorted
+/// * passes        = Vector where passes[i] is Some of the cards seat i picked to pass, or None if they haven't yet
+/// * trick         = Vector of (seat, Card) pairs played to the current trick, in order
+/// * turn          = Seat of the player to play next
+/// * hearts_broken = Boolean indicating if a heart has been played this hand, so hearts can be led
+/// * tricks_played = Number of tricks taken so far this hand
+/// * taken         = Vector of the cards each seat has taken in tricks this hand
+/// * scores        = Vector of each seat's total score; the lowest score wins
+/// * hand_points   = Vector of the points each seat scored in the last hand that was finished
+/// * moon_shooter  = Option of the seat that took every point in the last hand that was finished
+/// * hand_number   = Number of hands finished, which sets the pass direction
+/// * phase         = HeartsPhase enum for the part of the game being played
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HeartsGame {
+    pub hands: Vec<Vec<Card>>,
+    pub passes: Vec<Option<Vec<Card>>>,
+    pub trick: Vec<(usize, Card)>,
+    pub turn: usize,
+    pub hearts_broken: bool,
+    pub tricks_played: usize,
+    pub taken: Vec<Vec<Card>>,
+    pub scores: Vec<i32>,
+    pub hand_points: Vec<i32>,
+    pub moon_shooter: Option<usize>,
+    pub hand_number: usize,
+    pub phase: HeartsPhase,
+}
+
+impl HeartsGame {
+    ///Constructor for a new game with every score at zero and the first hand dealt
+    pub fn new<R: Rng>(rng: &mut R) -> Self {
+        let mut game = HeartsGame {
+            hands: vec![Vec::new(); PLAYERS],
+            passes: vec![None; PLAYERS],
+            trick: Vec::new(),
+            turn: 0,
+            hearts_broken: false,
+            tricks_played: 0,
+            taken: vec![Vec::new(); PLAYERS],
+            scores: vec![0; PLAYERS],
+            hand_points: vec![0; PLAYERS],
+            moon_shooter: None,
+            hand_number: 0,
+            phase: HeartsPhase::Passing,
+        };
+        game.deal(rng);
+        game
+    }
+
+    ///Method to shuffle and deal the next hand. Passing is skipped on hands where cards are held
+    pub fn deal<R: Rng>(&mut self, rng: &mut R) {
+        self.hands = Deck::shuffled(rng).deal(PLAYERS, HAND_SIZE);
+        for hand in &mut self.hands {
+            sort_hand(hand);
+        }
+        self.passes = vec![None; PLAYERS];
+        self.trick.clear();
+        self.hearts_broken = false;
+        self.tricks_played = 0;
+        self.taken = vec![Vec::new(); PLAYERS];
+        if self.pass_direction() == PassDirection::Hold {
+            self.start_play();
+        } else {
+            self.phase = HeartsPhase::Passing;
+        }
+    }
+
+    ///Returns the direction cards are passed in this hand
+    pub fn pass_direction(&self) -> PassDirection {
+        PassDirection::for_hand(self.hand_number)
+    }
+
+    ///
+    /// Method to set the cards a seat passes. Once every seat has picked, the cards are exchanged and play starts.
+    /// Returns false if the cards aren't three different cards from the seat's hand
+    ///
+    /// # Arguments
+    /// * seat  = Seat passing the cards
+    /// * cards = Cards picked to pass
+    ///
+    pub fn set_pass(&mut self, seat: usize, cards: Vec<Card>) -> bool {
+        let mut unique = cards.clone();
+        unique.sort();
+        unique.dedup();
+        if self.phase != HeartsPhase::Passing
+            || unique.len() != PASS_SIZE
+            || cards.len() != PASS_SIZE
+            || !cards.iter().all(|card| self.hands[seat].contains(card))
+        {
+            return false;
+        }
+        self.passes[seat] = Some(cards);
+        if self.passes.iter().all(|pass| pass.is_some()) {
+            self.exchange_passes();
+        }
+        true
+    }
+
+    ///Method to move every seat's passed cards into the hand they were passed to, then start play
+    fn exchange_passes(&mut self) {
+        let direction = self.pass_direction();
+        let passes: Vec<Vec<Card>> = self
+            .passes
+            .iter()
+            .map(|p| p.clone().unwrap_or_default())
+            .collect();
+        for (seat, cards) in passes.iter().enumerate() {
+            self.hands[seat].retain(|card| !cards.contains(card));
+        }
+        for (seat, cards) in passes.into_iter().enumerate() {
+            let target = direction.target(seat);
+            self.hands[target].extend(cards);
+            sort_hand(&mut self.hands[target]);
+        }
+        self.start_play();
+    }
+
+    ///Method to start playing tricks, with the holder of the two of clubs leading
+    fn start_play(&mut self) {
+        self.phase = HeartsPhase::Playing;
+        self.turn = (0..PLAYERS)
+            .find(|seat| self.hands[*seat].contains(&TWO_OF_CLUBS))
+            .unwrap_or(0);
+    }
+
+    ///
+    /// Returns the cards a seat may play to the current trick. The first trick is led with the two of clubs and no
+    /// points may be played on it unless there is no other choice. Hearts can't be led until one has been played,
+    /// unless the hand holds nothing else, and players must follow the suit led if they can
+    ///
+    /// # Arguments
+    /// * seat = Seat of the player to play
+    ///
+    pub fn legal_plays(&self, seat: usize) -> Vec<Card> {
+        let hand = &self.hands[seat];
+        let keep_if_any = |cards: Vec<Card>| {
+            if cards.is_empty() {
+                hand.clone()
+            } else {
+                cards
+            }
+        };
+        match self.trick.first() {
+            None if self.tricks_played == 0 && hand.contains(&TWO_OF_CLUBS) => vec![TWO_OF_CLUBS],
+            None if !self.hearts_broken => keep_if_any(
+                hand.iter()
+                    .filter(|card| card.suit != Suit::Hearts)
+                    .cloned()
+                    .collect(),
+            ),
+            None => hand.clone(),
+            Some((_, lead)) if self.tricks_played == 0 && count_suit(hand, lead.suit) == 0 => {
+                keep_if_any(
+                    hand.iter()
+                        .filter(|card| points(**card) == 0)
+                        .cloned()
+                        .collect(),
+                )
+            }
+            Some(_) => follow_suit(hand, lead_suit(&self.trick)),
+        }
+    }
+
+    ///
+    /// Method for a seat to play a card to the current trick. Returns false, leaving the game as it was, if it isn't
+    /// the seat's turn or the card can't be played. Once the trick has four cards, collect_trick must be called
+    ///
+    /// # Arguments
+    /// * seat = Seat of the player playing the card
+    /// * card = Card played
+    ///
+    pub fn play(&mut self, seat: usize, card: Card) -> bool {
+        if self.phase != HeartsPhase::Playing
+            || seat != self.turn
+            || self.is_trick_complete()
+            || !self.legal_plays(seat).contains(&card)
+        {
+            return false;
+        }
+        self.hands[seat].retain(|c| *c != card);
+        self.trick.push((seat, card));
+        if card.suit == Suit::Hearts {
+            self.hearts_broken = true;
+        }
+        if !self.is_trick_complete() {
+            self.turn = (seat + 1) % PLAYERS;
+        }
+        true
+    }
+
+    ///Returns true if every player has played to the current trick
+    pub fn is_trick_complete(&self) -> bool {
+        self.trick.len() == PLAYERS
+    }
+
+    ///Method to give a complete trick to its winner, who leads the next one. Scores the hand after the last trick.
+    ///Returns the seat that took the trick
+    pub fn collect_trick(&mut self) -> usize {
+        let winner = trick_winner(&self.trick, None);
+        let cards: Vec<Card> = self.trick.drain(..).map(|(_, card)| card).collect();
+        self.taken[winner].extend(cards);
+        self.tricks_played += 1;
+        self.turn = winner;
+        if self.tricks_played == HAND_SIZE {
+            self.score_hand();
+        }
+        winner
+    }
+
+    ///Method to add the points taken this hand to the scores. A player who took every point "shoots the moon" and
+    ///every other player scores 26 instead. The game ends once a player reaches the end score
+    fn score_hand(&mut self) {
+        let taken: Vec<i32> = self
+            .taken
+            .iter()
+            .map(|cards| cards.iter().map(|card| points(*card)).sum())
+            .collect();
+        self.moon_shooter = taken.iter().position(|points| *points == MOON_POINTS);
+        self.hand_points = match self.moon_shooter {
+            Some(shooter) => (0..PLAYERS)
+                .map(|seat| if seat == shooter { 0 } else { MOON_POINTS })
+                .collect(),
+            None => taken,
+        };
+        for seat in 0..PLAYERS {
+            self.scores[seat] += self.hand_points[seat];
+        }
+        self.hand_number += 1;
+        self.phase = if self.scores.iter().any(|score| *score >= GAME_END_SCORE) {
+            HeartsPhase::GameOver
+        } else {
+            HeartsPhase::HandOver
+        };
+    }
+
+    ///Returns the seats with the lowest score, who win once the game is over
+    pub fn leaders(&self) -> Vec<usize> {
+        let lowest = self.scores.iter().min().cloned().unwrap_or(0);
+        (0..PLAYERS)
+            .filter(|seat| self.scores[*seat] == lowest)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod game_tests {
+    use super::rand::{SeedableRng, StdRng};
+    use super::*;
+    use cards::hand::cards_of_suit;
+
+    //Method to create a game in play with the given hands, skipping the deal and passes
+    fn create_test_game(hands: Vec<Vec<Card>>) -> HeartsGame {
+        let mut rng: StdRng = SeedableRng::from_seed(&[7][..]);
+        let mut game = HeartsGame::new(&mut rng);
+        game.hands = hands;
+        game.start_play();
+        game
+    }
+
+    //Method to return every card, sorted by suit and rank
+    fn full_deck() -> Vec<Card> {
+        let mut cards = Deck::new().deal(1, 52).remove(0);
+        sort_hand(&mut cards);
+        cards
+    }
+
+    //Method to create a card from a suit and rank, kept short for building test hands
+    fn c(suit: Suit, rank: i32) -> Card {
+        Card::new(suit, Rank::all()[rank as usize - 2])
+    }
+
+    mod pass_direction {
+        use super::*;
+
+        #[test]
+        fn should_cycle_left_right_across_hold() {
+            assert_eq!(PassDirection::for_hand(0).target(0), 1);
+            assert_eq!(PassDirection::for_hand(1).target(0), 3);
+            assert_eq!(PassDirection::for_hand(2).target(1), 3);
+            assert_eq!(PassDirection::for_hand(3), PassDirection::Hold);
+            assert_eq!(PassDirection::for_hand(4), PassDirection::Left);
+        }
+    }
+
+    mod hearts_game {
+        use super::*;
+
+        mod new {
+            use super::*;
+
+            #[test]
+            fn should_deal_thirteen_cards_each() {
+                let mut rng: StdRng = SeedableRng::from_seed(&[7][..]);
+                let game = HeartsGame::new(&mut rng);
+                assert_eq!(game.phase, HeartsPhase::Passing);
+                let mut cards: Vec<Card> = game.hands.iter().flatten().cloned().collect();
+                assert!(game.hands.iter().all(|hand| hand.len() == HAND_SIZE));
+                cards.sort();
+                assert_eq!(cards, full_deck());
+            }
+        }
+
+        mod set_pass {
+            use super::*;
+
+            #[test]
+            fn should_exchange_once_every_seat_passes() {
+                let mut rng: StdRng = SeedableRng::from_seed(&[7][..]);
+                let mut game = HeartsGame::new(&mut rng);
+                let picks: Vec<Vec<Card>> = game.hands.iter().map(|h| h[..3].to_vec()).collect();
+                assert!(!game.set_pass(0, vec![picks[0][0], picks[0][0], picks[0][1]]));
+                assert!(!game.set_pass(0, picks[1].clone()));
+                for (seat, pick) in picks.iter().enumerate() {
+                    assert_eq!(game.phase, HeartsPhase::Passing);
+                    assert!(game.set_pass(seat, pick.clone()));
+                }
+                //Passing left, so seat 1 holds what seat 0 passed
+                assert_eq!(game.phase, HeartsPhase::Playing);
+                assert!(picks[0].iter().all(|card| game.hands[1].contains(card)));
+                assert!(game.hands.iter().all(|hand| hand.len() == HAND_SIZE));
+                assert!(game.hands[game.turn].contains(&TWO_OF_CLUBS));
+            }
+        }
+
+        mod legal_plays {
+            use super::*;
+
+            #[test]
+            fn should_lead_two_of_clubs_and_hold_points_on_first_trick() {
+                let mut game = create_test_game(vec![
+                    vec![c(Suit::Clubs, 2), c(Suit::Hearts, 4)],
+                    vec![QUEEN_OF_SPADES, c(Suit::Hearts, 5), c(Suit::Diamonds, 3)],
+                    vec![QUEEN_OF_SPADES, c(Suit::Hearts, 6)],
+                    vec![c(Suit::Clubs, 9), c(Suit::Hearts, 7)],
+                ]);
+                assert_eq!(game.turn, 0);
+                assert_eq!(game.legal_plays(0), vec![TWO_OF_CLUBS]);
+                assert!(!game.play(0, c(Suit::Hearts, 4)));
+                assert!(game.play(0, TWO_OF_CLUBS));
+                //Void in clubs, but points can't be dumped on the first trick while other cards are held
+                assert_eq!(game.legal_plays(1), vec![c(Suit::Diamonds, 3)]);
+                assert_eq!(game.legal_plays(2).len(), 2);
+                assert_eq!(game.legal_plays(3), vec![c(Suit::Clubs, 9)]);
+            }
+
+            #[test]
+            fn should_not_lead_hearts_until_broken() {
+                let mut game = create_test_game(vec![
+                    vec![c(Suit::Hearts, 4), c(Suit::Spades, 3)],
+                    vec![c(Suit::Hearts, 5)],
+                    vec![c(Suit::Hearts, 6)],
+                    vec![c(Suit::Hearts, 7)],
+                ]);
+                game.tricks_played = 1;
+                assert_eq!(game.legal_plays(0), vec![c(Suit::Spades, 3)]);
+                assert_eq!(game.legal_plays(1), vec![c(Suit::Hearts, 5)]);
+                game.hearts_broken = true;
+                assert_eq!(game.legal_plays(0).len(), 2);
+            }
+        }
+
+        mod collect_trick {
+            use super::*;
+
+            #[test]
+            fn should_give_trick_to_highest_card_of_suit_led() {
+                let mut game = create_test_game(vec![
+                    vec![c(Suit::Clubs, 2), c(Suit::Clubs, 3)],
+                    vec![c(Suit::Clubs, 10), c(Suit::Clubs, 4)],
+                    vec![c(Suit::Diamonds, 14), c(Suit::Diamonds, 5)],
+                    vec![c(Suit::Clubs, 6), c(Suit::Clubs, 7)],
+                ]);
+                assert!(game.play(0, TWO_OF_CLUBS));
+                assert!(game.play(1, c(Suit::Clubs, 10)));
+                assert!(!game.play(3, c(Suit::Clubs, 6)));
+                assert!(game.play(2, c(Suit::Diamonds, 14)));
+                assert!(game.play(3, c(Suit::Clubs, 6)));
+                assert!(game.is_trick_complete());
+                assert!(!game.play(0, c(Suit::Clubs, 3)));
+                assert_eq!(game.collect_trick(), 1);
+                assert_eq!(game.turn, 1);
+                assert_eq!(game.taken[1].len(), 4);
+            }
+        }
+
+        mod score_hand {
+            use super::*;
+
+            #[test]
+            fn should_score_points_taken() {
+                let mut game = create_test_game(vec![Vec::new(); PLAYERS]);
+                game.taken[0] = vec![QUEEN_OF_SPADES, c(Suit::Hearts, 2)];
+                game.taken[2] = cards_of_suit(&full_deck(), Suit::Hearts)
+                    .into_iter()
+                    .filter(|c| c.rank > Rank::Two)
+                    .collect();
+                game.score_hand();
+                assert_eq!(game.hand_points, vec![14, 0, 12, 0]);
+                assert_eq!(game.moon_shooter, None);
+                assert_eq!(game.phase, HeartsPhase::HandOver);
+                assert_eq!(game.leaders(), vec![1, 3]);
+            }
+
+            #[test]
+            fn should_give_others_points_when_shooting_moon() {
+                let mut game = create_test_game(vec![Vec::new(); PLAYERS]);
+                game.taken[3] = full_deck();
+                game.score_hand();
+                assert_eq!(game.moon_shooter, Some(3));
+                assert_eq!(game.scores, vec![26, 26, 26, 0]);
+            }
+
+            #[test]
+            fn should_end_game_at_end_score() {
+                let mut game = create_test_game(vec![Vec::new(); PLAYERS]);
+                game.scores = vec![90, 40, 60, 20];
+                game.taken[0] = vec![QUEEN_OF_SPADES];
+                game.score_hand();
+                assert_eq!(game.phase, HeartsPhase::GameOver);
+                assert_eq!(game.leaders(), vec![3]);
+            }
+        }
+    }
+}
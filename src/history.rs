@@ -0,0 +1,317 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+extern crate toml;
+
+use ggez::{filesystem, Context, GameResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+///Constant name of the match history file inside the user data directory
+const HISTORY_FILE_NAME: &str = "history.toml";
+
+///
+/// A struct describing one player in a finished match
+///
+/// # Fields
+/// * name  = Name the player entered on the setup screen
+/// * is_ai = Boolean indicating if the player was an AI
+///
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PlayerRecord {
+    pub name: String,
+    pub is_ai: bool,
+}
+
+impl PlayerRecord {
+    ///Constructor - name is the player's name and is_ai indicates if the player was an AI
+    pub fn new(name: &str, is_ai: bool) -> Self {
+        PlayerRecord {
+            name: name.to_string(),
+            is_ai,
+        }
+    }
+}
+
+///
+/// A struct describing a finished game, stored in the match history
+///
+/// # Fields
+/// * game          = Name of the game played (e.g. "Connect 4")
+/// * timestamp     = Seconds since the Unix epoch when the game ended
+/// * ai_difficulty = Difficulty of the AI players, or 0 if there were no AI players
+/// * moves         = Vector of moves in the order they were made, encoded for each game as:
+///   - Connect 4: each move is a column index
+///   - Battleship: each shot is row * 10 + column
+///   - Hearts: each card played is its index from 0 to 51
+///   - Tic-Tac-Toe and Gomoku: each mark or stone is row * columns + column
+///   - Othello: each disc is row * 8 + column, and a pass is -1
+///   - Checkers: each hop of a piece is from * 32 + to, numbering the dark squares row by row
+///   - Dots and Boxes: each edge drawn is its number, edges across row by row, then edges down
+///   - Mancala: each move is the pit sown, counting from 0 in the order its player sows them
+/// * winner        = Team number of the winner, or 0 for a draw
+/// * on_time       = Boolean indicating if the game was won because the other team ran out of time
+/// * hints         = Vector of hints used, where hints[i] is the number of hints team i + 1 asked for
+/// * duration      = Seconds the game took to play
+/// * players       = Vector of PlayerRecords, where players[i] is team i + 1. Kept last as it is written as TOML tables
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MatchRecord {
+    pub game: String,
+    pub timestamp: u64,
+    pub ai_difficulty: i32,
+    pub moves: Vec<i32>,
+    pub winner: i32,
+    #[serde(default)]
+    pub on_time: bool,
+    #[serde(default)]
+    pub hints: Vec<u32>,
+    pub duration: f32,
+    pub players: Vec<PlayerRecord>,
+}
+
+impl MatchRecord {
+    ///
+    /// Method to initialize and return a MatchRecord for a game that just ended
+    ///
+    /// # Arguments
+    /// * game          = Name of the game played
+    /// * players       = Vector of PlayerRecords in team order
+    /// * ai_difficulty = Difficulty of the AI players, or 0 if there were no AI players
+    /// * moves         = Vector of moves in the order they were made
+    /// * winner        = Team number of the winner, or 0 for a draw
+    /// * duration      = Seconds the game took to play
+    ///
+    pub fn new(
+        game: &str,
+        players: Vec<PlayerRecord>,
+        ai_difficulty: i32,
+        moves: Vec<i32>,
+        winner: i32,
+        duration: f32,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or(0);
+        MatchRecord {
+            game: game.to_string(),
+            timestamp,
+            ai_difficulty,
+            moves,
+            winner,
+            on_time: false,
+            hints: Vec::new(),
+            duration,
+            players,
+        }
+    }
+
+    ///
+    /// Method to initialize and return a MatchRecord for a game played by numbered teams, marking which were played by
+    /// the AI. The AI difficulty is only recorded if there was an AI player
+    ///
+    /// # Arguments
+    /// * game          = Name of the game played
+    /// * names         = Slice of player names in team order
+    /// * ai_teams      = Slice of the team numbers (starting at 1) played by the AI
+    /// * ai_difficulty = Difficulty setting of the AI players
+    /// * moves         = Vector of moves in the order they were made
+    /// * winner        = Team number of the winner, or 0 for a draw
+    /// * duration      = Seconds the game took to play
+    ///
+    pub fn for_teams(
+        game: &str,
+        names: &[String],
+        ai_teams: &[i32],
+        ai_difficulty: i32,
+        moves: Vec<i32>,
+        winner: i32,
+        duration: f32,
+    ) -> Self {
+        let players = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| PlayerRecord::new(name, ai_teams.contains(&(i as i32 + 1))))
+            .collect();
+        let ai_difficulty = if ai_teams.is_empty() {
+            0
+        } else {
+            ai_difficulty
+        };
+        MatchRecord::new(game, players, ai_difficulty, moves, winner, duration)
+    }
+
+    ///Returns the result of the match for team i + 1: Some(true) for a win, Some(false) for a loss or None for a draw
+    pub fn won_by(&self, index: usize) -> Option<bool> {
+        if self.winner == 0 {
+            None
+        } else {
+            Some(self.winner == index as i32 + 1)
+        }
+    }
+}
+
+///
+/// A struct holding every finished match, oldest first. Stored as TOML in the user data directory
+///
+/// # Fields
+/// * matches = Vector of MatchRecords in the order they were played
+///
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct MatchHistory {
+    #[serde(default)]
+    pub matches: Vec<MatchRecord>,
+}
+
+impl MatchHistory {
+    ///Returns the path of the match history file inside the user data directory
+    pub fn path(ctx: &Context) -> PathBuf {
+        filesystem::user_data_dir(ctx).join(HISTORY_FILE_NAME)
+    }
+
+    ///
+    /// Method to read match history from a TOML string
+    ///
+    /// # Arguments
+    /// * text = TOML contents of a match history file
+    ///
+    pub fn from_toml(text: &str) -> Result<MatchHistory, String> {
+        toml::from_str::<MatchHistory>(text).map_err(|e| e.to_string())
+    }
+
+    ///Method to write match history as a TOML string
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).unwrap_or_default()
+    }
+
+    ///
+    /// Method to load match history from a file. If the file is missing or invalid, an empty history is returned so the
+    /// game can still start
+    ///
+    /// # Arguments
+    /// * path = Location of the match history file
+    ///
+    pub fn load(path: &Path) -> MatchHistory {
+        match fs::read_to_string(path) {
+            Ok(text) => match MatchHistory::from_toml(&text) {
+                Ok(history) => history,
+                Err(e) => {
+                    println!("Error reading match history {:?}: {}", path, e);
+                    MatchHistory::default()
+                }
+            },
+            Err(_) => MatchHistory::default(),
+        }
+    }
+
+    ///
+    /// Method to save match history to a file, creating its directory if needed
+    ///
+    /// # Arguments
+    /// * path = Location of the match history file
+    ///
+    pub fn save(&self, path: &Path) -> GameResult {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+
+    ///
+    /// Method to add a finished match to the history and save it
+    ///
+    /// # Arguments
+    /// * record = MatchRecord of the finished match
+    /// * path   = Location of the match history file
+    ///
+    pub fn record(&mut self, record: MatchRecord, path: &Path) {
+        self.matches.push(record);
+        if let Err(e) = self.save(path) {
+            println!("Error saving match history: {:?}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod history_tests {
+    use super::*;
+
+    fn create_test_record(winner: i32) -> MatchRecord {
+        MatchRecord::new(
+            "Connect 4",
+            vec![
+                PlayerRecord::new("Lane", false),
+                PlayerRecord::new("AI 2", true),
+            ],
+            3,
+            vec![3, 3, 4, 2],
+            winner,
+            42.5,
+        )
+    }
+
+    mod match_record {
+        use super::*;
+
+        mod won_by {
+            use super::*;
+
+            #[test]
+            fn should_return_result_for_each_team() {
+                let record = create_test_record(2);
+                assert_eq!(record.won_by(0), Some(false));
+                assert_eq!(record.won_by(1), Some(true));
+                assert_eq!(create_test_record(0).won_by(0), None);
+            }
+        }
+
+        mod for_teams {
+            use super::*;
+
+            #[test]
+            fn should_mark_ai_teams_and_only_keep_difficulty_with_an_ai() {
+                let names = vec!["Lane".to_string(), "AI 2".to_string()];
+                let record = MatchRecord::for_teams("Othello", &names, &[2], 3, vec![19], 1, 8.0);
+                assert_eq!(record.players, create_test_record(1).players);
+                assert_eq!(record.ai_difficulty, 3);
+                let record = MatchRecord::for_teams("Othello", &names, &[], 3, vec![19], 1, 8.0);
+                assert!(record.players.iter().all(|player| !player.is_ai));
+                assert_eq!(record.ai_difficulty, 0);
+            }
+        }
+    }
+
+    mod match_history {
+        use super::*;
+
+        mod from_toml {
+            use super::*;
+
+            #[test]
+            fn should_round_trip_through_to_toml() {
+                let mut record = create_test_record(1);
+                record.hints = vec![2, 0];
+                let history = MatchHistory {
+                    matches: vec![record, create_test_record(0)],
+                };
+                let text = history.to_toml();
+                assert_eq!(MatchHistory::from_toml(&text), Ok(history));
+            }
+
+            #[test]
+            fn should_read_empty_file_as_empty_history() {
+                assert_eq!(MatchHistory::from_toml(""), Ok(MatchHistory::default()));
+            }
+
+            #[test]
+            fn should_return_error_for_invalid_file() {
+                assert!(MatchHistory::from_toml("[[matches]]\ngame = 4").is_err());
+            }
+        }
+    }
+}
@@ -7,23 +7,93 @@
 /// Specifically, the design of 02_hello_world.rs (see
 /// https://github.com/ggez/ggez/blob/master/examples/02_hello_world.rs)
 extern crate ggez;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+mod battleship;
+mod cards;
+mod checkers;
+mod clock;
 mod connect4;
+mod dots;
+mod gomoku;
+mod hearts;
+mod history;
+mod mancala;
+mod mnk;
+mod othello;
+mod rating;
+mod replay;
+mod screen;
+mod settings;
+mod sound;
+mod statistics;
 
+use battleship::core::BattleshipState;
+use checkers::core::CheckersState;
 use connect4::button::{Button, BUTTON_PADDING, BUTTON_SPACING};
-use connect4::core::MyColor;
+use connect4::core::{GameAction, MyColor, PlayerInfo};
+use connect4::puzzle::{PuzzleProgress, PuzzleScreen, PuzzleSet};
+use connect4::review::ReviewScreen;
+use connect4::setup::{PlayerSetup, SetupAction};
+use connect4::tutorial::{TutorialScreen, TutorialScript};
+use dots::core::DotsState;
 use ggez::event;
+use ggez::event::{KeyCode, KeyMods};
 use ggez::graphics;
 use ggez::input::mouse::MouseButton;
 use ggez::{Context, GameResult};
+use gomoku::core::GomokuState;
+use hearts::core::HeartsState;
+use history::MatchHistory;
+use mancala::core::MancalaState;
+use mnk::core::MnkState;
+use othello::core::OthelloState;
+use rating::{AiCalibration, Ratings};
+use screen::GameScreen;
+use settings::{Settings, SettingsAction, SettingsScreen};
+use sound::{SoundEffect, SoundPlayer};
+use statistics::StatisticsScreen;
+use std::env;
 use std::fmt;
+use std::path;
 
 ///Constant dimensions for screen
-const SCREEN_SIZE: (f32, f32) = (910.0, 500.0); //Note - this is hard coded based on the known title sizes and should be adjusted if titles change
+const SCREEN_SIZE: (f32, f32) = (1110.0, 600.0); //Note - this is hard coded based on the known title sizes and should be adjusted if titles change
+
+///Constant font size of the game buttons, smaller than the titles so more games fit under "Select Game"
+const GAME_FONT_SIZE: f32 = 32.0;
+
+///Constant vertical and horizontal gap between game buttons, which wrap into another column when they reach the
+///buttons along the bottom of the menu
+const GAME_BUTTON_GAP: f32 = 20.0;
 
 /// Enum representing which game is loaded
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 enum GameLoaded {
     NONE,
     CONNECT4,
+    BATTLESHIP,
+    HEARTS,
+    TICTACTOE,
+    GOMOKU,
+    OTHELLO,
+    CHECKERS,
+    DOTS,
+    MANCALA,
+}
+
+/// Enum representing which screen is shown and receives input
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ActiveScreen {
+    MainMenu,
+    PlayerSetup,
+    Settings,
+    Statistics,
+    Game,
+    Review,
+    Puzzle,
+    Tutorial,
 }
 
 //To_string implementation, adapted from https://doc.rust-lang.org/rust-by-example/conversion/string.html
@@ -32,6 +102,14 @@ impl fmt::Display for GameLoaded {
         let text = match self {
             GameLoaded::NONE => "None",
             GameLoaded::CONNECT4 => "Connect 4",
+            GameLoaded::BATTLESHIP => "Battleship",
+            GameLoaded::HEARTS => "Hearts",
+            GameLoaded::TICTACTOE => "Tic-Tac-Toe",
+            GameLoaded::GOMOKU => "Gomoku",
+            GameLoaded::OTHELLO => "Othello",
+            GameLoaded::CHECKERS => "Checkers",
+            GameLoaded::DOTS => "Dots and Boxes",
+            GameLoaded::MANCALA => "Mancala",
         };
         write!(f, "{}", text)
     }
@@ -41,11 +119,52 @@ impl From<String> for GameLoaded {
     fn from(text: String) -> Self {
         match text.as_str() {
             "Connect 4" => GameLoaded::CONNECT4,
+            "Battleship" => GameLoaded::BATTLESHIP,
+            "Hearts" => GameLoaded::HEARTS,
+            "Tic-Tac-Toe" => GameLoaded::TICTACTOE,
+            "Gomoku" => GameLoaded::GOMOKU,
+            "Othello" => GameLoaded::OTHELLO,
+            "Checkers" => GameLoaded::CHECKERS,
+            "Dots and Boxes" => GameLoaded::DOTS,
+            "Mancala" => GameLoaded::MANCALA,
             _ => GameLoaded::NONE,
         }
     }
 }
 
+impl GameLoaded {
+    ///
+    /// Returns a new game of this kind, in its starting position, to be played on the game screen
+    ///
+    /// # Arguments
+    /// * ctx         = Context used to measure the button text
+    /// * player_info = Vector of PlayerInfo from player setup, holding the names, colors and AI players in team order
+    /// * settings    = Settings holding the board, AI, animation and theme options
+    ///
+    fn create(
+        self,
+        ctx: &mut Context,
+        player_info: Vec<PlayerInfo>,
+        settings: &Settings,
+    ) -> Box<dyn GameScreen> {
+        match self {
+            GameLoaded::BATTLESHIP => Box::new(BattleshipState::new(ctx, player_info, settings)),
+            GameLoaded::HEARTS => Box::new(HeartsState::new(ctx, player_info, settings)),
+            GameLoaded::TICTACTOE => Box::new(MnkState::new(ctx, player_info, settings)),
+            GameLoaded::GOMOKU => Box::new(GomokuState::new(ctx, player_info, settings)),
+            GameLoaded::OTHELLO => Box::new(OthelloState::new(ctx, player_info, settings)),
+            GameLoaded::CHECKERS => Box::new(CheckersState::new(ctx, player_info, settings)),
+            GameLoaded::DOTS => Box::new(DotsState::new(ctx, player_info, settings)),
+            GameLoaded::MANCALA => Box::new(MancalaState::new(ctx, player_info, settings)),
+            GameLoaded::CONNECT4 | GameLoaded::NONE => {
+                let mut game = connect4::core::GameState::new(ctx, player_info, settings);
+                game.turn_indicator.change_team(1);
+                Box::new(game)
+            }
+        }
+    }
+}
+
 ///
 /// A struct that contains the states for the main menu
 ///
@@ -60,23 +179,48 @@ impl From<String> for GameLoaded {
 ///                           the the first two columns should both be displayed which the final "Start Game" column  should not be visible. This
 ///                           value should never be less than 1 so titles and at least one set of options are displayed
 /// * game_loaded           = GameLoaded struct indicating what is loaded     
-/// * connect4_state        = GameState for a Connect4 game in `src/connect4/core.rs`. Used when Connect4 is being played      
-/// * main_screen_is_active = Boolean indicating if main menu is loaded or not       
+/// * game                  = Option of the GameScreen (see `src/screen.rs`) for the game being played, created from game_loaded when
+///                           player setup is done, or None before the first game starts
+/// * player_setup          = PlayerSetup for entering player names and colors in `src/connect4/setup.rs`. Used before a game starts
+/// * active_screen         = ActiveScreen enum indicating if the main menu, player setup, settings, statistics, a game, its review, puzzles or the tutorial are loaded
+/// * menu_buttons          = Vector of Buttons along the bottom of the main menu that open other screens (e.g. "Settings")
+/// * settings              = Settings struct in `src/settings.rs` loaded at startup and saved when leaving the settings screen
+/// * settings_screen       = SettingsScreen used to change settings
+/// * sound                 = SoundPlayer in `src/sound.rs` that plays sound effects and background music
+/// * history               = MatchHistory in `src/history.rs` holding every finished game, saved after each game ends
+/// * statistics_screen     = StatisticsScreen in `src/statistics.rs` showing records computed from the match history
+/// * ai_calibration        = AiCalibration in `src/rating.rs` giving the rating of each AI difficulty
+/// * ratings               = Ratings of every human player, recomputed from the match history after each game
+/// * review_screen         = ReviewScreen in `src/connect4/review.rs` replaying a finished Connect4 game and flagging its blunders
+/// * puzzle_screen         = PuzzleScreen in `src/connect4/puzzle.rs` playing Connect4 "win in N" puzzles against the AI
+/// * tutorial_screen       = TutorialScreen in `src/connect4/tutorial.rs` teaching the Connect4 rules with scripted lessons
 ///
 struct GameState {
     frames: usize,
     buttons: Vec<Vec<Button>>,
     buttons_available: usize,
     game_loaded: GameLoaded,
-    connect4_state: connect4::core::GameState,
-    main_screen_is_active: bool,
+    game: Option<Box<dyn GameScreen>>,
+    player_setup: PlayerSetup,
+    active_screen: ActiveScreen,
+    menu_buttons: Vec<Button>,
+    settings: Settings,
+    settings_screen: SettingsScreen,
+    sound: SoundPlayer,
+    history: MatchHistory,
+    statistics_screen: StatisticsScreen,
+    ai_calibration: AiCalibration,
+    ratings: Ratings,
+    review_screen: ReviewScreen,
+    puzzle_screen: PuzzleScreen,
+    tutorial_screen: TutorialScreen,
 }
 
 impl event::EventHandler for GameState {
     ///Main update for menu - handles actions either for main menu or game being played
     fn update(&mut self, _ctx: &mut Context) -> GameResult {
         self.frames += 1; //"Timer"
-        if self.main_screen_is_active {
+        if self.active_screen == ActiveScreen::MainMenu {
             //Only allow buttons to be active if previous options selected
             for i in 0..self.buttons.len() {
                 for j in 0..self.buttons[i].len() {
@@ -101,28 +245,54 @@ impl event::EventHandler for GameState {
                     println!("No player number selected to start games!");
                     return Ok(());
                 }
-                //Create new connect4 state
-                self.connect4_state = connect4::core::GameState::new(_ctx, 2 - players_index);
-                //Change windows size for connect4
-                graphics::set_mode(
-                    _ctx,
-                    ggez::conf::WindowMode::default()
-                        .dimensions(connect4::core::SCREEN_SIZE.0, connect4::core::SCREEN_SIZE.1),
-                )?;
-                graphics::set_screen_coordinates(
-                    _ctx,
-                    graphics::Rect::new(
-                        0.0,
-                        0.0,
-                        connect4::core::SCREEN_SIZE.0 + 10.0,
-                        connect4::core::SCREEN_SIZE.1 + 10.0,
+                //Move to player setup so names and colors can be entered before the game starts. The AI fills every
+                //seat the picked number of human players leaves open. Battleship, Tic-Tac-Toe, Gomoku, Othello,
+                //Checkers, Dots and Boxes and Mancala are always played by two players and Hearts by four, at least one
+                //of them a person
+                self.player_setup = match self.game_loaded {
+                    GameLoaded::BATTLESHIP
+                    | GameLoaded::TICTACTOE
+                    | GameLoaded::GOMOKU
+                    | GameLoaded::OTHELLO
+                    | GameLoaded::CHECKERS
+                    | GameLoaded::DOTS
+                    | GameLoaded::MANCALA => PlayerSetup::new(_ctx, 2, players_index, SCREEN_SIZE),
+                    GameLoaded::HEARTS => {
+                        PlayerSetup::new(_ctx, 4, players_index.max(1), SCREEN_SIZE)
+                    }
+                    _ => PlayerSetup::new(
+                        _ctx,
+                        self.settings.board.played().teams,
+                        players_index,
+                        SCREEN_SIZE,
                     ),
-                )?;
-                self.main_screen_is_active = false;
-                self.connect4_state.turn_indicator.change_team(1);
+                };
+                let start_index = self.buttons.len() - 1;
+                self.buttons[start_index][0].selected = false;
+                self.active_screen = ActiveScreen::PlayerSetup;
+            }
+        } else if self.active_screen == ActiveScreen::Game {
+            if let Some(game) = self.game.as_mut() {
+                game.update(_ctx)?;
+            }
+        } else if self.active_screen == ActiveScreen::Review {
+            self.review_screen.update();
+        } else if self.active_screen == ActiveScreen::Tutorial {
+            self.tutorial_screen.update(_ctx)?;
+        }
+        //Play sounds triggered by the game or tutorial since the last update
+        for effect in self.tutorial_screen.game.sound_queue.drain(..) {
+            self.sound.play(effect);
+        }
+        if let Some(game) = self.game.as_mut() {
+            for effect in game.drain_sounds() {
+                self.sound.play(effect);
+            }
+            //Save games to the match history as soon as they end
+            if let Some(record) = game.take_finished_match() {
+                self.history.record(record, &MatchHistory::path(_ctx));
+                self.ratings = Ratings::from_history(&self.history, &self.ai_calibration);
             }
-        } else {
-            self.connect4_state.update(_ctx)?;
         }
 
         Ok(())
@@ -130,27 +300,75 @@ impl event::EventHandler for GameState {
 
     ///Method that draws all buttons on grid
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
-        if self.main_screen_is_active {
-            graphics::clear(ctx, [0.1, 0.2, 0.3, 1.0].into());
-            self.draw_buttons(ctx);
-            graphics::present(ctx)?;
-        } else {
-            self.connect4_state.draw(ctx)?;
+        match self.active_screen {
+            ActiveScreen::MainMenu => {
+                graphics::clear(ctx, self.settings.theme.menu_background());
+                self.draw_buttons(ctx);
+                graphics::present(ctx)?;
+            }
+            ActiveScreen::PlayerSetup => {
+                graphics::clear(ctx, self.settings.theme.menu_background());
+                self.player_setup.draw(ctx)?;
+                graphics::present(ctx)?;
+            }
+            ActiveScreen::Settings => {
+                graphics::clear(ctx, self.settings.theme.menu_background());
+                self.settings_screen.draw(ctx, &self.settings)?;
+                graphics::present(ctx)?;
+            }
+            ActiveScreen::Statistics => {
+                graphics::clear(ctx, self.settings.theme.menu_background());
+                self.statistics_screen.draw(ctx)?;
+                graphics::present(ctx)?;
+            }
+            ActiveScreen::Game => {
+                if let Some(game) = self.game.as_mut() {
+                    game.draw(ctx)?;
+                }
+            }
+            ActiveScreen::Review => {
+                graphics::clear(ctx, self.settings.theme.game_background());
+                self.review_screen.draw(ctx)?;
+                graphics::present(ctx)?;
+            }
+            ActiveScreen::Puzzle => {
+                graphics::clear(ctx, self.settings.theme.game_background());
+                self.puzzle_screen.draw(ctx)?;
+                graphics::present(ctx)?;
+            }
+            ActiveScreen::Tutorial => {
+                graphics::clear(ctx, self.settings.theme.game_background());
+                self.tutorial_screen.draw(ctx)?;
+                graphics::present(ctx)?;
+            }
         }
         Ok(())
     }
 
     ///Method to update state of all buttons if mouse moves, either for main menu or active game
     fn mouse_motion_event(&mut self, _ctx: &mut Context, _x: f32, _y: f32, _dx: f32, _dy: f32) {
-        if self.main_screen_is_active {
-            for i in 0..self.buttons.len() {
-                for j in 0..self.buttons[i].len() {
-                    self.buttons[i][j].check_button_under_mouse(_ctx);
+        match self.active_screen {
+            ActiveScreen::MainMenu => {
+                for i in 0..self.buttons.len() {
+                    for j in 0..self.buttons[i].len() {
+                        self.buttons[i][j].check_button_under_mouse(_ctx);
+                    }
+                }
+                for button in &mut self.menu_buttons {
+                    button.check_button_under_mouse(_ctx);
+                }
+            }
+            ActiveScreen::PlayerSetup => self.player_setup.mouse_motion_event(_ctx),
+            ActiveScreen::Settings => self.settings_screen.mouse_motion_event(_ctx),
+            ActiveScreen::Statistics => self.statistics_screen.mouse_motion_event(_ctx),
+            ActiveScreen::Review => self.review_screen.mouse_motion_event(_ctx),
+            ActiveScreen::Puzzle => self.puzzle_screen.mouse_motion_event(_ctx),
+            ActiveScreen::Tutorial => self.tutorial_screen.mouse_motion_event(_ctx),
+            ActiveScreen::Game => {
+                if let Some(game) = self.game.as_mut() {
+                    game.mouse_motion_event(_ctx);
                 }
             }
-        } else {
-            self.connect4_state
-                .mouse_motion_event(_ctx, _x, _y, _dx, _dy);
         }
     }
 
@@ -162,16 +380,29 @@ impl event::EventHandler for GameState {
         _x: f32,
         _y: f32,
     ) {
-        if self.main_screen_is_active {
-            //Check whether buttons are highlighted, updated states accordingly
-            for i in 0..self.buttons.len() {
-                for j in 0..self.buttons[i].len() {
-                    self.buttons[i][j].check_button_under_mouse(_ctx);
+        match self.active_screen {
+            ActiveScreen::MainMenu => {
+                //Check whether buttons are highlighted, updated states accordingly
+                for i in 0..self.buttons.len() {
+                    for j in 0..self.buttons[i].len() {
+                        self.buttons[i][j].check_button_under_mouse(_ctx);
+                    }
+                }
+                for button in &mut self.menu_buttons {
+                    button.check_button_under_mouse(_ctx);
+                }
+            }
+            ActiveScreen::PlayerSetup => self.player_setup.mouse_motion_event(_ctx),
+            ActiveScreen::Settings => self.settings_screen.mouse_motion_event(_ctx),
+            ActiveScreen::Statistics => self.statistics_screen.mouse_motion_event(_ctx),
+            ActiveScreen::Review => self.review_screen.mouse_motion_event(_ctx),
+            ActiveScreen::Puzzle => self.puzzle_screen.mouse_motion_event(_ctx),
+            ActiveScreen::Tutorial => self.tutorial_screen.mouse_button_down_event(_ctx, _button),
+            ActiveScreen::Game => {
+                if let Some(game) = self.game.as_mut() {
+                    game.mouse_button_down_event(_ctx, _button);
                 }
             }
-        } else {
-            self.connect4_state
-                .mouse_button_down_event(_ctx, _button, _x, _y);
         }
     }
 
@@ -183,7 +414,7 @@ impl event::EventHandler for GameState {
         _x: f32,
         _y: f32,
     ) {
-        if self.main_screen_is_active {
+        if self.active_screen == ActiveScreen::MainMenu {
             //Check whether buttons are highlighted (set by clicking down). If one is highlighted and mouse still on it, button is "clicked"
             for i in 1..self.buttons.len() {
                 for j in 0..self.buttons[i].len() {
@@ -202,57 +433,229 @@ impl event::EventHandler for GameState {
                             self.buttons[i][j].selected = false;
                             self.buttons_available = i;
                         }
+                        //Preselect the default number of players once a game is picked
+                        if i == 1
+                            && self.buttons[i][j].selected
+                            && self.is_button_in_column_selected(2) < 0
+                        {
+                            self.buttons[2][self.settings.default_players as usize].selected = true;
+                            self.buttons_available = 3;
+                        }
                         println!("Button '{}' clicked!", self.buttons[i][j].text.contents());
+                        self.sound.play(SoundEffect::Click);
                         return;
                     }
                 }
             }
-        } else if self
-            .connect4_state
-            .mouse_button_up_event(_ctx, _button, _x, _y)
-        {
-            self.main_screen_is_active = true;
-
-            //Need to reset button selection, otherwise it only "resets" connect4
-            for i in 1..self.buttons.len() {
-                for j in 0..self.buttons[i].len() {
-                    self.buttons[i][j].selected = false;
-                    self.buttons_available = 1;
+            for button in &mut self.menu_buttons {
+                if button.highlighted && button.check_button_under_mouse(_ctx) {
+                    println!("Button '{}' clicked!", button.text.contents());
+                    self.sound.play(SoundEffect::Click);
+                    if button.text.contents() == "Settings" {
+                        self.active_screen = ActiveScreen::Settings;
+                    } else if button.text.contents() == "Statistics" {
+                        self.statistics_screen.refresh(&self.history, &self.ratings);
+                        self.active_screen = ActiveScreen::Statistics;
+                    } else if button.text.contents() == "Puzzles" {
+                        //Puzzles are played on a Connect4 board, so use its window size
+                        if let Err(e) = set_window_size(
+                            _ctx,
+                            connect4::core::SCREEN_SIZE,
+                            self.settings.window_scale,
+                        ) {
+                            println!("Error resizing window: {:?}", e);
+                        }
+                        self.active_screen = ActiveScreen::Puzzle;
+                    } else if button.text.contents() == "Tutorial" {
+                        //The tutorial is played on a Connect4 board, so use its window size
+                        if let Err(e) = set_window_size(
+                            _ctx,
+                            connect4::core::SCREEN_SIZE,
+                            self.settings.window_scale,
+                        ) {
+                            println!("Error resizing window: {:?}", e);
+                        }
+                        self.tutorial_screen.restart(_ctx, &self.settings);
+                        self.active_screen = ActiveScreen::Tutorial;
+                    }
                 }
             }
-            //Change windows size for main menu
-            let result = graphics::set_mode(
-                _ctx,
-                ggez::conf::WindowMode::default().dimensions(SCREEN_SIZE.0, SCREEN_SIZE.1),
-            );
-            match result {
-                Ok(_) => (),
-                Err(e) => (println!("Error drawing button: {:?}", e)),
+        } else if self.active_screen == ActiveScreen::Settings {
+            match self
+                .settings_screen
+                .mouse_button_up_event(_ctx, &mut self.settings)
+            {
+                SettingsAction::Back => {
+                    self.sound.play(SoundEffect::Click);
+                    if let Err(e) = self.settings.save(&Settings::path(_ctx)) {
+                        println!("Error saving settings: {:?}", e);
+                    }
+                    //Resize the main menu in case the window scale was changed
+                    if let Err(e) = set_window_size(_ctx, SCREEN_SIZE, self.settings.window_scale) {
+                        println!("Error resizing window: {:?}", e);
+                    }
+                    self.active_screen = ActiveScreen::MainMenu;
+                }
+                SettingsAction::Changed => {
+                    //Apply volume changes right away so they can be heard
+                    self.sound.apply_settings(&self.settings);
+                    self.sound.play(SoundEffect::Click);
+                }
+                SettingsAction::None => (),
+            }
+        } else if self.active_screen == ActiveScreen::Statistics {
+            if self.statistics_screen.mouse_button_up_event(_ctx) {
+                self.sound.play(SoundEffect::Click);
+                self.active_screen = ActiveScreen::MainMenu;
+            }
+        } else if self.active_screen == ActiveScreen::PlayerSetup {
+            match self.player_setup.mouse_button_up_event(_ctx) {
+                SetupAction::Back => {
+                    self.sound.play(SoundEffect::Click);
+                    self.active_screen = ActiveScreen::MainMenu;
+                }
+                SetupAction::Play => {
+                    self.sound.play(SoundEffect::Click);
+                    //Create the loaded game with the names, colors and AI players from setup, and size the window for it
+                    let game = self.game_loaded.create(
+                        _ctx,
+                        self.player_setup.player_info(),
+                        &self.settings,
+                    );
+                    if let Err(e) =
+                        set_window_size(_ctx, game.screen_size(), self.settings.window_scale)
+                    {
+                        println!("Error resizing window: {:?}", e);
+                    }
+                    self.game = Some(game);
+                    self.active_screen = ActiveScreen::Game;
+                }
+                SetupAction::None => (),
+            }
+        } else if self.active_screen == ActiveScreen::Puzzle {
+            if self.puzzle_screen.mouse_button_up_event(_ctx) {
+                self.sound.play(SoundEffect::Click);
+                if let Err(e) = set_window_size(_ctx, SCREEN_SIZE, self.settings.window_scale) {
+                    println!("Error resizing window: {:?}", e);
+                }
+                self.active_screen = ActiveScreen::MainMenu;
+            }
+        } else if self.active_screen == ActiveScreen::Tutorial {
+            if self.tutorial_screen.mouse_button_up_event(_ctx, _button) {
+                if let Err(e) = set_window_size(_ctx, SCREEN_SIZE, self.settings.window_scale) {
+                    println!("Error resizing window: {:?}", e);
+                }
+                self.active_screen = ActiveScreen::MainMenu;
+            }
+        } else if self.active_screen == ActiveScreen::Review {
+            if self.review_screen.mouse_button_up_event(_ctx) {
+                self.sound.play(SoundEffect::Click);
+                self.active_screen = ActiveScreen::Game;
+            }
+        } else {
+            let game = match self.game.as_mut() {
+                Some(game) => game,
+                None => return,
             };
+            match game.mouse_button_up_event(_ctx, _button) {
+                GameAction::MainMenu => {
+                    self.active_screen = ActiveScreen::MainMenu;
 
-            let result = graphics::set_screen_coordinates(
-                _ctx,
-                graphics::Rect::new(0.0, 0.0, SCREEN_SIZE.0 + 10.0, SCREEN_SIZE.1 + 10.0),
-            );
-            match result {
-                Ok(_) => (),
-                Err(e) => (println!("Error drawing button: {:?}", e)),
-            };
+                    //Need to reset button selection, otherwise it only "resets" connect4
+                    for i in 1..self.buttons.len() {
+                        for j in 0..self.buttons[i].len() {
+                            self.buttons[i][j].selected = false;
+                            self.buttons_available = 1;
+                        }
+                    }
+                    //Change windows size for main menu
+                    if let Err(e) = set_window_size(_ctx, SCREEN_SIZE, self.settings.window_scale) {
+                        println!("Error resizing window: {:?}", e);
+                    }
+                }
+                GameAction::Review => {
+                    game.start_review(&mut self.review_screen);
+                    self.active_screen = ActiveScreen::Review;
+                }
+                GameAction::None => (),
+            }
+        }
+    }
+
+    ///Method to forward typed characters to the player setup screen
+    fn text_input_event(&mut self, _ctx: &mut Context, character: char) {
+        if self.active_screen == ActiveScreen::PlayerSetup {
+            self.player_setup.text_input_event(character);
+        }
+    }
+
+    ///Method to forward special keys to the player setup screen, active game or review screen. Escape still quits, as in the default implementation
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        _keymods: KeyMods,
+        _repeat: bool,
+    ) {
+        if keycode == KeyCode::Escape {
+            event::quit(ctx);
+        } else if self.active_screen == ActiveScreen::PlayerSetup {
+            self.player_setup.key_down_event(keycode);
+        } else if self.active_screen == ActiveScreen::Game {
+            if let Some(game) = self.game.as_mut() {
+                game.key_down_event(keycode);
+            }
+        } else if self.active_screen == ActiveScreen::Review {
+            self.review_screen.key_down_event(keycode);
         }
     }
 }
 
+///
+/// Function to resize the window to the given dimensions times the window scale from settings, and its screen
+/// coordinates (with a 10 pixel margin) to the given dimensions, so everything is drawn scaled to fit the window
+///
+fn set_window_size(ctx: &mut Context, size: (f32, f32), scale: f32) -> GameResult {
+    graphics::set_mode(
+        ctx,
+        ggez::conf::WindowMode::default().dimensions(size.0 * scale, size.1 * scale),
+    )?;
+    graphics::set_screen_coordinates(
+        ctx,
+        graphics::Rect::new(0.0, 0.0, size.0 + 10.0, size.1 + 10.0),
+    )
+}
+
 //Implementation based on structure in example from GGEZ repo (see https://github.com/ggez/ggez/blob/master/examples/02_hello_world.rs)
 impl GameState {
-    fn new(ctx: &mut Context) -> GameResult<GameState> {
+    fn new(ctx: &mut Context, settings: Settings, audio_available: bool) -> GameResult<GameState> {
+        let tutorial_script = TutorialScript::load(ctx);
+        let tutorial_screen = TutorialScreen::new(ctx, tutorial_script, &settings);
         let mut s = GameState {
             frames: 0,
             buttons: Vec::<Vec<Button>>::new(),
             buttons_available: 1,
             game_loaded: GameLoaded::NONE,
-            connect4_state: connect4::core::GameState::new(ctx, 0),
-            main_screen_is_active: true,
+            game: None,
+            player_setup: PlayerSetup::new(ctx, 2, 2, SCREEN_SIZE),
+            active_screen: ActiveScreen::MainMenu,
+            menu_buttons: Vec::<Button>::new(),
+            sound: SoundPlayer::new(ctx, audio_available, &settings),
+            settings,
+            settings_screen: SettingsScreen::new(ctx, SCREEN_SIZE),
+            history: MatchHistory::load(&MatchHistory::path(ctx)),
+            statistics_screen: StatisticsScreen::new(ctx, SCREEN_SIZE),
+            ai_calibration: AiCalibration::load(&AiCalibration::path(ctx)),
+            ratings: Ratings::default(),
+            review_screen: ReviewScreen::new(connect4::core::BoardConfig::standard()),
+            puzzle_screen: PuzzleScreen::new(
+                PuzzleSet::load(ctx).puzzles,
+                PuzzleProgress::load(&PuzzleProgress::path(ctx)),
+                connect4::core::SCREEN_SIZE,
+            ),
+            tutorial_screen,
         };
+        s.ratings = Ratings::from_history(&s.history, &s.ai_calibration);
         s.create_buttons(ctx);
         Ok(s)
     }
@@ -268,6 +671,35 @@ impl GameState {
                 };
             }
         }
+        for button in &self.menu_buttons {
+            if let Err(e) = button.draw(ctx) {
+                println!("Error drawing button: {:?}", e);
+            }
+        }
+        if let Err(e) = self.draw_ratings(ctx) {
+            println!("Error drawing ratings: {:?}", e);
+        }
+    }
+
+    ///Method to draw the top player ratings in the bottom right corner of the main menu
+    fn draw_ratings(&self, ctx: &mut Context) -> GameResult {
+        if self.ratings.profiles.is_empty() {
+            return Ok(());
+        }
+        let mut lines = vec![String::from("Top Ratings")];
+        for profile in self.ratings.profiles.iter().take(3) {
+            lines.push(format!("{}: {:.0}", profile.name, profile.rating));
+        }
+        let text = graphics::Text::new((lines.join("\n"), graphics::Font::default(), 20f32));
+        let dim = text.dimensions(ctx);
+        graphics::draw(
+            ctx,
+            &text,
+            (ggez::mint::Point2 {
+                x: SCREEN_SIZE.0 - dim.0 as f32 - BUTTON_PADDING.0,
+                y: SCREEN_SIZE.1 - dim.1 as f32 - BUTTON_PADDING.1,
+            },),
+        )
     }
 
     ///Method to determine if a button in a menu column is selected. Returns index of a highlighted button or -1 if none is highlighted
@@ -284,10 +716,67 @@ impl GameState {
         -1
     }
 
+    ///
+    /// Method to create an equally sized button for each game under the "Select Game" title. The buttons fill a column
+    /// down to the screen buttons along the bottom of the menu, then wrap into another column to its right
+    ///
+    /// # Arguments
+    /// * ctx   = Context used to measure the button text
+    /// * games = GameLoaded enums for every game that can be picked, in the order they are listed
+    ///
+    fn create_game_buttons(&mut self, ctx: &mut Context, games: &[GameLoaded]) {
+        let title_outline = self.buttons[0][0].outline;
+        //Identify max length for text for all games
+        let mut max_dim = (0, 0);
+        for game in games {
+            let button_text =
+                graphics::Text::new((game.to_string(), graphics::Font::default(), GAME_FONT_SIZE));
+            max_dim.0 = max_dim.0.max(button_text.width(ctx));
+            max_dim.1 = max_dim.1.max(button_text.height(ctx));
+        }
+        let dim = (
+            2.0 * BUTTON_PADDING.0 + max_dim.0 as f32,
+            2.0 * BUTTON_PADDING.1 + max_dim.1 as f32,
+        );
+        //The screen buttons along the bottom use the same font size, so they are as tall as a game button
+        let top = title_outline.y + title_outline.h + BUTTON_SPACING.1;
+        let bottom = SCREEN_SIZE.1 - dim.1 - 2.0 * BUTTON_PADDING.1;
+        let rows = (((bottom - top + GAME_BUTTON_GAP) / (dim.1 + GAME_BUTTON_GAP)) as usize).max(1);
+        let columns = games.len().div_ceil(rows);
+        //Center the columns under the title unless they are wider than it
+        let width = columns as f32 * (dim.0 + GAME_BUTTON_GAP) - GAME_BUTTON_GAP;
+        let left = title_outline.x + ((title_outline.w - width) / 2.0).max(0.0);
+        for (i, game) in games.iter().enumerate() {
+            let button_text =
+                graphics::Text::new((game.to_string(), graphics::Font::default(), GAME_FONT_SIZE));
+            let mut button = Button::new(
+                button_text,
+                graphics::Rect::new(
+                    left + (i / rows) as f32 * (dim.0 + GAME_BUTTON_GAP),
+                    top + (i % rows) as f32 * (dim.1 + GAME_BUTTON_GAP),
+                    dim.0,
+                    dim.1,
+                ),
+            );
+            button.set_colors(MyColor::Blue, MyColor::Green);
+            self.buttons[1].push(button);
+        }
+    }
+
     ///Function to initialize buttons vector for the main menu. Buttons are
     fn create_buttons(&mut self, ctx: &mut Context) {
         //Apparently can't loop through enums, so have to manually add each game
-        let games = vec![GameLoaded::CONNECT4];
+        let games = vec![
+            GameLoaded::CONNECT4,
+            GameLoaded::BATTLESHIP,
+            GameLoaded::HEARTS,
+            GameLoaded::TICTACTOE,
+            GameLoaded::GOMOKU,
+            GameLoaded::OTHELLO,
+            GameLoaded::CHECKERS,
+            GameLoaded::DOTS,
+            GameLoaded::MANCALA,
+        ];
         //Init button vec for titles, games and num players
         while self.buttons.len() < 4 {
             self.buttons.push(Vec::<Button>::new());
@@ -319,37 +808,13 @@ impl GameState {
             }
 
             loc = loc + button_outline.w + BUTTON_SPACING.0;
-        }
-        //GAME SELECTION BUTTONS (buttons[1])
-        let mut max_dim = (0, 0);
-        //Identify max length for text for all games
-        for game in &games {
-            let button_text =
-                graphics::Text::new((game.to_string(), graphics::Font::default(), 48f32));
-            max_dim.0 = max_dim.0.max(button_text.width(ctx));
-            max_dim.1 = max_dim.1.max(button_text.height(ctx));
-        }
-        //Create buttons for games based on max dimensions so they are equal size
-        for i in 0..games.len() {
-            let mut title_outline = if i == 0 {
-                self.buttons[0][0].outline
-            } else {
-                self.buttons[1][i - 1].outline
-            };
-            let button_text =
-                graphics::Text::new((games[0].to_string(), graphics::Font::default(), 48f32));
-            let x_offset = (title_outline.w - (2.0 * BUTTON_PADDING.0 + max_dim.0 as f32)) / 2.0;
-            let mut button = Button::new(
-                button_text,
-                graphics::Rect::new(
-                    title_outline.x + x_offset,
-                    title_outline.y + title_outline.h + BUTTON_SPACING.1,
-                    2.0 * BUTTON_PADDING.0 + max_dim.0 as f32,
-                    2.0 * BUTTON_PADDING.1 + max_dim.1 as f32,
-                ),
-            );
-            button.set_colors(MyColor::Blue, MyColor::Green);
-            self.buttons[1].push(button);
+            //GAME SELECTION BUTTONS (buttons[1]) go under "Select Game", pushing the next title right of their columns
+            if self.buttons[1].is_empty() {
+                self.create_game_buttons(ctx, &games);
+                for button in &self.buttons[1] {
+                    loc = loc.max(button.outline.x + button.outline.w + BUTTON_SPACING.0);
+                }
+            }
         }
         //PLAYER NUMBERS (buttons[2])
         for i in 0..3 {
@@ -374,17 +839,76 @@ impl GameState {
             button.set_colors(MyColor::Blue, MyColor::Green);
             self.buttons[2].push(button);
         }
+        //SCREEN BUTTONS (menu_buttons), left to right along the bottom of the menu
+        let screen_titles = vec!["Settings", "Statistics", "Puzzles", "Tutorial"];
+        let mut loc = BUTTON_SPACING.0;
+        for title in screen_titles {
+            let button_text = graphics::Text::new((title, graphics::Font::default(), 32f32));
+            let dim = (
+                2.0 * BUTTON_PADDING.0 + button_text.width(ctx) as f32,
+                2.0 * BUTTON_PADDING.1 + button_text.height(ctx) as f32,
+            );
+            let mut button = Button::new(
+                button_text,
+                graphics::Rect::new(loc, SCREEN_SIZE.1 - dim.1 - BUTTON_PADDING.1, dim.0, dim.1),
+            );
+            button.set_colors(MyColor::Blue, MyColor::Green);
+            self.menu_buttons.push(button);
+            loc += dim.0 + BUTTON_PADDING.0;
+        }
     }
 }
 
 //Main game loop - tweaked from example in GGEZ repo (see https://github.com/ggez/ggez/blob/master/examples/02_hello_world.rs)
 pub fn main() -> GameResult {
-    let (ctx, event_loop) =
-        &mut ggez::ContextBuilder::new("Games Closet", "Lane Barton & Andre Mukhsia")
-            .window_setup(ggez::conf::WindowSetup::default().title("Game Closet - Main Menu"))
-            .window_mode(ggez::conf::WindowMode::default().dimensions(SCREEN_SIZE.0, SCREEN_SIZE.1))
-            .build()?;
+    let mut audio_available = true;
+    let (ctx, event_loop) = &mut match context_builder(true).build() {
+        Ok(built) => built,
+        //No audio device available, so run without sound rather than failing to start
+        Err(ggez::GameError::AudioError(e)) => {
+            println!("Error opening audio device: {}", e);
+            audio_available = false;
+            context_builder(false).build()?
+        }
+        Err(e) => return Err(e),
+    };
 
-    let state = &mut GameState::new(ctx)?;
+    //`--calibrate-ai` plays AI difficulties against each other to rate them, then exits without opening the menu
+    if env::args().any(|arg| arg == "--calibrate-ai") {
+        let calibration = AiCalibration {
+            ratings: rating::run_ai_tournament(6, 20, 510),
+        };
+        println!("AI ratings: {:?}", calibration.ratings);
+        return calibration.save(&AiCalibration::path(ctx));
+    }
+    //`--generate-puzzles` mines "win in N" puzzles from AI self-play games, then exits without opening the menu
+    if env::args().any(|arg| arg == "--generate-puzzles") {
+        let set = PuzzleSet {
+            puzzles: connect4::puzzle::generate_puzzles(20, 3, 510),
+        };
+        println!("Generated {} puzzles", set.puzzles.len());
+        return set.save(&PuzzleSet::generated_path(ctx));
+    }
+
+    //Settings are read from the user config directory, which ggez creates paths for when building the context
+    let settings = Settings::load(&Settings::path(ctx));
+    //The window is built before the settings can be found, so it is resized for the saved window scale here
+    set_window_size(ctx, SCREEN_SIZE, settings.window_scale)?;
+    let state = &mut GameState::new(ctx, settings, audio_available)?;
     event::run(ctx, event_loop, state)
 }
+
+///Function to create the ContextBuilder for the main window, with or without the audio module
+fn context_builder(audio: bool) -> ggez::ContextBuilder {
+    let mut cb = ggez::ContextBuilder::new("Games Closet", "Lane Barton & Andre Mukhsia")
+        .window_setup(ggez::conf::WindowSetup::default().title("Game Closet - Main Menu"))
+        .window_mode(ggez::conf::WindowMode::default().dimensions(SCREEN_SIZE.0, SCREEN_SIZE.1))
+        .modules(ggez::conf::ModuleConf::default().audio(audio));
+    //Sounds and puzzles are loaded from the resources directory, found next to Cargo.toml when using `cargo run`
+    if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+        let mut path = path::PathBuf::from(manifest_dir);
+        path.push("resources");
+        cb = cb.add_resource_path(path);
+    }
+    cb
+}
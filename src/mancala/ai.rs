@@ -0,0 +1,245 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::other_team;
+use mancala::board::Board;
+
+///Constant deepest the AI searches, counting each pit sown as one move, whatever its difficulty
+pub const MAX_SEARCH_DEPTH: i32 = 10;
+
+///Constant score of a won game, well above any score from weighing the position
+const WIN_SCORE: i32 = 100_000;
+
+///Constant weight of each seed in a store, which is kept whatever happens next
+const STORE_WEIGHT: i32 = 4;
+
+///Returns the number of seeds on the board, in pits and stores
+fn total_seeds(board: &Board) -> i32 {
+    (0..board.holes()).map(|hole| board.seeds(hole)).sum()
+}
+
+///
+/// Returns the score of a board from a team's point of view. A team with more than half the seeds in its store has
+/// won, since seeds never leave a store, and scores WIN_SCORE plus its margin. Otherwise the seeds in each store are
+/// weighed above the seeds left in each team's pits, which may still be captured
+///
+/// # Arguments
+/// * board = Board to score
+/// * team  = Integer value (1 or 2) of the team to score for
+///
+pub fn evaluate(board: &Board, team: i32) -> i32 {
+    let other = other_team(team);
+    let margin = board.store(team) - board.store(other);
+    let total = total_seeds(board);
+    if 2 * board.store(team) > total {
+        WIN_SCORE + margin
+    } else if 2 * board.store(other) > total {
+        -WIN_SCORE + margin
+    } else if board.is_over() {
+        0
+    } else {
+        STORE_WEIGHT * margin + board.side_seeds(team) - board.side_seeds(other)
+    }
+}
+
+///Returns the pits a team may sow, those ending in its own store first, then those nearest the store
+fn ordered_moves(board: &Board, team: i32) -> Vec<usize> {
+    let pits = board.pits();
+    let mut moves = board.legal_moves(team);
+    moves.sort_by_key(|pit| {
+        let lands_in_store = board.seeds(board.hole(team, *pit)) as usize == pits - pit;
+        (!lands_in_store, pits - pit)
+    });
+    moves
+}
+
+///
+/// Returns the score of the board from the team to move's point of view, searching depth moves ahead with alpha-beta
+/// pruning. A pit ending in the team's own store is followed by another move for the same team
+///
+/// # Arguments
+/// * board = Board to search from
+/// * team  = Integer value (1 or 2) of the team to move
+/// * depth = Number of moves left to search
+/// * alpha = Lowest score the team to move is already sure of
+/// * beta  = Highest score the other team will allow
+///
+fn alpha_beta(board: &Board, team: i32, depth: i32, mut alpha: i32, beta: i32) -> i32 {
+    let score = evaluate(board, team);
+    if depth == 0 || board.is_over() || score.abs() >= WIN_SCORE / 2 {
+        return score;
+    }
+    for pit in ordered_moves(board, team) {
+        let mut next = *board;
+        let sowing = next.play(pit, team).unwrap();
+        let score = if sowing.extra_turn {
+            alpha_beta(&next, team, depth - 1, alpha, beta)
+        } else {
+            -alpha_beta(&next, other_team(team), depth - 1, -beta, -alpha)
+        };
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    alpha
+}
+
+///
+/// A struct representing an AI or bot player for Mancala which has methods to pick moves
+///
+/// # Fields
+/// * team            = Integer value (1 or 2) of the team the AI plays for
+/// * difficulty      = Integer value for how many moves ahead the AI searches
+/// * last_move_frame = Integer used to track when the AI picked its move. Set to -1 until it picks each turn
+///
+pub struct AI {
+    pub team: i32,
+    difficulty: i32,
+    pub last_move_frame: i32,
+}
+
+impl AI {
+    ///
+    /// Method to initialize and return an AI object
+    ///
+    /// # Arguments
+    /// * team       = Integer value (1 or 2) of the team the AI plays for
+    /// * difficulty = Integer value for how many moves ahead the AI searches
+    ///
+    pub fn new(team: i32, difficulty: i32) -> Self {
+        AI {
+            team,
+            difficulty,
+            last_move_frame: -1,
+        }
+    }
+
+    ///Returns how many pits ahead to search: twice the difficulty, up to MAX_SEARCH_DEPTH
+    fn search_depth(&self) -> i32 {
+        (2 * self.difficulty).clamp(2, MAX_SEARCH_DEPTH)
+    }
+
+    ///
+    /// Method to pick the pit to sow, searching the legal moves with alpha-beta pruning. Ties go to the move searched
+    /// first. Returns None if the AI has no seeds left to sow
+    ///
+    /// # Arguments
+    /// * board = Board with the AI's team to move
+    ///
+    pub fn pick_move(&self, board: &Board) -> Option<usize> {
+        let depth = self.search_depth();
+        let mut best: Option<(i32, usize)> = None;
+        for pit in ordered_moves(board, self.team) {
+            let mut next = *board;
+            let sowing = next.play(pit, self.team).unwrap();
+            let alpha = best.map_or(-WIN_SCORE * 2, |(score, _)| score);
+            let score = if sowing.extra_turn {
+                alpha_beta(&next, self.team, depth - 1, alpha, WIN_SCORE * 2)
+            } else {
+                -alpha_beta(
+                    &next,
+                    other_team(self.team),
+                    depth - 1,
+                    -WIN_SCORE * 2,
+                    -alpha,
+                )
+            };
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, pit));
+            }
+        }
+        best.map(|(_, pit)| pit)
+    }
+}
+
+#[cfg(test)]
+mod ai_tests {
+    use super::*;
+    use mancala::board::MancalaConfig;
+
+    //Method to play a game between two AIs, returning the finished board
+    fn play_game(ais: [AI; 2], config: MancalaConfig) -> Board {
+        let mut board = Board::new(config);
+        let mut team = 1;
+        while !board.is_over() {
+            let pit = ais[team as usize - 1].pick_move(&board).unwrap();
+            if !board.play(pit, team).unwrap().extra_turn {
+                team = other_team(team);
+            }
+        }
+        board
+    }
+
+    mod ai {
+        use super::*;
+
+        mod pick_move {
+            use super::*;
+
+            #[test]
+            fn should_capture_the_most_seeds() {
+                //Pit 0 captures the 8 seeds across from team 1's empty pit 1, while pit 2 only sows one seed into the store
+                let board = Board::from(&[1, 0, 3, 0, 1, 8, 1, 0][..]);
+                for difficulty in 1..5 {
+                    assert_eq!(AI::new(1, difficulty).pick_move(&board), Some(0));
+                }
+            }
+
+            #[test]
+            fn should_take_the_extra_turn_before_sowing_past_the_store() {
+                //Pit 2 ends in the store, then pit 1 ends there too, and only then does pit 0 give up the turn
+                let board = Board::from(&[1, 2, 1, 0, 3, 3, 3, 0][..]);
+                assert_eq!(AI::new(1, 3).pick_move(&board), Some(2));
+            }
+
+            #[test]
+            fn should_return_none_without_seeds_to_sow() {
+                let board = Board::from(&[0, 0, 0, 5, 1, 2, 0, 4][..]);
+                assert_eq!(AI::new(1, 3).pick_move(&board), None);
+            }
+
+            #[test]
+            fn should_play_a_full_game_against_itself() {
+                let config = MancalaConfig::default();
+                let board = play_game([AI::new(1, 2), AI::new(2, 2)], config);
+                assert_eq!(
+                    board.store(1) + board.store(2),
+                    2 * config.pits * config.seeds
+                );
+            }
+
+            #[test]
+            fn should_beat_a_weaker_ai_from_either_side() {
+                let config = MancalaConfig::default();
+                assert_eq!(
+                    play_game([AI::new(1, 4), AI::new(2, 1)], config).leader(),
+                    1
+                );
+                assert_eq!(
+                    play_game([AI::new(1, 1), AI::new(2, 4)], config).leader(),
+                    2
+                );
+            }
+        }
+    }
+
+    mod evaluate {
+        use super::*;
+
+        #[test]
+        fn should_be_symmetric_between_teams() {
+            let board = Board::from(&[1, 4, 0, 6, 2, 0, 3, 5][..]);
+            assert_eq!(evaluate(&board, 1), -evaluate(&board, 2));
+            assert_eq!(evaluate(&Board::new(MancalaConfig::default()), 1), 0);
+        }
+
+        #[test]
+        fn should_score_a_store_with_over_half_the_seeds_as_a_win() {
+            let board = Board::from(&[1, 1, 1, 13, 1, 1, 1, 5][..]);
+            assert!(evaluate(&board, 1) > WIN_SCORE);
+            assert!(evaluate(&board, 2) < -WIN_SCORE / 2);
+        }
+    }
+}
@@ -0,0 +1,337 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::other_team;
+
+///Constant fewest and most pits on each player's side of the board
+pub const MANCALA_PITS_RANGE: (i32, i32) = (3, 8);
+
+///Constant fewest and most seeds in each pit at the start of a game
+pub const MANCALA_SEEDS_RANGE: (i32, i32) = (2, 8);
+
+///Constant number of holes, pits and stores, on the largest board
+const MAX_HOLES: usize = 2 * MANCALA_PITS_RANGE.1 as usize + 2;
+
+///
+/// A struct describing a game of Kalah, the mancala played with a store at either end of the board
+///
+/// # Fields
+/// * pits  = Number of pits on each player's side of the board
+/// * seeds = Number of seeds in each pit at the start of a game
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct MancalaConfig {
+    pub pits: i32,
+    pub seeds: i32,
+}
+
+impl Default for MancalaConfig {
+    fn default() -> Self {
+        MancalaConfig::new(6, 4)
+    }
+}
+
+impl MancalaConfig {
+    ///Constructor - pits is the number of pits on each side and seeds the number each pit starts with
+    pub fn new(pits: i32, seeds: i32) -> Self {
+        MancalaConfig { pits, seeds }
+    }
+
+    ///Returns a copy of the config with the pits and seeds moved into their valid ranges
+    pub fn clamped(self) -> Self {
+        MancalaConfig::new(
+            self.pits.clamp(MANCALA_PITS_RANGE.0, MANCALA_PITS_RANGE.1),
+            self.seeds
+                .clamp(MANCALA_SEEDS_RANGE.0, MANCALA_SEEDS_RANGE.1),
+        )
+    }
+}
+
+///
+/// A struct describing how a pit was sown, returned by Board::play
+///
+/// # Fields
+/// * captured   = Number of seeds moved to the store by a capture, counting the seed that made it, or 0
+/// * extra_turn = Boolean indicating if the last seed landed in the player's own store, so they move again
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Sowing {
+    pub captured: i32,
+    pub extra_turn: bool,
+}
+
+///
+/// A struct representing a Kalah board. Seeds are sown counter-clockwise: team 1's pits run left to right along the
+/// bottom of the board into its store on the right, then team 2's pits run right to left along the top into its store
+/// on the left. Holes are numbered in that order from team 1's leftmost pit, and each team numbers its own pits from 0
+/// in the order they are sown, which is how moves are recorded in the match history
+///
+/// # Fields
+/// * pits  = Number of pits on each side of the board
+/// * holes = Array of the seeds in each hole, of which only the first 2 * pits + 2 are used
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Board {
+    pits: usize,
+    holes: [i32; MAX_HOLES],
+}
+
+/// From trait converting the seeds in each hole, team 1's pits and store followed by team 2's, to a Board set up in
+/// that position.
+impl<'a> From<&'a [i32]> for Board {
+    fn from(seeds: &'a [i32]) -> Self {
+        let mut holes = [0; MAX_HOLES];
+        holes[..seeds.len()].copy_from_slice(seeds);
+        Board {
+            pits: (seeds.len() - 2) / 2,
+            holes,
+        }
+    }
+}
+
+impl Board {
+    ///Constructor - returns the starting position for a config, with every pit holding the same number of seeds and
+    ///both stores empty
+    pub fn new(config: MancalaConfig) -> Self {
+        let config = config.clamped();
+        let pits = config.pits as usize;
+        let mut holes = [0; MAX_HOLES];
+        for pit in 0..pits {
+            holes[pit] = config.seeds;
+            holes[pits + 1 + pit] = config.seeds;
+        }
+        Board { pits, holes }
+    }
+
+    ///Returns the number of pits on each side of the board
+    pub fn pits(&self) -> usize {
+        self.pits
+    }
+
+    ///Returns the number of holes, pits and stores, on the board
+    pub fn holes(&self) -> usize {
+        2 * self.pits + 2
+    }
+
+    ///Returns the hole of one of a team's pits, numbered from 0 in the order they are sown
+    pub fn hole(&self, team: i32, pit: usize) -> usize {
+        if team == 1 {
+            pit
+        } else {
+            self.pits + 1 + pit
+        }
+    }
+
+    ///Returns the hole of a team's store
+    pub fn store_hole(&self, team: i32) -> usize {
+        if team == 1 {
+            self.pits
+        } else {
+            2 * self.pits + 1
+        }
+    }
+
+    ///Returns the team (1 or 2) whose side of the board a hole is on, counting its store
+    pub fn side(&self, hole: usize) -> i32 {
+        if hole <= self.pits {
+            1
+        } else {
+            2
+        }
+    }
+
+    ///Returns the pit across the board from a pit, whose seeds are taken by a capture
+    pub fn opposite(&self, hole: usize) -> usize {
+        2 * self.pits - hole
+    }
+
+    ///Returns the number of seeds in a hole
+    pub fn seeds(&self, hole: usize) -> i32 {
+        self.holes[hole]
+    }
+
+    ///Returns the number of seeds in a team's store
+    pub fn store(&self, team: i32) -> i32 {
+        self.holes[self.store_hole(team)]
+    }
+
+    ///Returns the number of seeds left in a team's pits
+    pub fn side_seeds(&self, team: i32) -> i32 {
+        (0..self.pits)
+            .map(|pit| self.holes[self.hole(team, pit)])
+            .sum()
+    }
+
+    ///Returns the pits a team may sow, those holding any seeds
+    pub fn legal_moves(&self, team: i32) -> Vec<usize> {
+        (0..self.pits)
+            .filter(|pit| self.holes[self.hole(team, *pit)] > 0)
+            .collect()
+    }
+
+    ///
+    /// Returns the holes the seeds of a pit are sown into, in order, skipping the other team's store. A hole appears
+    /// more than once if the seeds go all the way around the board
+    ///
+    /// # Arguments
+    /// * pit  = Number of the team's pit to sow, from 0
+    /// * team = Integer value (1 or 2) of the team sowing
+    ///
+    pub fn sow_path(&self, pit: usize, team: i32) -> Vec<usize> {
+        let skip = self.store_hole(other_team(team));
+        let mut hole = self.hole(team, pit);
+        let mut path = Vec::new();
+        for _ in 0..self.holes[hole] {
+            hole = (hole + 1) % self.holes();
+            if hole == skip {
+                hole = (hole + 1) % self.holes();
+            }
+            path.push(hole);
+        }
+        path
+    }
+
+    ///
+    /// Method to sow a pit for a team. A last seed landing in the team's own empty pit captures it along with the seeds
+    /// in the pit opposite, if there are any. Once either side has no seeds left, the seeds left on the other side go
+    /// to its owner's store and the game is over. Returns how the pit was sown, or None if it is empty or off the board
+    ///
+    /// # Arguments
+    /// * pit  = Number of the team's pit to sow, from 0
+    /// * team = Integer value (1 or 2) of the team sowing
+    ///
+    pub fn play(&mut self, pit: usize, team: i32) -> Option<Sowing> {
+        if pit >= self.pits || self.holes[self.hole(team, pit)] == 0 {
+            return None;
+        }
+        let store = self.store_hole(team);
+        let skip = self.store_hole(other_team(team));
+        let mut hole = self.hole(team, pit);
+        let mut seeds = self.holes[hole];
+        self.holes[hole] = 0;
+        while seeds > 0 {
+            hole = (hole + 1) % self.holes();
+            if hole != skip {
+                self.holes[hole] += 1;
+                seeds -= 1;
+            }
+        }
+        let mut captured = 0;
+        if hole != store && self.side(hole) == team && self.holes[hole] == 1 {
+            let opposite = self.opposite(hole);
+            if self.holes[opposite] > 0 {
+                captured = self.holes[opposite] + 1;
+                self.holes[opposite] = 0;
+                self.holes[hole] = 0;
+                self.holes[store] += captured;
+            }
+        }
+        if self.side_seeds(1) == 0 || self.side_seeds(2) == 0 {
+            for team in 1..=2 {
+                let left = self.side_seeds(team);
+                for pit in 0..self.pits {
+                    let hole = self.hole(team, pit);
+                    self.holes[hole] = 0;
+                }
+                self.holes[self.store_hole(team)] += left;
+            }
+        }
+        Some(Sowing {
+            captured,
+            extra_turn: hole == store,
+        })
+    }
+
+    ///Returns true once every seed has been moved to a store
+    pub fn is_over(&self) -> bool {
+        self.side_seeds(1) == 0 && self.side_seeds(2) == 0
+    }
+
+    ///Returns the team with more seeds in its store, or 0 if both have the same number
+    pub fn leader(&self) -> i32 {
+        let (first, second) = (self.store(1), self.store(2));
+        if first > second {
+            1
+        } else if second > first {
+            2
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod board_tests {
+    use super::*;
+
+    mod mancala_config {
+        use super::*;
+
+        #[test]
+        fn should_clamp_pits_and_seeds() {
+            assert_eq!(
+                MancalaConfig::new(1, 20).clamped(),
+                MancalaConfig::new(3, 8)
+            );
+            assert_eq!(MancalaConfig::default().clamped(), MancalaConfig::new(6, 4));
+        }
+    }
+
+    mod board {
+        use super::*;
+
+        #[test]
+        fn should_set_up_every_pit_with_the_same_seeds() {
+            let board = Board::new(MancalaConfig::new(4, 3));
+            assert_eq!(board.holes(), 10);
+            assert_eq!(board.side_seeds(1), 12);
+            assert_eq!(board.side_seeds(2), 12);
+            assert_eq!(board.store(1) + board.store(2), 0);
+            assert_eq!(board.legal_moves(2), vec![0, 1, 2, 3]);
+        }
+
+        #[test]
+        fn should_move_again_after_ending_in_own_store() {
+            let mut board = Board::new(MancalaConfig::default());
+            let sowing = board.play(2, 1).unwrap();
+            assert!(sowing.extra_turn);
+            assert_eq!(board.store(1), 1);
+            assert_eq!(board.seeds(5), 5);
+            assert_eq!(board.play(2, 1), None);
+        }
+
+        #[test]
+        fn should_capture_the_opposite_pit() {
+            //Team 1 sows pit 0 into its empty pit 1, across from team 2's pit 1 with 5 seeds
+            let mut board = Board::from(&[1, 0, 2, 0, 1, 5, 1, 0][..]);
+            let sowing = board.play(0, 1).unwrap();
+            assert_eq!(sowing.captured, 6);
+            assert_eq!(board.store(1), 6);
+            assert_eq!(board.seeds(1), 0);
+            assert_eq!(board.seeds(board.opposite(1)), 0);
+            assert!(!sowing.extra_turn);
+        }
+
+        #[test]
+        fn should_skip_the_other_store_when_going_around() {
+            let mut board = Board::from(&[0, 0, 9, 0, 1, 1, 1, 0][..]);
+            let path = board.sow_path(2, 1);
+            assert_eq!(path, vec![3, 4, 5, 6, 0, 1, 2, 3, 4]);
+            board.play(2, 1);
+            assert_eq!(board.store(1), 2);
+            assert_eq!(board.store(2), 0);
+        }
+
+        #[test]
+        fn should_sweep_the_seeds_left_once_a_side_is_empty() {
+            let mut board = Board::from(&[0, 0, 1, 3, 2, 0, 4, 5][..]);
+            let sowing = board.play(2, 1).unwrap();
+            assert!(sowing.extra_turn);
+            assert!(board.is_over());
+            assert_eq!(board.store(1), 4);
+            assert_eq!(board.store(2), 11);
+            assert_eq!(board.leader(), 2);
+        }
+    }
+}
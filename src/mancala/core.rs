@@ -0,0 +1,622 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use connect4::button::{create_button, draw_text, mouse_location, Button};
+use connect4::core::{GameAction, MyColor, PlayerInfo, TurnIndicator};
+use ggez::input::mouse::MouseButton;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+use history::MatchRecord;
+use mancala::ai::AI;
+use mancala::board::{Board, MancalaConfig, Sowing};
+use mancala::game::{Game, TurnEnd};
+use screen::GameScreen;
+use settings::Settings;
+use sound::{result_sound, SoundEffect};
+
+///Constant dimensions for the Mancala window, fitting the board with a panel of players and buttons to its right
+pub const MANCALA_SCREEN_SIZE: (f32, f32) = (900.0, 660.0);
+
+///Constant top-left corner of the board, below the turn indicator
+const BOARD_POSITION: (f32, f32) = (40.0, 200.0);
+
+///Constant width of the area the board is fitted in, pits and stores together
+const BOARD_PIXELS: f32 = 540.0;
+
+///Constant largest width and height of each pit's cell of the board
+const MAX_CELL_SIZE: f32 = 80.0;
+
+///Constant x location of the panel of players and buttons to the right of the board
+const PANEL_POS_X: f32 = 600.0;
+
+///Constant font size for the panel text and buttons
+const MANCALA_FONT_SIZE: f32 = 24.0;
+
+///Constant number of seeds sown each frame at an animation speed of 1.0
+const SOW_SPEED: f32 = 0.15;
+
+///Constant most seeds drawn in a hole; any more are only counted
+const MAX_SEEDS_DRAWN: i32 = 24;
+
+///Constant side of the board each team's pits are on, named on the panel, where SIDE_NAMES[i] is team i + 1's
+const SIDE_NAMES: [&str; 2] = ["Bottom", "Top"];
+
+///
+/// A struct that contains the state of a Mancala game, played by the Kalah rules. Players take turns sowing the seeds
+/// of one of their pits counter-clockwise around the board, and whoever has more seeds in their store once a side runs
+/// out of seeds wins
+///
+/// # Fields
+/// * game              = Game holding the board, the team to move, the pits sown and the winner
+/// * config            = MancalaConfig from the settings for the number of pits and seeds each new game starts with
+/// * team_names        = Vector of player names, where team_names[i] is the name for team i + 1
+/// * team_colors       = Vector of MyColor objects, where team_colors[i] is the color team i + 1's side is marked in
+/// * turn_indicator    = TurnIndicator from Connect 4 showing whose turn it is and who won
+/// * ai_players        = Vector of AI objects, one for each team played by the AI
+/// * ai_difficulty     = Difficulty given to the AI players, recorded in the match history
+/// * ai_move_delay     = Number of frames an AI shows the pit it picked before sowing it
+/// * aim               = Option of the pit the AI is about to sow, or None
+/// * frames            = Integer counter for the number of times the update method is called; times AI moves
+/// * hover             = Option of the pit under the mouse the team to move may sow during a human player's turn, or None
+/// * last_move         = Option of the hole sown last, or None at the start of a game
+/// * before            = Board as it was before the pit being sown, shown while its seeds are animated
+/// * sow_path          = Vector of the holes the seeds being sown land in, in order; empty once they have all landed
+/// * sown              = Number of seeds in sow_path that have landed so far, counting part of the next one
+/// * sowing            = Option of how the pit being sown ends, applied once its seeds have all landed
+/// * animation_speed   = Multiplier for SOW_SPEED; seeds are sown instantly if this is 0.0
+/// * message           = Message shown on the panel, such as a capture or a player moving again
+/// * main_menu_button  = Button drawn to return to the main menu
+/// * play_again_button = Button drawn to reset the board and start a new game with the same players
+/// * background_color  = Color drawn behind the board
+/// * sound_queue       = Vector of SoundEffect enums triggered since the main menu last played them
+/// * elapsed           = Seconds the current game has been played for
+/// * finished_match    = Option of a MatchRecord for a game that just ended, taken by the main menu to be saved
+///
+pub struct MancalaState {
+    game: Game,
+    config: MancalaConfig,
+    team_names: Vec<String>,
+    team_colors: Vec<MyColor>,
+    turn_indicator: TurnIndicator,
+    ai_players: Vec<AI>,
+    ai_difficulty: i32,
+    ai_move_delay: i32,
+    aim: Option<usize>,
+    frames: usize,
+    hover: Option<usize>,
+    last_move: Option<usize>,
+    before: Board,
+    sow_path: Vec<usize>,
+    sown: f32,
+    sowing: Option<Sowing>,
+    animation_speed: f32,
+    message: String,
+    main_menu_button: Button,
+    play_again_button: Button,
+    background_color: graphics::Color,
+    pub sound_queue: Vec<SoundEffect>,
+    elapsed: f32,
+    pub finished_match: Option<MatchRecord>,
+}
+
+impl MancalaState {
+    ///Constructor - player_info holds the name, color and whether the AI plays for team 1 (bottom) and team 2 (top),
+    ///in that order, and settings holds the board, AI, animation and theme options. Teams without player info get the
+    ///defaults
+    pub fn new(
+        ctx: &mut Context,
+        player_info: Vec<PlayerInfo>,
+        settings: &Settings,
+    ) -> MancalaState {
+        let mut team_names = Vec::new();
+        let mut team_colors = Vec::new();
+        let mut ai_players = Vec::new();
+        let given = player_info.len();
+        for (i, info) in player_info
+            .into_iter()
+            .chain(PlayerInfo::defaults().into_iter().skip(given))
+            .take(2)
+            .enumerate()
+        {
+            if info.is_ai {
+                ai_players.push(AI::new(i as i32 + 1, settings.ai_difficulty));
+            }
+            team_names.push(info.name);
+            team_colors.push(info.color);
+        }
+        //The turn indicator names team i with names[i], so names[0] is left unused
+        let mut indicator_names = vec![String::new()];
+        indicator_names.extend(team_names.iter().cloned());
+        let game = Game::new(settings.mancala);
+        let before = game.board;
+        let buttons_y = MANCALA_SCREEN_SIZE.1 - 90.0;
+        let mut state = MancalaState {
+            game,
+            config: settings.mancala,
+            team_names,
+            team_colors,
+            turn_indicator: TurnIndicator::new(indicator_names),
+            ai_players,
+            ai_difficulty: settings.ai_difficulty,
+            ai_move_delay: settings.ai_move_delay,
+            aim: None,
+            frames: 0,
+            hover: None,
+            last_move: None,
+            before,
+            sow_path: Vec::new(),
+            sown: 0.0,
+            sowing: None,
+            animation_speed: settings.animation_speed,
+            message: String::new(),
+            main_menu_button: create_button(
+                ctx,
+                "Main Menu",
+                (PANEL_POS_X, buttons_y),
+                MANCALA_FONT_SIZE,
+                (MyColor::Brown, MyColor::Green),
+            ),
+            play_again_button: create_button(
+                ctx,
+                "Play Again",
+                (PANEL_POS_X, buttons_y - 60.0),
+                MANCALA_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            background_color: settings.theme.game_background(),
+            sound_queue: Vec::new(),
+            elapsed: 0.0,
+            finished_match: None,
+        };
+        state.reset_game();
+        state
+    }
+
+    ///Method to fill every pit again with the bottom player to move
+    fn reset_game(&mut self) {
+        self.game = Game::new(self.config);
+        self.before = self.game.board;
+        for ai in &mut self.ai_players {
+            ai.last_move_frame = -1;
+        }
+        self.aim = None;
+        self.hover = None;
+        self.last_move = None;
+        self.sow_path.clear();
+        self.sowing = None;
+        self.message.clear();
+        self.elapsed = 0.0;
+        self.play_again_button.active = false;
+        self.turn_indicator.reset();
+        self.turn_indicator.change_team(1);
+    }
+
+    ///Returns true if the AI plays for the team
+    fn is_ai(&self, team: i32) -> bool {
+        self.ai_players.iter().any(|ai| ai.team == team)
+    }
+
+    ///Returns the width and height of each pit's cell, fitting the pits and both stores in BOARD_PIXELS
+    fn cell_size(&self) -> f32 {
+        (BOARD_PIXELS / (self.game.board.pits() + 2) as f32).min(MAX_CELL_SIZE)
+    }
+
+    ///
+    /// Returns the center of a hole on the screen. Team 1's pits run left to right along the bottom row into its store
+    /// on the right, and team 2's run right to left along the top row into its store on the left
+    ///
+    /// # Arguments
+    /// * hole = Number of the hole, see Board for how holes are numbered
+    ///
+    fn hole_center(&self, hole: usize) -> Point2<f32> {
+        let cell = self.cell_size();
+        let pits = self.game.board.pits();
+        let (column, row) = if hole == self.game.board.store_hole(1) {
+            (pits + 1, 0.5)
+        } else if hole == self.game.board.store_hole(2) {
+            (0, 0.5)
+        } else if self.game.board.side(hole) == 1 {
+            (hole + 1, 1.0)
+        } else {
+            (2 * pits + 1 - hole, 0.0)
+        };
+        Point2 {
+            x: BOARD_POSITION.0 + (column as f32 + 0.5) * cell,
+            y: BOARD_POSITION.1 + (row + 0.5) * cell,
+        }
+    }
+
+    ///Returns the radius of each pit, leaving a gap between neighbouring pits
+    fn pit_radius(&self) -> f32 {
+        0.42 * self.cell_size()
+    }
+
+    ///Returns the team to move's pit under a point, or None if the point is not over one of its pits
+    fn pit_at(&self, point: Point2<f32>) -> Option<usize> {
+        let radius = self.pit_radius();
+        (0..self.game.board.pits()).find(|pit| {
+            let center = self.hole_center(self.game.board.hole(self.game.team, *pit));
+            (point.x - center.x).powi(2) + (point.y - center.y).powi(2) <= radius * radius
+        })
+    }
+
+    ///Returns the seeds shown in a hole, which while a pit is being sown counts only the seeds that have landed
+    fn shown_seeds(&self, hole: usize) -> i32 {
+        if self.sow_path.is_empty() {
+            return self.game.board.seeds(hole);
+        }
+        let from = self.last_move.unwrap_or(hole);
+        let lifted = if hole == from {
+            self.before.seeds(from)
+        } else {
+            0
+        };
+        let landed = self.sow_path[..self.sown as usize]
+            .iter()
+            .filter(|landed| **landed == hole)
+            .count() as i32;
+        self.before.seeds(hole) - lifted + landed
+    }
+
+    ///
+    /// Method to sow a pit for the team to move. The seeds are animated landing one at a time before any capture is
+    /// made and the turn passes. Returns false if the pit is empty
+    ///
+    /// # Arguments
+    /// * pit = Number of the team's pit to sow, numbered from 0 in the order its team sows them
+    ///
+    fn play(&mut self, pit: usize) -> bool {
+        let before = self.game.board;
+        let sowing = match self.game.play(pit) {
+            Some(sowing) => sowing,
+            None => {
+                self.sound_queue.push(SoundEffect::InvalidMove);
+                return false;
+            }
+        };
+        self.sound_queue.push(SoundEffect::Drop);
+        let from = self.game.board.hole(self.game.team, pit);
+        println!(
+            "{} sows pit {} with {} seeds",
+            self.team_names[self.game.team as usize - 1],
+            pit,
+            before.seeds(from)
+        );
+        self.last_move = Some(from);
+        self.hover = None;
+        self.message.clear();
+        if self.animation_speed <= 0.0 {
+            self.end_turn(sowing);
+        } else {
+            self.before = before;
+            self.sow_path = before.sow_path(pit, self.game.team);
+            self.sown = 0.0;
+            self.sowing = Some(sowing);
+        }
+        true
+    }
+
+    ///Method to show how a sowing ended, then show whose turn it is or the end of the game
+    fn end_turn(&mut self, sowing: Sowing) {
+        let name = self.team_names[self.game.team as usize - 1].clone();
+        if sowing.captured > 0 {
+            self.message = format!("{} captures {} seeds", name, sowing.captured);
+            println!("{}", self.message);
+        }
+        match self.game.end_turn(sowing) {
+            TurnEnd::GameOver(winner) => self.end_game(winner),
+            TurnEnd::ExtraTurn => self.message = format!("{} moves again", name),
+            TurnEnd::Next => self.turn_indicator.change_team(self.game.team),
+        }
+    }
+
+    ///Method to show the end of the game, won by the team with more seeds in its store or drawn if both have the same
+    fn end_game(&mut self, winner: i32) {
+        println!(
+            "Game ends {} - {}",
+            self.game.board.store(1),
+            self.game.board.store(2)
+        );
+        self.turn_indicator.change_team(winner);
+        self.turn_indicator.game_ends();
+        self.play_again_button.active = true;
+        self.finish_match(winner);
+    }
+
+    ///Method to play the end-of-game sound and record the finished game so the main menu can save it to the match history
+    fn finish_match(&mut self, winner: i32) {
+        let ai_teams: Vec<i32> = self.ai_players.iter().map(|ai| ai.team).collect();
+        self.sound_queue.push(result_sound(winner, &ai_teams));
+        self.finished_match = Some(MatchRecord::for_teams(
+            "Mancala",
+            &self.team_names,
+            &ai_teams,
+            self.ai_difficulty,
+            self.game.moves.clone(),
+            winner,
+            self.elapsed,
+        ));
+    }
+
+    ///
+    /// Method to add the seeds in a hole to the MeshBuilder, spiralling out from its center so any number of seeds
+    /// stays packed together, up to MAX_SEEDS_DRAWN
+    ///
+    /// # Arguments
+    /// * mb     = MeshBuilder the seeds are added to
+    /// * center = Center of the hole on the screen
+    /// * seeds  = Number of seeds in the hole
+    ///
+    fn draw_seeds(&self, mb: &mut graphics::MeshBuilder, center: Point2<f32>, seeds: i32) {
+        let radius = self.pit_radius() / 7.0;
+        for seed in 0..seeds.min(MAX_SEEDS_DRAWN) {
+            //Place each seed a golden angle around from the last, further out the more seeds there are
+            let angle = seed as f32 * 2.4;
+            let distance = 1.9 * radius * (seed as f32).sqrt();
+            mb.circle(
+                graphics::DrawMode::fill(),
+                Point2 {
+                    x: center.x + distance * angle.cos(),
+                    y: center.y + distance * angle.sin(),
+                },
+                radius,
+                0.5,
+                graphics::Color::from_rgba(230, 215, 170, 255),
+            );
+        }
+    }
+
+    ///Method to draw the board, the pits and stores with their seeds, the pit under the mouse or picked by the AI and
+    ///the pit sown last
+    fn draw_board(&self, ctx: &mut Context) -> GameResult {
+        let cell = self.cell_size();
+        let pits = self.game.board.pits();
+        let radius = self.pit_radius();
+        let mut mb = graphics::MeshBuilder::new();
+        mb.rectangle(
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(
+                BOARD_POSITION.0,
+                BOARD_POSITION.1,
+                (pits + 2) as f32 * cell,
+                2.0 * cell,
+            ),
+            graphics::Color::from_rgba(140, 90, 40, 255),
+        );
+        let hole_color = graphics::Color::from_rgba(90, 55, 25, 255);
+        for hole in 0..self.game.board.holes() {
+            let center = self.hole_center(hole);
+            let team = self.game.board.side(hole);
+            if hole == self.game.board.store_hole(team) {
+                //Stores span both rows and are ringed in their team's color
+                mb.ellipse(
+                    graphics::DrawMode::fill(),
+                    center,
+                    radius,
+                    0.9 * cell,
+                    0.5,
+                    hole_color,
+                );
+                mb.ellipse(
+                    graphics::DrawMode::stroke(3.0),
+                    center,
+                    radius,
+                    0.9 * cell,
+                    0.5,
+                    self.team_colors[team as usize - 1].get_draw_color(),
+                );
+            } else {
+                mb.circle(graphics::DrawMode::fill(), center, radius, 0.5, hole_color);
+            }
+            self.draw_seeds(&mut mb, center, self.shown_seeds(hole));
+        }
+        if let Some(pit) = self.hover.or(self.aim) {
+            mb.circle(
+                graphics::DrawMode::stroke(3.0),
+                self.hole_center(self.game.board.hole(self.game.team, pit)),
+                radius,
+                0.5,
+                MyColor::Yellow.get_draw_color(),
+            );
+        }
+        if let Some(hole) = self.last_move {
+            let center = self.hole_center(hole);
+            mb.circle(
+                graphics::DrawMode::fill(),
+                Point2 {
+                    x: center.x,
+                    y: center.y + radius + 0.04 * cell,
+                },
+                4.0,
+                0.5,
+                MyColor::Red.get_draw_color(),
+            );
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))?;
+        //Count the seeds in each hole above the top row and below the bottom row, and beside each store
+        for hole in 0..self.game.board.holes() {
+            let center = self.hole_center(hole);
+            let y =
+                if hole == self.game.board.store_hole(1) || hole == self.game.board.store_hole(2) {
+                    center.y + cell
+                } else if self.game.board.side(hole) == 1 {
+                    center.y + 0.5 * cell + 4.0
+                } else {
+                    center.y - 0.5 * cell - 24.0
+                };
+            draw_text(
+                ctx,
+                &self.shown_seeds(hole).to_string(),
+                (center.x - 8.0, y),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        Ok(())
+    }
+
+    ///Method to draw the players with the seeds in their stores, the seeds left to sow and any message to the right of
+    ///the board
+    fn draw_panel(&self, ctx: &mut Context) -> GameResult {
+        draw_text(
+            ctx,
+            "Mancala",
+            (PANEL_POS_X, BOARD_POSITION.1 - 80.0),
+            MANCALA_FONT_SIZE,
+            MyColor::White,
+        )?;
+        for (i, name) in self.team_names.iter().enumerate() {
+            let team = i as i32 + 1;
+            let player = if self.is_ai(team) {
+                format!("{}: {} (AI)", SIDE_NAMES[i], name)
+            } else {
+                format!("{}: {}", SIDE_NAMES[i], name)
+            };
+            let y = BOARD_POSITION.1 - 30.0 + 70.0 * i as f32;
+            draw_text(
+                ctx,
+                &player,
+                (PANEL_POS_X, y),
+                MANCALA_FONT_SIZE,
+                self.team_colors[i],
+            )?;
+            draw_text(
+                ctx,
+                &format!("Store: {}", self.game.board.store(team)),
+                (PANEL_POS_X + 20.0, y + 30.0),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        let mut lines = vec![format!(
+            "Seeds left: {}",
+            self.game.board.side_seeds(1) + self.game.board.side_seeds(2)
+        )];
+        if !self.message.is_empty() {
+            lines.push(self.message.clone());
+        }
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(
+                ctx,
+                line,
+                (PANEL_POS_X, BOARD_POSITION.1 + 120.0 + 30.0 * i as f32),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl GameScreen for MancalaState {
+    fn screen_size(&self) -> (f32, f32) {
+        MANCALA_SCREEN_SIZE
+    }
+
+    ///Update method for Mancala, which animates seeds being sown, then lets the AI pick a pit, show it and sow it once
+    ///enough frames pass
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        self.frames += 1; //Timing mechanism for AI moves
+        if self.game.winner.is_some() {
+            return Ok(());
+        }
+        self.elapsed += ggez::timer::delta(_ctx).as_secs_f32();
+        if !self.sow_path.is_empty() {
+            self.sown += SOW_SPEED * self.animation_speed;
+            if self.sown >= self.sow_path.len() as f32 {
+                self.sow_path.clear();
+                if let Some(sowing) = self.sowing.take() {
+                    self.end_turn(sowing);
+                }
+            }
+            return Ok(());
+        }
+        let team = self.game.team;
+        if let Some(i) = self.ai_players.iter().position(|ai| ai.team == team) {
+            if self.ai_players[i].last_move_frame < 0 {
+                self.aim = self.ai_players[i].pick_move(&self.game.board);
+                self.ai_players[i].last_move_frame = self.frames as i32;
+            } else if self.frames
+                > (self.ai_players[i].last_move_frame + self.ai_move_delay) as usize
+            {
+                self.ai_players[i].last_move_frame = -1;
+                if let Some(pit) = self.aim.take() {
+                    self.play(pit);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    ///Draw method for Mancala, which draws the turn indicator, the board, the player panel and the buttons
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::clear(ctx, self.background_color);
+        self.turn_indicator
+            .draw(ctx, BOARD_POSITION.0 + BOARD_PIXELS / 2.0)?;
+        self.draw_board(ctx)?;
+        self.draw_panel(ctx)?;
+        self.main_menu_button.draw(ctx)?;
+        self.play_again_button.draw(ctx)?;
+        graphics::present(ctx)?;
+        ggez::timer::yield_now();
+        Ok(())
+    }
+
+    ///Method active whenever the mouse moves, which tracks the pit under it that the team to move may sow during a
+    ///human player's turn
+    fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        self.hover = if self.game.winner.is_some()
+            || !self.sow_path.is_empty()
+            || self.is_ai(self.game.team)
+        {
+            None
+        } else {
+            self.pit_at(mouse_location(ctx)).filter(|pit| {
+                let hole = self.game.board.hole(self.game.team, *pit);
+                self.game.board.seeds(hole) > 0
+            })
+        };
+        self.main_menu_button.check_button_under_mouse(ctx);
+        self.play_again_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method to handle a click on the Mancala screen, returning what the main menu should do next
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton) -> GameAction {
+        self.mouse_motion_event(ctx);
+        if self.main_menu_button.check_button_under_mouse(ctx) {
+            println!("Main Menu Button pressed; Main Menu should pop up");
+            self.sound_queue.push(SoundEffect::Click);
+            return GameAction::MainMenu;
+        }
+        if self.play_again_button.check_button_under_mouse(ctx) {
+            println!("Play Again button pressed; Board reset");
+            self.sound_queue.push(SoundEffect::Click);
+            self.reset_game();
+        } else if button == MouseButton::Left {
+            if let Some(pit) = self.hover {
+                self.play(pit);
+            } else if self.game.winner.is_none()
+                && self.sow_path.is_empty()
+                && !self.is_ai(self.game.team)
+                && self.pit_at(mouse_location(ctx)).is_some()
+            {
+                self.sound_queue.push(SoundEffect::InvalidMove);
+            }
+        }
+        GameAction::None
+    }
+
+    fn drain_sounds(&mut self) -> Vec<SoundEffect> {
+        self.sound_queue.drain(..).collect()
+    }
+
+    fn take_finished_match(&mut self) -> Option<MatchRecord> {
+        self.finished_match.take()
+    }
+}
+
+//NOTE: Tests not included since testing requires usage of mouse and other UI features
@@ -0,0 +1,128 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::other_team;
+use mancala::board::{Board, MancalaConfig, Sowing};
+
+///
+/// Enum representing what happened when a turn of Mancala ended
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TurnEnd {
+    /// The other team moves next
+    Next,
+    /// The last seed landed in the team's own store, so the same team moves again
+    ExtraTurn,
+    /// Every seed is in a store, and the game was won by the team, or drawn if it is 0
+    GameOver(i32),
+}
+
+///
+/// A struct tracking a game of Mancala: the board, whose turn it is and the pits sown. A pit is sown and the turn ended
+/// in separate steps, so the seeds can be shown landing before the next team moves
+///
+/// # Fields
+/// * board  = Board holding the seeds in each pit and store
+/// * team   = Integer value (1 or 2) of the team to move
+/// * moves  = Vector of pits sown so far, in order, each numbered from 0 in the order its team sows them
+/// * winner = Option of the team with more seeds in its store once every seed is in a store, 0 for a draw, or None
+///   while it is being played
+///
+#[derive(Clone, Debug)]
+pub struct Game {
+    pub board: Board,
+    pub team: i32,
+    pub moves: Vec<i32>,
+    pub winner: Option<i32>,
+}
+
+impl Game {
+    ///Constructor - returns a game at the starting position for a config with the bottom player (team 1) to move
+    pub fn new(config: MancalaConfig) -> Self {
+        Game {
+            board: Board::new(config),
+            team: 1,
+            moves: Vec::new(),
+            winner: None,
+        }
+    }
+
+    ///
+    /// Method to sow a pit for the team to move, without passing the turn. Returns how the pit was sown, or None if it
+    /// is empty, off the board or the game is over
+    ///
+    /// # Arguments
+    /// * pit = Number of the team's pit to sow, numbered from 0 in the order its team sows them
+    ///
+    pub fn play(&mut self, pit: usize) -> Option<Sowing> {
+        if self.winner.is_some() {
+            return None;
+        }
+        let sowing = self.board.play(pit, self.team);
+        if sowing.is_some() {
+            self.moves.push(pit as i32);
+        }
+        sowing
+    }
+
+    ///
+    /// Method to end the game once every seed is in a store, or else give the same team another turn if its sowing
+    /// ended in its store, or pass the turn to the other team
+    ///
+    /// # Arguments
+    /// * sowing = Sowing returned by play for the pit just sown
+    ///
+    pub fn end_turn(&mut self, sowing: Sowing) -> TurnEnd {
+        if self.board.is_over() {
+            let winner = self.board.leader();
+            self.winner = Some(winner);
+            TurnEnd::GameOver(winner)
+        } else if sowing.extra_turn {
+            TurnEnd::ExtraTurn
+        } else {
+            self.team = other_team(self.team);
+            TurnEnd::Next
+        }
+    }
+}
+
+#[cfg(test)]
+mod game_tests {
+    use super::*;
+
+    mod game {
+        use super::*;
+
+        mod end_turn {
+            use super::*;
+
+            #[test]
+            fn should_give_another_turn_after_ending_in_own_store() {
+                let mut game = Game::new(MancalaConfig::default());
+                let sowing = game.play(2).unwrap();
+                assert_eq!(game.end_turn(sowing), TurnEnd::ExtraTurn);
+                assert_eq!(game.team, 1);
+                let sowing = game.play(0).unwrap();
+                assert_eq!(game.end_turn(sowing), TurnEnd::Next);
+                assert_eq!(game.team, 2);
+                assert_eq!(game.moves, vec![2, 0]);
+            }
+
+            #[test]
+            fn should_end_the_game_once_a_side_is_swept() {
+                let mut game = Game::new(MancalaConfig::new(3, 4));
+                game.board = Board::from(&[0, 0, 1, 3, 2, 0, 4, 5][..]);
+                let sowing = game.play(2).unwrap();
+                //Ending in its own store gives no extra turn once the game is over
+                assert!(sowing.extra_turn);
+                assert_eq!(game.end_turn(sowing), TurnEnd::GameOver(2));
+                assert_eq!(game.winner, Some(2));
+                assert_eq!(game.board.store(1), 4);
+                assert_eq!(game.board.store(2), 11);
+                assert_eq!(game.play(0), None);
+                assert_eq!(game.moves, vec![2]);
+            }
+        }
+    }
+}
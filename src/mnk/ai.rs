@@ -0,0 +1,331 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::{other_team, GridPosition};
+use mnk::board::{Board, DIRECTIONS};
+use std::collections::HashMap;
+
+///Constant number of empty cells at or below which the AI searches every move to the end of the game, so it plays
+///perfectly. Every 3x3 game is searched this way
+pub const PERFECT_SEARCH_CELLS: usize = 9;
+
+///Constant deepest the AI searches on boards too large to search to the end, whatever its difficulty
+pub const MAX_SEARCH_DEPTH: i32 = 4;
+
+///Constant number of the most promising moves searched at each step on boards too large to search to the end
+const CANDIDATE_MOVES: usize = 8;
+
+///Constant score of a won position, well above any score from counting runs
+const WIN_SCORE: i32 = 1_000_000;
+
+///Constant score of a line of marks that could still become a winning run, where RUN_SCORES[n] is for n marks
+const RUN_SCORES: [i32; 7] = [0, 1, 10, 100, 1_000, 10_000, 100_000];
+
+///
+/// Returns the score of a game from the team to move's point of view when both sides play perfectly, searching every
+/// move to the end of the game. A win scores one more than the number of cells left empty, so quicker wins score
+/// higher, and a draw scores 0. Scores of positions already searched are kept in memo
+///
+/// # Arguments
+/// * board = Board to search from, which is changed while searching and left as it was
+/// * team  = Integer value (1 or 2) of the team to move
+/// * memo  = HashMap of the scores of positions already searched, with the same team to move
+///
+fn perfect_score(board: &mut Board, team: i32, memo: &mut HashMap<Board, i32>) -> i32 {
+    if let Some(score) = memo.get(board) {
+        return *score;
+    }
+    let mut best = i32::MIN;
+    for cell in board.empty_cells() {
+        board.place(cell, team);
+        let score = if board.is_win(cell) {
+            board.empty_cells().len() as i32 + 1
+        } else if board.is_full() {
+            0
+        } else {
+            -perfect_score(board, other_team(team), memo)
+        };
+        board.clear(cell);
+        best = best.max(score);
+    }
+    memo.insert(board.clone(), best);
+    best
+}
+
+///
+/// Returns the score of a board from a team's point of view, counting every line of connect cells that only one team
+/// has marks in. Lines with more marks score far more, and the other team's lines count against the team
+///
+/// # Arguments
+/// * board   = Board to score
+/// * windows = Every line of connect cells on the board, from Board::windows
+/// * team    = Integer value (1 or 2) of the team the score is for
+///
+fn evaluate(board: &Board, windows: &[Vec<usize>], team: i32) -> i32 {
+    let mut score = 0;
+    for window in windows {
+        let own = window.iter().filter(|c| board.cell(**c) == team).count();
+        let other = window
+            .iter()
+            .filter(|c| board.cell(**c) == other_team(team))
+            .count();
+        if other == 0 {
+            score += RUN_SCORES[own.min(RUN_SCORES.len() - 1)];
+        } else if own == 0 {
+            score -= RUN_SCORES[other.min(RUN_SCORES.len() - 1)];
+        }
+    }
+    score
+}
+
+///
+/// Returns the most promising empty cells for the team to move, best first. Only cells within two cells of a mark
+/// are considered, and cells are ranked by the longest runs they make for either team. An empty board gives its center
+///
+/// # Arguments
+/// * board = Board to pick cells on
+/// * team  = Integer value (1 or 2) of the team to move
+///
+fn candidate_moves(board: &mut Board, team: i32) -> Vec<usize> {
+    if board.marks() == 0 {
+        let center = GridPosition::new(board.config.columns / 2, board.config.rows / 2);
+        return vec![board.index(center)];
+    }
+    let near_mark = |board: &Board, pos: GridPosition| {
+        (-2..3)
+            .any(|dy| (-2..3).any(|dx| board.get(GridPosition::new(pos.x + dx, pos.y + dy)) != 0))
+    };
+    let mut ranked = Vec::new();
+    for cell in board.empty_cells() {
+        let pos = board.position(cell);
+        if !near_mark(board, pos) {
+            continue;
+        }
+        let mut rank = 0;
+        for t in [team, other_team(team)].iter() {
+            board.place(cell, *t);
+            for dir in DIRECTIONS.iter() {
+                let run = board.run_length(pos, *dir, *t);
+                rank += run * run;
+            }
+            board.clear(cell);
+        }
+        ranked.push((rank, cell));
+    }
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    ranked
+        .into_iter()
+        .take(CANDIDATE_MOVES)
+        .map(|(_, cell)| cell)
+        .collect()
+}
+
+///
+/// Returns the score of the board from the team to move's point of view, searching depth moves ahead with alpha-beta
+/// pruning. Wins found sooner score higher
+///
+/// # Arguments
+/// * board   = Board to search from, which is changed while searching and left as it was
+/// * windows = Every line of connect cells on the board, from Board::windows
+/// * team    = Integer value (1 or 2) of the team to move
+/// * depth   = Number of moves left to search
+/// * alpha   = Lowest score the team to move is already sure of
+/// * beta    = Highest score the other team will allow
+///
+fn alpha_beta(
+    board: &mut Board,
+    windows: &[Vec<usize>],
+    team: i32,
+    depth: i32,
+    mut alpha: i32,
+    beta: i32,
+) -> i32 {
+    if depth == 0 {
+        return evaluate(board, windows, team);
+    }
+    let moves = candidate_moves(board, team);
+    if moves.is_empty() {
+        return 0;
+    }
+    for cell in moves {
+        board.place(cell, team);
+        let score = if board.is_win(cell) {
+            WIN_SCORE + depth
+        } else if board.is_full() {
+            0
+        } else {
+            -alpha_beta(board, windows, other_team(team), depth - 1, -beta, -alpha)
+        };
+        board.clear(cell);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    alpha
+}
+
+///
+/// A struct representing an AI or bot player for an m,n,k-game which has methods to pick moves
+///
+/// # Fields
+/// * team            = Integer value (1 or 2) of the team the AI plays for
+/// * difficulty      = Integer value for how many moves ahead the AI searches on boards too large to search to the end
+/// * last_move_frame = Integer used to track when the AI picked its move. Set to -1 until it picks each turn
+///
+pub struct AI {
+    pub team: i32,
+    difficulty: i32,
+    pub last_move_frame: i32,
+}
+
+impl AI {
+    ///
+    /// Method to initialize and return an AI object
+    ///
+    /// # Arguments
+    /// * team       = Integer value (1 or 2) of the team the AI plays for
+    /// * difficulty = Integer value for how many moves ahead the AI searches
+    ///
+    pub fn new(team: i32, difficulty: i32) -> Self {
+        AI {
+            team,
+            difficulty,
+            last_move_frame: -1,
+        }
+    }
+
+    ///
+    /// Method to pick the cell to mark. Once few enough cells are empty, every move is searched to the end of the game
+    /// and the quickest win (or slowest loss) is played; ties go to the cell nearest the center. Before that, the most
+    /// promising moves are searched as many moves ahead as the difficulty allows, up to MAX_SEARCH_DEPTH
+    ///
+    /// # Arguments
+    /// * board = Board with the AI's team to move, which must have an empty cell
+    ///
+    pub fn pick_move(&self, board: &Board) -> usize {
+        let mut board = board.clone();
+        let center = (
+            (board.config.columns - 1) as f32 / 2.0,
+            (board.config.rows - 1) as f32 / 2.0,
+        );
+        let distance = |board: &Board, cell: usize| {
+            let pos = board.position(cell);
+            (pos.x as f32 - center.0).abs() + (pos.y as f32 - center.1).abs()
+        };
+        let perfect = board.empty_cells().len() <= PERFECT_SEARCH_CELLS;
+        let moves = if perfect {
+            board.empty_cells()
+        } else {
+            candidate_moves(&mut board, self.team)
+        };
+        let windows = board.windows();
+        let depth = self.difficulty.clamp(1, MAX_SEARCH_DEPTH);
+        let mut memo = HashMap::new();
+        let mut best: Option<(i32, usize)> = None;
+        for cell in moves {
+            board.place(cell, self.team);
+            let score = if board.is_win(cell) {
+                WIN_SCORE + depth + board.empty_cells().len() as i32
+            } else if board.is_full() {
+                0
+            } else if perfect {
+                -perfect_score(&mut board, other_team(self.team), &mut memo)
+            } else {
+                -alpha_beta(
+                    &mut board,
+                    &windows,
+                    other_team(self.team),
+                    depth - 1,
+                    -WIN_SCORE * 2,
+                    WIN_SCORE * 2,
+                )
+            };
+            board.clear(cell);
+            best = match best {
+                Some((best_score, best_cell))
+                    if best_score > score
+                        || (best_score == score
+                            && distance(&board, best_cell) <= distance(&board, cell)) =>
+                {
+                    best
+                }
+                _ => Some((score, cell)),
+            };
+        }
+        best.map_or(0, |(_, cell)| cell)
+    }
+}
+
+#[cfg(test)]
+mod ai_tests {
+    use super::*;
+    use mnk::board::MnkConfig;
+
+    //Method to create a board with marks for team 1 and team 2 placed in the given cells
+    fn create_test_board(config: MnkConfig, ones: &[usize], twos: &[usize]) -> Board {
+        let mut board = Board::new(config);
+        for cell in ones {
+            board.place(*cell, 1);
+        }
+        for cell in twos {
+            board.place(*cell, 2);
+        }
+        board
+    }
+
+    mod ai {
+        use super::*;
+
+        mod pick_move {
+            use super::*;
+
+            #[test]
+            fn should_win_before_blocking() {
+                let board = create_test_board(MnkConfig::tic_tac_toe(), &[0, 1, 8], &[3, 4]);
+                assert_eq!(AI::new(2, 1).pick_move(&board), 5);
+            }
+
+            #[test]
+            fn should_block_a_run() {
+                let board = create_test_board(MnkConfig::tic_tac_toe(), &[0, 1], &[4]);
+                assert_eq!(AI::new(2, 1).pick_move(&board), 2);
+            }
+
+            #[test]
+            fn should_draw_against_itself() {
+                let mut board = Board::new(MnkConfig::tic_tac_toe());
+                let players = [AI::new(1, 1), AI::new(2, 1)];
+                let mut team = 1;
+                while !board.is_full() {
+                    let cell = players[team as usize - 1].pick_move(&board);
+                    assert!(board.place(cell, team));
+                    assert!(!board.is_win(cell));
+                    team = other_team(team);
+                }
+            }
+
+            #[test]
+            fn should_answer_corner_with_center() {
+                let board = create_test_board(MnkConfig::tic_tac_toe(), &[0], &[]);
+                assert_eq!(AI::new(2, 1).pick_move(&board), 4);
+            }
+
+            #[test]
+            fn should_complete_or_block_four_on_large_board() {
+                //Team 1 has four in a row across the middle of a 7x7 board, blocked on the left
+                let config = MnkConfig::new(7, 7, 5);
+                let board = create_test_board(config, &[22, 23, 24, 25], &[21, 16, 30]);
+                assert_eq!(AI::new(2, 2).pick_move(&board), 26);
+                assert_eq!(AI::new(1, 2).pick_move(&board), 26);
+            }
+
+            #[test]
+            fn should_start_in_center_of_empty_board() {
+                let board = Board::new(MnkConfig::new(15, 15, 5));
+                assert_eq!(AI::new(1, 3).pick_move(&board), 7 * 15 + 7);
+            }
+        }
+    }
+}
@@ -0,0 +1,401 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::GridPosition;
+
+///Constant smallest and largest number of rows or columns a board may have
+pub const MNK_SIZE_RANGE: (i32, i32) = (3, 19);
+
+///Constant shortest and longest run that may be needed to win
+pub const MNK_CONNECT_RANGE: (i32, i32) = (3, 6);
+
+///Constant board sizes and run lengths (rows, columns, connect) offered on the settings screen, smallest first
+pub const MNK_PRESETS: [(i32, i32, i32); 6] = [
+    (3, 3, 3),
+    (4, 4, 4),
+    (5, 5, 4),
+    (7, 7, 5),
+    (10, 10, 5),
+    (15, 15, 5),
+];
+
+///Constant directions a run can be counted in: across, down and along both diagonals. Runs are counted both ways
+pub const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
+
+///
+/// A struct describing an m,n,k-game: the board is rows x columns and the first player to get connect marks in a row
+/// (across, down or diagonally) wins. Tic-tac-toe is the 3,3,3-game and free-style Gomoku is the 15,15,5-game
+///
+/// # Fields
+/// * rows    = Number of rows of the board
+/// * columns = Number of columns of the board
+/// * connect = Number of marks in a row needed to win
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct MnkConfig {
+    pub rows: i32,
+    pub columns: i32,
+    pub connect: i32,
+}
+
+impl Default for MnkConfig {
+    fn default() -> Self {
+        MnkConfig::tic_tac_toe()
+    }
+}
+
+impl MnkConfig {
+    ///Constructor - rows and columns give the size of the board and connect is the length of a winning run
+    pub fn new(rows: i32, columns: i32, connect: i32) -> Self {
+        MnkConfig {
+            rows,
+            columns,
+            connect,
+        }
+    }
+
+    ///Returns the 3x3 tic-tac-toe board
+    pub fn tic_tac_toe() -> Self {
+        MnkConfig::new(3, 3, 3)
+    }
+
+    ///Returns a copy of the config with the size and run length moved into their valid ranges. The run length is
+    ///kept short enough to fit on the board
+    pub fn clamped(self) -> Self {
+        let rows = self.rows.clamp(MNK_SIZE_RANGE.0, MNK_SIZE_RANGE.1);
+        let columns = self.columns.clamp(MNK_SIZE_RANGE.0, MNK_SIZE_RANGE.1);
+        let longest = MNK_CONNECT_RANGE.1.min(rows.max(columns));
+        MnkConfig::new(
+            rows,
+            columns,
+            self.connect.clamp(MNK_CONNECT_RANGE.0, longest),
+        )
+    }
+
+    ///
+    /// Returns the next larger or smaller preset, or the config itself if there is none in that direction. A custom
+    /// config from the settings file moves to the nearest preset in that direction
+    ///
+    /// # Arguments
+    /// * up = Boolean indicating if the board should get larger (true) or smaller (false)
+    ///
+    pub fn step(self, up: bool) -> Self {
+        let key = |preset: (i32, i32, i32)| (preset.0 * preset.1, preset.2);
+        let current = key((self.rows, self.columns, self.connect));
+        let next = if up {
+            MNK_PRESETS.iter().find(|preset| key(**preset) > current)
+        } else {
+            MNK_PRESETS
+                .iter()
+                .rev()
+                .find(|preset| key(**preset) < current)
+        };
+        next.map_or(self, |preset| MnkConfig::new(preset.0, preset.1, preset.2))
+    }
+
+    ///Returns the name of the game played on the board, e.g. "Tic-Tac-Toe" or "5 in a row (15x15)"
+    pub fn label(&self) -> String {
+        if *self == MnkConfig::tic_tac_toe() {
+            String::from("Tic-Tac-Toe")
+        } else {
+            format!("{} in a row ({}x{})", self.connect, self.rows, self.columns)
+        }
+    }
+
+    ///Returns the number of cells on the board
+    pub fn cells(&self) -> usize {
+        (self.rows * self.columns) as usize
+    }
+}
+
+///
+/// A struct representing the board of an m,n,k-game. Cells are numbered row by row from the top-left corner, which is
+/// how moves are recorded in the match history
+///
+/// # Fields
+/// * config = MnkConfig for the size of the board and the length of a winning run
+/// * cells  = Vector of the team (1 or 2) with a mark in each cell, or 0 for an empty cell
+///
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Board {
+    pub config: MnkConfig,
+    cells: Vec<i32>,
+}
+
+impl Board {
+    ///Constructor - returns an empty board with the given size and run length
+    pub fn new(config: MnkConfig) -> Self {
+        Board {
+            config,
+            cells: vec![0; config.cells()],
+        }
+    }
+
+    ///Returns true if the position is on the board
+    pub fn on_board(&self, pos: GridPosition) -> bool {
+        pos.x >= 0 && pos.x < self.config.columns && pos.y >= 0 && pos.y < self.config.rows
+    }
+
+    ///Returns the number of the cell at a position on the board
+    pub fn index(&self, pos: GridPosition) -> usize {
+        (pos.y * self.config.columns + pos.x) as usize
+    }
+
+    ///Returns the position of a cell from its number
+    pub fn position(&self, cell: usize) -> GridPosition {
+        let columns = self.config.columns as usize;
+        GridPosition::new((cell % columns) as i32, (cell / columns) as i32)
+    }
+
+    ///Returns the team with a mark at a position, or 0 if the cell is empty or off the board
+    pub fn get(&self, pos: GridPosition) -> i32 {
+        if self.on_board(pos) {
+            self.cells[self.index(pos)]
+        } else {
+            0
+        }
+    }
+
+    ///Returns the team with a mark in a cell, or 0 if it is empty
+    pub fn cell(&self, cell: usize) -> i32 {
+        self.cells[cell]
+    }
+
+    ///Returns the numbers of the empty cells, in order
+    pub fn empty_cells(&self) -> Vec<usize> {
+        (0..self.cells.len())
+            .filter(|cell| self.cells[*cell] == 0)
+            .collect()
+    }
+
+    ///Returns the number of marks on the board
+    pub fn marks(&self) -> usize {
+        self.cells.iter().filter(|team| **team != 0).count()
+    }
+
+    ///Returns true if every cell has a mark
+    pub fn is_full(&self) -> bool {
+        self.cells.iter().all(|team| *team != 0)
+    }
+
+    ///Method for a team to mark an empty cell. Returns false, leaving the board as it was, if the cell can't be marked
+    pub fn place(&mut self, cell: usize, team: i32) -> bool {
+        if cell >= self.cells.len() || self.cells[cell] != 0 {
+            return false;
+        }
+        self.cells[cell] = team;
+        true
+    }
+
+    ///Method to empty a cell again, used by the AI to take back moves it tries
+    pub fn clear(&mut self, cell: usize) {
+        self.cells[cell] = 0;
+    }
+
+    ///
+    /// Returns the number of marks of a team in an unbroken line through a cell, counted away from the cell in
+    /// direction dir and back the other way, as Connect 4's Board::get_run_in_direction does. The cell itself is counted,
+    /// so the run is at least 1 when it has the team's mark
+    ///
+    /// # Arguments
+    /// * start = GridPosition of the cell the run goes through
+    /// * dir   = One of DIRECTIONS, giving the line the run is counted along
+    /// * team  = Integer value (1 or 2) of the team whose marks are counted
+    ///
+    pub fn run_length(&self, start: GridPosition, dir: (i32, i32), team: i32) -> i32 {
+        let mut run = 1;
+        for sign in [1, -1].iter() {
+            let mut i = 1;
+            while self.get(GridPosition::new(
+                start.x + sign * i * dir.0,
+                start.y + sign * i * dir.1,
+            )) == team
+            {
+                run += 1;
+                i += 1;
+            }
+        }
+        run
+    }
+
+    ///
+    /// Returns the cells of a winning run through a cell, or None if the mark in the cell doesn't complete one. Runs
+    /// longer than needed to win are returned whole
+    ///
+    /// # Arguments
+    /// * cell = Number of the cell marked last
+    ///
+    pub fn winning_run(&self, cell: usize) -> Option<Vec<usize>> {
+        let team = self.cells[cell];
+        if team == 0 {
+            return None;
+        }
+        let start = self.position(cell);
+        for dir in DIRECTIONS.iter() {
+            if self.run_length(start, *dir, team) < self.config.connect {
+                continue;
+            }
+            //Walk back to the first mark of the run, then collect the marks going forward
+            let mut first = start;
+            loop {
+                let prev = GridPosition::new(first.x - dir.0, first.y - dir.1);
+                if self.get(prev) != team {
+                    break;
+                }
+                first = prev;
+            }
+            let mut run = Vec::new();
+            let mut pos = first;
+            while self.get(pos) == team {
+                run.push(self.index(pos));
+                pos = GridPosition::new(pos.x + dir.0, pos.y + dir.1);
+            }
+            return Some(run);
+        }
+        None
+    }
+
+    ///Returns true if the mark in a cell completes a winning run
+    pub fn is_win(&self, cell: usize) -> bool {
+        let team = self.cells[cell];
+        let start = self.position(cell);
+        team != 0
+            && DIRECTIONS
+                .iter()
+                .any(|dir| self.run_length(start, *dir, team) >= self.config.connect)
+    }
+
+    ///
+    /// Returns every line of connect cells that fits on the board, used by the AI to score positions. Each line is a
+    /// vector of cell numbers
+    ///
+    pub fn windows(&self) -> Vec<Vec<usize>> {
+        let connect = self.config.connect;
+        let mut windows = Vec::new();
+        for cell in 0..self.cells.len() {
+            let start = self.position(cell);
+            for dir in DIRECTIONS.iter() {
+                let end = GridPosition::new(
+                    start.x + (connect - 1) * dir.0,
+                    start.y + (connect - 1) * dir.1,
+                );
+                if self.on_board(end) {
+                    windows.push(
+                        (0..connect)
+                            .map(|i| {
+                                self.index(GridPosition::new(
+                                    start.x + i * dir.0,
+                                    start.y + i * dir.1,
+                                ))
+                            })
+                            .collect(),
+                    );
+                }
+            }
+        }
+        windows
+    }
+}
+
+#[cfg(test)]
+mod board_tests {
+    use super::*;
+
+    //Method to create a board with marks for team 1 and team 2 placed in the given cells
+    fn create_test_board(config: MnkConfig, ones: &[usize], twos: &[usize]) -> Board {
+        let mut board = Board::new(config);
+        for cell in ones {
+            board.place(*cell, 1);
+        }
+        for cell in twos {
+            board.place(*cell, 2);
+        }
+        board
+    }
+
+    mod mnk_config {
+        use super::*;
+
+        mod step {
+            use super::*;
+
+            #[test]
+            fn should_move_through_presets() {
+                let config = MnkConfig::tic_tac_toe();
+                assert_eq!(config.step(false), config);
+                assert_eq!(config.step(true), MnkConfig::new(4, 4, 4));
+                let largest = MnkConfig::new(15, 15, 5);
+                assert_eq!(largest.step(true), largest);
+                assert_eq!(largest.step(false), MnkConfig::new(10, 10, 5));
+                assert_eq!(MnkConfig::new(6, 6, 4).step(true), MnkConfig::new(7, 7, 5));
+            }
+        }
+
+        mod clamped {
+            use super::*;
+
+            #[test]
+            fn should_keep_run_on_board() {
+                assert_eq!(MnkConfig::new(1, 30, 9).clamped(), MnkConfig::new(3, 19, 6));
+                assert_eq!(MnkConfig::new(3, 4, 6).clamped(), MnkConfig::new(3, 4, 4));
+            }
+        }
+    }
+
+    mod board {
+        use super::*;
+
+        mod place {
+            use super::*;
+
+            #[test]
+            fn should_only_mark_empty_cells() {
+                let mut board = Board::new(MnkConfig::tic_tac_toe());
+                assert!(board.place(4, 1));
+                assert!(!board.place(4, 2));
+                assert!(!board.place(9, 2));
+                assert_eq!(board.get(GridPosition::new(1, 1)), 1);
+                assert_eq!(board.marks(), 1);
+                assert_eq!(board.empty_cells().len(), 8);
+            }
+        }
+
+        mod winning_run {
+            use super::*;
+
+            #[test]
+            fn should_find_runs_in_every_direction() {
+                let config = MnkConfig::tic_tac_toe();
+                let board = create_test_board(config, &[0, 1, 2], &[3, 4]);
+                assert_eq!(board.winning_run(1), Some(vec![0, 1, 2]));
+                assert_eq!(board.winning_run(4), None);
+                let board = create_test_board(config, &[2, 4, 6], &[0, 1]);
+                assert_eq!(board.winning_run(4), Some(vec![6, 4, 2]));
+                let board = create_test_board(config, &[0, 4, 8], &[]);
+                assert!(board.is_win(8));
+                let board = create_test_board(config, &[1, 4, 7], &[]);
+                assert!(board.is_win(1));
+            }
+
+            #[test]
+            fn should_need_connect_marks() {
+                let config = MnkConfig::new(5, 5, 4);
+                let board = create_test_board(config, &[0, 1, 2], &[]);
+                assert!(!board.is_win(2));
+                let board = create_test_board(config, &[0, 1, 2, 3, 4], &[]);
+                assert_eq!(board.winning_run(2), Some(vec![0, 1, 2, 3, 4]));
+            }
+        }
+
+        mod windows {
+            use super::*;
+
+            #[test]
+            fn should_count_every_line_that_fits() {
+                assert_eq!(Board::new(MnkConfig::tic_tac_toe()).windows().len(), 8);
+                assert_eq!(Board::new(MnkConfig::new(4, 4, 3)).windows().len(), 24);
+            }
+        }
+    }
+}
@@ -0,0 +1,501 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use connect4::button::{create_button, draw_text, mouse_location, Button};
+use connect4::core::{other_team, GameAction, GridPosition, MyColor, PlayerInfo};
+use ggez::input::mouse::MouseButton;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+use history::MatchRecord;
+use mnk::ai::AI;
+use mnk::board::Board;
+use screen::GameScreen;
+use settings::Settings;
+use sound::{result_sound, SoundEffect};
+
+///Constant dimensions for the m,n,k-game window, fitting the board with a panel of players and buttons to its right
+pub const MNK_SCREEN_SIZE: (f32, f32) = (900.0, 660.0);
+
+///Constant top-left corner of the board
+const BOARD_POSITION: (f32, f32) = (40.0, 90.0);
+
+///Constant width and height of the square the board is drawn in. Cells shrink to fit larger boards in it
+const BOARD_PIXELS: f32 = 540.0;
+
+///Constant x location of the panel of players and buttons to the right of the board
+const PANEL_POS_X: f32 = 620.0;
+
+///Constant font size for the panel text and buttons
+const MNK_FONT_SIZE: f32 = 24.0;
+
+///Constant marks drawn for each team, where MARKS[i] is team i + 1's
+const MARKS: [&str; 2] = ["X", "O"];
+
+///
+/// A struct that contains the state of an m,n,k-game such as tic-tac-toe. Team 1 plays X and moves first, team 2
+/// plays O, and the first to get a run of the board's connect marks wins
+///
+/// # Fields
+/// * board             = Board holding the marks placed so far
+/// * team_names        = Vector of player names, where team_names[i] is the name for team i + 1
+/// * team_colors       = Vector of MyColor objects, where team_colors[i] is the color team i + 1's marks are drawn in
+/// * team              = Integer value (1 or 2) of the team to move
+/// * ai_players        = Vector of AI objects, one for each team played by the AI
+/// * ai_difficulty     = Difficulty given to the AI players, recorded in the match history
+/// * ai_move_delay     = Number of frames an AI shows the cell it picked before marking it
+/// * aim               = Option of the cell the AI is about to mark, or None
+/// * frames            = Integer counter for the number of times the update method is called; times AI moves
+/// * hover             = Option of the empty cell under the mouse during a human player's turn, or None
+/// * game_over         = Boolean indicating if the game has been won or drawn
+/// * winner            = Integer value (1 or 2) of the team that won, or 0 if there is no winner
+/// * winning_run       = Vector of the cells in the winning run, highlighted once the game is won
+/// * main_menu_button  = Button drawn to return to the main menu
+/// * play_again_button = Button drawn to clear the board and start a new game with the same players
+/// * background_color  = Color drawn behind the board
+/// * sound_queue       = Vector of SoundEffect enums triggered since the main menu last played them
+/// * moves             = Vector of cells marked so far, in order, each stored as row * columns + column
+/// * elapsed           = Seconds the current game has been played for
+/// * finished_match    = Option of a MatchRecord for a game that just ended, taken by the main menu to be saved
+///
+pub struct MnkState {
+    board: Board,
+    team_names: Vec<String>,
+    team_colors: Vec<MyColor>,
+    team: i32,
+    ai_players: Vec<AI>,
+    ai_difficulty: i32,
+    ai_move_delay: i32,
+    aim: Option<usize>,
+    frames: usize,
+    hover: Option<usize>,
+    game_over: bool,
+    winner: i32,
+    winning_run: Vec<usize>,
+    main_menu_button: Button,
+    play_again_button: Button,
+    background_color: graphics::Color,
+    pub sound_queue: Vec<SoundEffect>,
+    moves: Vec<i32>,
+    elapsed: f32,
+    pub finished_match: Option<MatchRecord>,
+}
+
+impl MnkState {
+    ///Constructor - player_info holds the name, color and whether the AI plays for team 1 (X) and team 2 (O), in that
+    ///order, and settings holds the board, AI and theme options. Teams without player info get the defaults
+    pub fn new(ctx: &mut Context, player_info: Vec<PlayerInfo>, settings: &Settings) -> MnkState {
+        let mut team_names = Vec::new();
+        let mut team_colors = Vec::new();
+        let mut ai_players = Vec::new();
+        let given = player_info.len();
+        for (i, info) in player_info
+            .into_iter()
+            .chain(PlayerInfo::defaults().into_iter().skip(given))
+            .take(2)
+            .enumerate()
+        {
+            if info.is_ai {
+                ai_players.push(AI::new(i as i32 + 1, settings.ai_difficulty));
+            }
+            team_names.push(info.name);
+            team_colors.push(info.color);
+        }
+        let buttons_y = BOARD_POSITION.1 + BOARD_PIXELS - 45.0;
+        let mut state = MnkState {
+            board: Board::new(settings.mnk.clamped()),
+            team_names,
+            team_colors,
+            team: 1,
+            ai_players,
+            ai_difficulty: settings.ai_difficulty,
+            ai_move_delay: settings.ai_move_delay,
+            aim: None,
+            frames: 0,
+            hover: None,
+            game_over: false,
+            winner: 0,
+            winning_run: Vec::new(),
+            main_menu_button: create_button(
+                ctx,
+                "Main Menu",
+                (PANEL_POS_X, buttons_y),
+                MNK_FONT_SIZE,
+                (MyColor::Brown, MyColor::Green),
+            ),
+            play_again_button: create_button(
+                ctx,
+                "Play Again",
+                (PANEL_POS_X, buttons_y - 60.0),
+                MNK_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            background_color: settings.theme.game_background(),
+            sound_queue: Vec::new(),
+            moves: Vec::new(),
+            elapsed: 0.0,
+            finished_match: None,
+        };
+        state.reset_game();
+        state
+    }
+
+    ///Method to clear the board and start again with team 1 to move
+    fn reset_game(&mut self) {
+        self.board = Board::new(self.board.config);
+        self.team = 1;
+        for ai in &mut self.ai_players {
+            ai.last_move_frame = -1;
+        }
+        self.aim = None;
+        self.hover = None;
+        self.game_over = false;
+        self.winner = 0;
+        self.winning_run.clear();
+        self.moves.clear();
+        self.elapsed = 0.0;
+        self.play_again_button.active = false;
+    }
+
+    ///Returns true if the AI plays for the team
+    fn is_ai(&self, team: i32) -> bool {
+        self.ai_players.iter().any(|ai| ai.team == team)
+    }
+
+    ///Returns the pixel size of each cell, so the longer side of the board fills BOARD_PIXELS
+    fn cell_size(&self) -> f32 {
+        BOARD_PIXELS / self.board.config.rows.max(self.board.config.columns) as f32
+    }
+
+    ///Returns the rectangle covered by a cell of the board
+    fn cell_rect(&self, cell: usize) -> graphics::Rect {
+        let pos = self.board.position(cell);
+        let size = self.cell_size();
+        graphics::Rect::new(
+            BOARD_POSITION.0 + pos.x as f32 * size,
+            BOARD_POSITION.1 + pos.y as f32 * size,
+            size,
+            size,
+        )
+    }
+
+    ///Returns the cell under a point, or None if it is off the board
+    fn cell_at(&self, point: Point2<f32>) -> Option<usize> {
+        let size = self.cell_size();
+        let pos = GridPosition::new(
+            ((point.x - BOARD_POSITION.0) / size).floor() as i32,
+            ((point.y - BOARD_POSITION.1) / size).floor() as i32,
+        );
+        if self.board.on_board(pos) {
+            Some(self.board.index(pos))
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Method to mark a cell for the team to move, then end the game if it made a winning run or filled the board,
+    /// or pass the turn otherwise. Returns false if the cell is already marked
+    ///
+    /// # Arguments
+    /// * cell = Index of the cell to mark, numbered row by row from the top-left corner
+    ///
+    fn play(&mut self, cell: usize) -> bool {
+        if !self.board.place(cell, self.team) {
+            self.sound_queue.push(SoundEffect::InvalidMove);
+            return false;
+        }
+        self.moves.push(cell as i32);
+        self.sound_queue.push(SoundEffect::Drop);
+        let name = self.team_names[self.team as usize - 1].clone();
+        let pos = self.board.position(cell);
+        println!("{} marks ({}, {})", name, pos.x, pos.y);
+        if let Some(run) = self.board.winning_run(cell) {
+            println!("{} wins; Game ends", name);
+            self.game_over = true;
+            self.winner = self.team;
+            self.winning_run = run;
+            self.finish_match(self.team);
+        } else if self.board.is_full() {
+            println!("Board is full; Game ends in a draw");
+            self.game_over = true;
+            self.finish_match(0);
+        } else {
+            self.team = other_team(self.team);
+        }
+        self.hover = None;
+        self.play_again_button.active = self.game_over;
+        true
+    }
+
+    ///Method to play the end-of-game sound and record the finished game so the main menu can save it to the match history
+    fn finish_match(&mut self, winner: i32) {
+        let ai_teams: Vec<i32> = self.ai_players.iter().map(|ai| ai.team).collect();
+        self.sound_queue.push(result_sound(winner, &ai_teams));
+        self.finished_match = Some(MatchRecord::for_teams(
+            &self.board.config.label(),
+            &self.team_names,
+            &ai_teams,
+            self.ai_difficulty,
+            self.moves.clone(),
+            winner,
+            self.elapsed,
+        ));
+    }
+
+    ///Method to draw the grid, the marks and the highlighted cells
+    fn draw_board(&self, ctx: &mut Context) -> GameResult {
+        let config = self.board.config;
+        let size = self.cell_size();
+        let width = size * config.columns as f32;
+        let height = size * config.rows as f32;
+        let mut mb = graphics::MeshBuilder::new();
+        mb.rectangle(
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(BOARD_POSITION.0, BOARD_POSITION.1, width, height),
+            graphics::Color::from_rgba(230, 215, 170, 255),
+        );
+        //Shade the winning run, or outline the cell the player is pointing at or the AI is about to mark
+        for cell in &self.winning_run {
+            mb.rectangle(
+                graphics::DrawMode::fill(),
+                self.cell_rect(*cell),
+                graphics::Color::from_rgba(250, 240, 120, 255),
+            );
+        }
+        if let Some(cell) = self.hover.or(self.aim) {
+            mb.rectangle(
+                graphics::DrawMode::stroke(3.0),
+                self.cell_rect(cell),
+                MyColor::Yellow.get_draw_color(),
+            );
+        }
+        for i in 0..=config.columns {
+            let x = BOARD_POSITION.0 + i as f32 * size;
+            mb.line(
+                &[
+                    Point2 {
+                        x,
+                        y: BOARD_POSITION.1,
+                    },
+                    Point2 {
+                        x,
+                        y: BOARD_POSITION.1 + height,
+                    },
+                ],
+                2.0,
+                graphics::BLACK,
+            )?;
+        }
+        for i in 0..=config.rows {
+            let y = BOARD_POSITION.1 + i as f32 * size;
+            mb.line(
+                &[
+                    Point2 {
+                        x: BOARD_POSITION.0,
+                        y,
+                    },
+                    Point2 {
+                        x: BOARD_POSITION.0 + width,
+                        y,
+                    },
+                ],
+                2.0,
+                graphics::BLACK,
+            )?;
+        }
+        //X is drawn as two crossed lines and O as a ring, both in the team's color
+        let inset = size * 0.2;
+        let thickness = (size * 0.1).max(2.0);
+        for cell in 0..config.cells() {
+            let team = self.board.cell(cell);
+            if team == 0 {
+                continue;
+            }
+            let rect = self.cell_rect(cell);
+            let color = self.team_colors[team as usize - 1].get_draw_color();
+            if team == 1 {
+                mb.line(
+                    &[
+                        Point2 {
+                            x: rect.x + inset,
+                            y: rect.y + inset,
+                        },
+                        Point2 {
+                            x: rect.right() - inset,
+                            y: rect.bottom() - inset,
+                        },
+                    ],
+                    thickness,
+                    color,
+                )?;
+                mb.line(
+                    &[
+                        Point2 {
+                            x: rect.right() - inset,
+                            y: rect.y + inset,
+                        },
+                        Point2 {
+                            x: rect.x + inset,
+                            y: rect.bottom() - inset,
+                        },
+                    ],
+                    thickness,
+                    color,
+                )?;
+            } else {
+                mb.circle(
+                    graphics::DrawMode::stroke(thickness),
+                    Point2 {
+                        x: rect.x + size / 2.0,
+                        y: rect.y + size / 2.0,
+                    },
+                    size / 2.0 - inset,
+                    0.5,
+                    color,
+                );
+            }
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))
+    }
+
+    ///Method to draw the players, the goal of the game and the number of moves played to the right of the board
+    fn draw_panel(&self, ctx: &mut Context) -> GameResult {
+        draw_text(
+            ctx,
+            &self.board.config.label(),
+            (PANEL_POS_X, BOARD_POSITION.1),
+            MNK_FONT_SIZE,
+            MyColor::White,
+        )?;
+        for (i, name) in self.team_names.iter().enumerate() {
+            let player = if self.is_ai(i as i32 + 1) {
+                format!("{}: {} (AI)", MARKS[i], name)
+            } else {
+                format!("{}: {}", MARKS[i], name)
+            };
+            draw_text(
+                ctx,
+                &player,
+                (PANEL_POS_X, BOARD_POSITION.1 + 50.0 + 35.0 * i as f32),
+                MNK_FONT_SIZE,
+                self.team_colors[i],
+            )?;
+        }
+        let lines = [
+            format!("Get {} in a row to win", self.board.config.connect),
+            format!("Moves: {}", self.moves.len()),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(
+                ctx,
+                line,
+                (PANEL_POS_X, BOARD_POSITION.1 + 150.0 + 30.0 * i as f32),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl GameScreen for MnkState {
+    fn screen_size(&self) -> (f32, f32) {
+        MNK_SCREEN_SIZE
+    }
+
+    ///Update method for the m,n,k-game, which lets the AI pick a cell, show it, then mark it once enough frames pass
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        self.frames += 1; //Timing mechanism for AI moves
+        if self.game_over {
+            return Ok(());
+        }
+        self.elapsed += ggez::timer::delta(_ctx).as_secs_f32();
+        let team = self.team;
+        if let Some(i) = self.ai_players.iter().position(|ai| ai.team == team) {
+            if self.ai_players[i].last_move_frame < 0 {
+                self.aim = Some(self.ai_players[i].pick_move(&self.board));
+                self.ai_players[i].last_move_frame = self.frames as i32;
+            } else if self.frames
+                > (self.ai_players[i].last_move_frame + self.ai_move_delay) as usize
+            {
+                self.ai_players[i].last_move_frame = -1;
+                if let Some(cell) = self.aim.take() {
+                    self.play(cell);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    ///Draw method for the m,n,k-game, which draws the board, the marks, the player panel and the buttons
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::clear(ctx, self.background_color);
+        let name = &self.team_names[self.team as usize - 1];
+        let status = if !self.game_over {
+            format!("{} to move", name)
+        } else if self.winner == 0 {
+            String::from("It's a draw!")
+        } else {
+            format!("{} wins!", name)
+        };
+        let status_color = if self.game_over && self.winner == 0 {
+            MyColor::White
+        } else {
+            self.team_colors[self.team as usize - 1]
+        };
+        draw_text(ctx, &status, (BOARD_POSITION.0, 25.0), 40.0, status_color)?;
+        self.draw_board(ctx)?;
+        self.draw_panel(ctx)?;
+        self.main_menu_button.draw(ctx)?;
+        self.play_again_button.draw(ctx)?;
+        graphics::present(ctx)?;
+        ggez::timer::yield_now();
+        Ok(())
+    }
+
+    ///Method active whenever the mouse moves, which tracks the empty cell under it during a human player's turn
+    fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        self.hover = if self.game_over || self.is_ai(self.team) {
+            None
+        } else {
+            self.cell_at(mouse_location(ctx))
+                .filter(|cell| self.board.cell(*cell) == 0)
+        };
+        self.main_menu_button.check_button_under_mouse(ctx);
+        self.play_again_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method to handle a click on the m,n,k-game screen, returning what the main menu should do next
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton) -> GameAction {
+        self.mouse_motion_event(ctx);
+        if self.main_menu_button.check_button_under_mouse(ctx) {
+            println!("Main Menu Button pressed; Main Menu should pop up");
+            self.sound_queue.push(SoundEffect::Click);
+            return GameAction::MainMenu;
+        }
+        if self.play_again_button.check_button_under_mouse(ctx) {
+            println!("Play Again button pressed; Board reset");
+            self.sound_queue.push(SoundEffect::Click);
+            self.reset_game();
+        } else if button == MouseButton::Left {
+            if let Some(cell) = self.hover {
+                self.play(cell);
+            }
+        }
+        GameAction::None
+    }
+
+    fn drain_sounds(&mut self) -> Vec<SoundEffect> {
+        self.sound_queue.drain(..).collect()
+    }
+
+    fn take_finished_match(&mut self) -> Option<MatchRecord> {
+        self.finished_match.take()
+    }
+}
+
+//NOTE: Tests not included since testing requires usage of mouse and other UI features
@@ -0,0 +1,304 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::other_team;
+use othello::board::{Board, CORNERS, SQUARES};
+
+///Constant deepest the AI searches before the end of the game comes into view, whatever its difficulty
+pub const MAX_SEARCH_DEPTH: i32 = 5;
+
+///Constant number of empty squares at or below which an AI of difficulty 3 or more searches every move to the end of
+///the game, playing for the most discs
+pub const ENDGAME_SQUARES: usize = 10;
+
+///Constant score of a won game, well above any score from weighing the position
+const WIN_SCORE: i32 = 100_000;
+
+///Constant weight of each corner held, since corner discs can never be flipped
+const CORNER_WEIGHT: i32 = 30;
+
+///Constant weight of each disc next to an empty corner, which gives the other team a way into the corner
+const CORNER_NEIGHBOUR_WEIGHT: i32 = 10;
+
+///Constant weight of each legal move, so the AI keeps its options open and takes its opponent's away
+const MOBILITY_WEIGHT: i32 = 5;
+
+///Constant weight of each disc that can never be flipped again
+const STABILITY_WEIGHT: i32 = 8;
+
+///Constant value of each square, used to search the most promising moves first: corners are best and the squares
+///next to them worst
+const SQUARE_ORDER: [i32; SQUARES] = [
+    100, -20, 10, 5, 5, 10, -20, 100, //
+    -20, -50, -2, -2, -2, -2, -50, -20, //
+    10, -2, 1, 1, 1, 1, -2, 10, //
+    5, -2, 1, 0, 0, 1, -2, 5, //
+    5, -2, 1, 0, 0, 1, -2, 5, //
+    10, -2, 1, 1, 1, 1, -2, 10, //
+    -20, -50, -2, -2, -2, -2, -50, -20, //
+    100, -20, 10, 5, 5, 10, -20, 100,
+];
+
+///Constant squares next to each corner in CORNERS, in the same order
+const CORNER_NEIGHBOURS: [[usize; 3]; 4] = [[1, 8, 9], [6, 14, 15], [48, 49, 57], [54, 55, 62]];
+
+///Returns the score of a finished game from a team's point of view: a win scores WIN_SCORE plus the disc margin
+fn final_score(board: &Board, team: i32) -> i32 {
+    let margin = board.count(team) as i32 - board.count(other_team(team)) as i32;
+    if margin > 0 {
+        WIN_SCORE + margin
+    } else if margin < 0 {
+        -WIN_SCORE + margin
+    } else {
+        0
+    }
+}
+
+///
+/// Returns the score of an unfinished board from a team's point of view, weighing the corners each team holds, the
+/// discs next to empty corners, the number of legal moves each team has and the discs that can never be flipped.
+/// The disc count itself only breaks ties, since having fewer discs early on often means more moves later
+///
+/// # Arguments
+/// * board = Board to score
+/// * team  = Integer value (1 or 2) of the team to score for
+///
+pub fn evaluate(board: &Board, team: i32) -> i32 {
+    let other = other_team(team);
+    let weigh = |owner: i32| {
+        if owner == team {
+            1
+        } else if owner == other {
+            -1
+        } else {
+            0
+        }
+    };
+    let mut score = 0;
+    for (corner, neighbours) in CORNERS.iter().zip(CORNER_NEIGHBOURS.iter()) {
+        let owner = board.square(*corner);
+        score += CORNER_WEIGHT * weigh(owner);
+        if owner == 0 {
+            for square in neighbours {
+                score -= CORNER_NEIGHBOUR_WEIGHT * weigh(board.square(*square));
+            }
+        }
+    }
+    score += MOBILITY_WEIGHT
+        * (board.legal_moves(team).len() as i32 - board.legal_moves(other).len() as i32);
+    score +=
+        STABILITY_WEIGHT * (board.stable_count(team) as i32 - board.stable_count(other) as i32);
+    score + board.count(team) as i32 - board.count(other) as i32
+}
+
+///Returns the legal moves for a team, most promising first
+fn ordered_moves(board: &Board, team: i32) -> Vec<usize> {
+    let mut moves = board.legal_moves(team);
+    moves.sort_by_key(|square| -SQUARE_ORDER[*square]);
+    moves
+}
+
+///
+/// Returns the score of the board from the team to move's point of view, searching depth moves ahead with alpha-beta
+/// pruning. A team with no legal move passes, which does not use up depth
+///
+/// # Arguments
+/// * board = Board to search from
+/// * team  = Integer value (1 or 2) of the team to move
+/// * depth = Number of moves left to search
+/// * alpha = Lowest score the team to move is already sure of
+/// * beta  = Highest score the other team will allow
+///
+fn alpha_beta(board: &Board, team: i32, depth: i32, mut alpha: i32, beta: i32) -> i32 {
+    let moves = ordered_moves(board, team);
+    if moves.is_empty() {
+        if board.legal_moves(other_team(team)).is_empty() {
+            return final_score(board, team);
+        }
+        return -alpha_beta(board, other_team(team), depth, -beta, -alpha);
+    }
+    if depth == 0 {
+        return evaluate(board, team);
+    }
+    for square in moves {
+        let mut next = *board;
+        next.play(square, team);
+        let score = -alpha_beta(&next, other_team(team), depth - 1, -beta, -alpha);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    alpha
+}
+
+///
+/// A struct representing an AI or bot player for Othello which has methods to pick moves
+///
+/// # Fields
+/// * team            = Integer value (1 or 2) of the team the AI plays for
+/// * difficulty      = Integer value for how many moves ahead the AI searches
+/// * last_move_frame = Integer used to track when the AI picked its move. Set to -1 until it picks each turn
+///
+pub struct AI {
+    pub team: i32,
+    difficulty: i32,
+    pub last_move_frame: i32,
+}
+
+impl AI {
+    ///
+    /// Method to initialize and return an AI object
+    ///
+    /// # Arguments
+    /// * team       = Integer value (1 or 2) of the team the AI plays for
+    /// * difficulty = Integer value for how many moves ahead the AI searches
+    ///
+    pub fn new(team: i32, difficulty: i32) -> Self {
+        AI {
+            team,
+            difficulty,
+            last_move_frame: -1,
+        }
+    }
+
+    ///Returns how many moves ahead to search from a board: to the end of the game once it is close enough, or else
+    ///as many moves as the difficulty, up to MAX_SEARCH_DEPTH
+    fn search_depth(&self, board: &Board) -> i32 {
+        let empty = board.empty_squares();
+        if self.difficulty >= 3 && empty <= ENDGAME_SQUARES {
+            empty as i32
+        } else {
+            self.difficulty.clamp(1, MAX_SEARCH_DEPTH)
+        }
+    }
+
+    ///
+    /// Method to pick the square to place a disc on, searching the legal moves with alpha-beta pruning. Ties go to the
+    /// move searched first. Returns None if the AI has no legal move and must pass
+    ///
+    /// # Arguments
+    /// * board = Board with the AI's team to move
+    ///
+    pub fn pick_move(&self, board: &Board) -> Option<usize> {
+        let depth = self.search_depth(board);
+        let mut best: Option<(i32, usize)> = None;
+        for square in ordered_moves(board, self.team) {
+            let mut next = *board;
+            next.play(square, self.team);
+            let alpha = best.map_or(-WIN_SCORE * 2, |(score, _)| score);
+            let score = -alpha_beta(
+                &next,
+                other_team(self.team),
+                depth - 1,
+                -WIN_SCORE * 2,
+                -alpha,
+            );
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, square));
+            }
+        }
+        best.map(|(_, square)| square)
+    }
+}
+
+#[cfg(test)]
+mod ai_tests {
+    use super::*;
+    use othello::board::BOARD_SIDE;
+
+    //Method to create a board from 8 rows of text, where 'B' is a black disc (team 1), 'W' a white disc (team 2) and
+    //any other character an empty square
+    fn create_test_board(rows: [&str; 8]) -> Board {
+        let mut squares = [0; SQUARES];
+        for (square, owner) in squares.iter_mut().enumerate() {
+            *owner = match rows[square / 8].as_bytes()[square % 8] {
+                b'B' => 1,
+                b'W' => 2,
+                _ => 0,
+            };
+        }
+        Board::from(squares)
+    }
+
+    //Method to return the number of the square at a (column, row) position
+    fn square(pos: (i32, i32)) -> usize {
+        (pos.1 * BOARD_SIDE + pos.0) as usize
+    }
+
+    mod ai {
+        use super::*;
+
+        mod pick_move {
+            use super::*;
+
+            #[test]
+            fn should_take_an_open_corner() {
+                let board = create_test_board([
+                    "........", ".W......", "..WB....", "...WB...", "...BB...", "........",
+                    "........", "........",
+                ]);
+                for difficulty in 1..4 {
+                    assert_eq!(
+                        AI::new(1, difficulty).pick_move(&board),
+                        Some(square((0, 0)))
+                    );
+                }
+            }
+
+            #[test]
+            fn should_pass_without_a_legal_move() {
+                let board = create_test_board([
+                    "BBBB....", "........", "........", "........", "........", "........",
+                    "........", "........",
+                ]);
+                assert_eq!(AI::new(2, 3).pick_move(&board), None);
+            }
+
+            #[test]
+            fn should_play_a_full_game_against_itself() {
+                let ais = [AI::new(1, 2), AI::new(2, 2)];
+                let mut board = Board::new();
+                let mut team = 1;
+                while !board.is_over() {
+                    if let Some(square) = ais[team as usize - 1].pick_move(&board) {
+                        assert!(!board.play(square, team).is_empty());
+                    }
+                    team = other_team(team);
+                }
+                assert_eq!(
+                    board.count(1) + board.count(2) + board.empty_squares(),
+                    SQUARES
+                );
+            }
+        }
+    }
+
+    mod evaluate {
+        use super::*;
+
+        #[test]
+        fn should_be_symmetric_between_teams() {
+            let board = create_test_board([
+                "B.......", ".W......", "..WB....", "...WBW..", "...BW...", "........", "........",
+                ".......W",
+            ]);
+            assert_eq!(evaluate(&board, 1), -evaluate(&board, 2));
+            assert_eq!(evaluate(&Board::new(), 1), 0);
+        }
+
+        #[test]
+        fn should_penalise_discs_next_to_empty_corners() {
+            let near_corner = create_test_board([
+                "........", ".B......", "..BW....", "...BW...", "........", "........", "........",
+                "........",
+            ]);
+            let away_from_corner = create_test_board([
+                "........", "........", "..BW....", "...BW...", "....B...", "........", "........",
+                "........",
+            ]);
+            assert!(evaluate(&near_corner, 1) < evaluate(&away_from_corner, 1));
+        }
+    }
+}
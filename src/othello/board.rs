@@ -0,0 +1,361 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::{other_team, GridPosition};
+
+///Constant number of rows and columns of the board
+pub const BOARD_SIDE: i32 = 8;
+
+///Constant number of squares on the board
+pub const SQUARES: usize = (BOARD_SIDE * BOARD_SIDE) as usize;
+
+///Constant move recorded in the match history when a player has no legal move and passes
+pub const PASS_MOVE: i32 = -1;
+
+///Constant directions discs are flipped in: across, down, along both diagonals and back again
+const DIRECTIONS: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+];
+
+///Constant lines through a square a disc could be flipped along, each counted both ways
+const AXES: [(i32, i32); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
+
+///Constant squares in the corners of the board, which can never be flipped
+pub const CORNERS: [usize; 4] = [0, 7, 56, 63];
+
+///
+/// A struct representing the Othello board. Team 1 plays black and moves first, team 2 plays white
+///
+/// # Fields
+/// * squares = Array of the team with a disc on each square, or 0 if it is empty, numbered row by row from the
+///   top-left corner
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Board {
+    squares: [i32; SQUARES],
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::new()
+    }
+}
+
+/// From trait converting an array of the team on each square, or 0 if it is empty, to a Board set up in that position.
+impl From<[i32; SQUARES]> for Board {
+    fn from(squares: [i32; SQUARES]) -> Self {
+        Board { squares }
+    }
+}
+
+impl Board {
+    ///Constructor - returns the starting position, with two discs of each team crossed in the center
+    pub fn new() -> Self {
+        let mut board = Board {
+            squares: [0; SQUARES],
+        };
+        board.squares[27] = 2;
+        board.squares[28] = 1;
+        board.squares[35] = 1;
+        board.squares[36] = 2;
+        board
+    }
+
+    ///Returns true if the position is on the board
+    pub fn on_board(&self, pos: GridPosition) -> bool {
+        pos.x >= 0 && pos.x < BOARD_SIDE && pos.y >= 0 && pos.y < BOARD_SIDE
+    }
+
+    ///Returns the number of the square at a position on the board
+    pub fn index(&self, pos: GridPosition) -> usize {
+        (pos.y * BOARD_SIDE + pos.x) as usize
+    }
+
+    ///Returns the position of a square, where x is its column and y its row
+    pub fn position(&self, square: usize) -> GridPosition {
+        GridPosition::new(square as i32 % BOARD_SIDE, square as i32 / BOARD_SIDE)
+    }
+
+    ///Returns the team with a disc on a square, or 0 if it is empty
+    pub fn square(&self, square: usize) -> i32 {
+        self.squares[square]
+    }
+
+    ///Returns the number of discs a team has on the board
+    pub fn count(&self, team: i32) -> usize {
+        self.squares.iter().filter(|owner| **owner == team).count()
+    }
+
+    ///Returns the number of empty squares left
+    pub fn empty_squares(&self) -> usize {
+        self.count(0)
+    }
+
+    ///
+    /// Returns the squares of the other team's discs that a disc placed on a square would flip: every straight line of
+    /// them running from the square to one of the team's own discs. Empty if the square is taken or nothing is flipped
+    ///
+    /// # Arguments
+    /// * square = Number of the square the disc would be placed on
+    /// * team   = Integer value (1 or 2) of the team placing the disc
+    ///
+    pub fn flips(&self, square: usize, team: i32) -> Vec<usize> {
+        let mut flipped = Vec::new();
+        if self.squares[square] != 0 {
+            return flipped;
+        }
+        let start = self.position(square);
+        for dir in DIRECTIONS.iter() {
+            let mut line = Vec::new();
+            let mut pos = GridPosition::new(start.x + dir.0, start.y + dir.1);
+            while self.on_board(pos) && self.squares[self.index(pos)] == other_team(team) {
+                line.push(self.index(pos));
+                pos = GridPosition::new(pos.x + dir.0, pos.y + dir.1);
+            }
+            if !line.is_empty() && self.on_board(pos) && self.squares[self.index(pos)] == team {
+                flipped.extend(line);
+            }
+        }
+        flipped
+    }
+
+    ///Returns true if the team may place a disc on the square, which it may if the disc flips at least one other disc
+    pub fn is_legal(&self, square: usize, team: i32) -> bool {
+        !self.flips(square, team).is_empty()
+    }
+
+    ///Returns the squares the team may place a disc on, in order
+    pub fn legal_moves(&self, team: i32) -> Vec<usize> {
+        (0..SQUARES)
+            .filter(|square| self.is_legal(*square, team))
+            .collect()
+    }
+
+    ///
+    /// Method to place a team's disc on a square and flip the discs it captures. Returns the flipped squares, or an
+    /// empty vector without changing the board if the move is not legal
+    ///
+    /// # Arguments
+    /// * square = Number of the square to place the disc on
+    /// * team   = Integer value (1 or 2) of the team placing the disc
+    ///
+    pub fn play(&mut self, square: usize, team: i32) -> Vec<usize> {
+        let flipped = self.flips(square, team);
+        if !flipped.is_empty() {
+            self.squares[square] = team;
+            for other in &flipped {
+                self.squares[*other] = team;
+            }
+        }
+        flipped
+    }
+
+    ///Returns true once neither team has a legal move, which includes a full board
+    pub fn is_over(&self) -> bool {
+        self.legal_moves(1).is_empty() && self.legal_moves(2).is_empty()
+    }
+
+    ///Returns the team with more discs, or 0 if both have the same number
+    pub fn leader(&self) -> i32 {
+        let (black, white) = (self.count(1), self.count(2));
+        if black > white {
+            1
+        } else if white > black {
+            2
+        } else {
+            0
+        }
+    }
+
+    ///Returns true if every square from a square to the edge of the board, both ways along a line, holds a disc
+    fn line_full(&self, square: usize, axis: (i32, i32)) -> bool {
+        let start = self.position(square);
+        [1, -1].iter().all(|sign| {
+            let mut pos = GridPosition::new(start.x + sign * axis.0, start.y + sign * axis.1);
+            while self.on_board(pos) {
+                if self.squares[self.index(pos)] == 0 {
+                    return false;
+                }
+                pos = GridPosition::new(pos.x + sign * axis.0, pos.y + sign * axis.1);
+            }
+            true
+        })
+    }
+
+    ///
+    /// Returns which discs can never be flipped again. A disc is stable when, along each of the four lines through it,
+    /// the line is full or one of its neighbours on the line is the edge of the board or a stable disc of its own team.
+    /// Corners are always stable, and stability spreads from them along the edges and inwards
+    ///
+    pub fn stable_squares(&self) -> [bool; SQUARES] {
+        let mut stable = [false; SQUARES];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for square in 0..SQUARES {
+                let team = self.squares[square];
+                if team == 0 || stable[square] {
+                    continue;
+                }
+                let pos = self.position(square);
+                let anchored = |sign: i32, axis: &(i32, i32)| {
+                    let next = GridPosition::new(pos.x + sign * axis.0, pos.y + sign * axis.1);
+                    !self.on_board(next) || {
+                        let other = self.index(next);
+                        self.squares[other] == team && stable[other]
+                    }
+                };
+                if AXES.iter().all(|axis| {
+                    anchored(1, axis) || anchored(-1, axis) || self.line_full(square, *axis)
+                }) {
+                    stable[square] = true;
+                    changed = true;
+                }
+            }
+        }
+        stable
+    }
+
+    ///Returns the number of a team's discs that can never be flipped again
+    pub fn stable_count(&self, team: i32) -> usize {
+        let stable = self.stable_squares();
+        (0..SQUARES)
+            .filter(|square| stable[*square] && self.squares[*square] == team)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod board_tests {
+    use super::*;
+
+    //Method to create a board from 8 rows of text, where 'B' is a black disc (team 1), 'W' a white disc (team 2) and
+    //any other character an empty square
+    fn create_test_board(rows: [&str; 8]) -> Board {
+        let mut squares = [0; SQUARES];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                squares[y * 8 + x] = match c {
+                    'B' => 1,
+                    'W' => 2,
+                    _ => 0,
+                };
+            }
+        }
+        Board::from(squares)
+    }
+
+    //Method to return the number of the square at a (column, row) position
+    fn square(pos: (i32, i32)) -> usize {
+        (pos.1 * BOARD_SIDE + pos.0) as usize
+    }
+
+    mod board {
+        use super::*;
+
+        mod legal_moves {
+            use super::*;
+
+            #[test]
+            fn should_offer_four_moves_at_start() {
+                let board = Board::new();
+                assert_eq!(
+                    board.legal_moves(1),
+                    vec![
+                        square((3, 2)),
+                        square((2, 3)),
+                        square((5, 4)),
+                        square((4, 5))
+                    ]
+                );
+                assert_eq!(board.count(1), 2);
+                assert_eq!(board.count(2), 2);
+            }
+
+            #[test]
+            fn should_need_a_disc_to_close_the_line() {
+                let board = create_test_board([
+                    "BWW.....", "........", "........", "........", "........", "........",
+                    "........", "........",
+                ]);
+                assert_eq!(board.legal_moves(1), vec![square((3, 0))]);
+                assert!(board.legal_moves(2).is_empty());
+            }
+        }
+
+        mod play {
+            use super::*;
+
+            #[test]
+            fn should_flip_every_captured_line() {
+                let mut board = create_test_board([
+                    "B..B..B.", ".W.W.W..", "..WWW...", "BWW.WWWB", "..WWW...", ".W.W.W..",
+                    "B..B..B.", "........",
+                ]);
+                let flipped = board.play(square((3, 3)), 1);
+                assert_eq!(flipped.len(), 17);
+                assert_eq!(board.count(2), 0);
+                assert_eq!(board.count(1), 26);
+            }
+
+            #[test]
+            fn should_leave_board_unchanged_when_illegal() {
+                let mut board = Board::new();
+                assert!(board.play(square((0, 0)), 1).is_empty());
+                assert!(board.play(square((3, 3)), 1).is_empty());
+                assert_eq!(board, Board::new());
+            }
+        }
+
+        mod is_over {
+            use super::*;
+
+            #[test]
+            fn should_end_when_neither_team_can_move() {
+                let board = create_test_board([
+                    "BBBBBBBB", "BBBBBBBB", "BBBBBBBB", "BBBBBBBB", "WWWWWWWW", "WWWWWW..",
+                    "........", "........",
+                ]);
+                assert!(!board.is_over());
+                assert_eq!(board.leader(), 1);
+                let board = create_test_board([
+                    "BBBB....", "........", "........", "........", "........", "........",
+                    "........", "........",
+                ]);
+                assert!(board.is_over());
+                assert!(!Board::new().is_over());
+                assert_eq!(Board::new().leader(), 0);
+            }
+        }
+
+        mod stable_squares {
+            use super::*;
+
+            #[test]
+            fn should_spread_from_corners_along_edges() {
+                let board = create_test_board([
+                    "BBBW....", "BB......", "W.......", "........", "........", "........",
+                    "........", ".......W",
+                ]);
+                assert_eq!(board.stable_count(1), 5);
+                assert_eq!(board.stable_count(2), 1);
+                assert!(!board.stable_squares()[square((3, 0))]);
+                assert!(!board.stable_squares()[square((0, 2))]);
+            }
+
+            #[test]
+            fn should_count_nothing_stable_at_start() {
+                assert_eq!(Board::new().stable_count(1), 0);
+                assert_eq!(Board::new().stable_count(2), 0);
+            }
+        }
+    }
+}
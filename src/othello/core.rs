@@ -0,0 +1,556 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use connect4::button::{create_button, draw_text, mouse_location, Button};
+use connect4::core::{
+    other_team, GameAction, GridPosition, MyColor, PlayerInfo, TurnIndicator, BOARD_DISC_RADIUS,
+};
+use ggez::input::mouse::MouseButton;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+use history::MatchRecord;
+use othello::ai::AI;
+use othello::board::{BOARD_SIDE, PASS_MOVE, SQUARES};
+use othello::game::{Game, TurnEnd};
+use screen::GameScreen;
+use settings::Settings;
+use sound::{result_sound, SoundEffect};
+
+///Constant dimensions for the Othello window, fitting the board with a panel of players and buttons to its right
+pub const OTHELLO_SCREEN_SIZE: (f32, f32) = (900.0, 660.0);
+
+///Constant top-left corner of the board, below the turn indicator
+const BOARD_POSITION: (f32, f32) = (40.0, 120.0);
+
+///Constant width and height of each square of the board, the same as a Connect 4 cell
+const SQUARE_SIZE: f32 = 64.0;
+
+///Constant x location of the panel of players and buttons to the right of the board
+const PANEL_POS_X: f32 = 600.0;
+
+///Constant font size for the panel text and buttons
+const OTHELLO_FONT_SIZE: f32 = 24.0;
+
+///Constant fraction of a flip completed each frame at an animation speed of 1.0
+const FLIP_SPEED: f32 = 0.06;
+
+///Constant disc colors named on the panel for each team, where DISC_NAMES[i] is team i + 1's
+const DISC_NAMES: [&str; 2] = ["Black", "White"];
+
+///
+/// A struct that contains the state of an Othello game. Team 1 plays black and moves first, team 2 plays white, and
+/// whoever has more discs once neither can move wins
+///
+/// # Fields
+/// * game              = Game holding the board, the team to move, the moves played and the winner
+/// * team_names        = Vector of player names, where team_names[i] is the name for team i + 1
+/// * team_colors       = Vector of MyColor objects, where team_colors[i] is the color team i + 1's discs are drawn in
+/// * turn_indicator    = TurnIndicator from Connect 4 showing whose turn it is and who won
+/// * ai_players        = Vector of AI objects, one for each team played by the AI
+/// * ai_difficulty     = Difficulty given to the AI players, recorded in the match history
+/// * ai_move_delay     = Number of frames an AI shows the square it picked before placing a disc on it
+/// * aim               = Option of the square the AI is about to play, or None
+/// * frames            = Integer counter for the number of times the update method is called; times AI moves
+/// * legal_moves       = Vector of the squares the team to move may play, highlighted during a human player's turn
+/// * hover             = Option of the legal square under the mouse during a human player's turn, or None
+/// * last_move         = Option of the square played last, or None at the start of a game
+/// * flipping          = Vector of the squares whose discs are being flipped, which finish before the turn passes
+/// * flip_progress     = Fraction from 0.0 to 1.0 of the flip animation completed
+/// * animation_speed   = Multiplier for FLIP_SPEED; discs are flipped instantly if this is 0.0
+/// * message           = Message shown on the panel, such as a player passing
+/// * main_menu_button  = Button drawn to return to the main menu
+/// * play_again_button = Button drawn to reset the board and start a new game with the same players
+/// * background_color  = Color drawn behind the board
+/// * sound_queue       = Vector of SoundEffect enums triggered since the main menu last played them
+/// * elapsed           = Seconds the current game has been played for
+/// * finished_match    = Option of a MatchRecord for a game that just ended, taken by the main menu to be saved
+///
+pub struct OthelloState {
+    game: Game,
+    team_names: Vec<String>,
+    team_colors: Vec<MyColor>,
+    turn_indicator: TurnIndicator,
+    ai_players: Vec<AI>,
+    ai_difficulty: i32,
+    ai_move_delay: i32,
+    aim: Option<usize>,
+    frames: usize,
+    legal_moves: Vec<usize>,
+    hover: Option<usize>,
+    last_move: Option<usize>,
+    flipping: Vec<usize>,
+    flip_progress: f32,
+    animation_speed: f32,
+    message: String,
+    main_menu_button: Button,
+    play_again_button: Button,
+    background_color: graphics::Color,
+    pub sound_queue: Vec<SoundEffect>,
+    elapsed: f32,
+    pub finished_match: Option<MatchRecord>,
+}
+
+impl OthelloState {
+    ///Constructor - player_info holds the name, color and whether the AI plays for team 1 (black) and team 2 (white),
+    ///in that order, and settings holds the AI, animation and theme options. Teams without player info get the defaults
+    pub fn new(
+        ctx: &mut Context,
+        player_info: Vec<PlayerInfo>,
+        settings: &Settings,
+    ) -> OthelloState {
+        let mut team_names = Vec::new();
+        let mut team_colors = Vec::new();
+        let mut ai_players = Vec::new();
+        let given = player_info.len();
+        for (i, info) in player_info
+            .into_iter()
+            .chain(PlayerInfo::defaults().into_iter().skip(given))
+            .take(2)
+            .enumerate()
+        {
+            if info.is_ai {
+                ai_players.push(AI::new(i as i32 + 1, settings.ai_difficulty));
+            }
+            team_names.push(info.name);
+            team_colors.push(info.color);
+        }
+        //The turn indicator names team i with names[i], so names[0] is left unused
+        let mut indicator_names = vec![String::new()];
+        indicator_names.extend(team_names.iter().cloned());
+        let buttons_y = BOARD_POSITION.1 + SQUARE_SIZE * BOARD_SIDE as f32 - 45.0;
+        let mut state = OthelloState {
+            game: Game::new(),
+            team_names,
+            team_colors,
+            turn_indicator: TurnIndicator::new(indicator_names),
+            ai_players,
+            ai_difficulty: settings.ai_difficulty,
+            ai_move_delay: settings.ai_move_delay,
+            aim: None,
+            frames: 0,
+            legal_moves: Vec::new(),
+            hover: None,
+            last_move: None,
+            flipping: Vec::new(),
+            flip_progress: 0.0,
+            animation_speed: settings.animation_speed,
+            message: String::new(),
+            main_menu_button: create_button(
+                ctx,
+                "Main Menu",
+                (PANEL_POS_X, buttons_y),
+                OTHELLO_FONT_SIZE,
+                (MyColor::Brown, MyColor::Green),
+            ),
+            play_again_button: create_button(
+                ctx,
+                "Play Again",
+                (PANEL_POS_X, buttons_y - 60.0),
+                OTHELLO_FONT_SIZE,
+                (MyColor::Blue, MyColor::Green),
+            ),
+            background_color: settings.theme.game_background(),
+            sound_queue: Vec::new(),
+            elapsed: 0.0,
+            finished_match: None,
+        };
+        state.reset_game();
+        state
+    }
+
+    ///Method to set up the starting position again with black to move
+    fn reset_game(&mut self) {
+        self.game = Game::new();
+        for ai in &mut self.ai_players {
+            ai.last_move_frame = -1;
+        }
+        self.aim = None;
+        self.hover = None;
+        self.last_move = None;
+        self.flipping.clear();
+        self.message.clear();
+        self.elapsed = 0.0;
+        self.play_again_button.active = false;
+        self.turn_indicator.reset();
+        self.start_turn();
+    }
+
+    ///Method to show whose turn it is and find the squares they may play
+    fn start_turn(&mut self) {
+        self.legal_moves = self.game.legal_moves();
+        self.turn_indicator.change_team(self.game.team);
+    }
+
+    ///Returns true if the AI plays for the team
+    fn is_ai(&self, team: i32) -> bool {
+        self.ai_players.iter().any(|ai| ai.team == team)
+    }
+
+    ///Returns the top-left corner of a square on the screen
+    fn square_corner(&self, square: usize) -> Point2<f32> {
+        let pos = self.game.board.position(square);
+        Point2 {
+            x: BOARD_POSITION.0 + pos.x as f32 * SQUARE_SIZE,
+            y: BOARD_POSITION.1 + pos.y as f32 * SQUARE_SIZE,
+        }
+    }
+
+    ///Returns the square under a point, or None if it is off the board
+    fn square_at(&self, point: Point2<f32>) -> Option<usize> {
+        let pos = GridPosition::new(
+            ((point.x - BOARD_POSITION.0) / SQUARE_SIZE).floor() as i32,
+            ((point.y - BOARD_POSITION.1) / SQUARE_SIZE).floor() as i32,
+        );
+        if self.game.board.on_board(pos) {
+            Some(self.game.board.index(pos))
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Method to place a disc for the team to move and flip the discs it captures. The turn passes once the flips have
+    /// been animated. Returns false if the square is not a legal move
+    ///
+    /// # Arguments
+    /// * square = Number of the square to play, numbered row by row from the top-left corner
+    ///
+    fn play(&mut self, square: usize) -> bool {
+        let flipped = self.game.play(square);
+        if flipped.is_empty() {
+            self.sound_queue.push(SoundEffect::InvalidMove);
+            return false;
+        }
+        self.sound_queue.push(SoundEffect::Drop);
+        let pos = self.game.board.position(square);
+        println!(
+            "{} plays ({}, {}) and flips {}",
+            self.team_names[self.game.team as usize - 1],
+            pos.x,
+            pos.y,
+            flipped.len()
+        );
+        self.last_move = Some(square);
+        self.hover = None;
+        self.message.clear();
+        if self.animation_speed <= 0.0 {
+            self.end_turn();
+        } else {
+            self.flipping = flipped;
+            self.flip_progress = 0.0;
+        }
+        true
+    }
+
+    ///Method to pass the turn on once a move has been shown, announcing a pass or ending the game
+    fn end_turn(&mut self) {
+        match self.game.end_turn() {
+            TurnEnd::GameOver(winner) => self.end_game(winner),
+            TurnEnd::Pass(team) => {
+                self.message = format!(
+                    "{} has no moves and passes",
+                    self.team_names[team as usize - 1]
+                );
+                println!("{}", self.message);
+                self.start_turn();
+            }
+            TurnEnd::Next => self.start_turn(),
+        }
+    }
+
+    ///Method to show the end of the game, won by the team with more discs or drawn if both have the same number
+    fn end_game(&mut self, winner: i32) {
+        self.legal_moves.clear();
+        println!(
+            "Game ends {} - {}",
+            self.game.board.count(1),
+            self.game.board.count(2)
+        );
+        self.turn_indicator.change_team(winner);
+        self.turn_indicator.game_ends();
+        self.play_again_button.active = true;
+        self.finish_match(winner);
+    }
+
+    ///Method to play the end-of-game sound and record the finished game so the main menu can save it to the match history
+    fn finish_match(&mut self, winner: i32) {
+        let ai_teams: Vec<i32> = self.ai_players.iter().map(|ai| ai.team).collect();
+        self.sound_queue.push(result_sound(winner, &ai_teams));
+        self.finished_match = Some(MatchRecord::for_teams(
+            "Othello",
+            &self.team_names,
+            &ai_teams,
+            self.ai_difficulty,
+            self.game.moves.clone(),
+            winner,
+            self.elapsed,
+        ));
+    }
+
+    ///
+    /// Method to add a square of the board, in the style of a Connect 4 cell, and any disc on it to the MeshBuilder.
+    /// A disc being flipped narrows to its edge in its old color, then widens again in its new one
+    ///
+    /// # Arguments
+    /// * mb     = MeshBuilder the square is added to
+    /// * square = Number of the square to draw
+    ///
+    fn draw_square<'a>(
+        &self,
+        mb: &'a mut graphics::MeshBuilder,
+        square: usize,
+    ) -> &'a mut graphics::MeshBuilder {
+        let corner = self.square_corner(square);
+        let rect = graphics::Rect::new(corner.x, corner.y, SQUARE_SIZE, SQUARE_SIZE);
+        mb.rectangle(
+            graphics::DrawMode::fill(),
+            rect,
+            graphics::Color::from_rgba(0, 120, 60, 255),
+        );
+        mb.rectangle(graphics::DrawMode::stroke(1.0), rect, graphics::BLACK);
+        let team = self.game.board.square(square);
+        if team == 0 {
+            return mb;
+        }
+        let center = Point2 {
+            x: corner.x + SQUARE_SIZE / 2.0,
+            y: corner.y + SQUARE_SIZE / 2.0,
+        };
+        let radius = BOARD_DISC_RADIUS as f32;
+        let (shown, width) = if self.flipping.contains(&square) {
+            let turn = (1.0 - 2.0 * self.flip_progress).abs();
+            let shown = if self.flip_progress < 0.5 {
+                other_team(team)
+            } else {
+                team
+            };
+            (shown, (radius * turn).max(1.0))
+        } else {
+            (team, radius)
+        };
+        let color = self.team_colors[shown as usize - 1].get_draw_color();
+        mb.ellipse(
+            graphics::DrawMode::fill(),
+            center,
+            width,
+            radius,
+            0.5,
+            color,
+        );
+        mb.ellipse(
+            graphics::DrawMode::stroke(1.0),
+            center,
+            width,
+            radius,
+            0.5,
+            graphics::BLACK,
+        )
+    }
+
+    ///Method to draw the squares, the discs, the legal moves and the last move played
+    fn draw_board(&self, ctx: &mut Context) -> GameResult {
+        let mut mb = graphics::MeshBuilder::new();
+        for square in 0..SQUARES {
+            self.draw_square(&mut mb, square);
+        }
+        //Dot the squares a person may play, ring the square under the mouse or picked by the AI, and mark the last move
+        let center = |square: usize| {
+            let corner = self.square_corner(square);
+            Point2 {
+                x: corner.x + SQUARE_SIZE / 2.0,
+                y: corner.y + SQUARE_SIZE / 2.0,
+            }
+        };
+        if self.flipping.is_empty() && !self.is_ai(self.game.team) {
+            for square in &self.legal_moves {
+                mb.circle(
+                    graphics::DrawMode::fill(),
+                    center(*square),
+                    6.0,
+                    0.5,
+                    graphics::Color::from_rgba(0, 0, 0, 90),
+                );
+            }
+        }
+        if let Some(square) = self.hover.or(self.aim) {
+            mb.circle(
+                graphics::DrawMode::stroke(3.0),
+                center(square),
+                BOARD_DISC_RADIUS as f32,
+                0.5,
+                MyColor::Yellow.get_draw_color(),
+            );
+        }
+        if let Some(square) = self.last_move {
+            mb.circle(
+                graphics::DrawMode::fill(),
+                center(square),
+                5.0,
+                0.5,
+                MyColor::Red.get_draw_color(),
+            );
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))
+    }
+
+    ///Method to draw the players with their disc counts, the number of moves and any message to the right of the board
+    fn draw_panel(&self, ctx: &mut Context) -> GameResult {
+        draw_text(
+            ctx,
+            "Othello",
+            (PANEL_POS_X, BOARD_POSITION.1),
+            OTHELLO_FONT_SIZE,
+            MyColor::White,
+        )?;
+        for (i, name) in self.team_names.iter().enumerate() {
+            let team = i as i32 + 1;
+            let player = if self.is_ai(team) {
+                format!("{}: {} (AI)", DISC_NAMES[i], name)
+            } else {
+                format!("{}: {}", DISC_NAMES[i], name)
+            };
+            let y = BOARD_POSITION.1 + 50.0 + 70.0 * i as f32;
+            draw_text(
+                ctx,
+                &player,
+                (PANEL_POS_X, y),
+                OTHELLO_FONT_SIZE,
+                self.team_colors[i],
+            )?;
+            draw_text(
+                ctx,
+                &format!("Discs: {}", self.game.board.count(team)),
+                (PANEL_POS_X + 20.0, y + 30.0),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        let mut lines = vec![format!(
+            "Moves: {}",
+            self.game.moves.iter().filter(|m| **m != PASS_MOVE).count()
+        )];
+        if !self.message.is_empty() {
+            lines.push(self.message.clone());
+        }
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(
+                ctx,
+                line,
+                (PANEL_POS_X, BOARD_POSITION.1 + 200.0 + 30.0 * i as f32),
+                20.0,
+                MyColor::White,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl GameScreen for OthelloState {
+    fn screen_size(&self) -> (f32, f32) {
+        OTHELLO_SCREEN_SIZE
+    }
+
+    ///Update method for Othello, which animates flipping discs, then lets the AI pick a square, show it and play it
+    ///once enough frames pass
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        self.frames += 1; //Timing mechanism for AI moves
+        if self.game.winner.is_some() {
+            return Ok(());
+        }
+        self.elapsed += ggez::timer::delta(_ctx).as_secs_f32();
+        if !self.flipping.is_empty() {
+            self.flip_progress += FLIP_SPEED * self.animation_speed;
+            if self.flip_progress >= 1.0 {
+                self.flipping.clear();
+                self.end_turn();
+            }
+            return Ok(());
+        }
+        let team = self.game.team;
+        if let Some(i) = self.ai_players.iter().position(|ai| ai.team == team) {
+            if self.ai_players[i].last_move_frame < 0 {
+                self.aim = self.ai_players[i].pick_move(&self.game.board);
+                self.ai_players[i].last_move_frame = self.frames as i32;
+            } else if self.frames
+                > (self.ai_players[i].last_move_frame + self.ai_move_delay) as usize
+            {
+                self.ai_players[i].last_move_frame = -1;
+                if let Some(square) = self.aim.take() {
+                    self.play(square);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    ///Draw method for Othello, which draws the turn indicator, the board, the player panel and the buttons
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::clear(ctx, self.background_color);
+        self.turn_indicator.draw(
+            ctx,
+            BOARD_POSITION.0 + SQUARE_SIZE * BOARD_SIDE as f32 / 2.0,
+        )?;
+        self.draw_board(ctx)?;
+        self.draw_panel(ctx)?;
+        self.main_menu_button.draw(ctx)?;
+        self.play_again_button.draw(ctx)?;
+        graphics::present(ctx)?;
+        ggez::timer::yield_now();
+        Ok(())
+    }
+
+    ///Method active whenever the mouse moves, which tracks the legal square under it during a human player's turn
+    fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        self.hover = if self.game.winner.is_some()
+            || !self.flipping.is_empty()
+            || self.is_ai(self.game.team)
+        {
+            None
+        } else {
+            self.square_at(mouse_location(ctx))
+                .filter(|square| self.legal_moves.contains(square))
+        };
+        self.main_menu_button.check_button_under_mouse(ctx);
+        self.play_again_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method to handle a click on the Othello screen, returning what the main menu should do next
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton) -> GameAction {
+        self.mouse_motion_event(ctx);
+        if self.main_menu_button.check_button_under_mouse(ctx) {
+            println!("Main Menu Button pressed; Main Menu should pop up");
+            self.sound_queue.push(SoundEffect::Click);
+            return GameAction::MainMenu;
+        }
+        if self.play_again_button.check_button_under_mouse(ctx) {
+            println!("Play Again button pressed; Board reset");
+            self.sound_queue.push(SoundEffect::Click);
+            self.reset_game();
+        } else if button == MouseButton::Left {
+            if let Some(square) = self.hover {
+                self.play(square);
+            } else if self.game.winner.is_none()
+                && self.flipping.is_empty()
+                && !self.is_ai(self.game.team)
+                && self.square_at(mouse_location(ctx)).is_some()
+            {
+                self.sound_queue.push(SoundEffect::InvalidMove);
+            }
+        }
+        GameAction::None
+    }
+
+    fn drain_sounds(&mut self) -> Vec<SoundEffect> {
+        self.sound_queue.drain(..).collect()
+    }
+
+    fn take_finished_match(&mut self) -> Option<MatchRecord> {
+        self.finished_match.take()
+    }
+}
+
+//NOTE: Tests not included since testing requires usage of mouse and other UI features
@@ -0,0 +1,193 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+use connect4::core::other_team;
+use othello::board::{Board, PASS_MOVE};
+
+///
+/// Enum representing what happened when a turn of Othello ended
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TurnEnd {
+    /// The other team moves next
+    Next,
+    /// The team had no legal move and passed, so the team that just moved goes again
+    Pass(i32),
+    /// Neither team can move, and the game was won by the team, or drawn if it is 0
+    GameOver(i32),
+}
+
+///
+/// A struct tracking a game of Othello: the board, whose turn it is and the moves played. A move is played and the
+/// turn ended in separate steps, so flipped discs can be shown before the next team moves
+///
+/// # Fields
+/// * board  = Board holding the discs placed so far
+/// * team   = Integer value (1 or 2) of the team to move
+/// * moves  = Vector of squares played so far, in order, each stored as row * 8 + column, or PASS_MOVE
+/// * winner = Option of the team with more discs once neither can move, 0 for a draw, or None while it is being played
+///
+#[derive(Clone, Debug)]
+pub struct Game {
+    pub board: Board,
+    pub team: i32,
+    pub moves: Vec<i32>,
+    pub winner: Option<i32>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
+
+impl Game {
+    ///Constructor - returns a game at the starting position with black (team 1) to move
+    pub fn new() -> Self {
+        Game {
+            board: Board::new(),
+            team: 1,
+            moves: Vec::new(),
+            winner: None,
+        }
+    }
+
+    ///Returns the squares the team to move may play, or none once the game is over
+    pub fn legal_moves(&self) -> Vec<usize> {
+        if self.winner.is_some() {
+            Vec::new()
+        } else {
+            self.board.legal_moves(self.team)
+        }
+    }
+
+    ///
+    /// Method to place a disc for the team to move and flip the discs it captures, without passing the turn. Returns
+    /// the squares flipped, which is empty if the square is not a legal move
+    ///
+    /// # Arguments
+    /// * square = Number of the square to play, numbered row by row from the top-left corner
+    ///
+    pub fn play(&mut self, square: usize) -> Vec<usize> {
+        if self.winner.is_some() {
+            return Vec::new();
+        }
+        let flipped = self.board.play(square, self.team);
+        if !flipped.is_empty() {
+            self.moves.push(square as i32);
+        }
+        flipped
+    }
+
+    ///Method to pass the turn to the other team, or back to the same team if the other has no legal move, or to end
+    ///the game once neither team can move
+    pub fn end_turn(&mut self) -> TurnEnd {
+        let other = other_team(self.team);
+        if self.board.is_over() {
+            let winner = self.board.leader();
+            self.winner = Some(winner);
+            TurnEnd::GameOver(winner)
+        } else if self.board.legal_moves(other).is_empty() {
+            self.moves.push(PASS_MOVE);
+            TurnEnd::Pass(other)
+        } else {
+            self.team = other;
+            TurnEnd::Next
+        }
+    }
+}
+
+#[cfg(test)]
+mod game_tests {
+    use super::*;
+    use othello::board::{BOARD_SIDE, SQUARES};
+
+    //Method to create a game from 8 rows of text, where 'B' is a black disc (team 1), 'W' a white disc (team 2) and
+    //any other character an empty square
+    fn create_test_game(rows: [&str; 8], team: i32) -> Game {
+        let mut squares = [0; SQUARES];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                squares[y * 8 + x] = match c {
+                    'B' => 1,
+                    'W' => 2,
+                    _ => 0,
+                };
+            }
+        }
+        let mut game = Game::new();
+        game.board = Board::from(squares);
+        game.team = team;
+        game
+    }
+
+    //Method to return the number of the square at a (column, row) position
+    fn square(pos: (i32, i32)) -> usize {
+        (pos.1 * BOARD_SIDE + pos.0) as usize
+    }
+
+    mod game {
+        use super::*;
+
+        mod end_turn {
+            use super::*;
+
+            #[test]
+            fn should_pass_the_turn_to_the_other_team() {
+                let mut game = Game::new();
+                assert_eq!(game.play(square((3, 2))).len(), 1);
+                assert_eq!(game.end_turn(), TurnEnd::Next);
+                assert_eq!(game.team, 2);
+                assert_eq!(game.moves, vec![square((3, 2)) as i32]);
+            }
+
+            #[test]
+            fn should_record_a_pass_when_the_other_team_cannot_move() {
+                let mut game = create_test_game(
+                    [
+                        "BWW.....", "........", "........", "........", "........", "........",
+                        "........", "BWW.....",
+                    ],
+                    1,
+                );
+                game.play(square((3, 0)));
+                assert_eq!(game.end_turn(), TurnEnd::Pass(2));
+                assert_eq!(game.team, 1);
+                assert_eq!(game.winner, None);
+                assert_eq!(game.moves, vec![square((3, 0)) as i32, PASS_MOVE]);
+                assert_eq!(game.legal_moves(), vec![square((3, 7))]);
+            }
+
+            #[test]
+            fn should_end_the_game_when_neither_team_can_move() {
+                let mut game = create_test_game(
+                    [
+                        "BBBB....", "........", "........", "........", "........", "........",
+                        "........", "BWW.....",
+                    ],
+                    1,
+                );
+                game.play(square((3, 7)));
+                assert_eq!(game.end_turn(), TurnEnd::GameOver(1));
+                assert_eq!(game.winner, Some(1));
+                assert!(game.legal_moves().is_empty());
+                assert!(game.play(square((4, 0))).is_empty());
+                assert_eq!(game.moves, vec![square((3, 7)) as i32]);
+            }
+
+            #[test]
+            fn should_draw_when_both_teams_have_the_same_number_of_discs() {
+                let mut game = create_test_game(
+                    [
+                        "BB......", "........", "........", "........", "........", "........",
+                        "........", "WW......",
+                    ],
+                    2,
+                );
+                assert_eq!(game.end_turn(), TurnEnd::GameOver(0));
+                assert_eq!(game.winner, Some(0));
+            }
+        }
+    }
+}
@@ -0,0 +1,474 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+extern crate rand;
+extern crate toml;
+
+use connect4::ai::AI;
+use connect4::core::{Board, BoardConfig, GridPosition, MyColor, BOARD_SIZE};
+use ggez::{filesystem, Context, GameResult};
+use history::MatchHistory;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use self::rand::{Rng, SeedableRng, StdRng};
+
+///Constant name of the AI calibration file inside the user data directory
+const AI_RATINGS_FILE_NAME: &str = "ai_ratings.toml";
+
+///Constant rating every new profile starts with
+pub const DEFAULT_RATING: f64 = 1200.0;
+
+///Constant for how much a single game can move a profile's rating
+const K_FACTOR: f64 = 32.0;
+
+///Constant ratings for AI difficulties 1 to 6, found by running `cargo run --release -- --calibrate-ai`. Deeper searches
+///rate lower since the AI averages the outcomes of future moves instead of assuming the best reply
+const DEFAULT_AI_RATINGS: [f64; 6] = [1350.0, 1234.0, 1278.0, 1084.0, 1143.0, 1111.0];
+
+///Constant number of random moves made at the start of each tournament game so AI games are not all the same
+const TOURNAMENT_OPENING_MOVES: usize = 2;
+
+///
+/// Function to return the expected score (from 0.0 for a certain loss to 1.0 for a certain win) of a player against
+/// an opponent
+///
+/// # Arguments
+/// * rating   = Rating of the player
+/// * opponent = Rating of the opponent
+///
+pub fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+///
+/// Function to return a player's rating after a game
+///
+/// # Arguments
+/// * rating   = Rating of the player before the game
+/// * opponent = Rating of the opponent before the game
+/// * score    = 1.0 for a win, 0.5 for a draw and 0.0 for a loss
+///
+pub fn updated_rating(rating: f64, opponent: f64, score: f64) -> f64 {
+    rating + K_FACTOR * (score - expected_score(rating, opponent))
+}
+
+///
+/// A struct for a human player's rating, keyed by the name entered on the setup screen
+///
+/// # Fields
+/// * name   = Name of the player
+/// * rating = Current Elo rating of the player
+/// * games  = Number of rated games the player has played
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Profile {
+    pub name: String,
+    pub rating: f64,
+    pub games: u32,
+}
+
+///
+/// A struct holding the AI level ratings written by the tournament runner
+///
+/// # Fields
+/// * ratings = Vector of ratings, where ratings[i] is for AI difficulty i + 1
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AiCalibration {
+    pub ratings: Vec<f64>,
+}
+
+impl Default for AiCalibration {
+    fn default() -> Self {
+        AiCalibration {
+            ratings: DEFAULT_AI_RATINGS.to_vec(),
+        }
+    }
+}
+
+impl AiCalibration {
+    ///Returns the path of the AI calibration file inside the user data directory
+    pub fn path(ctx: &Context) -> PathBuf {
+        filesystem::user_data_dir(ctx).join(AI_RATINGS_FILE_NAME)
+    }
+
+    ///
+    /// Method to load AI ratings from a file. If the file is missing or invalid, the built-in ratings are returned
+    ///
+    /// # Arguments
+    /// * path = Location of the AI calibration file
+    ///
+    pub fn load(path: &Path) -> AiCalibration {
+        match fs::read_to_string(path) {
+            Ok(text) => match toml::from_str::<AiCalibration>(&text) {
+                Ok(ref calibration) if !calibration.ratings.is_empty() => calibration.clone(),
+                _ => {
+                    println!("Error reading AI ratings {:?}; using defaults", path);
+                    AiCalibration::default()
+                }
+            },
+            Err(_) => AiCalibration::default(),
+        }
+    }
+
+    ///
+    /// Method to save AI ratings to a file, creating its directory if needed
+    ///
+    /// # Arguments
+    /// * path = Location of the AI calibration file
+    ///
+    pub fn save(&self, path: &Path) -> GameResult {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(self).unwrap_or_default())?;
+        Ok(())
+    }
+
+    ///Returns the rating of an AI difficulty. Difficulties past the calibrated ones use the closest calibrated rating
+    pub fn rating(&self, difficulty: i32) -> f64 {
+        let index = (difficulty.max(1) as usize - 1).min(self.ratings.len() - 1);
+        self.ratings[index]
+    }
+}
+
+///
+/// A struct holding the rating of every human player, computed by replaying the match history in order
+///
+/// # Fields
+/// * profiles = Vector of Profiles, highest rating first
+///
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Ratings {
+    pub profiles: Vec<Profile>,
+}
+
+impl Ratings {
+    ///
+    /// Method to compute every human player's rating from the match history. Human players are rated against each
+    /// AI opponent using the AI's calibrated rating and against each human opponent using their rating before the game.
+    /// Only games of Connect 4 on the standard board are rated, since that is the game the AI ratings were calibrated
+    /// on. Games between AI players are skipped
+    ///
+    /// # Arguments
+    /// * history     = MatchHistory holding every finished match, oldest first
+    /// * calibration = AiCalibration giving the rating of each AI difficulty
+    ///
+    pub fn from_history(history: &MatchHistory, calibration: &AiCalibration) -> Ratings {
+        let mut ratings = Ratings::default();
+        let rated_game = BoardConfig::standard().label();
+        for record in history.matches.iter().filter(|m| m.game == rated_game) {
+            //Ratings before the game, so every player in a game is rated from the same starting point
+            let before: Vec<f64> = record
+                .players
+                .iter()
+                .map(|p| {
+                    if p.is_ai {
+                        calibration.rating(record.ai_difficulty)
+                    } else {
+                        ratings.rating(&p.name)
+                    }
+                })
+                .collect();
+            for (i, player) in record.players.iter().enumerate() {
+                if player.is_ai {
+                    continue;
+                }
+                let score = match record.won_by(i) {
+                    Some(true) => 1.0,
+                    Some(false) => 0.0,
+                    None => 0.5,
+                };
+                let mut rating = before[i];
+                for j in 0..record.players.len() {
+                    if i != j {
+                        rating += updated_rating(before[i], before[j], score) - before[i];
+                    }
+                }
+                match ratings.profiles.iter_mut().find(|p| p.name == player.name) {
+                    Some(profile) => {
+                        profile.rating = rating;
+                        profile.games += 1;
+                    }
+                    None => ratings.profiles.push(Profile {
+                        name: player.name.clone(),
+                        rating,
+                        games: 1,
+                    }),
+                }
+            }
+        }
+        ratings.profiles.sort_by(|a, b| {
+            b.rating
+                .partial_cmp(&a.rating)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        ratings
+    }
+
+    ///Returns the rating of a player, or DEFAULT_RATING if they have not played a rated game
+    pub fn rating(&self, name: &str) -> f64 {
+        self.profiles
+            .iter()
+            .find(|p| p.name == name)
+            .map_or(DEFAULT_RATING, |p| p.rating)
+    }
+}
+
+///
+/// Function to play a game of Connect 4 between two AI players without drawing anything. Returns the winning team, or
+/// 0 for a draw
+///
+/// # Arguments
+/// * difficulties = Difficulty of the AI for team 1 and team 2, in that order
+/// * opening      = Vector of columns played in order before the AI players take over
+///
+pub fn play_ai_game(difficulties: (i32, i32), opening: &[i32]) -> i32 {
+    let players = [AI::new(1, difficulties.0), AI::new(2, difficulties.1)];
+    let mut board = Board::new(GridPosition::new(0, 0));
+    let mut team = 1;
+    for turn in 0..(BOARD_SIZE.0 * BOARD_SIZE.1) as usize {
+        let col = if turn < opening.len() {
+            opening[turn]
+        } else {
+            players[team as usize - 1].pick_optimal_move(board.clone(), None)
+        };
+        if col < 0 || !board.insert(col, team, MyColor::White) {
+            //A player with no legal move has nowhere to go, so the game is a draw
+            return 0;
+        }
+        let row = board.get_column_height(col as usize) as i32 - 1;
+        if board.connects(GridPosition::new(col, row), team) {
+            return team;
+        }
+        team = team % 2 + 1;
+    }
+    0
+}
+
+///
+/// Function to calibrate AI ratings by playing every AI difficulty against every other one. Each pair plays
+/// games_per_pair games from random openings, swapping who goes first, and ratings are then fit to the results and
+/// centered on DEFAULT_RATING. Returns the ratings, where ratings[i] is for AI difficulty i + 1
+///
+/// # Arguments
+/// * max_difficulty = Highest AI difficulty to include
+/// * games_per_pair = Number of games each pair of difficulties plays
+/// * seed           = Seed for the random openings, so results can be repeated
+///
+pub fn run_ai_tournament(max_difficulty: i32, games_per_pair: usize, seed: usize) -> Vec<f64> {
+    let levels = max_difficulty.max(1) as usize;
+    let mut rng: StdRng = SeedableRng::from_seed(&[seed][..]);
+    //scores[i][j] is the points level i + 1 scored against level j + 1
+    let mut scores = vec![vec![0f64; levels]; levels];
+    for i in 0..levels {
+        for j in (i + 1)..levels {
+            for game in 0..games_per_pair {
+                let opening: Vec<i32> = (0..TOURNAMENT_OPENING_MOVES)
+                    .map(|_| rng.gen_range(0, BOARD_SIZE.1))
+                    .collect();
+                let (first, second) = if game % 2 == 0 { (i, j) } else { (j, i) };
+                let winner = play_ai_game((first as i32 + 1, second as i32 + 1), &opening);
+                match winner {
+                    1 => scores[first][second] += 1.0,
+                    2 => scores[second][first] += 1.0,
+                    _ => {
+                        scores[first][second] += 0.5;
+                        scores[second][first] += 0.5;
+                    }
+                }
+            }
+            println!(
+                "AI {} vs AI {}: {} - {}",
+                i + 1,
+                j + 1,
+                scores[i][j],
+                scores[j][i]
+            );
+        }
+    }
+    fit_ratings(&scores, games_per_pair)
+}
+
+///
+/// Function to find ratings whose expected scores match a table of results, centered on DEFAULT_RATING
+///
+/// # Arguments
+/// * scores         = Table where scores[i][j] is the points player i scored against player j
+/// * games_per_pair = Number of games each pair of players played
+///
+fn fit_ratings(scores: &[Vec<f64>], games_per_pair: usize) -> Vec<f64> {
+    let players = scores.len();
+    let mut ratings = vec![DEFAULT_RATING; players];
+    if players < 2 || games_per_pair == 0 {
+        return ratings;
+    }
+    let games = (games_per_pair * (players - 1)) as f64;
+    for _ in 0..1000 {
+        let mut next = ratings.clone();
+        for i in 0..players {
+            let mut actual = 0.0;
+            let mut expected = 0.0;
+            for j in 0..players {
+                if i != j {
+                    actual += scores[i][j];
+                    expected += games_per_pair as f64 * expected_score(ratings[i], ratings[j]);
+                }
+            }
+            next[i] += 4.0 * K_FACTOR * (actual - expected) / games;
+        }
+        //Keep the average rating at DEFAULT_RATING, since only rating differences come from results
+        let shift = DEFAULT_RATING - next.iter().sum::<f64>() / players as f64;
+        ratings = next.iter().map(|r| r + shift).collect();
+    }
+    ratings.iter().map(|r| r.round()).collect()
+}
+
+#[cfg(test)]
+mod rating_tests {
+    use super::*;
+    use history::{MatchRecord, PlayerRecord};
+
+    fn create_test_record(players: Vec<(&str, bool)>, winner: i32, difficulty: i32) -> MatchRecord {
+        MatchRecord::new(
+            "Connect 4",
+            players
+                .iter()
+                .map(|p| PlayerRecord::new(p.0, p.1))
+                .collect(),
+            difficulty,
+            vec![],
+            winner,
+            0.0,
+        )
+    }
+
+    mod updated_rating {
+        use super::*;
+
+        #[test]
+        fn should_move_equal_ratings_by_half_k() {
+            assert_eq!(updated_rating(1200.0, 1200.0, 1.0), 1216.0);
+            assert_eq!(updated_rating(1200.0, 1200.0, 0.0), 1184.0);
+            assert_eq!(updated_rating(1200.0, 1200.0, 0.5), 1200.0);
+        }
+
+        #[test]
+        fn should_gain_more_for_beating_stronger_opponent() {
+            let upset = updated_rating(1200.0, 1600.0, 1.0) - 1200.0;
+            let expected_win = updated_rating(1200.0, 800.0, 1.0) - 1200.0;
+            assert!(upset > expected_win);
+        }
+    }
+
+    mod ai_calibration {
+        use super::*;
+
+        mod rating {
+            use super::*;
+
+            #[test]
+            fn should_use_closest_calibrated_difficulty() {
+                let calibration = AiCalibration {
+                    ratings: vec![900.0, 1000.0],
+                };
+                assert_eq!(calibration.rating(1), 900.0);
+                assert_eq!(calibration.rating(2), 1000.0);
+                assert_eq!(calibration.rating(6), 1000.0);
+                assert_eq!(calibration.rating(0), 900.0);
+            }
+        }
+    }
+
+    mod ratings {
+        use super::*;
+
+        mod from_history {
+            use super::*;
+
+            #[test]
+            fn should_rate_humans_against_ai_level() {
+                let calibration = AiCalibration::default();
+                let history = MatchHistory {
+                    matches: vec![create_test_record(
+                        vec![("Lane", false), ("AI 2", true)],
+                        1,
+                        6,
+                    )],
+                };
+                let ratings = Ratings::from_history(&history, &calibration);
+                assert_eq!(ratings.profiles.len(), 1);
+                assert_eq!(ratings.profiles[0].games, 1);
+                assert_eq!(
+                    ratings.rating("Lane"),
+                    updated_rating(DEFAULT_RATING, calibration.rating(6), 1.0)
+                );
+            }
+
+            #[test]
+            fn should_rate_humans_against_each_other() {
+                let history = MatchHistory {
+                    matches: vec![
+                        create_test_record(vec![("Lane", false), ("Andre", false)], 2, 0),
+                        create_test_record(vec![("AI 1", true), ("AI 2", true)], 1, 3),
+                    ],
+                };
+                let ratings = Ratings::from_history(&history, &AiCalibration::default());
+                assert_eq!(ratings.profiles.len(), 2);
+                assert_eq!(ratings.profiles[0].name, "Andre");
+                assert_eq!(ratings.rating("Andre"), 1216.0);
+                assert_eq!(ratings.rating("Lane"), 1184.0);
+                assert_eq!(ratings.rating("Nobody"), DEFAULT_RATING);
+            }
+
+            #[test]
+            fn should_skip_games_other_than_standard_connect_4() {
+                let calibration = AiCalibration::default();
+                let mut record = create_test_record(vec![("Lane", false), ("AI 3", true)], 1, 3);
+                let history = MatchHistory {
+                    matches: vec![record.clone()],
+                };
+                let rated = Ratings::from_history(&history, &calibration);
+                for game in &["Mancala", "Othello", "Connect 5 (9x7)"] {
+                    record.game = game.to_string();
+                    let history = MatchHistory {
+                        matches: vec![
+                            create_test_record(vec![("Lane", false), ("AI 3", true)], 1, 3),
+                            record.clone(),
+                        ],
+                    };
+                    assert_eq!(Ratings::from_history(&history, &calibration), rated);
+                }
+            }
+        }
+    }
+
+    mod play_ai_game {
+        use super::*;
+
+        #[test]
+        fn should_finish_with_a_result() {
+            let winner = play_ai_game((1, 1), &[3, 3]);
+            assert!((0..=2).contains(&winner));
+        }
+    }
+
+    mod fit_ratings {
+        use super::*;
+
+        #[test]
+        fn should_rate_winner_higher_and_center_on_default() {
+            let scores = vec![vec![0.0, 3.0], vec![1.0, 0.0]];
+            let ratings = fit_ratings(&scores, 4);
+            assert!(ratings[0] > ratings[1]);
+            assert!((ratings[0] + ratings[1] - 2.0 * DEFAULT_RATING).abs() <= 1.0);
+            //A 3-1 result means the winner should be expected to score about 75%
+            let expected = expected_score(ratings[0], ratings[1]);
+            assert!((expected - 0.75).abs() < 0.02);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use ggez::event::KeyCode;
+
+///
+/// A struct stepping through the positions of a finished game, from 0 (before any move) to the number of moves made.
+/// It only tracks which position is shown, so each game rebuilds the board for a position in its own way
+///
+/// # Fields
+/// * moves    = Number of moves in the game, which is also the last position
+/// * position = Number of moves made in the position shown
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ReplayStepper {
+    moves: usize,
+    position: usize,
+}
+
+impl ReplayStepper {
+    ///Constructor - moves is the number of moves in the game, and the position after the last of them is shown first
+    pub fn new(moves: usize) -> Self {
+        ReplayStepper {
+            moves,
+            position: moves,
+        }
+    }
+
+    ///Returns the number of moves made in the position shown
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    ///Returns the number of moves in the game
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    ///Returns true if the position after the last move is shown
+    pub fn is_at_end(&self) -> bool {
+        self.position == self.moves
+    }
+
+    ///Method to show the given number of moves, kept within the length of the game
+    pub fn show(&mut self, position: usize) {
+        self.position = position.min(self.moves);
+    }
+
+    ///Method to step back one move, staying at the start of the game
+    pub fn back(&mut self) {
+        self.position = self.position.saturating_sub(1);
+    }
+
+    ///Method to step forward one move, staying at the end of the game
+    pub fn forward(&mut self) {
+        self.show(self.position + 1);
+    }
+
+    ///
+    /// Method to step with the keyboard: the arrow keys step one move and Home and End jump to either end. Returns true
+    /// if the key was one of these
+    ///
+    /// # Arguments
+    /// * keycode = KeyCode of the key pressed
+    ///
+    pub fn key_down_event(&mut self, keycode: KeyCode) -> bool {
+        match keycode {
+            KeyCode::Left => self.back(),
+            KeyCode::Right => self.forward(),
+            KeyCode::Home => self.position = 0,
+            KeyCode::End => self.position = self.moves,
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod replay_tests {
+    use super::*;
+
+    mod replay_stepper {
+        use super::*;
+
+        mod show {
+            use super::*;
+
+            #[test]
+            fn should_start_at_the_end_and_stay_within_the_game() {
+                let mut stepper = ReplayStepper::new(3);
+                assert_eq!(stepper.position(), 3);
+                assert!(stepper.is_at_end());
+                stepper.show(10);
+                assert_eq!(stepper.position(), 3);
+                stepper.show(1);
+                assert!(!stepper.is_at_end());
+            }
+        }
+
+        mod key_down_event {
+            use super::*;
+
+            #[test]
+            fn should_step_and_jump_with_arrows_home_and_end() {
+                let mut stepper = ReplayStepper::new(2);
+                assert!(stepper.key_down_event(KeyCode::Right));
+                assert_eq!(stepper.position(), 2);
+                stepper.key_down_event(KeyCode::Left);
+                assert_eq!(stepper.position(), 1);
+                stepper.key_down_event(KeyCode::Home);
+                stepper.key_down_event(KeyCode::Left);
+                assert_eq!(stepper.position(), 0);
+                stepper.key_down_event(KeyCode::End);
+                assert_eq!(stepper.position(), 2);
+                assert!(!stepper.key_down_event(KeyCode::Space));
+                assert_eq!(stepper.position(), 2);
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use connect4::core::GameAction;
+use connect4::review::ReviewScreen;
+use ggez::event::KeyCode;
+use ggez::input::mouse::MouseButton;
+use ggez::{Context, GameResult};
+use history::MatchRecord;
+use sound::SoundEffect;
+
+///
+/// A trait for the screen of a game being played, implemented by the state of each game so the main menu can run
+/// whichever game is loaded the same way
+///
+pub trait GameScreen {
+    ///Returns the dimensions of the game's window, before the window scale from settings is applied
+    fn screen_size(&self) -> (f32, f32);
+
+    ///Update method for the game, called every frame while it is loaded
+    fn update(&mut self, ctx: &mut Context) -> GameResult;
+
+    ///Draw method for the game, which clears and presents the whole window
+    fn draw(&mut self, ctx: &mut Context) -> GameResult;
+
+    ///Method active whenever the mouse moves
+    fn mouse_motion_event(&mut self, ctx: &mut Context);
+
+    ///Method active whenever a mouse button is pressed down. Games that only act on a click track the mouse
+    fn mouse_button_down_event(&mut self, ctx: &mut Context, _button: MouseButton) {
+        self.mouse_motion_event(ctx);
+    }
+
+    ///Method to handle a click on the game, returning what the main menu should do next
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton) -> GameAction;
+
+    ///Method active whenever a key is pressed. Games without keyboard controls ignore it
+    fn key_down_event(&mut self, _keycode: KeyCode) {}
+
+    ///Returns the sound effects triggered since the main menu last played them, emptying the queue
+    fn drain_sounds(&mut self) -> Vec<SoundEffect>;
+
+    ///Returns the MatchRecord for a game that just ended, or None if there is none the main menu hasn't saved
+    fn take_finished_match(&mut self) -> Option<MatchRecord>;
+
+    ///Method to load the finished game into the review screen, for games that return GameAction::Review
+    fn start_review(&self, _review: &mut ReviewScreen) {}
+}
@@ -0,0 +1,1069 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+extern crate toml;
+
+use clock::TimeControl;
+use connect4::ai::MAX_DIFFICULTY;
+use connect4::button::{mouse_location, Button, BUTTON_PADDING};
+use connect4::core::{BoardConfig, MyColor, BOARD_SIZE_PRESETS};
+use dots::board::DotsConfig;
+use ggez::mint::Point2;
+use ggez::{filesystem, graphics, Context, GameError, GameResult};
+use gomoku::rules::GomokuRules;
+use mancala::board::MancalaConfig;
+use mnk::board::MnkConfig;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+///Constant name of the settings file inside the user config directory
+const SETTINGS_FILE_NAME: &str = "settings.toml";
+
+///Constant font size for the settings screen rows
+const SETTINGS_FONT_SIZE: f32 = 26.0;
+
+///Constant location of the first settings row; each later row is SETTINGS_ROW_SPACING below the previous one
+const SETTINGS_ROW_OFFSET: (f32, f32) = (50.0, 72.0);
+
+///Constant vertical spacing between settings rows
+const SETTINGS_ROW_SPACING: f32 = 30.0;
+
+///Constant number of rows that fit on one page of the settings screen; the "Next Page" button shows the others
+const SETTINGS_ROWS_PER_PAGE: usize = 15;
+
+///Constant x location of the "-" button in each row; the value and "+" button follow it
+const SETTINGS_CONTROL_X: f32 = 420.0;
+
+///Constant width reserved for the value text between the "-" and "+" buttons
+const SETTINGS_VALUE_WIDTH: f32 = 220.0;
+
+///Constant dimensions of the bar drawn for slider rows, centered in the value area
+const SLIDER_BAR_SIZE: (f32, f32) = (180.0, 20.0);
+
+/// Enum representing the color themes available for backgrounds
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Theme {
+    Classic,
+    Dark,
+    Light,
+}
+
+impl Theme {
+    ///
+    /// Method that produces the background color for menu screens
+    ///
+    pub fn menu_background(self) -> graphics::Color {
+        match self {
+            Theme::Classic => [0.1, 0.2, 0.3, 1.0].into(),
+            Theme::Dark => [0.05, 0.05, 0.05, 1.0].into(),
+            Theme::Light => [0.55, 0.65, 0.75, 1.0].into(),
+        }
+    }
+
+    ///
+    /// Method that produces the background color behind a game board
+    ///
+    pub fn game_background(self) -> graphics::Color {
+        match self {
+            Theme::Classic => graphics::BLACK,
+            Theme::Dark => [0.1, 0.1, 0.12, 1.0].into(),
+            Theme::Light => [0.85, 0.85, 0.85, 1.0].into(),
+        }
+    }
+
+    ///Returns the theme after this one, wrapping around, so it can be cycled with a button
+    fn next(self) -> Theme {
+        match self {
+            Theme::Classic => Theme::Dark,
+            Theme::Dark => Theme::Light,
+            Theme::Light => Theme::Classic,
+        }
+    }
+
+    ///Returns the theme before this one, wrapping around, so it can be cycled with a button
+    fn prev(self) -> Theme {
+        match self {
+            Theme::Classic => Theme::Light,
+            Theme::Dark => Theme::Classic,
+            Theme::Light => Theme::Dark,
+        }
+    }
+}
+
+/// Enum representing what the main menu should do after a click on the settings screen
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettingsAction {
+    None,
+    Changed,
+    Back,
+}
+
+/// Enum naming each value that can be changed on the settings screen, in the order the rows are drawn
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettingField {
+    AiMoveDelay,
+    AnimationSpeed,
+    SoundVolume,
+    MusicVolume,
+    Music,
+    Mute,
+    Theme,
+    WindowScale,
+    DefaultPlayers,
+    AiDifficulty,
+    HintStrength,
+    Variant,
+    PlayerCount,
+    BoardSize,
+    ConnectLength,
+    Clock,
+    MnkBoard,
+    GomokuRules,
+    DotsGrid,
+    MancalaPits,
+    MancalaSeeds,
+}
+
+impl SettingField {
+    ///Returns every field in the order the rows are drawn
+    pub fn all() -> Vec<SettingField> {
+        vec![
+            SettingField::AiMoveDelay,
+            SettingField::AnimationSpeed,
+            SettingField::SoundVolume,
+            SettingField::MusicVolume,
+            SettingField::Music,
+            SettingField::Mute,
+            SettingField::Theme,
+            SettingField::WindowScale,
+            SettingField::DefaultPlayers,
+            SettingField::AiDifficulty,
+            SettingField::HintStrength,
+            SettingField::Variant,
+            SettingField::PlayerCount,
+            SettingField::BoardSize,
+            SettingField::ConnectLength,
+            SettingField::Clock,
+            SettingField::MnkBoard,
+            SettingField::GomokuRules,
+            SettingField::DotsGrid,
+            SettingField::MancalaPits,
+            SettingField::MancalaSeeds,
+        ]
+    }
+
+    ///Returns the label drawn for the field's row
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingField::AiMoveDelay => "AI Move Delay",
+            SettingField::AnimationSpeed => "Animation Speed",
+            SettingField::SoundVolume => "Sound Volume",
+            SettingField::MusicVolume => "Music Volume",
+            SettingField::Music => "Music",
+            SettingField::Mute => "Mute",
+            SettingField::Theme => "Theme",
+            SettingField::WindowScale => "Window Size",
+            SettingField::DefaultPlayers => "Default Players",
+            SettingField::AiDifficulty => "AI Difficulty",
+            SettingField::HintStrength => "Hint Strength",
+            SettingField::Variant => "Rules",
+            SettingField::PlayerCount => "Player Count",
+            SettingField::BoardSize => "Board Size",
+            SettingField::ConnectLength => "Connect",
+            SettingField::Clock => "Game Clock",
+            SettingField::MnkBoard => "Tic-Tac-Toe Board",
+            SettingField::GomokuRules => "Gomoku Rules",
+            SettingField::DotsGrid => "Dots and Boxes Grid",
+            SettingField::MancalaPits => "Mancala Pits",
+            SettingField::MancalaSeeds => "Mancala Seeds",
+        }
+    }
+
+    ///Returns true if the field is a volume drawn as a slider bar that can be clicked to set its value
+    pub fn is_slider(self) -> bool {
+        matches!(self, SettingField::SoundVolume | SettingField::MusicVolume)
+    }
+}
+
+///
+/// A struct holding every user configurable value. Stored as TOML in the user config directory and loaded at startup
+///
+/// # Fields
+/// * ai_move_delay   = Number of frames the AI shows its chosen column before dropping a disc
+/// * animation_speed = Multiplier for how fast discs fall, or flip in Othello; 0.0 places discs instantly
+/// * sound_volume    = Volume from 0.0 to 1.0 for sound effects
+/// * music_volume    = Volume from 0.0 to 1.0 for background music
+/// * music_enabled   = Boolean indicating if background music is played
+/// * muted           = Boolean indicating if all sound effects and music are muted
+/// * theme           = Theme enum used for background colors
+/// * window_scale    = Multiplier for the size of the window; everything is drawn larger or smaller to fill it
+/// * default_players = Number of human players (0-2) selected on the main menu at startup
+/// * ai_difficulty   = Integer value passed to new AI players to determine how deep they search for moves
+/// * hint_strength   = Integer value for how deep the AI searches when a player asks for a hint
+/// * gomoku_rules    = GomokuRules in `src/gomoku/rules.rs` for free-style Gomoku, Renju or Connect6; written before
+///   the TOML tables since it is a plain value
+/// * board           = BoardConfig in `src/connect4/core.rs` for the Connect 4 board size, run length and rules; a TOML table
+/// * mnk             = MnkConfig in `src/mnk/board.rs` for the Tic-Tac-Toe board size and run length; a TOML table
+/// * dots            = DotsConfig in `src/dots/board.rs` for the Dots and Boxes grid size; a TOML table
+/// * mancala         = MancalaConfig in `src/mancala/board.rs` for the number of Mancala pits and seeds; a TOML table
+/// * time_control    = TimeControl in `src/clock.rs` for game clocks; kept last since it is written as its own TOML table
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub ai_move_delay: i32,
+    pub animation_speed: f32,
+    pub sound_volume: f32,
+    pub music_volume: f32,
+    pub music_enabled: bool,
+    pub muted: bool,
+    pub theme: Theme,
+    pub window_scale: f32,
+    pub default_players: i32,
+    pub ai_difficulty: i32,
+    pub hint_strength: i32,
+    pub gomoku_rules: GomokuRules,
+    pub board: BoardConfig,
+    pub mnk: MnkConfig,
+    pub dots: DotsConfig,
+    pub mancala: MancalaConfig,
+    pub time_control: TimeControl,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            ai_move_delay: 100,
+            animation_speed: 1.0,
+            sound_volume: 0.5,
+            music_volume: 0.3,
+            music_enabled: false,
+            muted: false,
+            theme: Theme::Classic,
+            window_scale: 1.0,
+            default_players: 1,
+            ai_difficulty: 3,
+            hint_strength: 3,
+            gomoku_rules: GomokuRules::FreeStyle,
+            board: BoardConfig::standard(),
+            mnk: MnkConfig::tic_tac_toe(),
+            dots: DotsConfig::default(),
+            mancala: MancalaConfig::default(),
+            time_control: TimeControl::off(),
+        }
+    }
+}
+
+impl Settings {
+    ///Returns the path of the settings file inside the user config directory
+    pub fn path(ctx: &Context) -> PathBuf {
+        filesystem::user_config_dir(ctx).join(SETTINGS_FILE_NAME)
+    }
+
+    ///
+    /// Method to read settings from a TOML string. Missing values fall back to their defaults
+    ///
+    /// # Arguments
+    /// * text = TOML contents of a settings file
+    ///
+    pub fn from_toml(text: &str) -> Result<Settings, String> {
+        toml::from_str::<Settings>(text)
+            .map(|settings| settings.clamped())
+            .map_err(|e| e.to_string())
+    }
+
+    ///Method to write settings as a TOML string, or return why they could not be written
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string(self).map_err(|e| e.to_string())
+    }
+
+    ///
+    /// Method to load settings from a file. If the file is missing or invalid, the defaults are returned so the game can
+    /// still start
+    ///
+    /// # Arguments
+    /// * path = Location of the settings file
+    ///
+    pub fn load(path: &Path) -> Settings {
+        match fs::read_to_string(path) {
+            Ok(text) => match Settings::from_toml(&text) {
+                Ok(settings) => settings,
+                Err(e) => {
+                    println!("Error reading settings file {:?}: {}", path, e);
+                    Settings::default()
+                }
+            },
+            Err(_) => {
+                println!("No settings file at {:?}; using defaults", path);
+                Settings::default()
+            }
+        }
+    }
+
+    ///
+    /// Method to save settings to a file, creating its directory if needed. The file is left as it was if the settings
+    /// can't be written as TOML
+    ///
+    /// # Arguments
+    /// * path = Location of the settings file
+    ///
+    pub fn save(&self, path: &Path) -> GameResult {
+        let text = self.to_toml().map_err(GameError::ConfigError)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, text)?;
+        Ok(())
+    }
+
+    ///Returns a copy of the settings with every value moved into its valid range
+    fn clamped(mut self) -> Settings {
+        self.ai_move_delay = self.ai_move_delay.clamp(0, 300);
+        self.animation_speed = self.animation_speed.clamp(0.0, 4.0);
+        self.sound_volume = self.sound_volume.clamp(0.0, 1.0);
+        self.music_volume = self.music_volume.clamp(0.0, 1.0);
+        self.window_scale = self.window_scale.clamp(0.5, 2.0);
+        self.default_players = self.default_players.clamp(0, 2);
+        self.ai_difficulty = self.ai_difficulty.clamp(1, MAX_DIFFICULTY);
+        self.hint_strength = self.hint_strength.clamp(1, MAX_DIFFICULTY);
+        self.board = self.board.clamped();
+        self.mnk = self.mnk.clamped();
+        self.dots = self.dots.clamped();
+        self.mancala = self.mancala.clamped();
+        self.time_control = self.time_control.clamped();
+        self
+    }
+
+    ///
+    /// Method to raise or lower a value by one step, staying within the value's range
+    ///
+    /// # Arguments
+    /// * field = SettingField enum naming the value to change
+    /// * up    = Boolean indicating if the value should go up (true) or down (false)
+    ///
+    pub fn step(&mut self, field: SettingField, up: bool) {
+        let dir = if up { 1 } else { -1 };
+        match field {
+            SettingField::AiMoveDelay => self.ai_move_delay += 20 * dir,
+            SettingField::AnimationSpeed => self.animation_speed += 0.5 * dir as f32,
+            SettingField::SoundVolume => self.sound_volume += 0.1 * dir as f32,
+            SettingField::MusicVolume => self.music_volume += 0.1 * dir as f32,
+            SettingField::Music => self.music_enabled = !self.music_enabled,
+            SettingField::Mute => self.muted = !self.muted,
+            SettingField::Theme => {
+                self.theme = if up {
+                    self.theme.next()
+                } else {
+                    self.theme.prev()
+                }
+            }
+            SettingField::WindowScale => self.window_scale += 0.25 * dir as f32,
+            SettingField::DefaultPlayers => self.default_players += dir,
+            SettingField::AiDifficulty => self.ai_difficulty += dir,
+            SettingField::HintStrength => self.hint_strength += dir,
+            SettingField::Variant => self.board.variant = self.board.variant.step(up),
+            //The rules fix the board for some variants, so the size set for the others is left alone
+            SettingField::BoardSize | SettingField::ConnectLength | SettingField::PlayerCount
+                if self.board.variant.fixes_board() => {}
+            SettingField::PlayerCount => self.board.teams += dir,
+            SettingField::BoardSize => self.step_board_size(up),
+            SettingField::ConnectLength => self.board.connect += dir,
+            SettingField::Clock => self.time_control = self.time_control.step(up),
+            SettingField::MnkBoard => self.mnk = self.mnk.step(up),
+            SettingField::GomokuRules => self.gomoku_rules = self.gomoku_rules.step(up),
+            SettingField::DotsGrid => self.dots = self.dots.step(up),
+            SettingField::MancalaPits => self.mancala.pits += dir,
+            SettingField::MancalaSeeds => self.mancala.seeds += dir,
+        }
+        //Round away float error from repeated steps
+        self.sound_volume = (self.sound_volume * 10.0).round() / 10.0;
+        self.music_volume = (self.music_volume * 10.0).round() / 10.0;
+        *self = self.clone().clamped();
+    }
+
+    ///
+    /// Method to move the board size to the next larger or smaller preset. A custom size from the settings file moves to
+    /// the nearest preset in that direction
+    ///
+    /// # Arguments
+    /// * up = Boolean indicating if the board should get larger (true) or smaller (false)
+    ///
+    fn step_board_size(&mut self, up: bool) {
+        let cells = |size: (i32, i32)| size.0 * size.1;
+        let current = (self.board.rows, self.board.columns);
+        let next = if up {
+            BOARD_SIZE_PRESETS
+                .iter()
+                .find(|size| cells(**size) > cells(current))
+        } else {
+            BOARD_SIZE_PRESETS
+                .iter()
+                .rev()
+                .find(|size| cells(**size) < cells(current))
+        };
+        if let Some(size) = next {
+            self.board.rows = size.0;
+            self.board.columns = size.1;
+        }
+    }
+
+    ///
+    /// Method to set a slider field from a position along its bar, rounded to the nearest 10%
+    ///
+    /// # Arguments
+    /// * field    = SettingField enum naming the volume to change. Fields that are not sliders are left unchanged
+    /// * fraction = Position along the bar, where 0.0 is the left end and 1.0 is the right end
+    ///
+    pub fn set_fraction(&mut self, field: SettingField, fraction: f32) {
+        let value = (fraction.clamp(0.0, 1.0) * 10.0).round() / 10.0;
+        match field {
+            SettingField::SoundVolume => self.sound_volume = value,
+            SettingField::MusicVolume => self.music_volume = value,
+            _ => (),
+        }
+    }
+
+    ///Returns the 0.0 to 1.0 value of a slider field, or 0.0 for fields that are not sliders
+    pub fn fraction(&self, field: SettingField) -> f32 {
+        match field {
+            SettingField::SoundVolume => self.sound_volume,
+            SettingField::MusicVolume => self.music_volume,
+            _ => 0.0,
+        }
+    }
+
+    ///Returns the text drawn for a value on the settings screen
+    pub fn display(&self, field: SettingField) -> String {
+        match field {
+            SettingField::AiMoveDelay => format!("{} frames", self.ai_move_delay),
+            SettingField::AnimationSpeed => {
+                if self.animation_speed == 0.0 {
+                    String::from("Off")
+                } else {
+                    format!("{:.1}x", self.animation_speed)
+                }
+            }
+            SettingField::SoundVolume => format!("{}%", (self.sound_volume * 100.0).round()),
+            SettingField::MusicVolume => format!("{}%", (self.music_volume * 100.0).round()),
+            SettingField::Music => String::from(if self.music_enabled { "On" } else { "Off" }),
+            SettingField::Mute => String::from(if self.muted { "On" } else { "Off" }),
+            SettingField::Theme => format!("{:?}", self.theme),
+            SettingField::WindowScale => format!("{}%", (self.window_scale * 100.0).round()),
+            SettingField::DefaultPlayers => self.default_players.to_string(),
+            SettingField::AiDifficulty => self.ai_difficulty.to_string(),
+            SettingField::HintStrength => self.hint_strength.to_string(),
+            SettingField::Variant => String::from(self.board.variant.name()),
+            SettingField::PlayerCount => format!("{} players", self.board.played().teams),
+            SettingField::BoardSize => {
+                let board = self.board.played();
+                format!("{} x {}", board.rows, board.columns)
+            }
+            SettingField::ConnectLength => format!("{} in a row", self.board.played().connect),
+            SettingField::Clock => self.time_control.to_string(),
+            SettingField::MnkBoard => format!(
+                "{}x{}, {} in a row",
+                self.mnk.rows, self.mnk.columns, self.mnk.connect
+            ),
+            SettingField::GomokuRules => String::from(self.gomoku_rules.name()),
+            SettingField::DotsGrid => self.dots.label(),
+            SettingField::MancalaPits => format!("{} a side", self.mancala.pits),
+            SettingField::MancalaSeeds => format!("{} a pit", self.mancala.seeds),
+        }
+    }
+}
+
+///Returns the y location of row i, which is drawn at the same height as the matching row of every other page
+fn row_y(i: usize) -> f32 {
+    SETTINGS_ROW_OFFSET.1 + SETTINGS_ROW_SPACING * (i % SETTINGS_ROWS_PER_PAGE) as f32
+}
+
+///
+/// A struct for the settings screen, which draws one row per SettingField with "-" and "+" buttons to change it. Rows
+/// are split into pages of SETTINGS_ROWS_PER_PAGE, shown one at a time
+///
+/// # Fields
+/// * fields        = Vector of SettingField enums, where fields[i] is the value changed by row i
+/// * minus_buttons = Vector of Buttons, where minus_buttons[i] lowers the value in row i
+/// * plus_buttons  = Vector of Buttons, where plus_buttons[i] raises the value in row i
+/// * page          = Index of the page of rows being shown
+/// * page_button   = Button drawn to show the next page of rows, wrapping around to the first
+/// * reset_button  = Button drawn to restore every default value
+/// * back_button   = Button drawn to save the settings and return to the main menu
+///
+pub struct SettingsScreen {
+    fields: Vec<SettingField>,
+    minus_buttons: Vec<Button>,
+    plus_buttons: Vec<Button>,
+    page: usize,
+    page_button: Button,
+    reset_button: Button,
+    back_button: Button,
+}
+
+impl SettingsScreen {
+    ///Constructor - screen_size is the size of the window the screen is drawn in
+    pub fn new(ctx: &mut Context, screen_size: (f32, f32)) -> SettingsScreen {
+        let fields = SettingField::all();
+        let mut minus_buttons = Vec::new();
+        let mut plus_buttons = Vec::new();
+        for i in 0..fields.len() {
+            let row_y = row_y(i);
+            let minus_text =
+                graphics::Text::new(("-", graphics::Font::default(), SETTINGS_FONT_SIZE));
+            let plus_text =
+                graphics::Text::new(("+", graphics::Font::default(), SETTINGS_FONT_SIZE));
+            let dim = (
+                2.0 * BUTTON_PADDING.0 + plus_text.width(ctx) as f32,
+                plus_text.height(ctx) as f32,
+            );
+            let mut minus = Button::new(
+                minus_text,
+                graphics::Rect::new(SETTINGS_CONTROL_X, row_y, dim.0, dim.1),
+            );
+            minus.set_colors(MyColor::Blue, MyColor::Green);
+            let mut plus = Button::new(
+                plus_text,
+                graphics::Rect::new(
+                    SETTINGS_CONTROL_X + dim.0 + SETTINGS_VALUE_WIDTH,
+                    row_y,
+                    dim.0,
+                    dim.1,
+                ),
+            );
+            plus.set_colors(MyColor::Blue, MyColor::Green);
+            minus_buttons.push(minus);
+            plus_buttons.push(plus);
+        }
+
+        let back_text =
+            graphics::Text::new(("Back", graphics::Font::default(), SETTINGS_FONT_SIZE));
+        let reset_text = graphics::Text::new((
+            "Reset Defaults",
+            graphics::Font::default(),
+            SETTINGS_FONT_SIZE,
+        ));
+        let back_dim = (
+            2.0 * BUTTON_PADDING.0 + back_text.width(ctx) as f32,
+            2.0 * BUTTON_PADDING.1 + back_text.height(ctx) as f32,
+        );
+        let reset_dim = (
+            2.0 * BUTTON_PADDING.0 + reset_text.width(ctx) as f32,
+            2.0 * BUTTON_PADDING.1 + reset_text.height(ctx) as f32,
+        );
+        let button_y = screen_size.1 - back_dim.1 - 20.0;
+        let mut back_button = Button::new(
+            back_text,
+            graphics::Rect::new(SETTINGS_ROW_OFFSET.0, button_y, back_dim.0, back_dim.1),
+        );
+        back_button.set_colors(MyColor::Red, MyColor::Green);
+        let mut reset_button = Button::new(
+            reset_text,
+            graphics::Rect::new(
+                screen_size.0 - SETTINGS_ROW_OFFSET.0 - reset_dim.0,
+                button_y,
+                reset_dim.0,
+                reset_dim.1,
+            ),
+        );
+        reset_button.set_colors(MyColor::Blue, MyColor::Green);
+        let page_text =
+            graphics::Text::new(("Next Page", graphics::Font::default(), SETTINGS_FONT_SIZE));
+        let page_dim = (
+            2.0 * BUTTON_PADDING.0 + page_text.width(ctx) as f32,
+            2.0 * BUTTON_PADDING.1 + page_text.height(ctx) as f32,
+        );
+        let mut page_button = Button::new(
+            page_text,
+            graphics::Rect::new(
+                (screen_size.0 - page_dim.0) / 2.0,
+                button_y,
+                page_dim.0,
+                page_dim.1,
+            ),
+        );
+        page_button.set_colors(MyColor::Blue, MyColor::Green);
+        page_button.active = fields.len() > SETTINGS_ROWS_PER_PAGE;
+
+        SettingsScreen {
+            fields,
+            minus_buttons,
+            plus_buttons,
+            page: 0,
+            page_button,
+            reset_button,
+            back_button,
+        }
+    }
+
+    ///Returns the number of pages needed to show every row
+    fn pages(&self) -> usize {
+        self.fields.len().div_ceil(SETTINGS_ROWS_PER_PAGE)
+    }
+
+    ///Returns the indices of the rows on the page being shown
+    fn page_rows(&self) -> Range<usize> {
+        let first = self.page * SETTINGS_ROWS_PER_PAGE;
+        first..self.fields.len().min(first + SETTINGS_ROWS_PER_PAGE)
+    }
+
+    ///Draw method to render the title, one row per setting on the page being shown and the buttons
+    pub fn draw(&mut self, ctx: &mut Context, settings: &Settings) -> GameResult {
+        let title = if self.pages() > 1 {
+            format!("Settings ({}/{})", self.page + 1, self.pages())
+        } else {
+            String::from("Settings")
+        };
+        let title = graphics::Text::new((title, graphics::Font::default(), 48.0));
+        graphics::draw(
+            ctx,
+            &title,
+            (Point2 {
+                x: SETTINGS_ROW_OFFSET.0,
+                y: 20.0,
+            },),
+        )?;
+        for i in self.page_rows() {
+            let row_y = row_y(i);
+            let label = graphics::Text::new((
+                self.fields[i].label(),
+                graphics::Font::default(),
+                SETTINGS_FONT_SIZE,
+            ));
+            graphics::draw(
+                ctx,
+                &label,
+                (Point2 {
+                    x: SETTINGS_ROW_OFFSET.0,
+                    y: row_y,
+                },),
+            )?;
+            let value = graphics::Text::new((
+                settings.display(self.fields[i]),
+                graphics::Font::default(),
+                SETTINGS_FONT_SIZE,
+            ));
+            if self.fields[i].is_slider() {
+                //Sliders draw a bar filled up to the current value
+                let bar = self.slider_bar(i);
+                let mut filled = bar;
+                filled.w *= settings.fraction(self.fields[i]);
+                let mut mb = graphics::MeshBuilder::new();
+                mb.rectangle(
+                    graphics::DrawMode::fill(),
+                    bar,
+                    graphics::Color::from_rgba(200, 200, 200, 255),
+                );
+                if filled.w > 0.0 {
+                    mb.rectangle(
+                        graphics::DrawMode::fill(),
+                        filled,
+                        MyColor::Green.get_draw_color(),
+                    );
+                }
+                mb.rectangle(graphics::DrawMode::stroke(1.0), bar, graphics::BLACK);
+                let mesh = mb.build(ctx)?;
+                graphics::draw(ctx, &mesh, (Point2 { x: 0.0, y: 0.0 },))?;
+            } else {
+                let minus_outline = self.minus_buttons[i].outline;
+                let value_x = minus_outline.x
+                    + minus_outline.w
+                    + (SETTINGS_VALUE_WIDTH - value.width(ctx) as f32) / 2.0;
+                graphics::draw(
+                    ctx,
+                    &value,
+                    (Point2 {
+                        x: value_x,
+                        y: row_y,
+                    },),
+                )?;
+            }
+            self.minus_buttons[i].draw(ctx)?;
+            self.plus_buttons[i].draw(ctx)?;
+        }
+        self.back_button.draw(ctx)?;
+        self.reset_button.draw(ctx)?;
+        self.page_button.draw(ctx)?;
+        Ok(())
+    }
+
+    ///Returns the outline of the slider bar drawn in row i
+    fn slider_bar(&self, i: usize) -> graphics::Rect {
+        let minus_outline = self.minus_buttons[i].outline;
+        graphics::Rect::new(
+            minus_outline.x + minus_outline.w + (SETTINGS_VALUE_WIDTH - SLIDER_BAR_SIZE.0) / 2.0,
+            minus_outline.y + (minus_outline.h - SLIDER_BAR_SIZE.1) / 2.0,
+            SLIDER_BAR_SIZE.0,
+            SLIDER_BAR_SIZE.1,
+        )
+    }
+
+    ///Method to update highlighted state of all buttons if mouse moves
+    pub fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        for button in self
+            .minus_buttons
+            .iter_mut()
+            .chain(self.plus_buttons.iter_mut())
+        {
+            button.check_button_under_mouse(ctx);
+        }
+        self.back_button.check_button_under_mouse(ctx);
+        self.reset_button.check_button_under_mouse(ctx);
+        self.page_button.check_button_under_mouse(ctx);
+    }
+
+    ///
+    /// Method to handle a click on the settings screen. Changes settings for any clicked "-", "+", slider or reset button,
+    /// and returns whether settings changed or the back button was clicked so the main menu can save and leave the screen
+    ///
+    /// # Arguments
+    /// * settings = Settings struct changed by the click
+    ///
+    pub fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut Context,
+        settings: &mut Settings,
+    ) -> SettingsAction {
+        let loc = mouse_location(ctx);
+        let before = settings.clone();
+        for i in self.page_rows() {
+            let bar = self.slider_bar(i);
+            if self.fields[i].is_slider() && bar.contains(loc) {
+                settings.set_fraction(self.fields[i], (loc.x - bar.x) / bar.w);
+                println!(
+                    "{} set to {}",
+                    self.fields[i].label(),
+                    settings.display(self.fields[i])
+                );
+            } else if self.minus_buttons[i].check_button_under_mouse(ctx) {
+                settings.step(self.fields[i], false);
+                println!(
+                    "{} set to {}",
+                    self.fields[i].label(),
+                    settings.display(self.fields[i])
+                );
+            } else if self.plus_buttons[i].check_button_under_mouse(ctx) {
+                settings.step(self.fields[i], true);
+                println!(
+                    "{} set to {}",
+                    self.fields[i].label(),
+                    settings.display(self.fields[i])
+                );
+            }
+        }
+        if self.page_button.check_button_under_mouse(ctx) {
+            self.page = (self.page + 1) % self.pages();
+        }
+        if self.reset_button.check_button_under_mouse(ctx) {
+            println!("Settings reset to defaults");
+            *settings = Settings::default();
+        }
+        if self.back_button.check_button_under_mouse(ctx) {
+            SettingsAction::Back
+        } else if *settings != before {
+            SettingsAction::Changed
+        } else {
+            SettingsAction::None
+        }
+    }
+}
+
+#[cfg(test)]
+mod settings_tests {
+    use super::*;
+    use connect4::core::Variant;
+
+    mod settings {
+        use super::*;
+
+        mod from_toml {
+            use super::*;
+
+            #[test]
+            fn should_round_trip_through_to_toml() {
+                let settings = Settings {
+                    ai_move_delay: 40,
+                    theme: Theme::Dark,
+                    time_control: TimeControl::game(3, 2),
+                    ..Settings::default()
+                };
+                let text = settings.to_toml().unwrap();
+                assert_eq!(Settings::from_toml(&text), Ok(settings));
+            }
+
+            #[test]
+            fn should_use_defaults_for_missing_values() {
+                let settings = Settings::from_toml("ai_move_delay = 60").unwrap();
+                assert_eq!(settings.ai_move_delay, 60);
+                assert_eq!(settings.theme, Settings::default().theme);
+                assert_eq!(settings.ai_difficulty, Settings::default().ai_difficulty);
+                assert_eq!(settings.time_control, TimeControl::off());
+            }
+
+            #[test]
+            fn should_read_time_control_table() {
+                let settings =
+                    Settings::from_toml("[time_control]\ntotal_seconds = 120\nmove_seconds = 900")
+                        .unwrap();
+                assert_eq!(settings.time_control.total_seconds, 120);
+                assert_eq!(settings.time_control.increment_seconds, 0);
+                assert_eq!(settings.time_control.move_seconds, 300);
+            }
+
+            #[test]
+            fn should_read_board_table_without_rules() {
+                let settings =
+                    Settings::from_toml("[board]\nrows = 7\ncolumns = 8\nconnect = 5").unwrap();
+                assert_eq!(settings.board, BoardConfig::new(7, 8, 5));
+                assert_eq!(settings.board.variant, Variant::Standard);
+                let settings = Settings::from_toml(
+                    "[board]\nrows = 6\ncolumns = 7\nconnect = 4\nvariant = \"PopOut\"",
+                )
+                .unwrap();
+                assert_eq!(settings.board.variant, Variant::PopOut);
+                assert_eq!(settings.board.teams, 2);
+            }
+
+            #[test]
+            fn should_clamp_out_of_range_values() {
+                let settings =
+                    Settings::from_toml("sound_volume = 3.0\ndefault_players = -4").unwrap();
+                assert_eq!(settings.sound_volume, 1.0);
+                assert_eq!(settings.default_players, 0);
+            }
+
+            #[test]
+            fn should_return_error_for_invalid_file() {
+                assert!(Settings::from_toml("theme = \"Neon\"").is_err());
+                assert!(Settings::from_toml("ai_move_delay = ").is_err());
+            }
+        }
+
+        mod step {
+            use super::*;
+
+            #[test]
+            fn should_stay_within_range() {
+                let mut settings = Settings::default();
+                for _ in 0..20 {
+                    settings.step(SettingField::SoundVolume, true);
+                    settings.step(SettingField::AiDifficulty, false);
+                }
+                assert_eq!(settings.sound_volume, 1.0);
+                assert_eq!(settings.ai_difficulty, 1);
+            }
+
+            #[test]
+            fn should_toggle_on_off_values() {
+                let mut settings = Settings::default();
+                settings.step(SettingField::Mute, true);
+                assert!(settings.muted);
+                settings.step(SettingField::Mute, false);
+                assert!(!settings.muted);
+            }
+
+            #[test]
+            fn should_cycle_themes_both_ways() {
+                let mut settings = Settings::default();
+                settings.step(SettingField::Theme, false);
+                assert_eq!(settings.theme, Theme::Light);
+                settings.step(SettingField::Theme, true);
+                settings.step(SettingField::Theme, true);
+                assert_eq!(settings.theme, Theme::Dark);
+            }
+
+            #[test]
+            fn should_scale_window_in_quarter_steps() {
+                let mut settings = Settings::default();
+                settings.step(SettingField::WindowScale, true);
+                assert_eq!(settings.window_scale, 1.25);
+                assert_eq!(settings.display(SettingField::WindowScale), "125%");
+                for _ in 0..10 {
+                    settings.step(SettingField::WindowScale, false);
+                }
+                assert_eq!(settings.window_scale, 0.5);
+            }
+
+            #[test]
+            fn should_cycle_gomoku_rules() {
+                let mut settings = Settings::default();
+                assert_eq!(settings.display(SettingField::GomokuRules), "Gomoku");
+                settings.step(SettingField::GomokuRules, false);
+                assert_eq!(settings.gomoku_rules, GomokuRules::Connect6);
+                let text = settings.to_toml().unwrap();
+                assert_eq!(Settings::from_toml(&text), Ok(settings));
+            }
+
+            #[test]
+            fn should_cycle_rules_both_ways() {
+                let mut settings = Settings::default();
+                settings.step(SettingField::Variant, true);
+                assert_eq!(settings.board.variant, Variant::PopOut);
+                settings.step(SettingField::Variant, true);
+                settings.step(SettingField::Variant, true);
+                assert_eq!(settings.board.variant, Variant::Pop10);
+                settings.step(SettingField::Variant, true);
+                assert_eq!(settings.board.variant, Variant::Standard);
+                settings.step(SettingField::Variant, false);
+                assert_eq!(settings.display(SettingField::Variant), "Pop 10");
+            }
+        }
+
+        mod step_board_size {
+            use super::*;
+
+            #[test]
+            fn should_move_through_presets() {
+                let mut settings = Settings::default();
+                settings.step(SettingField::BoardSize, true);
+                assert_eq!((settings.board.rows, settings.board.columns), (7, 8));
+                settings.step(SettingField::BoardSize, true);
+                settings.step(SettingField::BoardSize, true);
+                assert_eq!((settings.board.rows, settings.board.columns), (9, 7));
+                settings.board = BoardConfig::new(5, 5, 4);
+                settings.step(SettingField::BoardSize, false);
+                assert_eq!((settings.board.rows, settings.board.columns), (5, 4));
+            }
+
+            #[test]
+            fn should_keep_connect_length_on_small_boards() {
+                let mut settings = Settings::default();
+                for _ in 0..3 {
+                    settings.step(SettingField::ConnectLength, true);
+                }
+                assert_eq!(settings.board.connect, 6);
+                for _ in 0..5 {
+                    settings.step(SettingField::BoardSize, false);
+                }
+                assert_eq!(settings.board, BoardConfig::new(4, 4, 4));
+            }
+
+            #[test]
+            fn should_step_player_count_within_range() {
+                let mut settings = Settings::default();
+                assert_eq!(settings.display(SettingField::PlayerCount), "2 players");
+                settings.step(SettingField::PlayerCount, false);
+                assert_eq!(settings.board.teams, 2);
+                for _ in 0..3 {
+                    settings.step(SettingField::PlayerCount, true);
+                }
+                assert_eq!(settings.board.teams, 4);
+                //Larger games are played on at least a 7x9 board
+                assert_eq!(settings.display(SettingField::BoardSize), "7 x 9");
+                settings.board.variant = Variant::Pop10;
+                assert_eq!(settings.display(SettingField::PlayerCount), "2 players");
+            }
+
+            #[test]
+            fn should_show_fixed_board_for_five_in_a_row() {
+                let mut settings = Settings {
+                    board: BoardConfig::new(4, 4, 3).with_variant(Variant::FiveInARow),
+                    ..Settings::default()
+                };
+                assert_eq!(settings.display(SettingField::BoardSize), "6 x 9");
+                assert_eq!(settings.display(SettingField::ConnectLength), "5 in a row");
+                settings.step(SettingField::BoardSize, true);
+                //The size set is kept for other rules
+                settings.step(SettingField::Variant, false);
+                assert_eq!(settings.display(SettingField::BoardSize), "4 x 4");
+            }
+        }
+
+        mod step_mnk_board {
+            use super::*;
+
+            #[test]
+            fn should_move_through_presets_and_stop_at_ends() {
+                let mut settings = Settings::default();
+                assert_eq!(settings.display(SettingField::MnkBoard), "3x3, 3 in a row");
+                settings.step(SettingField::MnkBoard, false);
+                assert_eq!(settings.mnk, MnkConfig::tic_tac_toe());
+                for _ in 0..3 {
+                    settings.step(SettingField::MnkBoard, true);
+                }
+                assert_eq!(settings.display(SettingField::MnkBoard), "7x7, 5 in a row");
+                for _ in 0..5 {
+                    settings.step(SettingField::MnkBoard, true);
+                }
+                assert_eq!(settings.mnk, MnkConfig::new(15, 15, 5));
+            }
+
+            #[test]
+            fn should_read_and_clamp_mnk_table() {
+                let settings =
+                    Settings::from_toml("[mnk]\nrows = 30\ncolumns = 4\nconnect = 9").unwrap();
+                assert_eq!(settings.mnk, MnkConfig::new(19, 4, 6));
+                assert_eq!(settings.board, BoardConfig::standard());
+            }
+        }
+
+        mod step_dots_grid {
+            use super::*;
+
+            #[test]
+            fn should_move_through_presets_and_stop_at_ends() {
+                let mut settings = Settings::default();
+                assert_eq!(settings.display(SettingField::DotsGrid), "3x3 boxes");
+                settings.step(SettingField::DotsGrid, false);
+                settings.step(SettingField::DotsGrid, false);
+                assert_eq!(settings.dots, DotsConfig::new(2, 2));
+                for _ in 0..6 {
+                    settings.step(SettingField::DotsGrid, true);
+                }
+                assert_eq!(settings.display(SettingField::DotsGrid), "8x8 boxes");
+            }
+
+            #[test]
+            fn should_read_and_clamp_dots_table() {
+                let settings = Settings::from_toml("[dots]\nrows = 1\ncolumns = 12").unwrap();
+                assert_eq!(settings.dots, DotsConfig::new(2, 9));
+                assert_eq!(settings.mnk, MnkConfig::tic_tac_toe());
+            }
+        }
+
+        mod step_mancala {
+            use super::*;
+
+            #[test]
+            fn should_step_pits_and_seeds_within_their_ranges() {
+                let mut settings = Settings::default();
+                assert_eq!(settings.display(SettingField::MancalaPits), "6 a side");
+                assert_eq!(settings.display(SettingField::MancalaSeeds), "4 a pit");
+                for _ in 0..5 {
+                    settings.step(SettingField::MancalaPits, true);
+                    settings.step(SettingField::MancalaSeeds, false);
+                }
+                assert_eq!(settings.mancala, MancalaConfig::new(8, 2));
+            }
+
+            #[test]
+            fn should_read_and_clamp_mancala_table() {
+                let settings = Settings::from_toml("[mancala]\npits = 12\nseeds = 3").unwrap();
+                assert_eq!(settings.mancala, MancalaConfig::new(8, 3));
+                assert_eq!(settings.dots, DotsConfig::default());
+            }
+        }
+
+        mod set_fraction {
+            use super::*;
+
+            #[test]
+            fn should_round_to_nearest_tenth_and_clamp() {
+                let mut settings = Settings::default();
+                settings.set_fraction(SettingField::MusicVolume, 0.74);
+                assert_eq!(settings.music_volume, 0.7);
+                settings.set_fraction(SettingField::SoundVolume, 1.3);
+                assert_eq!(settings.sound_volume, 1.0);
+            }
+
+            #[test]
+            fn should_ignore_fields_that_are_not_sliders() {
+                let mut settings = Settings::default();
+                settings.set_fraction(SettingField::AiDifficulty, 0.0);
+                assert_eq!(settings, Settings::default());
+            }
+        }
+    }
+}
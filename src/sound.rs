@@ -0,0 +1,188 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use ggez::audio;
+use ggez::audio::SoundSource;
+use ggez::Context;
+use settings::Settings;
+use std::collections::HashMap;
+
+///Constant resource path of the background music loop
+const MUSIC_PATH: &str = "/sounds/music.wav";
+
+/// Enum naming each sound effect played by the menus and games
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum SoundEffect {
+    Drop,
+    InvalidMove,
+    Win,
+    Lose,
+    Draw,
+    Click,
+}
+
+impl SoundEffect {
+    ///Returns every sound effect, used to load them all at startup
+    fn all() -> Vec<SoundEffect> {
+        vec![
+            SoundEffect::Drop,
+            SoundEffect::InvalidMove,
+            SoundEffect::Win,
+            SoundEffect::Lose,
+            SoundEffect::Draw,
+            SoundEffect::Click,
+        ]
+    }
+
+    ///Returns the resource path the sound effect is loaded from
+    fn path(self) -> &'static str {
+        match self {
+            SoundEffect::Drop => "/sounds/drop.wav",
+            SoundEffect::InvalidMove => "/sounds/invalid.wav",
+            SoundEffect::Win => "/sounds/win.wav",
+            SoundEffect::Lose => "/sounds/lose.wav",
+            SoundEffect::Draw => "/sounds/draw.wav",
+            SoundEffect::Click => "/sounds/click.wav",
+        }
+    }
+}
+
+///
+/// Returns the sound for the end of a two-team game: the draw sound for a draw, the losing sound if an AI beat a person,
+/// otherwise the winning sound
+///
+/// # Arguments
+/// * winner   = Team number of the winner, or 0 for a draw
+/// * ai_teams = Slice of the team numbers played by the AI
+///
+pub fn result_sound(winner: i32, ai_teams: &[i32]) -> SoundEffect {
+    if winner == 0 {
+        SoundEffect::Draw
+    } else if ai_teams.contains(&winner) && ai_teams.len() < 2 {
+        SoundEffect::Lose
+    } else {
+        SoundEffect::Win
+    }
+}
+
+///
+/// A struct that loads and plays sound effects and background music. If there is no audio device or a sound file fails
+/// to load, the affected sounds are skipped so the games can still be played silently
+///
+/// # Fields
+/// * effects        = HashMap from SoundEffect enums to loaded audio sources. Missing entries are never played
+/// * music          = Option of the looping background music source, or None if it could not be loaded
+/// * effects_volume = Volume from 0.0 to 1.0 for sound effects
+/// * music_volume   = Volume from 0.0 to 1.0 for background music
+/// * muted          = Boolean indicating if all sound is muted
+/// * music_enabled  = Boolean indicating if background music should be playing
+///
+pub struct SoundPlayer {
+    effects: HashMap<SoundEffect, audio::Source>,
+    music: Option<audio::Source>,
+    effects_volume: f32,
+    music_volume: f32,
+    muted: bool,
+    music_enabled: bool,
+}
+
+impl SoundPlayer {
+    ///
+    /// Method to initialize and return a SoundPlayer object, loading every sound from the resources directory
+    ///
+    /// # Arguments
+    /// * audio_available = Boolean indicating if ggez was able to open an audio device. If false, nothing is loaded
+    /// * settings        = Settings struct holding the volumes, mute and music options
+    ///
+    pub fn new(ctx: &mut Context, audio_available: bool, settings: &Settings) -> Self {
+        let mut effects = HashMap::new();
+        let mut music = None;
+        if audio_available {
+            for effect in SoundEffect::all() {
+                match audio::Source::new(ctx, effect.path()) {
+                    Ok(source) => {
+                        effects.insert(effect, source);
+                    }
+                    Err(e) => println!("Error loading sound {}: {:?}", effect.path(), e),
+                }
+            }
+            match audio::Source::new(ctx, MUSIC_PATH) {
+                Ok(mut source) => {
+                    source.set_repeat(true);
+                    music = Some(source);
+                }
+                Err(e) => println!("Error loading music {}: {:?}", MUSIC_PATH, e),
+            }
+        } else {
+            println!("No audio device available; sound is disabled");
+        }
+        let mut player = SoundPlayer {
+            effects,
+            music,
+            effects_volume: 0.0,
+            music_volume: 0.0,
+            muted: false,
+            music_enabled: false,
+        };
+        player.apply_settings(settings);
+        player
+    }
+
+    ///Method to update volumes from settings, starting or stopping the background music if needed
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        self.effects_volume = settings.sound_volume;
+        self.music_volume = settings.music_volume;
+        self.muted = settings.muted;
+        self.music_enabled = settings.music_enabled;
+        let play_music = self.music_enabled && !self.muted;
+        let volume = self.music_volume;
+        if let Some(ref mut music) = self.music {
+            music.set_volume(volume);
+            if play_music && !music.playing() {
+                if let Err(e) = music.play() {
+                    println!("Error playing music: {:?}", e);
+                }
+            } else if !play_music && music.playing() {
+                music.stop();
+            }
+        }
+    }
+
+    ///Method to play a sound effect, unless sound is muted or the effect was not loaded
+    pub fn play(&mut self, effect: SoundEffect) {
+        if self.muted || self.effects_volume <= 0.0 {
+            return;
+        }
+        let volume = self.effects_volume;
+        if let Some(source) = self.effects.get_mut(&effect) {
+            //Detached sources lose their volume, so it is set before every play
+            source.set_volume(volume);
+            if let Err(e) = source.play_detached() {
+                println!("Error playing sound {}: {:?}", effect.path(), e);
+            }
+        }
+    }
+}
+
+//NOTE: Tests not included since testing requires an audio device
+
+#[cfg(test)]
+mod sound_tests {
+    use super::*;
+
+    mod result_sound {
+        use super::*;
+
+        #[test]
+        fn should_only_play_the_losing_sound_when_an_ai_beats_a_person() {
+            assert_eq!(result_sound(0, &[2]), SoundEffect::Draw);
+            assert_eq!(result_sound(2, &[2]), SoundEffect::Lose);
+            assert_eq!(result_sound(1, &[2]), SoundEffect::Win);
+            assert_eq!(result_sound(2, &[1, 2]), SoundEffect::Win);
+            assert_eq!(result_sound(1, &[]), SoundEffect::Win);
+        }
+    }
+}
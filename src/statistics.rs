@@ -0,0 +1,510 @@
+// Copyright © 2019 Andre Mukhsia, Lane Barton
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+extern crate ggez;
+
+use clock::format_time;
+use connect4::button::{Button, BUTTON_PADDING};
+use connect4::core::MyColor;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+use history::{MatchHistory, MatchRecord};
+use rating::{Profile, Ratings};
+
+///Constant font size for the statistics text
+const STATS_FONT_SIZE: f32 = 24.0;
+
+///Constant x location of the left edge of the statistics text
+const STATS_LEFT: f32 = 50.0;
+
+///Constant x locations of the game, player, opponent, wins, losses and draws columns of the records table
+const STATS_COLUMNS: [f32; 6] = [STATS_LEFT, 380.0, 580.0, 820.0, 900.0, 980.0];
+
+///Constant vertical spacing between lines of text
+const STATS_LINE_SPACING: f32 = 30.0;
+
+///Constant x location of the ratings list, drawn beside the streaks list
+const STATS_RATINGS_X: f32 = 480.0;
+
+///Constant max number of rows shown in the records table
+const MAX_RECORD_ROWS: usize = 6;
+
+///Constant max number of players shown in the streaks and ratings lists
+const MAX_STREAK_ROWS: usize = 3;
+
+///Constant max number of games whose average length is listed under the totals
+const MAX_GAME_TOTALS: usize = 3;
+
+///
+/// A struct for the win/loss/draw record of a human player against one opponent in one game
+///
+/// # Fields
+/// * game     = Name of the game played, as saved in the match history
+/// * player   = Name of the human player
+/// * opponent = Name of the opponent, or "AI (Level N)" for an AI of difficulty N
+/// * wins     = Number of games the player won against the opponent
+/// * losses   = Number of games the player lost against the opponent
+/// * draws    = Number of games against the opponent that ended in a draw
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HeadToHead {
+    pub game: String,
+    pub player: String,
+    pub opponent: String,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl HeadToHead {
+    ///Returns the number of games the player played against the opponent
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses + self.draws
+    }
+}
+
+///
+/// A struct for the number of times one game was played and how long it lasted, since a move means something different
+/// in each game
+///
+/// # Fields
+/// * game          = Name of the game played, as saved in the match history
+/// * games         = Number of times the game was played
+/// * average_moves = Average number of moves per game
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct GameTotals {
+    pub game: String,
+    pub games: usize,
+    pub average_moves: f32,
+}
+
+///
+/// A struct holding statistics computed from the match history
+///
+/// # Fields
+/// * games            = Number of games in the history
+/// * average_duration = Average seconds per game
+/// * game_totals      = Vector of GameTotals for every game played, most played first
+/// * records          = Vector of HeadToHead records for every game, human player and opponent, most played first
+/// * streaks          = Vector of (name, length) pairs for each human player's longest win streak, longest first
+///
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Statistics {
+    pub games: usize,
+    pub average_duration: f32,
+    pub game_totals: Vec<GameTotals>,
+    pub records: Vec<HeadToHead>,
+    pub streaks: Vec<(String, u32)>,
+}
+
+///Function to return the name an opponent is listed under: AI players are grouped by difficulty
+fn opponent_label(record: &MatchRecord, index: usize) -> String {
+    let opponent = &record.players[index];
+    if opponent.is_ai {
+        format!("AI (Level {})", record.ai_difficulty)
+    } else {
+        opponent.name.clone()
+    }
+}
+
+impl Statistics {
+    ///
+    /// Method to compute statistics from every match in the history. Move counts and records are kept apart for each
+    /// game. Records and streaks are only kept for human players, with each player's games counted against every other
+    /// player in the match
+    ///
+    /// # Arguments
+    /// * history = MatchHistory holding every finished match, oldest first
+    ///
+    pub fn from_history(history: &MatchHistory) -> Statistics {
+        let mut stats = Statistics {
+            games: history.matches.len(),
+            ..Statistics::default()
+        };
+        if stats.games == 0 {
+            return stats;
+        }
+        let total_duration: f32 = history.matches.iter().map(|m| m.duration).sum();
+        stats.average_duration = total_duration / stats.games as f32;
+        //Total moves for each game, averaged once every match is counted
+        for record in &history.matches {
+            match stats.game_totals.iter_mut().find(|t| t.game == record.game) {
+                Some(totals) => {
+                    totals.games += 1;
+                    totals.average_moves += record.moves.len() as f32;
+                }
+                None => stats.game_totals.push(GameTotals {
+                    game: record.game.clone(),
+                    games: 1,
+                    average_moves: record.moves.len() as f32,
+                }),
+            }
+        }
+        for totals in &mut stats.game_totals {
+            totals.average_moves /= totals.games as f32;
+        }
+        stats
+            .game_totals
+            .sort_by(|a, b| b.games.cmp(&a.games).then_with(|| a.game.cmp(&b.game)));
+
+        //Current and longest win streaks, as (name, current, longest)
+        let mut streaks: Vec<(String, u32, u32)> = Vec::new();
+        for record in &history.matches {
+            for (i, player) in record.players.iter().enumerate() {
+                if player.is_ai {
+                    continue;
+                }
+                let result = record.won_by(i);
+                for j in 0..record.players.len() {
+                    if i == j {
+                        continue;
+                    }
+                    let opponent = opponent_label(record, j);
+                    let index = match stats.records.iter().position(|r| {
+                        r.game == record.game && r.player == player.name && r.opponent == opponent
+                    }) {
+                        Some(index) => index,
+                        None => {
+                            stats.records.push(HeadToHead {
+                                game: record.game.clone(),
+                                player: player.name.clone(),
+                                opponent,
+                                wins: 0,
+                                losses: 0,
+                                draws: 0,
+                            });
+                            stats.records.len() - 1
+                        }
+                    };
+                    match result {
+                        Some(true) => stats.records[index].wins += 1,
+                        Some(false) => stats.records[index].losses += 1,
+                        None => stats.records[index].draws += 1,
+                    }
+                }
+                let index = match streaks.iter().position(|s| s.0 == player.name) {
+                    Some(index) => index,
+                    None => {
+                        streaks.push((player.name.clone(), 0, 0));
+                        streaks.len() - 1
+                    }
+                };
+                if result == Some(true) {
+                    streaks[index].1 += 1;
+                    streaks[index].2 = streaks[index].2.max(streaks[index].1);
+                } else {
+                    streaks[index].1 = 0;
+                }
+            }
+        }
+        //Most played records first, then by name so the order is stable
+        stats.records.sort_by(|a, b| {
+            b.games()
+                .cmp(&a.games())
+                .then_with(|| a.game.cmp(&b.game))
+                .then_with(|| a.player.cmp(&b.player))
+                .then_with(|| a.opponent.cmp(&b.opponent))
+        });
+        stats.streaks = streaks
+            .into_iter()
+            .filter(|s| s.2 > 0)
+            .map(|s| (s.0, s.2))
+            .collect();
+        stats
+            .streaks
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        stats
+    }
+}
+
+///
+/// A struct for the statistics screen, which shows totals, win/loss/draw records, win streaks and ratings from the match
+/// history
+///
+/// # Fields
+/// * statistics  = Statistics computed when the screen was last opened
+/// * ratings     = Vector of player Profiles when the screen was last opened, highest rating first
+/// * back_button = Button drawn to return to the main menu
+///
+pub struct StatisticsScreen {
+    statistics: Statistics,
+    ratings: Vec<Profile>,
+    back_button: Button,
+}
+
+impl StatisticsScreen {
+    ///Constructor - screen_size is the size of the window the screen is drawn in
+    pub fn new(ctx: &mut Context, screen_size: (f32, f32)) -> StatisticsScreen {
+        let back_text = graphics::Text::new(("Back", graphics::Font::default(), 32.0));
+        let back_dim = (
+            2.0 * BUTTON_PADDING.0 + back_text.width(ctx) as f32,
+            2.0 * BUTTON_PADDING.1 + back_text.height(ctx) as f32,
+        );
+        let mut back_button = Button::new(
+            back_text,
+            graphics::Rect::new(
+                STATS_LEFT,
+                screen_size.1 - back_dim.1 - 20.0,
+                back_dim.0,
+                back_dim.1,
+            ),
+        );
+        back_button.set_colors(MyColor::Red, MyColor::Green);
+        StatisticsScreen {
+            statistics: Statistics::default(),
+            ratings: Vec::new(),
+            back_button,
+        }
+    }
+
+    ///Method to recompute the statistics shown from the match history and ratings, called when the screen is opened
+    pub fn refresh(&mut self, history: &MatchHistory, ratings: &Ratings) {
+        self.statistics = Statistics::from_history(history);
+        self.ratings = ratings.profiles.clone();
+    }
+
+    ///Function to draw one line of text at the given location
+    fn draw_text(
+        ctx: &mut Context,
+        text: &str,
+        x: f32,
+        y: f32,
+        color: graphics::Color,
+    ) -> GameResult {
+        let text = graphics::Text::new((text, graphics::Font::default(), STATS_FONT_SIZE));
+        graphics::draw(ctx, &text, (Point2 { x, y }, color))
+    }
+
+    ///Draw method to render the title, totals, records table, streaks and back button
+    pub fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let title = graphics::Text::new(("Statistics", graphics::Font::default(), 48.0));
+        graphics::draw(
+            ctx,
+            &title,
+            (Point2 {
+                x: STATS_LEFT,
+                y: 20.0,
+            },),
+        )?;
+        let stats = &self.statistics;
+        let mut y = 90.0;
+        if stats.games == 0 {
+            StatisticsScreen::draw_text(
+                ctx,
+                "No games played yet",
+                STATS_LEFT,
+                y,
+                graphics::WHITE,
+            )?;
+            return self.back_button.draw(ctx);
+        }
+        let summary = format!(
+            "Games played: {}    Average length: {}",
+            stats.games,
+            format_time(stats.average_duration)
+        );
+        StatisticsScreen::draw_text(ctx, &summary, STATS_LEFT, y, graphics::WHITE)?;
+        let moves: Vec<String> = stats
+            .game_totals
+            .iter()
+            .take(MAX_GAME_TOTALS)
+            .map(|t| format!("{} {:.1}", t.game, t.average_moves))
+            .collect();
+        y += STATS_LINE_SPACING;
+        let moves = format!("Average moves: {}", moves.join(", "));
+        StatisticsScreen::draw_text(ctx, &moves, STATS_LEFT, y, graphics::WHITE)?;
+
+        //Records table
+        y += STATS_LINE_SPACING * 1.5;
+        let header = ["Game", "Player", "Opponent", "W", "L", "D"];
+        let header_color = MyColor::Yellow.get_draw_color();
+        for (i, text) in header.iter().enumerate() {
+            StatisticsScreen::draw_text(ctx, text, STATS_COLUMNS[i], y, header_color)?;
+        }
+        for record in stats.records.iter().take(MAX_RECORD_ROWS) {
+            y += STATS_LINE_SPACING;
+            let row = [
+                record.game.clone(),
+                record.player.clone(),
+                record.opponent.clone(),
+                record.wins.to_string(),
+                record.losses.to_string(),
+                record.draws.to_string(),
+            ];
+            for (i, text) in row.iter().enumerate() {
+                StatisticsScreen::draw_text(ctx, text, STATS_COLUMNS[i], y, graphics::WHITE)?;
+            }
+        }
+
+        //Longest win streaks, with ratings beside them
+        y += STATS_LINE_SPACING * 1.5;
+        if !stats.streaks.is_empty() {
+            StatisticsScreen::draw_text(ctx, "Longest win streaks", STATS_LEFT, y, header_color)?;
+            for (i, (name, length)) in stats.streaks.iter().take(MAX_STREAK_ROWS).enumerate() {
+                let line = format!("{}: {} in a row", name, length);
+                let line_y = y + STATS_LINE_SPACING * (i + 1) as f32;
+                StatisticsScreen::draw_text(ctx, &line, STATS_LEFT, line_y, graphics::WHITE)?;
+            }
+        }
+        if !self.ratings.is_empty() {
+            StatisticsScreen::draw_text(ctx, "Ratings", STATS_RATINGS_X, y, header_color)?;
+            for (i, profile) in self.ratings.iter().take(MAX_STREAK_ROWS).enumerate() {
+                let line = format!(
+                    "{}: {:.0} ({} games)",
+                    profile.name, profile.rating, profile.games
+                );
+                let line_y = y + STATS_LINE_SPACING * (i + 1) as f32;
+                StatisticsScreen::draw_text(ctx, &line, STATS_RATINGS_X, line_y, graphics::WHITE)?;
+            }
+        }
+        self.back_button.draw(ctx)
+    }
+
+    ///Method to update highlighted state of the back button if mouse moves
+    pub fn mouse_motion_event(&mut self, ctx: &mut Context) {
+        self.back_button.check_button_under_mouse(ctx);
+    }
+
+    ///Method to handle a click on the statistics screen. Returns true if the back button was clicked
+    pub fn mouse_button_up_event(&mut self, ctx: &mut Context) -> bool {
+        self.back_button.check_button_under_mouse(ctx)
+    }
+}
+
+#[cfg(test)]
+mod statistics_tests {
+    use super::*;
+    use history::PlayerRecord;
+
+    fn create_test_record(players: Vec<(&str, bool)>, winner: i32, moves: usize) -> MatchRecord {
+        create_game_record("Connect 4", players, winner, moves)
+    }
+
+    //Method to create a record of any game with the given players, winner and number of moves
+    fn create_game_record(
+        game: &str,
+        players: Vec<(&str, bool)>,
+        winner: i32,
+        moves: usize,
+    ) -> MatchRecord {
+        MatchRecord::new(
+            game,
+            players
+                .iter()
+                .map(|p| PlayerRecord::new(p.0, p.1))
+                .collect(),
+            2,
+            vec![0; moves],
+            winner,
+            moves as f32 * 2.0,
+        )
+    }
+
+    mod statistics {
+        use super::*;
+
+        mod from_history {
+            use super::*;
+
+            #[test]
+            fn should_handle_empty_history() {
+                let stats = Statistics::from_history(&MatchHistory::default());
+                assert_eq!(stats, Statistics::default());
+            }
+
+            #[test]
+            fn should_average_game_length() {
+                let history = MatchHistory {
+                    matches: vec![
+                        create_test_record(vec![("Lane", false), ("AI 2", true)], 1, 10),
+                        create_test_record(vec![("Lane", false), ("AI 2", true)], 2, 20),
+                    ],
+                };
+                let stats = Statistics::from_history(&history);
+                assert_eq!(stats.games, 2);
+                assert_eq!(stats.game_totals[0].average_moves, 15.0);
+                assert_eq!(stats.average_duration, 30.0);
+            }
+
+            #[test]
+            fn should_keep_moves_and_records_apart_for_each_game() {
+                let lane_ai = vec![("Lane", false), ("AI 2", true)];
+                let history = MatchHistory {
+                    matches: vec![
+                        create_test_record(lane_ai.clone(), 1, 10),
+                        create_game_record("Hearts", lane_ai.clone(), 2, 52),
+                        create_test_record(lane_ai.clone(), 1, 20),
+                    ],
+                };
+                let stats = Statistics::from_history(&history);
+                assert_eq!(stats.games, 3);
+                assert_eq!(
+                    stats.game_totals,
+                    vec![
+                        GameTotals {
+                            game: String::from("Connect 4"),
+                            games: 2,
+                            average_moves: 15.0,
+                        },
+                        GameTotals {
+                            game: String::from("Hearts"),
+                            games: 1,
+                            average_moves: 52.0,
+                        },
+                    ]
+                );
+                assert_eq!(stats.records.len(), 2);
+                assert_eq!(stats.records[0].game, "Connect 4");
+                assert_eq!(stats.records[0].wins, 2);
+                assert_eq!(stats.records[1].game, "Hearts");
+                assert_eq!(stats.records[1].losses, 1);
+            }
+
+            #[test]
+            fn should_group_records_by_opponent_and_difficulty() {
+                let history = MatchHistory {
+                    matches: vec![
+                        create_test_record(vec![("Lane", false), ("AI 2", true)], 1, 7),
+                        create_test_record(vec![("Lane", false), ("AI 2", true)], 0, 42),
+                        create_test_record(vec![("Andre", false), ("Lane", false)], 1, 9),
+                        create_test_record(vec![("AI 1", true), ("AI 2", true)], 2, 9),
+                    ],
+                };
+                let stats = Statistics::from_history(&history);
+                assert_eq!(stats.records.len(), 3);
+                let first = &stats.records[0];
+                assert_eq!(first.player, "Lane");
+                assert_eq!(first.opponent, "AI (Level 2)");
+                assert_eq!((first.wins, first.losses, first.draws), (1, 0, 1));
+                let lane_vs_andre = stats
+                    .records
+                    .iter()
+                    .find(|r| r.player == "Lane" && r.opponent == "Andre")
+                    .unwrap();
+                assert_eq!(lane_vs_andre.losses, 1);
+            }
+
+            #[test]
+            fn should_find_longest_win_streaks() {
+                let lane_ai = vec![("Lane", false), ("AI 2", true)];
+                let history = MatchHistory {
+                    matches: vec![
+                        create_test_record(lane_ai.clone(), 1, 7),
+                        create_test_record(lane_ai.clone(), 1, 7),
+                        create_test_record(lane_ai.clone(), 1, 7),
+                        create_test_record(lane_ai.clone(), 0, 42),
+                        create_test_record(lane_ai.clone(), 1, 7),
+                        create_test_record(vec![("Andre", false), ("Lane", false)], 1, 9),
+                    ],
+                };
+                let stats = Statistics::from_history(&history);
+                assert_eq!(
+                    stats.streaks,
+                    vec![(String::from("Lane"), 3), (String::from("Andre"), 1)]
+                );
+            }
+        }
+    }
+}